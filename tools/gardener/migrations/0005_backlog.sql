@@ -0,0 +1,13 @@
+ALTER TABLE backlog_tasks
+ADD COLUMN blocked_reason TEXT;
+
+CREATE TABLE IF NOT EXISTS task_dependencies (
+    repo_namespace TEXT NOT NULL,
+    task_id TEXT NOT NULL,
+    depends_on TEXT NOT NULL,
+    created_at INTEGER NOT NULL,
+    PRIMARY KEY (repo_namespace, task_id, depends_on)
+);
+
+CREATE INDEX IF NOT EXISTS idx_task_dependencies_prerequisite
+    ON task_dependencies(repo_namespace, depends_on);
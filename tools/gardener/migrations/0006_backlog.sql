@@ -0,0 +1,2 @@
+ALTER TABLE backlog_tasks
+ADD COLUMN status_note TEXT;
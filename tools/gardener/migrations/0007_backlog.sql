@@ -0,0 +1,32 @@
+CREATE TABLE IF NOT EXISTS task_events (
+    event_id INTEGER PRIMARY KEY AUTOINCREMENT,
+    repo_namespace TEXT NOT NULL,
+    task_id TEXT NOT NULL,
+    event_type TEXT NOT NULL,
+    from_status TEXT,
+    to_status TEXT NOT NULL,
+    actor TEXT NOT NULL,
+    reason TEXT,
+    run_id TEXT,
+    pr_number INTEGER,
+    failure_reason TEXT,
+    created_at INTEGER NOT NULL
+);
+
+CREATE INDEX IF NOT EXISTS idx_task_events_task
+    ON task_events(repo_namespace, task_id, event_id);
+
+-- History is append-only. Rows may only follow their task to a new namespace or
+-- a re-keyed task_id; everything describing what happened is immutable.
+CREATE TRIGGER IF NOT EXISTS task_events_immutable
+BEFORE UPDATE OF event_id, event_type, from_status, to_status, actor, reason, run_id, pr_number,
+    failure_reason, created_at ON task_events
+BEGIN
+    SELECT RAISE(ABORT, 'task_events is append-only');
+END;
+
+CREATE TRIGGER IF NOT EXISTS task_events_no_delete
+BEFORE DELETE ON task_events
+BEGIN
+    SELECT RAISE(ABORT, 'task_events is append-only');
+END;
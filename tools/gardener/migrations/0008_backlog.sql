@@ -0,0 +1,45 @@
+ALTER TABLE backlog_tasks RENAME TO backlog_tasks_v7;
+
+CREATE TABLE backlog_tasks (
+    repo_namespace TEXT NOT NULL DEFAULT 'legacy',
+    task_id TEXT NOT NULL,
+    kind TEXT NOT NULL,
+    title TEXT NOT NULL,
+    details TEXT NOT NULL,
+    scope_key TEXT NOT NULL,
+    priority TEXT NOT NULL CHECK(priority IN ('P0', 'P1', 'P2')),
+    status TEXT NOT NULL CHECK(status IN ('ready', 'leased', 'in_progress', 'complete', 'failed', 'unresolved', 'exhausted')),
+    last_updated INTEGER NOT NULL,
+    lease_owner TEXT,
+    lease_expires_at INTEGER,
+    source TEXT NOT NULL,
+    related_pr INTEGER,
+    related_branch TEXT,
+    rationale TEXT NOT NULL DEFAULT '',
+    attempt_count INTEGER NOT NULL DEFAULT 0,
+    created_at INTEGER NOT NULL,
+    blocked_reason TEXT,
+    status_note TEXT,
+    not_before INTEGER,
+    failure_reason TEXT,
+    PRIMARY KEY (repo_namespace, task_id)
+);
+
+INSERT INTO backlog_tasks (
+    repo_namespace, task_id, kind, title, details, scope_key, priority, status, last_updated,
+    lease_owner, lease_expires_at, source, related_pr, related_branch, rationale, attempt_count,
+    created_at, blocked_reason, status_note
+)
+SELECT
+    repo_namespace, task_id, kind, title, details, scope_key, priority, status, last_updated,
+    lease_owner, lease_expires_at, source, related_pr, related_branch, rationale, attempt_count,
+    created_at, blocked_reason, status_note
+FROM backlog_tasks_v7;
+
+DROP TABLE backlog_tasks_v7;
+
+CREATE INDEX IF NOT EXISTS idx_backlog_claim_order
+    ON backlog_tasks(repo_namespace, priority, status, last_updated, created_at);
+
+CREATE INDEX IF NOT EXISTS idx_backlog_lease_expiry
+    ON backlog_tasks(repo_namespace, status, lease_expires_at);
@@ -0,0 +1,30 @@
+CREATE VIRTUAL TABLE IF NOT EXISTS backlog_tasks_fts USING fts5(
+    repo_namespace UNINDEXED,
+    task_id UNINDEXED,
+    title,
+    details,
+    rationale,
+    tokenize = 'porter unicode61'
+);
+
+INSERT INTO backlog_tasks_fts (repo_namespace, task_id, title, details, rationale)
+SELECT repo_namespace, task_id, title, details, rationale
+FROM backlog_tasks;
+
+CREATE TRIGGER IF NOT EXISTS backlog_tasks_fts_insert AFTER INSERT ON backlog_tasks BEGIN
+    INSERT INTO backlog_tasks_fts (repo_namespace, task_id, title, details, rationale)
+    VALUES (new.repo_namespace, new.task_id, new.title, new.details, new.rationale);
+END;
+
+CREATE TRIGGER IF NOT EXISTS backlog_tasks_fts_delete AFTER DELETE ON backlog_tasks BEGIN
+    DELETE FROM backlog_tasks_fts
+    WHERE repo_namespace = old.repo_namespace AND task_id = old.task_id;
+END;
+
+CREATE TRIGGER IF NOT EXISTS backlog_tasks_fts_update
+AFTER UPDATE OF repo_namespace, task_id, title, details, rationale ON backlog_tasks BEGIN
+    DELETE FROM backlog_tasks_fts
+    WHERE repo_namespace = old.repo_namespace AND task_id = old.task_id;
+    INSERT INTO backlog_tasks_fts (repo_namespace, task_id, title, details, rationale)
+    VALUES (new.repo_namespace, new.task_id, new.title, new.details, new.rationale);
+END;
@@ -0,0 +1,117 @@
+ALTER TABLE backlog_tasks RENAME TO backlog_tasks_v9;
+
+CREATE TABLE backlog_tasks (
+    repo_namespace TEXT NOT NULL DEFAULT 'legacy',
+    task_id TEXT NOT NULL,
+    kind TEXT NOT NULL,
+    title TEXT NOT NULL,
+    details TEXT NOT NULL,
+    scope_key TEXT NOT NULL,
+    priority TEXT NOT NULL CHECK(priority IN ('P0', 'P1', 'P2')),
+    status TEXT NOT NULL CHECK(status IN ('ready', 'leased', 'in_progress', 'complete', 'failed', 'unresolved', 'exhausted', 'cancelled')),
+    last_updated INTEGER NOT NULL,
+    lease_owner TEXT,
+    lease_expires_at INTEGER,
+    source TEXT NOT NULL,
+    related_pr INTEGER,
+    related_branch TEXT,
+    rationale TEXT NOT NULL DEFAULT '',
+    attempt_count INTEGER NOT NULL DEFAULT 0,
+    created_at INTEGER NOT NULL,
+    blocked_reason TEXT,
+    status_note TEXT,
+    not_before INTEGER,
+    failure_reason TEXT,
+    PRIMARY KEY (repo_namespace, task_id),
+    CHECK(status <> 'cancelled' OR length(trim(COALESCE(status_note, ''))) > 0)
+);
+
+-- Cancellations and merged duplicates used to be stored as failed rows with a
+-- status note; they become real cancelled rows whose note is the reason.
+INSERT INTO backlog_tasks (
+    repo_namespace, task_id, kind, title, details, scope_key, priority, status, last_updated,
+    lease_owner, lease_expires_at, source, related_pr, related_branch, rationale, attempt_count,
+    created_at, blocked_reason, status_note, not_before, failure_reason
+)
+SELECT
+    repo_namespace, task_id, kind, title, details, scope_key, priority,
+    CASE
+        WHEN status = 'failed' AND status_note LIKE 'cancelled: %'
+            AND length(trim(substr(status_note, 12))) > 0
+            THEN 'cancelled'
+        WHEN status = 'failed' AND status_note LIKE 'duplicate of %' THEN 'cancelled'
+        ELSE status
+    END,
+    last_updated, lease_owner, lease_expires_at, source, related_pr, related_branch, rationale,
+    attempt_count, created_at, blocked_reason,
+    CASE
+        WHEN status = 'failed' AND status_note LIKE 'cancelled: %'
+            AND length(trim(substr(status_note, 12))) > 0
+            THEN substr(status_note, 12)
+        ELSE status_note
+    END,
+    not_before, failure_reason
+FROM backlog_tasks_v9;
+
+DROP TABLE backlog_tasks_v9;
+
+CREATE INDEX IF NOT EXISTS idx_backlog_claim_order
+    ON backlog_tasks(repo_namespace, priority, status, last_updated, created_at);
+
+CREATE INDEX IF NOT EXISTS idx_backlog_lease_expiry
+    ON backlog_tasks(repo_namespace, status, lease_expires_at);
+
+-- Dropping the old table dropped its triggers; re-create them and rebuild the
+-- index so it matches the copied rows.
+DELETE FROM backlog_tasks_fts;
+
+INSERT INTO backlog_tasks_fts (repo_namespace, task_id, title, details, rationale)
+SELECT repo_namespace, task_id, title, details, rationale
+FROM backlog_tasks;
+
+CREATE TRIGGER IF NOT EXISTS backlog_tasks_fts_insert AFTER INSERT ON backlog_tasks BEGIN
+    INSERT INTO backlog_tasks_fts (repo_namespace, task_id, title, details, rationale)
+    VALUES (new.repo_namespace, new.task_id, new.title, new.details, new.rationale);
+END;
+
+CREATE TRIGGER IF NOT EXISTS backlog_tasks_fts_delete AFTER DELETE ON backlog_tasks BEGIN
+    DELETE FROM backlog_tasks_fts
+    WHERE repo_namespace = old.repo_namespace AND task_id = old.task_id;
+END;
+
+CREATE TRIGGER IF NOT EXISTS backlog_tasks_fts_update
+AFTER UPDATE OF repo_namespace, task_id, title, details, rationale ON backlog_tasks BEGIN
+    DELETE FROM backlog_tasks_fts
+    WHERE repo_namespace = old.repo_namespace AND task_id = old.task_id;
+    INSERT INTO backlog_tasks_fts (repo_namespace, task_id, title, details, rationale)
+    VALUES (new.repo_namespace, new.task_id, new.title, new.details, new.rationale);
+END;
+
+-- Finished tasks moved out of the live table. Their task_ids stay reserved:
+-- an upsert of the same identity lands on the archived row instead of
+-- creating a new task.
+CREATE TABLE IF NOT EXISTS archived_tasks (
+    repo_namespace TEXT NOT NULL,
+    task_id TEXT NOT NULL,
+    kind TEXT NOT NULL,
+    title TEXT NOT NULL,
+    details TEXT NOT NULL,
+    scope_key TEXT NOT NULL,
+    priority TEXT NOT NULL,
+    status TEXT NOT NULL CHECK(status IN ('complete', 'cancelled')),
+    last_updated INTEGER NOT NULL,
+    lease_owner TEXT,
+    lease_expires_at INTEGER,
+    source TEXT NOT NULL,
+    related_pr INTEGER,
+    related_branch TEXT,
+    rationale TEXT NOT NULL,
+    attempt_count INTEGER NOT NULL,
+    created_at INTEGER NOT NULL,
+    blocked_reason TEXT,
+    status_note TEXT,
+    not_before INTEGER,
+    failure_reason TEXT,
+    archived_at INTEGER NOT NULL,
+    PRIMARY KEY (repo_namespace, task_id)
+);
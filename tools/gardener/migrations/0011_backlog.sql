@@ -0,0 +1,27 @@
+-- One row per finished worker run on a task: what it produced and how long it
+-- took. Rows follow their task through namespace moves and re-keys like
+-- task_events.
+CREATE TABLE IF NOT EXISTS task_outcomes (
+    outcome_id INTEGER PRIMARY KEY AUTOINCREMENT,
+    repo_namespace TEXT NOT NULL,
+    task_id TEXT NOT NULL,
+    title TEXT NOT NULL,
+    run_id TEXT,
+    worker_id TEXT NOT NULL,
+    attempt INTEGER NOT NULL,
+    final_state TEXT NOT NULL,
+    pr_number INTEGER,
+    branch TEXT,
+    merge_sha TEXT,
+    review_loops INTEGER NOT NULL DEFAULT 0,
+    failure_reason TEXT,
+    started_at INTEGER NOT NULL,
+    finished_at INTEGER NOT NULL,
+    duration_ms INTEGER NOT NULL
+);
+
+CREATE INDEX IF NOT EXISTS idx_task_outcomes_task
+    ON task_outcomes(repo_namespace, task_id, outcome_id);
+
+CREATE INDEX IF NOT EXISTS idx_task_outcomes_run
+    ON task_outcomes(repo_namespace, run_id, outcome_id);
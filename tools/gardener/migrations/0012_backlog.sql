@@ -0,0 +1,14 @@
+-- GitHub issues imported as tasks. The link makes the import idempotent across
+-- runs and tells the worker pool which issue to resolve when the task's PR
+-- merges. Links follow their task through namespace moves and re-keys.
+CREATE TABLE IF NOT EXISTS github_issue_links (
+    repo_namespace TEXT NOT NULL,
+    issue_number INTEGER NOT NULL,
+    task_id TEXT NOT NULL,
+    imported_at INTEGER NOT NULL,
+    resolved_at INTEGER,
+    PRIMARY KEY (repo_namespace, issue_number)
+);
+
+CREATE INDEX IF NOT EXISTS idx_github_issue_links_task
+    ON github_issue_links(repo_namespace, task_id);
@@ -1,7 +1,7 @@
 use crate::agent::{validate_model, AdapterCapabilities, AdapterContext, AgentAdapter};
 use crate::errors::GardenerError;
 use crate::logging::append_run_log;
-use crate::protocol::{map_claude_event, AgentEvent, AgentTerminal, StepResult};
+use crate::protocol::{claude_usage, map_claude_event, AgentEvent, AgentTerminal, StepResult};
 use crate::runtime::{ProcessRequest, ProcessRunner};
 use crate::types::AgentKind;
 use serde_json::{json, Value};
@@ -26,6 +26,7 @@ impl AgentAdapter for ClaudeAdapter {
             program: "claude".to_string(),
             args: vec!["--help".to_string()],
             cwd: None,
+            timeout: None,
         })?;
 
         let version = process_runner
@@ -33,6 +34,7 @@ impl AgentAdapter for ClaudeAdapter {
                 program: "claude".to_string(),
                 args: vec!["--version".to_string()],
                 cwd: None,
+                timeout: None,
             })
             .ok()
             .map(|out| out.stdout.trim().to_string())
@@ -120,6 +122,7 @@ impl AgentAdapter for ClaudeAdapter {
             program: "claude".to_string(),
             args,
             cwd: Some(context.cwd.clone()),
+            timeout: context.timeout,
         })?;
 
         let mut raw_events = Vec::new();
@@ -239,6 +242,7 @@ impl AgentAdapter for ClaudeAdapter {
                 events,
                 payload,
                 diagnostics,
+                usage: claude_usage(terminal_result),
             });
         }
 
@@ -315,6 +319,7 @@ mod tests {
             output_file: None,
             permissive_mode: true,
             max_turns: Some(4),
+            timeout: None,
         }
     }
 
@@ -1,7 +1,9 @@
 use crate::agent::{validate_model, AdapterCapabilities, AdapterContext, AgentAdapter};
 use crate::errors::GardenerError;
 use crate::logging::append_run_log;
-use crate::protocol::{map_codex_event, parse_json_records, AgentEvent, AgentTerminal, StepResult};
+use crate::protocol::{
+    codex_usage, map_codex_event, parse_json_records, AgentEvent, AgentTerminal, StepResult,
+};
 use crate::runtime::{ProcessRequest, ProcessRunner};
 use crate::types::AgentKind;
 use serde_json::{json, Value};
@@ -26,6 +28,7 @@ impl AgentAdapter for CodexAdapter {
             program: "codex".to_string(),
             args: vec!["--help".to_string()],
             cwd: None,
+            timeout: None,
         })?;
 
         let version = process_runner
@@ -33,6 +36,7 @@ impl AgentAdapter for CodexAdapter {
                 program: "codex".to_string(),
                 args: vec!["--version".to_string()],
                 cwd: None,
+                timeout: None,
             })
             .ok()
             .map(|out| out.stdout.trim().to_string())
@@ -132,6 +136,7 @@ impl AgentAdapter for CodexAdapter {
             program: "codex".to_string(),
             args,
             cwd: Some(context.cwd.clone()),
+            timeout: context.timeout,
         })?;
 
         let mut raw_events = Vec::new();
@@ -247,6 +252,7 @@ impl AgentAdapter for CodexAdapter {
                 events,
                 payload: failed.clone(),
                 diagnostics,
+                usage: codex_usage(&raw_events),
             });
         }
 
@@ -275,6 +281,7 @@ impl AgentAdapter for CodexAdapter {
                 events,
                 payload,
                 diagnostics,
+                usage: codex_usage(&raw_events),
             });
         }
 
@@ -318,6 +325,7 @@ impl AgentAdapter for CodexAdapter {
             events,
             payload: synthetic_payload,
             diagnostics,
+            usage: codex_usage(&raw_events),
         })
     }
 }
@@ -357,6 +365,7 @@ mod tests {
             output_file: Some(PathBuf::from("/repo/out.json")),
             permissive_mode: true,
             max_turns: None,
+            timeout: None,
         }
     }
 
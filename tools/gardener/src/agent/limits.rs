@@ -0,0 +1,282 @@
+use crate::config::AgentConfig;
+use crate::logging::append_run_log;
+use crate::types::{AgentKind, WorkerState};
+use serde_json::json;
+use std::collections::BTreeMap;
+use std::sync::{Arc, Condvar, Mutex, OnceLock, PoisonError};
+use std::time::{Duration, Instant};
+
+/// How often a queued turn re-checks whether its worker was told to stop.
+const QUEUE_POLL_INTERVAL: Duration = Duration::from_millis(200);
+
+/// Counting semaphore over named keys (`backend:codex`, `model:gpt-5`), each
+/// with its own cap. A turn takes every key it needs at once or none of them,
+/// so two turns can never hold half of each other's slots.
+#[derive(Debug, Clone, Default)]
+pub struct AgentLimiter {
+    inner: Arc<LimiterInner>,
+}
+
+#[derive(Debug, Default)]
+struct LimiterInner {
+    in_use: Mutex<BTreeMap<String, u32>>,
+    freed: Condvar,
+}
+
+/// A held concurrency slot; dropping it lets the next queued turn run.
+#[derive(Debug)]
+pub struct AgentSlot {
+    inner: Arc<LimiterInner>,
+    keys: Vec<String>,
+}
+
+impl Drop for AgentSlot {
+    fn drop(&mut self) {
+        if self.keys.is_empty() {
+            return;
+        }
+        let mut in_use = self
+            .inner
+            .in_use
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner);
+        for key in &self.keys {
+            if let Some(count) = in_use.get_mut(key) {
+                *count = count.saturating_sub(1);
+            }
+        }
+        drop(in_use);
+        self.inner.freed.notify_all();
+    }
+}
+
+impl AgentLimiter {
+    /// Blocks until a slot is free under every cap in `caps`, logging
+    /// `agent.turn.queued` once if it has to wait. Gives up and returns an
+    /// empty slot when `stop` turns true, so the caller's next process spawn
+    /// reports why the worker stopped.
+    pub fn acquire(
+        &self,
+        caps: &[(String, u32)],
+        worker_id: &str,
+        state: WorkerState,
+        backend: AgentKind,
+        model: &str,
+        stop: &dyn Fn() -> bool,
+    ) -> AgentSlot {
+        let mut slot = AgentSlot {
+            inner: Arc::clone(&self.inner),
+            keys: Vec::new(),
+        };
+        if caps.is_empty() {
+            return slot;
+        }
+        let mut in_use = self
+            .inner
+            .in_use
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner);
+        let mut queued_at: Option<Instant> = None;
+        loop {
+            let full = caps
+                .iter()
+                .find(|(key, cap)| in_use.get(key).copied().unwrap_or(0) >= *cap);
+            let Some((full_key, cap)) = full else {
+                break;
+            };
+            if stop() {
+                append_run_log(
+                    "warn",
+                    "agent.turn.queue_abandoned",
+                    json!({
+                        "worker_id": worker_id,
+                        "state": state.as_str(),
+                        "backend": backend.as_str(),
+                        "model": model,
+                    }),
+                );
+                return slot;
+            }
+            if queued_at.is_none() {
+                queued_at = Some(Instant::now());
+                append_run_log(
+                    "info",
+                    "agent.turn.queued",
+                    json!({
+                        "worker_id": worker_id,
+                        "state": state.as_str(),
+                        "backend": backend.as_str(),
+                        "model": model,
+                        "limit": full_key,
+                        "cap": cap,
+                    }),
+                );
+            }
+            in_use = self
+                .inner
+                .freed
+                .wait_timeout(in_use, QUEUE_POLL_INTERVAL)
+                .unwrap_or_else(PoisonError::into_inner)
+                .0;
+        }
+        for (key, _) in caps {
+            *in_use.entry(key.clone()).or_insert(0) += 1;
+        }
+        drop(in_use);
+        slot.keys = caps.iter().map(|(key, _)| key.clone()).collect();
+        if let Some(queued_at) = queued_at {
+            append_run_log(
+                "info",
+                "agent.turn.dequeued",
+                json!({
+                    "worker_id": worker_id,
+                    "state": state.as_str(),
+                    "backend": backend.as_str(),
+                    "model": model,
+                    "waited_ms": queued_at.elapsed().as_millis() as u64,
+                }),
+            );
+        }
+        slot
+    }
+}
+
+/// The caps from `[agent.concurrency]` and `[agent.model_concurrency]` that
+/// apply to a turn on `backend` with `model`.
+pub fn caps_for_turn(cfg: &AgentConfig, backend: AgentKind, model: &str) -> Vec<(String, u32)> {
+    let mut caps = Vec::new();
+    if let Some(cap) = cfg.concurrency.get(backend.as_str()) {
+        caps.push((format!("backend:{}", backend.as_str()), *cap));
+    }
+    if let Some(cap) = cfg.model_concurrency.get(model) {
+        caps.push((format!("model:{model}"), *cap));
+    }
+    caps
+}
+
+/// Takes a slot from the limiter shared by every worker in this process.
+pub fn acquire_agent_slot(
+    cfg: &AgentConfig,
+    backend: AgentKind,
+    model: &str,
+    worker_id: &str,
+    state: WorkerState,
+) -> AgentSlot {
+    static SHARED: OnceLock<AgentLimiter> = OnceLock::new();
+    SHARED.get_or_init(AgentLimiter::default).acquire(
+        &caps_for_turn(cfg, backend, model),
+        worker_id,
+        state,
+        backend,
+        model,
+        &crate::runtime::worker_stop_requested,
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{caps_for_turn, AgentLimiter};
+    use crate::config::AppConfig;
+    use crate::types::{AgentKind, WorkerState};
+    use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+    use std::sync::{Arc, Barrier};
+    use std::thread;
+    use std::time::Duration;
+
+    #[test]
+    fn caps_cover_the_backend_and_the_model_when_configured() {
+        let mut cfg = AppConfig::default().agent;
+        assert!(caps_for_turn(&cfg, AgentKind::Codex, "gpt-5").is_empty());
+        cfg.concurrency.insert("codex".to_string(), 2);
+        cfg.model_concurrency.insert("gpt-5".to_string(), 1);
+        assert_eq!(
+            caps_for_turn(&cfg, AgentKind::Codex, "gpt-5"),
+            vec![
+                ("backend:codex".to_string(), 2),
+                ("model:gpt-5".to_string(), 1)
+            ]
+        );
+        assert!(caps_for_turn(&cfg, AgentKind::Claude, "sonnet").is_empty());
+    }
+
+    #[test]
+    fn limiter_never_runs_more_turns_than_the_cap() {
+        let limiter = AgentLimiter::default();
+        let caps = vec![("backend:codex".to_string(), 2)];
+        let running = Arc::new(AtomicUsize::new(0));
+        let peak = Arc::new(AtomicUsize::new(0));
+        let start = Arc::new(Barrier::new(5));
+        let handles = (0..5)
+            .map(|idx| {
+                let limiter = limiter.clone();
+                let caps = caps.clone();
+                let running = Arc::clone(&running);
+                let peak = Arc::clone(&peak);
+                let start = Arc::clone(&start);
+                thread::spawn(move || {
+                    start.wait();
+                    let _slot = limiter.acquire(
+                        &caps,
+                        &format!("worker-{}", idx + 1),
+                        WorkerState::Doing,
+                        AgentKind::Codex,
+                        "gpt-5",
+                        &|| false,
+                    );
+                    let now = running.fetch_add(1, Ordering::SeqCst) + 1;
+                    peak.fetch_max(now, Ordering::SeqCst);
+                    thread::sleep(Duration::from_millis(20));
+                    running.fetch_sub(1, Ordering::SeqCst);
+                })
+            })
+            .collect::<Vec<_>>();
+        for handle in handles {
+            handle.join().expect("worker thread");
+        }
+        assert_eq!(peak.load(Ordering::SeqCst), 2);
+    }
+
+    #[test]
+    fn queued_turn_gives_up_once_its_worker_is_stopped() {
+        let limiter = AgentLimiter::default();
+        let caps = vec![("model:gpt-5".to_string(), 1)];
+        let held = limiter.acquire(
+            &caps,
+            "worker-1",
+            WorkerState::Doing,
+            AgentKind::Codex,
+            "gpt-5",
+            &|| false,
+        );
+        let stop = Arc::new(AtomicBool::new(false));
+        let waiter = {
+            let limiter = limiter.clone();
+            let caps = caps.clone();
+            let stop = Arc::clone(&stop);
+            thread::spawn(move || {
+                let slot = limiter.acquire(
+                    &caps,
+                    "worker-2",
+                    WorkerState::Reviewing,
+                    AgentKind::Codex,
+                    "gpt-5",
+                    &|| stop.load(Ordering::SeqCst),
+                );
+                slot.keys.is_empty()
+            })
+        };
+        thread::sleep(Duration::from_millis(50));
+        stop.store(true, Ordering::SeqCst);
+        assert!(waiter.join().expect("waiter thread"));
+        drop(held);
+        let next = limiter.acquire(
+            &caps,
+            "worker-3",
+            WorkerState::Doing,
+            AgentKind::Codex,
+            "gpt-5",
+            &|| false,
+        );
+        assert_eq!(next.keys, vec!["model:gpt-5".to_string()]);
+    }
+}
@@ -6,11 +6,12 @@ use crate::types::AgentKind;
 use serde::{Deserialize, Serialize};
 use serde_json::json;
 use std::path::{Path, PathBuf};
-use std::time::UNIX_EPOCH;
+use std::time::{Duration, UNIX_EPOCH};
 
 pub mod claude;
 pub mod codex;
 pub mod factory;
+pub mod limits;
 
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub struct AdapterContext {
@@ -25,6 +26,8 @@ pub struct AdapterContext {
     pub output_file: Option<PathBuf>,
     pub permissive_mode: bool,
     pub max_turns: Option<u32>,
+    /// Wall-clock budget for the agent process; see `ProcessRequest::timeout`.
+    pub timeout: Option<Duration>,
 }
 
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, Default)]
@@ -203,6 +206,7 @@ mod tests {
                 events: vec![],
                 payload: json!({}),
                 diagnostics: vec![],
+                usage: crate::protocol::TokenUsage::default(),
             })
         }
     }
@@ -0,0 +1,1307 @@
+use crate::backlog_store::{
+    compute_task_id_from_new_task, BacklogStore, BacklogTask, ChangeOutcome, ImportAction,
+    ImportMode, ImportReport, NewTask, TaskEdit, TaskFilter, TaskOutcome, TaskStatus,
+};
+use crate::backlog_snapshot::{
+    parse_snapshot, plan_snapshot_apply, render_event, SnapshotChange, SNAPSHOT_PATH,
+};
+use crate::errors::GardenerError;
+use crate::logging::append_run_log;
+use crate::priority::Priority;
+use crate::replay::recording::BacklogTaskRecord;
+use crate::runtime::{FileSystem, Terminal};
+use crate::task_identity::{compute_task_id, TaskIdentity, TaskKind};
+use clap::{Args, Subcommand, ValueEnum};
+use serde_json::json;
+use std::path::PathBuf;
+
+const SHORT_ID_CHARS: usize = 12;
+const TITLE_COLUMN_CHARS: usize = 60;
+
+#[derive(Debug, Clone, Subcommand)]
+pub enum BacklogCommand {
+    /// List tasks in this repository's backlog.
+    List {
+        #[command(flatten)]
+        filter: FilterArgs,
+        #[arg(long, value_enum, default_value_t = OutputFormat::Table)]
+        format: OutputFormat,
+    },
+    /// Full-text search over task titles, details and rationale, best match
+    /// first. Each word matches as a prefix.
+    Search {
+        query: String,
+        #[command(flatten)]
+        filter: FilterArgs,
+        #[arg(long, value_enum, default_value_t = OutputFormat::Table)]
+        format: OutputFormat,
+    },
+    /// Show one task, including its dependencies and notes.
+    Show {
+        /// Full task id or a unique prefix.
+        task_id: String,
+        #[arg(long, value_enum, default_value_t = OutputFormat::Table)]
+        format: OutputFormat,
+    },
+    /// Show what finished worker runs produced: final state, PR, merge commit,
+    /// review loops and duration, for one task or one gardener run.
+    Outcomes {
+        /// Task id or unique prefix; archived task ids are accepted in full.
+        #[arg(long, conflicts_with = "run", required_unless_present = "run")]
+        task: Option<String>,
+        /// Run id from the run log.
+        #[arg(long)]
+        run: Option<String>,
+        #[arg(long, value_enum, default_value_t = OutputFormat::Table)]
+        format: OutputFormat,
+    },
+    /// Add a task using the store's dedupe and priority-upgrade rules.
+    Add(AddArgs),
+    /// Edit a task. Changing kind, title or scope re-keys it to a new task id.
+    Edit(EditArgs),
+    /// Set a task's priority, including lowering it.
+    Reprioritize {
+        task_id: String,
+        #[arg(value_enum)]
+        priority: CliPriority,
+    },
+    /// Mark a task cancelled (won't fix). The reason is required and kept on
+    /// the task.
+    Cancel {
+        task_id: String,
+        #[arg(long)]
+        reason: String,
+    },
+    /// Put a complete, failed, unresolved, exhausted or cancelled task back in
+    /// the queue with a fresh retry budget.
+    Requeue { task_id: String },
+    /// Move complete and cancelled tasks out of the live backlog. Their ids stay
+    /// reserved, so adding the same task again does not reopen it.
+    Archive {
+        /// Only archive tasks last updated more than this many days ago.
+        #[arg(long, default_value_t = 30)]
+        older_than_days: u32,
+    },
+    /// Find open tasks that are reworded copies of each other and, with
+    /// `--apply`, merge each duplicate into its cluster's keeper.
+    Dedupe {
+        #[arg(long)]
+        apply: bool,
+    },
+    /// Load full task rows from a JSONL file written by `export`.
+    Import {
+        path: PathBuf,
+        /// `merge` keeps rows the file does not mention and stored rows that are
+        /// newer; `replace` makes the backlog match the file exactly.
+        #[arg(long, value_enum, default_value_t = CliImportMode::Merge)]
+        mode: CliImportMode,
+        /// Print the diff without changing the backlog.
+        #[arg(long)]
+        dry_run: bool,
+    },
+    /// Reconcile an edited markdown snapshot (written by `--sync-only`) into the
+    /// store: reprioritize, cancel, requeue and add rows without a task id. The
+    /// diff is printed before anything changes.
+    Apply {
+        #[arg(default_value = SNAPSHOT_PATH)]
+        path: PathBuf,
+        /// Print the diff without changing the backlog.
+        #[arg(long)]
+        dry_run: bool,
+    },
+    /// Write full task rows as JSONL backlog task records.
+    Export {
+        #[command(flatten)]
+        filter: FilterArgs,
+        /// Write to this file instead of stdout.
+        #[arg(long)]
+        output: Option<PathBuf>,
+    },
+}
+
+#[derive(Debug, Clone, Default, Args)]
+pub struct FilterArgs {
+    #[arg(long = "status", value_enum)]
+    pub statuses: Vec<CliTaskStatus>,
+    #[arg(long = "priority", value_enum)]
+    pub priorities: Vec<CliPriority>,
+    #[arg(long = "kind", value_enum)]
+    pub kinds: Vec<CliTaskKind>,
+    /// Scope prefix, e.g. `backlog`.
+    #[arg(long = "scope")]
+    pub scopes: Vec<String>,
+    /// Source prefix, e.g. `seed` or `manual`.
+    #[arg(long = "source")]
+    pub sources: Vec<String>,
+}
+
+#[derive(Debug, Clone, Args)]
+pub struct AddArgs {
+    #[arg(long)]
+    pub title: String,
+    #[arg(long)]
+    pub details: String,
+    #[arg(long, default_value = "")]
+    pub rationale: String,
+    #[arg(long = "scope", default_value = "runtime")]
+    pub scope_key: String,
+    #[arg(long, value_enum, default_value_t = CliPriority::P1)]
+    pub priority: CliPriority,
+    #[arg(long, value_enum, default_value_t = CliTaskKind::Feature)]
+    pub kind: CliTaskKind,
+    #[arg(long, default_value = "manual")]
+    pub source: String,
+    /// Task id (or unique prefix) that must complete first. Repeatable.
+    #[arg(long = "blocked-by")]
+    pub blocked_by: Vec<String>,
+}
+
+#[derive(Debug, Clone, Args)]
+pub struct EditArgs {
+    pub task_id: String,
+    #[arg(long)]
+    pub title: Option<String>,
+    #[arg(long)]
+    pub details: Option<String>,
+    #[arg(long)]
+    pub rationale: Option<String>,
+    #[arg(long = "scope")]
+    pub scope_key: Option<String>,
+    #[arg(long, value_enum)]
+    pub kind: Option<CliTaskKind>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, ValueEnum)]
+pub enum OutputFormat {
+    #[default]
+    Table,
+    Json,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum CliPriority {
+    #[value(name = "P0", alias = "p0")]
+    P0,
+    #[value(name = "P1", alias = "p1")]
+    P1,
+    #[value(name = "P2", alias = "p2")]
+    P2,
+}
+
+impl From<CliPriority> for Priority {
+    fn from(value: CliPriority) -> Self {
+        match value {
+            CliPriority::P0 => Priority::P0,
+            CliPriority::P1 => Priority::P1,
+            CliPriority::P2 => Priority::P2,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum CliImportMode {
+    Merge,
+    Replace,
+}
+
+impl From<CliImportMode> for ImportMode {
+    fn from(value: CliImportMode) -> Self {
+        match value {
+            CliImportMode::Merge => ImportMode::Merge,
+            CliImportMode::Replace => ImportMode::Replace,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum CliTaskStatus {
+    Ready,
+    Leased,
+    InProgress,
+    Complete,
+    Failed,
+    Unresolved,
+    Exhausted,
+    Cancelled,
+}
+
+impl From<CliTaskStatus> for TaskStatus {
+    fn from(value: CliTaskStatus) -> Self {
+        match value {
+            CliTaskStatus::Ready => TaskStatus::Ready,
+            CliTaskStatus::Leased => TaskStatus::Leased,
+            CliTaskStatus::InProgress => TaskStatus::InProgress,
+            CliTaskStatus::Complete => TaskStatus::Complete,
+            CliTaskStatus::Failed => TaskStatus::Failed,
+            CliTaskStatus::Unresolved => TaskStatus::Unresolved,
+            CliTaskStatus::Exhausted => TaskStatus::Exhausted,
+            CliTaskStatus::Cancelled => TaskStatus::Cancelled,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum CliTaskKind {
+    QualityGap,
+    MergeConflict,
+    PrCollision,
+    Feature,
+    Bugfix,
+    Maintenance,
+    Infra,
+}
+
+impl From<CliTaskKind> for TaskKind {
+    fn from(value: CliTaskKind) -> Self {
+        match value {
+            CliTaskKind::QualityGap => TaskKind::QualityGap,
+            CliTaskKind::MergeConflict => TaskKind::MergeConflict,
+            CliTaskKind::PrCollision => TaskKind::PrCollision,
+            CliTaskKind::Feature => TaskKind::Feature,
+            CliTaskKind::Bugfix => TaskKind::Bugfix,
+            CliTaskKind::Maintenance => TaskKind::Maintenance,
+            CliTaskKind::Infra => TaskKind::Infra,
+        }
+    }
+}
+
+impl From<&FilterArgs> for TaskFilter {
+    fn from(args: &FilterArgs) -> Self {
+        Self {
+            task_ids: Vec::new(),
+            statuses: args.statuses.iter().copied().map(Into::into).collect(),
+            priorities: args.priorities.iter().copied().map(Into::into).collect(),
+            kinds: args.kinds.iter().copied().map(Into::into).collect(),
+            scopes: args.scopes.clone(),
+            sources: args.sources.clone(),
+        }
+    }
+}
+
+/// Claim filters accepted by a worker run.
+#[derive(Debug, Clone, Default, Args)]
+pub struct ClaimFilterArgs {
+    /// Only claim tasks of this priority. Repeatable.
+    #[arg(long = "priority", value_enum)]
+    pub priorities: Vec<CliPriority>,
+    /// Only claim tasks of this kind. Repeatable.
+    #[arg(long = "kind", value_enum)]
+    pub kinds: Vec<CliTaskKind>,
+    /// Only claim tasks whose scope starts with this prefix. Repeatable.
+    #[arg(long = "scope")]
+    pub scopes: Vec<String>,
+    /// Only claim tasks whose source starts with this prefix. Repeatable.
+    #[arg(long = "source")]
+    pub sources: Vec<String>,
+}
+
+impl From<&ClaimFilterArgs> for TaskFilter {
+    fn from(args: &ClaimFilterArgs) -> Self {
+        Self {
+            priorities: args.priorities.iter().copied().map(Into::into).collect(),
+            kinds: args.kinds.iter().copied().map(Into::into).collect(),
+            scopes: args.scopes.clone(),
+            sources: args.sources.clone(),
+            ..Self::default()
+        }
+    }
+}
+
+/// Which backlog tasks a worker run may claim.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct RunSelection {
+    /// An ad-hoc task to add to the backlog and run on its own.
+    pub adhoc: Option<NewTask>,
+    /// A full task id or unique prefix to run on its own.
+    pub task_id: Option<String>,
+    pub filter: TaskFilter,
+}
+
+impl RunSelection {
+    pub fn new(adhoc_summary: Option<&str>, task_id: Option<&str>, filter: TaskFilter) -> Self {
+        Self {
+            adhoc: adhoc_summary.map(|summary| NewTask {
+                kind: TaskKind::Feature,
+                title: summary.to_string(),
+                details: summary.to_string(),
+                scope_key: "runtime".to_string(),
+                rationale: "requested with --task".to_string(),
+                priority: Priority::P1,
+                source: "manual".to_string(),
+                related_pr: None,
+                related_branch: None,
+                blocked_by: Vec::new(),
+            }),
+            task_id: task_id.map(str::to_string),
+            filter,
+        }
+    }
+
+    /// The filter claims should use, or `None` when every ready task may run.
+    /// The ad-hoc task is upserted first, on its own id rather than merged into
+    /// a near-duplicate, and a selected task must be ready, since the run would
+    /// otherwise end without claiming anything.
+    pub fn claim_filter(&self, store: &BacklogStore) -> Result<Option<TaskFilter>, GardenerError> {
+        let selected = match (&self.adhoc, &self.task_id) {
+            (Some(task), _) => Some(store.upsert_task_exact(task.clone())?),
+            (None, Some(task_id)) => Some(resolve_task(store, task_id)?),
+            (None, None) => None,
+        };
+        append_run_log(
+            "info",
+            "backlog_cli.run_selection.resolved",
+            json!({
+                "adhoc": self.adhoc.is_some(),
+                "task_id": selected.as_ref().map(|task| task.task_id.as_str()),
+                "filter": format!("{:?}", self.filter),
+            }),
+        );
+        self.narrow_to(selected)
+    }
+
+    /// The filter [`Self::claim_filter`] would return, without writing: an
+    /// ad-hoc task the backlog does not hold yet is selected by the id it
+    /// would be added under.
+    pub fn preview_filter(
+        &self,
+        store: &BacklogStore,
+    ) -> Result<Option<TaskFilter>, GardenerError> {
+        let selected = match (&self.adhoc, &self.task_id) {
+            (Some(task), _) => {
+                let task_id = compute_task_id_from_new_task(task);
+                match store.get_task(&task_id)? {
+                    Some(stored) => Some(stored),
+                    None => {
+                        return Ok(Some(TaskFilter {
+                            task_ids: vec![task_id],
+                            ..self.filter.clone()
+                        }))
+                    }
+                }
+            }
+            (None, Some(task_id)) => Some(resolve_task(store, task_id)?),
+            (None, None) => None,
+        };
+        append_run_log(
+            "info",
+            "backlog_cli.run_selection.previewed",
+            json!({
+                "adhoc": self.adhoc.is_some(),
+                "task_id": selected.as_ref().map(|task| task.task_id.as_str()),
+                "filter": format!("{:?}", self.filter),
+            }),
+        );
+        self.narrow_to(selected)
+    }
+
+    fn narrow_to(
+        &self,
+        selected: Option<BacklogTask>,
+    ) -> Result<Option<TaskFilter>, GardenerError> {
+        let Some(task) = selected else {
+            return Ok((self.filter != TaskFilter::default()).then(|| self.filter.clone()));
+        };
+        if task.status != TaskStatus::Ready {
+            return Err(GardenerError::Cli(format!(
+                "task {} is {}, not ready to run",
+                task.task_id,
+                task.status.as_str()
+            )));
+        }
+        Ok(Some(TaskFilter {
+            task_ids: vec![task.task_id],
+            ..self.filter.clone()
+        }))
+    }
+}
+
+pub fn run_backlog_command(
+    store: &BacklogStore,
+    command: &BacklogCommand,
+    terminal: &dyn Terminal,
+    file_system: &dyn FileSystem,
+) -> Result<i32, GardenerError> {
+    append_run_log(
+        "info",
+        "backlog_cli.command.started",
+        json!({
+            "command": format!("{command:?}"),
+            "namespace": store.namespace(),
+        }),
+    );
+    match command {
+        BacklogCommand::List { filter, format } => {
+            let tasks = store.list_tasks_filtered(&filter.into())?;
+            write_tasks(terminal, tasks, *format)?;
+        }
+        BacklogCommand::Search {
+            query,
+            filter,
+            format,
+        } => {
+            let tasks = store.search(query, &filter.into())?;
+            write_tasks(terminal, tasks, *format)?;
+        }
+        BacklogCommand::Show { task_id, format } => {
+            let task = resolve_task(store, task_id)?;
+            match format {
+                OutputFormat::Table => {
+                    for line in render_task_detail(&task) {
+                        terminal.write_line(&line)?;
+                    }
+                    let history = store.history(&task.task_id)?;
+                    if !history.is_empty() {
+                        terminal.write_line("history:")?;
+                        for event in &history {
+                            terminal.write_line(&format!("  {}", render_event(event)))?;
+                        }
+                    }
+                }
+                OutputFormat::Json => {
+                    terminal.write_line(&to_json(&BacklogTaskRecord::from(task))?)?;
+                }
+            }
+        }
+        BacklogCommand::Outcomes { task, run, format } => {
+            let outcomes = match (task, run) {
+                (Some(task_id), _) => {
+                    let task_id = match resolve_task(store, task_id) {
+                        Ok(task) => task.task_id,
+                        Err(_) if store.get_archived_task(task_id)?.is_some() => task_id.clone(),
+                        Err(err) => return Err(err),
+                    };
+                    store.outcomes_for_task(&task_id)?
+                }
+                (None, Some(run_id)) => store.outcomes_for_run(run_id)?,
+                (None, None) => {
+                    return Err(GardenerError::Cli(
+                        "backlog outcomes needs --task or --run".to_string(),
+                    ))
+                }
+            };
+            match format {
+                OutputFormat::Table => {
+                    for line in render_outcome_table(&outcomes) {
+                        terminal.write_line(&line)?;
+                    }
+                }
+                OutputFormat::Json => terminal.write_line(&to_json(&outcomes)?)?,
+            }
+        }
+        BacklogCommand::Add(args) => {
+            let blocked_by = args
+                .blocked_by
+                .iter()
+                .map(|id| resolve_task(store, id).map(|task| task.task_id))
+                .collect::<Result<Vec<_>, _>>()?;
+            let row = store.upsert_task(NewTask {
+                kind: args.kind.into(),
+                title: args.title.clone(),
+                details: args.details.clone(),
+                rationale: args.rationale.clone(),
+                scope_key: args.scope_key.clone(),
+                priority: args.priority.into(),
+                source: args.source.clone(),
+                related_pr: None,
+                related_branch: None,
+                blocked_by,
+            })?;
+            let merged = if row.task_id == compute_task_id_from_args(args) {
+                ""
+            } else {
+                " (merged into near-duplicate)"
+            };
+            terminal.write_line(&format!(
+                "upserted {} [{} {}] {}{merged}",
+                row.task_id,
+                row.priority.as_str(),
+                row.status.as_str(),
+                row.title
+            ))?;
+        }
+        BacklogCommand::Edit(args) => {
+            let task = resolve_task(store, &args.task_id)?;
+            let edit = TaskEdit {
+                kind: args.kind.map(Into::into),
+                title: args.title.clone(),
+                details: args.details.clone(),
+                rationale: args.rationale.clone(),
+                scope_key: args.scope_key.clone(),
+            };
+            let row = store
+                .edit_task(&task.task_id, edit)?
+                .ok_or_else(|| not_found(&task.task_id))?;
+            if row.task_id == task.task_id {
+                terminal.write_line(&format!("edited {}", row.task_id))?;
+            } else {
+                terminal.write_line(&format!(
+                    "edited {} (re-keyed to {})",
+                    task.task_id, row.task_id
+                ))?;
+            }
+        }
+        BacklogCommand::Reprioritize { task_id, priority } => {
+            let task = resolve_task(store, task_id)?;
+            let priority = Priority::from(*priority);
+            if !store.reprioritize_task(&task.task_id, priority)? {
+                // The row was archived or removed after it was looked up.
+                return Err(GardenerError::Cli(format!(
+                    "task {} is no longer in the live backlog and was not reprioritized",
+                    task.task_id
+                )));
+            }
+            terminal.write_line(&format!(
+                "reprioritized {} {} -> {}",
+                task.task_id,
+                task.priority.as_str(),
+                priority.as_str()
+            ))?;
+        }
+        BacklogCommand::Cancel { task_id, reason } => {
+            let task = resolve_task(store, task_id)?;
+            if !store.cancel_task(&task.task_id, reason)? {
+                return Err(GardenerError::Cli(format!(
+                    "task {} is {} and cannot be cancelled while leased",
+                    task.task_id,
+                    task.status.as_str()
+                )));
+            }
+            terminal.write_line(&format!("cancelled {}: {reason}", task.task_id))?;
+        }
+        BacklogCommand::Archive { older_than_days } => {
+            let archived = store.archive_finished_tasks(*older_than_days)?;
+            for task_id in &archived {
+                terminal.write_line(&format!("archived {task_id}"))?;
+            }
+            terminal.write_line(&format!(
+                "archived {} task(s) finished more than {older_than_days} day(s) ago",
+                archived.len()
+            ))?;
+        }
+        BacklogCommand::Requeue { task_id } => {
+            let task = resolve_task(store, task_id)?;
+            if !store.requeue_task(&task.task_id)? {
+                return Err(GardenerError::Cli(format!(
+                    "task {} is {}; only complete, failed, unresolved, exhausted or cancelled \
+                     tasks can be requeued",
+                    task.task_id,
+                    task.status.as_str()
+                )));
+            }
+            terminal.write_line(&format!("requeued {}", task.task_id))?;
+        }
+        BacklogCommand::Dedupe { apply } => {
+            let clusters = store.find_duplicate_clusters()?;
+            let mut duplicates = 0usize;
+            for cluster in &clusters {
+                terminal.write_line(&format!("scope {}", cluster.keeper.scope_key))?;
+                terminal
+                    .write_line(&format!("  keep   {}", render_cluster_task(&cluster.keeper)))?;
+                for (task, similarity) in &cluster.duplicates {
+                    duplicates += 1;
+                    let merged = *apply
+                        && store.merge_duplicate(&cluster.keeper.task_id, &task.task_id)?;
+                    terminal.write_line(&format!(
+                        "  {} {} (similarity {similarity:.2})",
+                        if merged { "merged" } else { "dup   " },
+                        render_cluster_task(task)
+                    ))?;
+                }
+            }
+            terminal.write_line(&if *apply {
+                format!("merged {duplicates} duplicate(s) in {} cluster(s)", clusters.len())
+            } else {
+                format!(
+                    "{duplicates} duplicate(s) in {} cluster(s); rerun with --apply to merge them",
+                    clusters.len()
+                )
+            })?;
+        }
+        BacklogCommand::Import {
+            path,
+            mode,
+            dry_run,
+        } => {
+            let contents = file_system.read_to_string(path)?;
+            let report = store.import_jsonl(&contents, (*mode).into(), *dry_run)?;
+            for line in render_import_report(&report) {
+                terminal.write_line(&line)?;
+            }
+        }
+        BacklogCommand::Apply { path, dry_run } => {
+            let rows = parse_snapshot(&file_system.read_to_string(path)?)?;
+            let changes = plan_snapshot_apply(&rows, &store.list_tasks()?)?;
+            for change in &changes {
+                terminal.write_line(&change.render())?;
+            }
+            if *dry_run || changes.is_empty() {
+                terminal.write_line(&format!(
+                    "{} change(s) in {}{}",
+                    changes.len(),
+                    path.display(),
+                    if *dry_run { "; rerun without --dry-run to apply" } else { "" }
+                ))?;
+                return Ok(0);
+            }
+            let outcomes = store.apply_changes(
+                changes.iter().map(SnapshotChange::to_backlog_change).collect(),
+            )?;
+            if let Some(stale) = outcomes
+                .iter()
+                .position(|outcome| *outcome == ChangeOutcome::Stale)
+            {
+                // The task moved on (e.g. a worker leased it) since the diff was planned.
+                return Err(GardenerError::Cli(format!(
+                    "could not apply `{}`; the task changed since the snapshot was read, so \
+                     nothing was applied",
+                    changes[stale].render()
+                )));
+            }
+            for outcome in &outcomes {
+                match outcome {
+                    ChangeOutcome::Added(task_id) => {
+                        terminal.write_line(&format!("added {task_id}"))?
+                    }
+                    ChangeOutcome::AlreadyPresent(task_id) => {
+                        terminal.write_line(&format!("already in the backlog as {task_id}"))?
+                    }
+                    ChangeOutcome::Applied | ChangeOutcome::Stale => {}
+                }
+            }
+            terminal.write_line(&format!(
+                "applied {} change(s); rerun `gardener --sync-only` to refresh the snapshot",
+                changes.len()
+            ))?;
+        }
+        BacklogCommand::Export { filter, output } => {
+            let rendered = store.export_jsonl(&filter.into())?;
+            match output {
+                Some(path) => {
+                    file_system.write_string(path, &rendered)?;
+                    terminal.write_line(&format!(
+                        "exported {} task(s) to {}",
+                        rendered.lines().count(),
+                        path.display()
+                    ))?;
+                }
+                None => {
+                    for line in rendered.lines() {
+                        terminal.write_line(line)?;
+                    }
+                }
+            }
+        }
+    }
+    Ok(0)
+}
+
+/// Finds a task by full id or unique id prefix.
+pub fn resolve_task(store: &BacklogStore, task_id: &str) -> Result<BacklogTask, GardenerError> {
+    if let Some(task) = store.get_task(task_id)? {
+        return Ok(task);
+    }
+    let mut matches = store
+        .list_tasks()?
+        .into_iter()
+        .filter(|task| !task_id.is_empty() && task.task_id.starts_with(task_id))
+        .collect::<Vec<_>>();
+    match matches.len() {
+        0 => Err(not_found(task_id)),
+        1 => Ok(matches.remove(0)),
+        count => {
+            append_run_log(
+                "warn",
+                "backlog_cli.task_prefix.ambiguous",
+                json!({ "prefix": task_id, "matches": count }),
+            );
+            Err(GardenerError::Cli(format!(
+                "task id prefix {task_id} matches {count} tasks"
+            )))
+        }
+    }
+}
+
+fn not_found(task_id: &str) -> GardenerError {
+    GardenerError::Cli(format!("no backlog task matches {task_id}"))
+}
+
+fn to_json<T: serde::Serialize>(value: &T) -> Result<String, GardenerError> {
+    serde_json::to_string(value).map_err(|e| GardenerError::OutputEnvelope(e.to_string()))
+}
+
+fn render_cluster_task(task: &BacklogTask) -> String {
+    format!(
+        "{:<12}  [{} {}] {}",
+        truncate(&task.task_id, SHORT_ID_CHARS),
+        task.priority.as_str(),
+        task.status.as_str(),
+        truncate(&task.title, TITLE_COLUMN_CHARS)
+    )
+}
+
+fn compute_task_id_from_args(args: &AddArgs) -> String {
+    compute_task_id(TaskIdentity {
+        kind: args.kind.into(),
+        title: args.title.clone(),
+        scope_key: args.scope_key.clone(),
+        related_pr: None,
+        related_branch: None,
+    })
+}
+
+/// One line per row the import touches or skips, then a summary. Unchanged
+/// rows are only counted.
+pub fn render_import_report(report: &ImportReport) -> Vec<String> {
+    let mut lines = Vec::new();
+    for change in &report.changes {
+        let marker = match change.action {
+            ImportAction::Add => "+",
+            ImportAction::Update => "~",
+            ImportAction::Keep => "=",
+            ImportAction::Remove => "-",
+            ImportAction::Unchanged => continue,
+        };
+        let mut line = format!(
+            "{marker} {:<6}  {:<12}  {}",
+            change.action.as_str(),
+            truncate(&change.task_id, SHORT_ID_CHARS),
+            truncate(&change.title, TITLE_COLUMN_CHARS)
+        );
+        if !change.fields.is_empty() {
+            line.push_str(&format!(" [{}]", change.fields.join(", ")));
+        }
+        if let Some(note) = &change.note {
+            line.push_str(&format!(" ({note})"));
+        }
+        lines.push(line);
+    }
+    lines.push(format!(
+        "{}{} added, {} updated, {} unchanged, {} kept, {} removed",
+        if report.applied { "imported: " } else { "dry run, nothing written: " },
+        report.count(ImportAction::Add),
+        report.count(ImportAction::Update),
+        report.count(ImportAction::Unchanged),
+        report.count(ImportAction::Keep),
+        report.count(ImportAction::Remove)
+    ));
+    lines
+}
+
+fn write_tasks(
+    terminal: &dyn Terminal,
+    tasks: Vec<BacklogTask>,
+    format: OutputFormat,
+) -> Result<(), GardenerError> {
+    append_run_log(
+        "debug",
+        "backlog_cli.tasks.written",
+        json!({ "task_count": tasks.len(), "format": format!("{format:?}") }),
+    );
+    match format {
+        OutputFormat::Table => {
+            for line in render_task_table(&tasks) {
+                terminal.write_line(&line)?;
+            }
+        }
+        OutputFormat::Json => {
+            let records = tasks
+                .into_iter()
+                .map(BacklogTaskRecord::from)
+                .collect::<Vec<_>>();
+            terminal.write_line(&to_json(&records)?)?;
+        }
+    }
+    Ok(())
+}
+
+pub fn render_outcome_table(outcomes: &[TaskOutcome]) -> Vec<String> {
+    let mut lines = vec![format!(
+        "{:<12}  {:<10}  {:<6}  {:<10}  {:<5}  {:>8}  TITLE",
+        "TASK_ID", "STATE", "PR", "MERGE", "LOOPS", "DURATION"
+    )];
+    for outcome in outcomes {
+        let mut title = truncate(&outcome.title, TITLE_COLUMN_CHARS);
+        if let Some(reason) = &outcome.failure_reason {
+            title.push_str(&format!(" ({})", truncate(reason, TITLE_COLUMN_CHARS)));
+        }
+        lines.push(format!(
+            "{:<12}  {:<10}  {:<6}  {:<10}  {:<5}  {:>8}  {}",
+            truncate(&outcome.task_id, SHORT_ID_CHARS),
+            outcome.final_state,
+            outcome
+                .pr_number
+                .map_or_else(|| "-".to_string(), |pr| format!("#{pr}")),
+            outcome
+                .merge_sha
+                .as_deref()
+                .map_or_else(|| "-".to_string(), |sha| truncate(sha, 10)),
+            outcome.review_loops,
+            format!("{}s", outcome.duration_ms / 1000),
+            title
+        ));
+    }
+    lines
+}
+
+pub fn render_task_table(tasks: &[BacklogTask]) -> Vec<String> {
+    let mut lines = vec![format!(
+        "{:<12}  {:<3}  {:<11}  {:<14}  {:<20}  TITLE",
+        "TASK_ID", "PRI", "STATUS", "KIND", "SOURCE"
+    )];
+    for task in tasks {
+        let mut title = truncate(&task.title, TITLE_COLUMN_CHARS);
+        if !task.blocked_by.is_empty() {
+            title.push_str(&format!(" (blocked by {})", task.blocked_by.len()));
+        }
+        lines.push(format!(
+            "{:<12}  {:<3}  {:<11}  {:<14}  {:<20}  {}",
+            truncate(&task.task_id, SHORT_ID_CHARS),
+            task.priority.as_str(),
+            task.status.as_str(),
+            task.kind.as_str(),
+            truncate(&task.source, 20),
+            title
+        ));
+    }
+    lines.push(format!("{} task(s)", tasks.len()));
+    lines
+}
+
+pub fn render_task_detail(task: &BacklogTask) -> Vec<String> {
+    let mut lines = vec![
+        format!("task_id:    {}", task.task_id),
+        format!("title:      {}", task.title),
+        format!("kind:       {}", task.kind.as_str()),
+        format!("priority:   {}", task.priority.as_str()),
+        format!("status:     {}", task.status.as_str()),
+        format!("scope:      {}", task.scope_key),
+        format!("source:     {}", task.source),
+        format!("attempts:   {}", task.attempt_count),
+    ];
+    if let Some(owner) = &task.lease_owner {
+        lines.push(format!("lease:      {owner}"));
+    }
+    if let Some(pr) = task.related_pr {
+        lines.push(format!("pr:         #{pr}"));
+    }
+    if !task.blocked_by.is_empty() {
+        lines.push(format!("blocked_by: {}", task.blocked_by.join(", ")));
+    }
+    if let Some(reason) = &task.blocked_reason {
+        lines.push(format!("blocked:    {reason}"));
+    }
+    if let Some(note) = &task.status_note {
+        lines.push(format!("note:       {note}"));
+    }
+    if let Some(not_before) = task.not_before {
+        lines.push(format!("not_before: {not_before}"));
+    }
+    if let Some(reason) = &task.failure_reason {
+        lines.push(format!("failure:    {reason}"));
+    }
+    lines.push(format!("details:    {}", task.details));
+    if !task.rationale.is_empty() {
+        lines.push(format!("rationale:  {}", task.rationale));
+    }
+    lines
+}
+
+fn truncate(value: &str, max_chars: usize) -> String {
+    if value.chars().count() <= max_chars {
+        return value.to_string();
+    }
+    value.chars().take(max_chars).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{run_backlog_command, AddArgs, BacklogCommand, CliPriority, CliTaskKind};
+    use super::{CliImportMode, ClaimFilterArgs, EditArgs, FilterArgs, OutputFormat, RunSelection};
+    use crate::backlog_snapshot::render_markdown;
+    use crate::backlog_store::{
+        BacklogStore, ClaimOptions, NewTaskOutcome, TaskFilter, TaskStatus,
+    };
+    use crate::task_identity::TaskKind;
+    use crate::priority::Priority;
+    use crate::runtime::{FakeFileSystem, FileSystem, FakeTerminal};
+    use std::path::PathBuf;
+    use tempfile::TempDir;
+
+    fn add(title: &str, priority: CliPriority, blocked_by: Vec<String>) -> BacklogCommand {
+        BacklogCommand::Add(AddArgs {
+            title: title.to_string(),
+            details: format!("{title} details"),
+            rationale: String::new(),
+            scope_key: "runtime".to_string(),
+            priority,
+            kind: CliTaskKind::Feature,
+            source: "manual".to_string(),
+            blocked_by,
+        })
+    }
+
+    fn run(store: &BacklogStore, terminal: &FakeTerminal, command: BacklogCommand) {
+        let fs = FakeFileSystem::default();
+        run_backlog_command(store, &command, terminal, &fs).expect("command succeeds");
+    }
+
+    #[test]
+    fn add_list_and_mutations_go_through_the_store() {
+        let dir = TempDir::new().expect("tempdir");
+        let store = BacklogStore::open(dir.path().join("backlog.sqlite")).expect("store");
+        let terminal = FakeTerminal::new(false);
+
+        run(&store, &terminal, add("Fix flaky test", CliPriority::P0, Vec::new()));
+        let base = store.list_tasks().expect("list").remove(0);
+        run(
+            &store,
+            &terminal,
+            add("Follow up", CliPriority::P2, vec![base.task_id[..8].to_string()]),
+        );
+
+        run(
+            &store,
+            &terminal,
+            BacklogCommand::List {
+                filter: FilterArgs {
+                    priorities: vec![CliPriority::P2],
+                    ..FilterArgs::default()
+                },
+                format: OutputFormat::Json,
+            },
+        );
+        let json_line = terminal.written_lines().last().cloned().expect("json");
+        let records: serde_json::Value = serde_json::from_str(&json_line).expect("json array");
+        assert_eq!(records.as_array().map(Vec::len), Some(1));
+        assert_eq!(records[0]["title"], "Follow up");
+        assert_eq!(records[0]["blocked_by"][0], base.task_id.as_str());
+
+        run(
+            &store,
+            &terminal,
+            BacklogCommand::Reprioritize {
+                task_id: base.task_id.clone(),
+                priority: CliPriority::P2,
+            },
+        );
+        run(
+            &store,
+            &terminal,
+            BacklogCommand::Cancel {
+                task_id: base.task_id.clone(),
+                reason: "superseded".to_string(),
+            },
+        );
+        let cancelled = store.get_task(&base.task_id).expect("get").expect("row");
+        assert_eq!(cancelled.priority, Priority::P2);
+        assert_eq!(cancelled.status, TaskStatus::Cancelled);
+        assert_eq!(cancelled.status_note.as_deref(), Some("superseded"));
+
+        run(
+            &store,
+            &terminal,
+            BacklogCommand::Requeue {
+                task_id: base.task_id.clone(),
+            },
+        );
+        let requeued = store.get_task(&base.task_id).expect("get").expect("row");
+        assert_eq!(requeued.status, TaskStatus::Ready);
+        assert_eq!(requeued.status_note, None);
+
+        run(
+            &store,
+            &terminal,
+            BacklogCommand::Edit(EditArgs {
+                task_id: base.task_id.clone(),
+                title: Some("Fix the flaky test".to_string()),
+                details: None,
+                rationale: None,
+                scope_key: None,
+                kind: None,
+            }),
+        );
+        assert!(store.get_task(&base.task_id).expect("get").is_none());
+        let tasks = store.list_tasks().expect("list");
+        let renamed = tasks
+            .iter()
+            .find(|task| task.title == "Fix the flaky test")
+            .expect("re-keyed task");
+        let follow_up = tasks
+            .iter()
+            .find(|task| task.title == "Follow up")
+            .expect("dependent");
+        assert_eq!(follow_up.blocked_by, vec![renamed.task_id.clone()]);
+        assert!(terminal
+            .written_lines()
+            .iter()
+            .any(|line| line.contains("re-keyed to")));
+
+        run(
+            &store,
+            &terminal,
+            BacklogCommand::Search {
+                query: "flaky".to_string(),
+                filter: FilterArgs::default(),
+                format: OutputFormat::Json,
+            },
+        );
+        let json_line = terminal.written_lines().last().cloned().expect("json");
+        let records: serde_json::Value = serde_json::from_str(&json_line).expect("json array");
+        assert_eq!(records.as_array().map(Vec::len), Some(1));
+        assert_eq!(records[0]["task_id"], renamed.task_id.as_str());
+
+        store
+            .record_outcome(NewTaskOutcome {
+                task_id: renamed.task_id.clone(),
+                title: renamed.title.clone(),
+                worker_id: "worker-1".to_string(),
+                attempt: 1,
+                final_state: "complete".to_string(),
+                pr_number: Some(7),
+                branch: Some("gardener/flaky".to_string()),
+                merge_sha: Some("0123456789abcdef".to_string()),
+                review_loops: 1,
+                failure_reason: None,
+                started_at: 0,
+                finished_at: 90_000,
+            })
+            .expect("record outcome");
+        run(
+            &store,
+            &terminal,
+            BacklogCommand::Outcomes {
+                task: Some(renamed.task_id[..8].to_string()),
+                run: None,
+                format: OutputFormat::Table,
+            },
+        );
+        let row = terminal.written_lines().last().cloned().expect("outcome row");
+        assert!(row.contains("complete") && row.contains("#7") && row.contains("0123456789"));
+        assert!(row.contains("90s"));
+    }
+
+    #[test]
+    fn export_then_import_round_trips_tasks_and_edges() {
+        let dir = TempDir::new().expect("tempdir");
+        let source = BacklogStore::open(dir.path().join("source.sqlite")).expect("store");
+        let terminal = FakeTerminal::new(false);
+        run(&source, &terminal, add("First", CliPriority::P1, Vec::new()));
+        let first = source.list_tasks().expect("list").remove(0);
+        run(
+            &source,
+            &terminal,
+            add("Second", CliPriority::P0, vec![first.task_id.clone()]),
+        );
+
+        let fs = FakeFileSystem::default();
+        let export_path = PathBuf::from("/backlog.jsonl");
+        run_backlog_command(
+            &source,
+            &BacklogCommand::Export {
+                filter: FilterArgs::default(),
+                output: Some(export_path.clone()),
+            },
+            &terminal,
+            &fs,
+        )
+        .expect("export");
+
+        let target = BacklogStore::open(dir.path().join("target.sqlite")).expect("store");
+        run_backlog_command(
+            &target,
+            &BacklogCommand::Import {
+                path: export_path.clone(),
+                mode: CliImportMode::Merge,
+                dry_run: true,
+            },
+            &terminal,
+            &fs,
+        )
+        .expect("dry run");
+        assert!(target.list_tasks().expect("list").is_empty());
+        assert!(terminal
+            .written_lines()
+            .iter()
+            .any(|line| line.starts_with("dry run, nothing written: 2 added")));
+        run_backlog_command(
+            &target,
+            &BacklogCommand::Import {
+                path: export_path,
+                mode: CliImportMode::Merge,
+                dry_run: false,
+            },
+            &terminal,
+            &fs,
+        )
+        .expect("import");
+        let imported = target.list_tasks().expect("list");
+        assert_eq!(imported.len(), 2);
+        let second = imported
+            .iter()
+            .find(|task| task.title == "Second")
+            .expect("second");
+        assert_eq!(second.blocked_by, vec![first.task_id]);
+    }
+
+    #[test]
+    fn apply_shows_the_snapshot_diff_before_changing_the_backlog() {
+        let dir = TempDir::new().expect("tempdir");
+        let store = BacklogStore::open(dir.path().join("backlog.sqlite")).expect("store");
+        let terminal = FakeTerminal::new(false);
+        run(&store, &terminal, add("Bump me", CliPriority::P2, Vec::new()));
+        let task = store.list_tasks().expect("list").remove(0);
+        let snapshot = render_markdown(&store.list_tasks().expect("list"), &Default::default(), 0)
+            .replacen("| P2 |", "| P0 |", 1);
+        let fs = FakeFileSystem::default();
+        let path = PathBuf::from("/backlog.md");
+        fs.write_string(&path, &snapshot).expect("write snapshot");
+        let apply = |dry_run| {
+            run_backlog_command(
+                &store,
+                &BacklogCommand::Apply {
+                    path: path.clone(),
+                    dry_run,
+                },
+                &terminal,
+                &fs,
+            )
+            .expect("apply")
+        };
+
+        apply(true);
+        let diff = format!("~ reprioritize {} P2 -> P0: Bump me", task.task_id);
+        assert!(terminal.written_lines().contains(&diff));
+        assert_eq!(
+            store.get_task(&task.task_id).expect("get").expect("row").priority,
+            Priority::P2
+        );
+
+        apply(false);
+        assert_eq!(
+            store.get_task(&task.task_id).expect("get").expect("row").priority,
+            Priority::P0
+        );
+        assert!(terminal
+            .written_lines()
+            .last()
+            .is_some_and(|line| line.starts_with("applied 1 change(s)")));
+    }
+
+    #[test]
+    fn unknown_or_active_tasks_are_reported_as_cli_errors() {
+        let dir = TempDir::new().expect("tempdir");
+        let store = BacklogStore::open(dir.path().join("backlog.sqlite")).expect("store");
+        let terminal = FakeTerminal::new(false);
+        let fs = FakeFileSystem::default();
+        let err = run_backlog_command(
+            &store,
+            &BacklogCommand::Show {
+                task_id: "missing".to_string(),
+                format: OutputFormat::Table,
+            },
+            &terminal,
+            &fs,
+        )
+        .expect_err("missing task");
+        assert!(err.to_string().contains("no backlog task matches missing"));
+
+        run(&store, &terminal, add("Leased", CliPriority::P1, Vec::new()));
+        let leased = store
+            .claim_next("worker-1", 60)
+            .expect("claim")
+            .expect("task");
+        let err = run_backlog_command(
+            &store,
+            &BacklogCommand::Cancel {
+                task_id: leased.task_id.clone(),
+                reason: "nope".to_string(),
+            },
+            &terminal,
+            &fs,
+        )
+        .expect_err("leased task");
+        assert!(err.to_string().contains("cannot be cancelled while leased"));
+        let err = run_backlog_command(
+            &store,
+            &BacklogCommand::Requeue {
+                task_id: leased.task_id,
+            },
+            &terminal,
+            &fs,
+        )
+        .expect_err("leased task");
+        assert!(err
+            .to_string()
+            .contains("only complete, failed, unresolved, exhausted or cancelled tasks"));
+    }
+
+    #[test]
+    fn run_selection_upserts_the_adhoc_task_and_narrows_claims_to_one_ready_task() {
+        let dir = TempDir::new().expect("tempdir");
+        let store = BacklogStore::open(dir.path().join("backlog.sqlite")).expect("open store");
+        let terminal = FakeTerminal::new(false);
+        run(&store, &terminal, add("Queued", CliPriority::P0, Vec::new()));
+
+        assert_eq!(
+            RunSelection::default().claim_filter(&store).expect("filter"),
+            None
+        );
+        let filters = ClaimFilterArgs {
+            kinds: vec![CliTaskKind::Bugfix],
+            ..ClaimFilterArgs::default()
+        };
+        let filter = RunSelection::new(None, None, (&filters).into())
+            .claim_filter(&store)
+            .expect("filter")
+            .expect("restricted");
+        assert_eq!(filter.kinds, vec![TaskKind::Bugfix]);
+
+        let adhoc = RunSelection::new(Some("Fix the flaky login test"), None, TaskFilter::default());
+        let filter = adhoc.claim_filter(&store).expect("filter").expect("restricted");
+        let added = store.get_task(&filter.task_ids[0]).expect("get").expect("row");
+        assert_eq!(added.title, "Fix the flaky login test");
+        assert_eq!(added.source, "manual");
+        let claimed = store
+            .claim_next_with(
+                "worker-1",
+                60,
+                &ClaimOptions {
+                    filter,
+                    ..ClaimOptions::default()
+                },
+            )
+            .expect("claim")
+            .expect("adhoc task");
+        assert_eq!(claimed.task_id, added.task_id);
+
+        let err = RunSelection::new(None, Some(&added.task_id[..8]), TaskFilter::default())
+            .claim_filter(&store)
+            .expect_err("leased task");
+        assert!(err.to_string().contains("is leased, not ready to run"));
+        let err = RunSelection::new(None, Some("missing"), TaskFilter::default())
+            .claim_filter(&store)
+            .expect_err("missing task");
+        assert!(err.to_string().contains("no backlog task matches missing"));
+    }
+
+    #[test]
+    fn run_selection_runs_the_adhoc_task_itself_next_to_a_near_duplicate() {
+        let dir = TempDir::new().expect("tempdir");
+        let store = BacklogStore::open(dir.path().join("backlog.sqlite")).expect("open store");
+        let adhoc = RunSelection::new(
+            Some("Add unit tests to backlog_store"),
+            None,
+            TaskFilter::default(),
+        );
+        let mut similar = adhoc.adhoc.clone().expect("adhoc task");
+        similar.title = "Add tests for backlog store".to_string();
+        similar.details = similar.title.clone();
+        let similar = store.upsert_task(similar).expect("similar");
+
+        let filter = adhoc.claim_filter(&store).expect("filter").expect("restricted");
+        assert_ne!(filter.task_ids, vec![similar.task_id.clone()]);
+        let added = store.get_task(&filter.task_ids[0]).expect("get").expect("row");
+        assert_eq!(added.title, "Add unit tests to backlog_store");
+        assert_eq!(store.get_task(&similar.task_id).expect("get"), Some(similar));
+    }
+}
@@ -1,13 +1,28 @@
 use crate::logging::append_run_log;
+use std::collections::HashMap;
 use std::path::Path;
 use serde_json::json;
 
-use crate::backlog_store::{BacklogStore, BacklogTask};
+use crate::backlog_store::{
+    system_time_unix, BacklogChange, BacklogStore, BacklogTask, NewTask, TaskEvent, TaskStatus,
+};
 use crate::errors::GardenerError;
+use crate::priority::{Priority, PriorityAging};
+use crate::task_identity::TaskKind;
+
+/// Where `--sync-only` writes the snapshot, relative to the working directory.
+/// It lives outside `.cache` so edits can be committed and reviewed.
+pub const SNAPSHOT_PATH: &str = ".gardener/backlog.md";
+
+const TABLE_HEADER: &str =
+    "| Task ID | Priority | Status | Kind | Scope | Title | Note | Blocked By | Updated |";
+const NEW_TASK_SCOPE: &str = "runtime";
+const NEW_TASK_SOURCE: &str = "snapshot";
 
 pub fn export_markdown_snapshot(
     store: &BacklogStore,
     output: impl AsRef<Path>,
+    aging: &PriorityAging,
 ) -> Result<String, GardenerError> {
     let tasks = store.list_tasks()?;
     append_run_log(
@@ -18,44 +33,441 @@ pub fn export_markdown_snapshot(
             "task_count": tasks.len(),
         }),
     );
-    let rendered = render_markdown(&tasks);
+    let mut history = HashMap::new();
+    for task in &tasks {
+        history.insert(task.task_id.clone(), store.history(&task.task_id)?);
+    }
+    let rendered = render_markdown_with_history(&tasks, &history, aging, system_time_unix());
     std::fs::write(output, rendered.as_bytes()).map_err(|e| GardenerError::Io(e.to_string()))?;
     Ok(rendered)
 }
 
-pub fn render_markdown(tasks: &[BacklogTask]) -> String {
+const HISTORY_EVENTS_PER_TASK: usize = 10;
+
+pub fn render_markdown(tasks: &[BacklogTask], aging: &PriorityAging, now: i64) -> String {
+    render_markdown_with_history(tasks, &HashMap::new(), aging, now)
+}
+
+/// Renders the task table followed by the most recent transitions of each task
+/// that has recorded history. Tasks that have aged into a higher priority show
+/// both levels, e.g. `P2 (aged to P1)`. The table is what `parse_snapshot`
+/// reads back; the task id column anchors each row to its task.
+pub fn render_markdown_with_history(
+    tasks: &[BacklogTask],
+    history: &HashMap<String, Vec<TaskEvent>>,
+    aging: &PriorityAging,
+    now: i64,
+) -> String {
     let mut out = String::new();
     out.push_str("# Gardener Backlog Snapshot\n\n");
-    out.push_str("| Priority | Status | Title | Task ID | Updated |\n");
-    out.push_str("| --- | --- | --- | --- | --- |\n");
+    out.push_str(
+        "<!-- Edit Priority, Status (ready or cancelled, with the reason in Note) or add \
+         rows with an empty Task ID, then run `gardener backlog apply` on this file. -->\n\n",
+    );
+    out.push_str(TABLE_HEADER);
+    out.push_str("\n| --- | --- | --- | --- | --- | --- | --- | --- | --- |\n");
 
     for task in tasks {
         out.push_str(&format!(
-            "| {} | {} | {} | {} | {} |\n",
-            task.priority.as_str(),
+            "| {} | {} | {} | {} | {} | {} | {} | {} | {} |\n",
+            task.task_id,
+            render_priority(task, aging, now),
             task.status.as_str(),
+            task.kind.as_str(),
+            sanitize_cell(&task.scope_key),
             sanitize_cell(&task.title),
-            task.task_id,
-            task.last_updated
+            sanitize_cell(task.status_note.as_deref().unwrap_or("")),
+            render_blocked_by(task),
+            task.last_updated,
         ));
     }
 
+    let with_history = tasks
+        .iter()
+        .filter_map(|task| {
+            history
+                .get(&task.task_id)
+                .filter(|events| !events.is_empty())
+                .map(|events| (task, events))
+        })
+        .collect::<Vec<_>>();
+    if !with_history.is_empty() {
+        out.push_str("\n## History\n");
+        for (task, events) in with_history {
+            out.push_str(&format!(
+                "\n### {} `{}`\n\n",
+                sanitize_cell(&task.title),
+                task.task_id
+            ));
+            let skip = events.len().saturating_sub(HISTORY_EVENTS_PER_TASK);
+            for event in &events[skip..] {
+                out.push_str(&format!("- {}\n", render_event(event)));
+            }
+        }
+    }
+
     out
 }
 
+/// One-line description of a history event, shared by the snapshot and
+/// `gardener backlog show`.
+pub fn render_event(event: &TaskEvent) -> String {
+    let mut line = format!(
+        "{} {} {} -> {} by {}",
+        event.created_at,
+        event.event_type,
+        event.from_status.as_deref().unwrap_or("new"),
+        event.to_status,
+        event.actor
+    );
+    if let Some(reason) = &event.reason {
+        line.push_str(&format!(" ({})", sanitize_cell(reason)));
+    }
+    if let Some(pr) = event.pr_number {
+        line.push_str(&format!(" pr=#{pr}"));
+    }
+    if let Some(run_id) = &event.run_id {
+        line.push_str(&format!(" run={run_id}"));
+    }
+    if let Some(failure) = &event.failure_reason {
+        line.push_str(&format!(" failure: {}", sanitize_cell(failure)));
+    }
+    line
+}
+
+fn render_priority(task: &BacklogTask, aging: &PriorityAging, now: i64) -> String {
+    let effective = aging.effective(task.priority, task.created_at, now);
+    if effective == task.priority {
+        task.priority.as_str().to_string()
+    } else {
+        format!("{} (aged to {})", task.priority.as_str(), effective.as_str())
+    }
+}
+
+fn render_blocked_by(task: &BacklogTask) -> String {
+    let edges = task.blocked_by.join(", ");
+    match task.blocked_reason.as_deref() {
+        Some(reason) if edges.is_empty() => sanitize_cell(reason),
+        Some(reason) => format!("{edges} ({})", sanitize_cell(reason)),
+        None => edges,
+    }
+}
+
 fn sanitize_cell(value: &str) -> String {
     value.replace('|', "\\|").replace('\n', " ")
 }
 
+/// One row of the snapshot table as written in the file. Cells are kept raw;
+/// `plan_snapshot_apply` validates them.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SnapshotRow {
+    /// Line in the file, for error messages.
+    pub line: usize,
+    pub task_id: Option<String>,
+    pub priority: String,
+    pub status: String,
+    pub kind: String,
+    pub scope: String,
+    pub title: String,
+    pub note: String,
+}
+
+/// A store mutation derived from an edited snapshot.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SnapshotChange {
+    Reprioritize {
+        task_id: String,
+        title: String,
+        from: Priority,
+        to: Priority,
+    },
+    Cancel {
+        task_id: String,
+        title: String,
+        reason: String,
+    },
+    Requeue {
+        task_id: String,
+        title: String,
+    },
+    Add(NewTask),
+}
+
+impl SnapshotChange {
+    /// Diff line shown before anything is applied.
+    pub fn render(&self) -> String {
+        match self {
+            Self::Reprioritize {
+                task_id,
+                title,
+                from,
+                to,
+            } => format!(
+                "~ reprioritize {task_id} {} -> {}: {title}",
+                from.as_str(),
+                to.as_str()
+            ),
+            Self::Cancel {
+                task_id,
+                title,
+                reason,
+            } => format!("- cancel {task_id}: {title} ({reason})"),
+            Self::Requeue { task_id, title } => format!("~ requeue {task_id}: {title}"),
+            Self::Add(task) => format!(
+                "+ add [{} {} {}] {}",
+                task.priority.as_str(),
+                task.kind.as_str(),
+                task.scope_key,
+                task.title
+            ),
+        }
+    }
+
+    /// The store edit this change applies.
+    pub fn to_backlog_change(&self) -> BacklogChange {
+        match self {
+            Self::Reprioritize { task_id, to, .. } => BacklogChange::Reprioritize {
+                task_id: task_id.clone(),
+                priority: *to,
+            },
+            Self::Cancel {
+                task_id, reason, ..
+            } => BacklogChange::Cancel {
+                task_id: task_id.clone(),
+                reason: reason.clone(),
+            },
+            Self::Requeue { task_id, .. } => BacklogChange::Requeue {
+                task_id: task_id.clone(),
+            },
+            Self::Add(task) => BacklogChange::Add(task.clone()),
+        }
+    }
+}
+
+/// Reads the task table back out of a snapshot. Everything outside the table,
+/// including the history section, is ignored.
+pub fn parse_snapshot(contents: &str) -> Result<Vec<SnapshotRow>, GardenerError> {
+    append_run_log(
+        "debug",
+        "backlog_snapshot.parse.started",
+        json!({ "bytes": contents.len() }),
+    );
+    let mut lines = contents.lines().enumerate();
+    let header = lines
+        .by_ref()
+        .find(|(_, line)| line.trim().starts_with('|'))
+        .map(|(_, line)| split_row(line))
+        .ok_or_else(|| GardenerError::Cli("snapshot has no task table".to_string()))?;
+    let column = |name: &str| {
+        header
+            .iter()
+            .position(|cell| cell.eq_ignore_ascii_case(name))
+            .ok_or_else(|| GardenerError::Cli(format!("snapshot table has no {name} column")))
+    };
+    let columns = [
+        column("Task ID")?,
+        column("Priority")?,
+        column("Status")?,
+        column("Kind")?,
+        column("Scope")?,
+        column("Title")?,
+        column("Note")?,
+    ];
+
+    let mut rows = Vec::new();
+    for (idx, line) in lines {
+        let trimmed = line.trim();
+        if !trimmed.starts_with('|') {
+            break;
+        }
+        let cells = split_row(trimmed);
+        if cells.iter().all(|cell| cell.chars().all(|ch| matches!(ch, '-' | ':'))) {
+            continue;
+        }
+        let cell = |position: usize| cells.get(columns[position]).cloned().unwrap_or_default();
+        let task_id = cell(0).trim_matches('`').to_string();
+        rows.push(SnapshotRow {
+            line: idx + 1,
+            task_id: (!task_id.is_empty()).then_some(task_id),
+            priority: cell(1),
+            status: cell(2),
+            kind: cell(3),
+            scope: cell(4),
+            title: cell(5),
+            note: cell(6),
+        });
+    }
+    Ok(rows)
+}
+
+/// Compares edited rows with the live tasks and lists what applying them would
+/// change. Only priority, status and note are read back for existing tasks;
+/// rows without a task id become new tasks. Every problem in the file is
+/// reported at once, before anything is changed.
+pub fn plan_snapshot_apply(
+    rows: &[SnapshotRow],
+    tasks: &[BacklogTask],
+) -> Result<Vec<SnapshotChange>, GardenerError> {
+    let mut changes = Vec::new();
+    let mut problems = Vec::new();
+    for row in rows {
+        let priority = row
+            .priority
+            .split_whitespace()
+            .next()
+            .and_then(|value| Priority::from_db(&value.to_ascii_uppercase()));
+        let status = TaskStatus::from_db(row.status.trim());
+        let Some(task_id) = &row.task_id else {
+            match build_new_task(row, priority) {
+                Ok(task) => changes.push(SnapshotChange::Add(task)),
+                Err(problem) => problems.push(format!("line {}: {problem}", row.line)),
+            }
+            continue;
+        };
+        let Some(task) = tasks.iter().find(|task| &task.task_id == task_id) else {
+            problems.push(format!("line {}: no live task {task_id}", row.line));
+            continue;
+        };
+        let Some(priority) = priority else {
+            problems.push(format!(
+                "line {}: priority {:?} is not P0, P1 or P2",
+                row.line, row.priority
+            ));
+            continue;
+        };
+        let Some(status) = status else {
+            problems.push(format!("line {}: unknown status {:?}", row.line, row.status));
+            continue;
+        };
+        if priority != task.priority {
+            changes.push(SnapshotChange::Reprioritize {
+                task_id: task.task_id.clone(),
+                title: task.title.clone(),
+                from: task.priority,
+                to: priority,
+            });
+        }
+        if status == task.status {
+            continue;
+        }
+        match (task.status, status) {
+            (TaskStatus::Leased | TaskStatus::InProgress, _) => problems.push(format!(
+                "line {}: {} is {} and cannot change status",
+                row.line,
+                task.task_id,
+                task.status.as_str()
+            )),
+            (_, TaskStatus::Cancelled) if row.note.trim().is_empty() => problems.push(format!(
+                "line {}: cancelling {} needs a reason in the Note column",
+                row.line, task.task_id
+            )),
+            (_, TaskStatus::Cancelled) => changes.push(SnapshotChange::Cancel {
+                task_id: task.task_id.clone(),
+                title: task.title.clone(),
+                reason: row.note.trim().to_string(),
+            }),
+            (
+                TaskStatus::Complete
+                | TaskStatus::Failed
+                | TaskStatus::Unresolved
+                | TaskStatus::Exhausted
+                | TaskStatus::Cancelled,
+                TaskStatus::Ready,
+            ) => changes.push(SnapshotChange::Requeue {
+                task_id: task.task_id.clone(),
+                title: task.title.clone(),
+            }),
+            (from, to) => problems.push(format!(
+                "line {}: status {} -> {} cannot be applied by hand",
+                row.line,
+                from.as_str(),
+                to.as_str()
+            )),
+        }
+    }
+    append_run_log(
+        if problems.is_empty() { "info" } else { "warn" },
+        "backlog_snapshot.apply.planned",
+        json!({ "rows": rows.len(), "changes": changes.len(), "problems": problems }),
+    );
+    if !problems.is_empty() {
+        return Err(GardenerError::Cli(format!(
+            "snapshot not applied:\n{}",
+            problems.join("\n")
+        )));
+    }
+    Ok(changes)
+}
+
+fn build_new_task(row: &SnapshotRow, priority: Option<Priority>) -> Result<NewTask, String> {
+    let title = row.title.trim();
+    if title.is_empty() {
+        return Err("a new task needs a title".to_string());
+    }
+    let priority = match row.priority.trim() {
+        "" => Priority::P1,
+        _ => priority.ok_or_else(|| format!("priority {:?} is not P0, P1 or P2", row.priority))?,
+    };
+    if !matches!(row.status.trim(), "" | "ready") {
+        return Err(format!("a new task starts ready, not {:?}", row.status));
+    }
+    let kind = match row.kind.trim() {
+        "" => TaskKind::Feature,
+        value => TaskKind::ALL
+            .iter()
+            .copied()
+            .find(|kind| kind.as_str() == value)
+            .ok_or_else(|| format!("unknown kind {value:?}"))?,
+    };
+    let scope = row.scope.trim();
+    let note = row.note.trim();
+    Ok(NewTask {
+        kind,
+        title: title.to_string(),
+        details: if note.is_empty() { title } else { note }.to_string(),
+        rationale: String::new(),
+        scope_key: if scope.is_empty() { NEW_TASK_SCOPE } else { scope }.to_string(),
+        priority,
+        source: NEW_TASK_SOURCE.to_string(),
+        related_pr: None,
+        related_branch: None,
+        blocked_by: Vec::new(),
+    })
+}
+
+/// Splits a markdown table row on unescaped pipes and trims each cell.
+fn split_row(line: &str) -> Vec<String> {
+    let inner = line.trim().trim_start_matches('|');
+    let inner = inner.strip_suffix('|').filter(|rest| !rest.ends_with('\\')).unwrap_or(inner);
+    let mut cells = Vec::new();
+    let mut cell = String::new();
+    let mut chars = inner.chars().peekable();
+    while let Some(ch) = chars.next() {
+        match ch {
+            '\\' if chars.peek() == Some(&'|') => {
+                cell.push('|');
+                chars.next();
+            }
+            '|' => cells.push(std::mem::take(&mut cell).trim().to_string()),
+            _ => cell.push(ch),
+        }
+    }
+    cells.push(cell.trim().to_string());
+    cells
+}
+
 #[cfg(test)]
 mod tests {
     use tempfile::tempdir;
 
-    use crate::backlog_store::{BacklogStore, NewTask};
-    use crate::priority::Priority;
+    use crate::backlog_store::{BacklogStore, NewTask, TaskStatus, TransitionContext};
+    use crate::priority::{Priority, PriorityAging};
     use crate::task_identity::TaskKind;
 
-    use super::{export_markdown_snapshot, render_markdown};
+    use super::{
+        export_markdown_snapshot, parse_snapshot, plan_snapshot_apply, render_markdown,
+        SnapshotChange,
+    };
 
     #[test]
     fn snapshot_renders_expected_table() {
@@ -73,12 +485,43 @@ mod tests {
                 source: "test".to_string(),
                 related_pr: None,
                 related_branch: None,
+                blocked_by: Vec::new(),
             })
             .expect("insert");
 
-        let rendered = render_markdown(&store.list_tasks().expect("tasks"));
+        let first = store.list_tasks().expect("tasks").remove(0);
+        store
+            .upsert_task(NewTask {
+                kind: TaskKind::Feature,
+                title: "Second task".to_string(),
+                details: String::new(),
+                rationale: String::new(),
+                scope_key: "global".to_string(),
+                priority: Priority::P2,
+                source: "test".to_string(),
+                related_pr: None,
+                related_branch: None,
+                blocked_by: vec![first.task_id.clone()],
+            })
+            .expect("insert dependent");
+
+        let tasks = store.list_tasks().expect("tasks");
+        let rendered = render_markdown(&tasks, &PriorityAging::default(), first.created_at);
         assert!(rendered.contains("# Gardener Backlog Snapshot"));
-        assert!(rendered.contains("| P1 | ready | First task |"));
+        assert!(rendered.contains(&format!(
+            "| {} | P1 | ready | feature | global | First task |  |  |",
+            first.task_id
+        )));
+        assert!(rendered.contains("| P2 | ready | feature | global | Second task |"));
+        assert!(rendered.contains(&format!("| Second task |  | {} |", first.task_id)));
+
+        let aging = PriorityAging {
+            p2_to_p1_seconds: 60,
+            p1_to_p0_seconds: 0,
+        };
+        let aged = render_markdown(&tasks, &aging, first.created_at + 3_600_000);
+        assert!(aged.contains("| P1 | ready | feature | global | First task |"));
+        assert!(aged.contains("| P2 (aged to P1) | ready | feature | global | Second task |"));
     }
 
     #[test]
@@ -88,12 +531,139 @@ mod tests {
         let out = dir.path().join("backlog.md");
         let store = BacklogStore::open(&db).expect("store");
 
-        let rendered = export_markdown_snapshot(&store, &out).expect("export");
+        let rendered = export_markdown_snapshot(&store, &out, &PriorityAging::default()).expect("export");
         let disk = std::fs::read_to_string(&out).expect("read");
 
         assert_eq!(rendered, disk);
     }
 
+    #[test]
+    fn exporter_includes_task_history() {
+        let dir = tempdir().expect("dir");
+        let db = dir.path().join("history.sqlite");
+        let out = dir.path().join("backlog.md");
+        let store = BacklogStore::open(&db).expect("store");
+        store
+            .upsert_task(NewTask {
+                kind: TaskKind::Bugfix,
+                title: "Flaky test".to_string(),
+                details: String::new(),
+                rationale: String::new(),
+                scope_key: "global".to_string(),
+                priority: Priority::P0,
+                source: "quality_gap".to_string(),
+                related_pr: None,
+                related_branch: None,
+                blocked_by: Vec::new(),
+            })
+            .expect("insert");
+        let claimed = store.claim_next("worker-1", 60).expect("claim").expect("task");
+        store
+            .mark_unresolved_with(
+                &claimed.task_id,
+                "worker-1",
+                TransitionContext {
+                    failure_reason: Some("gates failed".to_string()),
+                    ..TransitionContext::default()
+                },
+            )
+            .expect("unresolved");
+
+        let rendered = export_markdown_snapshot(&store, &out, &PriorityAging::default()).expect("export");
+        assert!(rendered.contains("## History"));
+        assert!(rendered.contains("created new -> ready by quality_gap"));
+        assert!(rendered.contains("claimed ready -> leased by worker-1"));
+        assert!(rendered.contains("unresolved leased -> unresolved by worker-1 failure: gates failed"));
+    }
+
+    #[test]
+    fn edited_snapshot_plans_reprioritize_cancel_requeue_and_add() {
+        let dir = tempdir().expect("dir");
+        let store = BacklogStore::open(dir.path().join("apply.sqlite")).expect("store");
+        let task = |title: &str, priority| NewTask {
+            kind: TaskKind::Feature,
+            title: title.to_string(),
+            details: String::new(),
+            rationale: String::new(),
+            scope_key: "global".to_string(),
+            priority,
+            source: "test".to_string(),
+            related_pr: None,
+            related_branch: None,
+            blocked_by: Vec::new(),
+        };
+        store.upsert_task(task("Keep | as is", Priority::P1)).expect("keep");
+        let bump = store.upsert_task(task("Bump me", Priority::P2)).expect("bump");
+        let drop = store.upsert_task(task("Drop me", Priority::P2)).expect("drop");
+        let done = store.upsert_task(task("Redo me", Priority::P1)).expect("done");
+        assert!(store.cancel_task(&done.task_id, "not now").expect("cancel"));
+
+        let tasks = store.list_tasks().expect("tasks");
+        let rendered = render_markdown(&tasks, &PriorityAging::default(), 0);
+        let unchanged = parse_snapshot(&rendered).expect("parse");
+        assert_eq!(unchanged.len(), 4);
+        assert!(unchanged.iter().any(|row| row.title == "Keep | as is"));
+        assert!(plan_snapshot_apply(&unchanged, &tasks).expect("plan").is_empty());
+
+        let edited = rendered
+            .lines()
+            .map(|line| {
+                if line.starts_with(&format!("| {} ", bump.task_id)) {
+                    line.replacen("| P2 |", "| P0 |", 1)
+                } else if line.starts_with(&format!("| {} ", drop.task_id)) {
+                    line.replacen("| ready |", "| cancelled |", 1)
+                        .replacen("| Drop me |  |", "| Drop me | out of scope |", 1)
+                } else if line.starts_with(&format!("| {} ", done.task_id)) {
+                    line.replacen("| cancelled |", "| ready |", 1)
+                } else {
+                    line.to_string()
+                }
+            })
+            .flat_map(|line| {
+                let mut lines = vec![line];
+                if lines[0].starts_with("| --- ") {
+                    lines.push("|  | P0 |  | bugfix |  | Fix the crash |  |  |  |".to_string());
+                }
+                lines
+            })
+            .collect::<Vec<_>>()
+            .join("\n");
+        let changes = plan_snapshot_apply(&parse_snapshot(&edited).expect("parse"), &tasks)
+            .expect("plan");
+        let rendered_changes = changes.iter().map(SnapshotChange::render).collect::<Vec<_>>();
+        assert_eq!(changes.len(), 4, "{rendered_changes:?}");
+        assert!(rendered_changes.contains(&format!(
+            "~ reprioritize {} P2 -> P0: Bump me",
+            bump.task_id
+        )));
+        assert!(rendered_changes.contains(&format!(
+            "- cancel {}: Drop me (out of scope)",
+            drop.task_id
+        )));
+        assert!(rendered_changes.contains(&format!("~ requeue {}: Redo me", done.task_id)));
+        assert!(rendered_changes.contains(&"+ add [P0 bugfix runtime] Fix the crash".to_string()));
+
+        let no_reason = rendered.replacen(
+            &format!("| {} | P2 | ready |", drop.task_id),
+            &format!("| {} | P2 | cancelled |", drop.task_id),
+            1,
+        );
+        let err = plan_snapshot_apply(&parse_snapshot(&no_reason).expect("parse"), &tasks)
+            .expect_err("reason required");
+        assert!(err.to_string().contains("needs a reason in the Note column"));
+
+        let claimed = store.claim_next("worker-1", 60).expect("claim").expect("task");
+        assert_eq!(claimed.status, TaskStatus::Leased);
+        let leased = store.list_tasks().expect("tasks");
+        let cancel_leased = render_markdown(&leased, &PriorityAging::default(), 0).replacen(
+            &format!("| {} | {} | leased |", claimed.task_id, claimed.priority.as_str()),
+            &format!("| {} | {} | cancelled |", claimed.task_id, claimed.priority.as_str()),
+            1,
+        );
+        assert!(plan_snapshot_apply(&parse_snapshot(&cancel_leased).expect("parse"), &leased)
+            .is_err());
+    }
+
     #[test]
     fn sanitizes_markdown_cells() {
         assert_eq!(super::sanitize_cell("a|b\nc"), "a\\|b c");
@@ -1,3 +1,4 @@
+use std::collections::{BTreeMap, HashMap, HashSet};
 use std::path::{Path, PathBuf};
 use std::sync::atomic::{AtomicUsize, Ordering};
 use std::sync::{Arc, Mutex};
@@ -5,18 +6,45 @@ use std::thread;
 use std::time::{SystemTime, UNIX_EPOCH};
 
 use rusqlite::{params, Connection, OpenFlags, OptionalExtension, Transaction};
+use serde::Serialize;
 use serde_json::json;
 use tokio::sync::{mpsc, oneshot};
 
+use crate::config::{SchedulerConfig, ScopeExclusivity};
 use crate::errors::GardenerError;
-use crate::logging::append_run_log;
-use crate::priority::Priority;
+use crate::logging::{append_run_log, current_run_id};
+use crate::priority::{Priority, PriorityAging};
+use crate::replay::recording::BacklogTaskRecord;
+use crate::repo_identity::RepoIdentity;
 use crate::task_identity::{compute_task_id, TaskIdentity, TaskKind};
+use crate::task_similarity::{near_duplicate_score, title_similarity};
 
 const READ_POOL_SIZE: usize = 4;
 
+/// Namespace for rows written before per-repository namespacing existed, and for
+/// stores opened without a repository identity. Legacy rows stay here until an
+/// operator adopts them with `gardener --adopt-namespace legacy`; opening a
+/// repository-scoped store only warns that they are pending.
+pub const LEGACY_NAMESPACE: &str = "legacy";
+
 type StoreResult<T> = Result<T, GardenerError>;
 
+/// Columns hydrated by `row_to_task`. `blocked_by` is folded into a comma-separated
+/// list so every read path sees a task's prerequisites without a second query.
+const TASK_COLUMNS: &str = "task_id, kind, title, details, scope_key, priority, status, last_updated,
+    lease_owner, lease_expires_at, source, related_pr, related_branch, rationale, attempt_count,
+    created_at, repo_namespace, blocked_reason, status_note,
+    (SELECT group_concat(depends_on, ',') FROM (
+        SELECT depends_on FROM task_dependencies dep
+        WHERE dep.repo_namespace = backlog_tasks.repo_namespace
+          AND dep.task_id = backlog_tasks.task_id
+        ORDER BY depends_on
+    )) AS blocked_by,
+    not_before, failure_reason";
+
+/// Statuses that no longer count as open work, as an SQL list for the counts.
+const CLOSED_STATUSES_SQL: &str = "('complete', 'failed', 'exhausted', 'cancelled')";
+
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum TaskStatus {
     Ready,
@@ -25,6 +53,12 @@ pub enum TaskStatus {
     Complete,
     Failed,
     Unresolved,
+    /// Terminal: the retry policy ran out of attempts. Only an operator requeue
+    /// brings the task back.
+    Exhausted,
+    /// Terminal: decided not to do it. `status_note` carries the reason, and
+    /// upserts leave the row alone until an operator requeues it.
+    Cancelled,
 }
 
 impl TaskStatus {
@@ -36,10 +70,12 @@ impl TaskStatus {
             Self::Complete => "complete",
             Self::Failed => "failed",
             Self::Unresolved => "unresolved",
+            Self::Exhausted => "exhausted",
+            Self::Cancelled => "cancelled",
         }
     }
 
-    fn from_db(value: &str) -> Option<Self> {
+    pub fn from_db(value: &str) -> Option<Self> {
         match value {
             "ready" => Some(Self::Ready),
             "leased" => Some(Self::Leased),
@@ -47,6 +83,8 @@ impl TaskStatus {
             "complete" => Some(Self::Complete),
             "failed" => Some(Self::Failed),
             "unresolved" => Some(Self::Unresolved),
+            "exhausted" => Some(Self::Exhausted),
+            "cancelled" => Some(Self::Cancelled),
             _ => None,
         }
     }
@@ -70,6 +108,370 @@ pub struct BacklogTask {
     pub related_branch: Option<String>,
     pub attempt_count: i64,
     pub created_at: i64,
+    pub repo_namespace: String,
+    /// Task ids that must be `complete` before this task can be claimed.
+    pub blocked_by: Vec<String>,
+    /// Set when a prerequisite (direct or transitive) was marked unresolved or cancelled.
+    pub blocked_reason: Option<String>,
+    /// Operator-facing note on the latest status change, e.g. why a task was cancelled.
+    pub status_note: Option<String>,
+    /// Earliest time (unix millis) a retried task may be claimed again.
+    pub not_before: Option<i64>,
+    /// Failure reason reported by the most recent failed attempt.
+    pub failure_reason: Option<String>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct NamespaceSummary {
+    pub repo_namespace: String,
+    pub repo_root: Option<String>,
+    pub remote_url: Option<String>,
+    pub task_count: usize,
+    pub active_count: usize,
+}
+
+/// Extra detail recorded in `task_events` alongside a status transition.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct TransitionContext {
+    pub reason: Option<String>,
+    pub pr_number: Option<i64>,
+    pub failure_reason: Option<String>,
+}
+
+/// Scheduling constraints applied while picking the next ready task.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ClaimOptions {
+    pub scope_exclusivity: BTreeMap<String, ScopeExclusivity>,
+    pub scope_separator: String,
+    pub priority_aging: PriorityAging,
+    /// Only tasks matching this filter are claimed; its statuses are ignored.
+    pub filter: TaskFilter,
+}
+
+impl From<&SchedulerConfig> for ClaimOptions {
+    fn from(scheduler: &SchedulerConfig) -> Self {
+        Self {
+            scope_exclusivity: scheduler.scope_exclusivity.clone(),
+            scope_separator: scheduler.scope_separator.clone(),
+            priority_aging: scheduler.priority_aging,
+            filter: TaskFilter::default(),
+        }
+    }
+}
+
+impl ClaimOptions {
+    fn exclusivity_for(&self, kind: TaskKind) -> ScopeExclusivity {
+        self.scope_exclusivity
+            .get(kind.as_str())
+            .copied()
+            .unwrap_or_default()
+    }
+
+    fn restricts_scopes(&self) -> bool {
+        self.scope_exclusivity
+            .values()
+            .any(|mode| *mode != ScopeExclusivity::Off)
+    }
+
+    /// Whether a ready task must wait for an active one. The stricter of the two
+    /// kinds' settings applies, and tasks without a scope never conflict.
+    pub fn scopes_conflict(
+        &self,
+        candidate_kind: TaskKind,
+        candidate_scope: &str,
+        active_kind: TaskKind,
+        active_scope: &str,
+    ) -> bool {
+        if candidate_scope.is_empty() || active_scope.is_empty() {
+            return false;
+        }
+        let mode = self
+            .exclusivity_for(candidate_kind)
+            .max(self.exclusivity_for(active_kind));
+        match mode {
+            ScopeExclusivity::Off => false,
+            ScopeExclusivity::Exact => candidate_scope == active_scope,
+            ScopeExclusivity::Prefix => {
+                candidate_scope == active_scope
+                    || self.is_descendant(candidate_scope, active_scope)
+                    || self.is_descendant(active_scope, candidate_scope)
+            }
+        }
+    }
+
+    fn is_descendant(&self, scope: &str, ancestor: &str) -> bool {
+        let separator = if self.scope_separator.is_empty() {
+            ":"
+        } else {
+            self.scope_separator.as_str()
+        };
+        scope
+            .strip_prefix(ancestor)
+            .is_some_and(|rest| rest.starts_with(separator))
+    }
+
+    /// Counts ready tasks that are held back by an active task's scope.
+    pub fn held_back(&self, tasks: &[BacklogTask]) -> usize {
+        if !self.restricts_scopes() {
+            return 0;
+        }
+        let active = tasks
+            .iter()
+            .filter(|task| matches!(task.status, TaskStatus::Leased | TaskStatus::InProgress))
+            .collect::<Vec<_>>();
+        tasks
+            .iter()
+            .filter(|task| task.status == TaskStatus::Ready)
+            .filter(|task| {
+                active.iter().any(|holder| {
+                    self.scopes_conflict(task.kind, &task.scope_key, holder.kind, &holder.scope_key)
+                })
+            })
+            .count()
+    }
+}
+
+/// One row of a task's append-only history.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TaskEvent {
+    pub event_id: i64,
+    pub task_id: String,
+    pub event_type: String,
+    pub from_status: Option<String>,
+    pub to_status: String,
+    pub actor: String,
+    pub reason: Option<String>,
+    pub run_id: Option<String>,
+    pub pr_number: Option<i64>,
+    pub failure_reason: Option<String>,
+    pub created_at: i64,
+}
+
+/// What one finished worker run on a task produced.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct TaskOutcome {
+    pub outcome_id: i64,
+    pub task_id: String,
+    /// Task title when the run finished; the task may since be re-keyed or archived.
+    pub title: String,
+    pub run_id: Option<String>,
+    pub worker_id: String,
+    pub attempt: i64,
+    /// Worker state the run ended in, e.g. `complete`, `failed` or `parked`.
+    pub final_state: String,
+    pub pr_number: Option<i64>,
+    pub branch: Option<String>,
+    pub merge_sha: Option<String>,
+    pub review_loops: i64,
+    pub failure_reason: Option<String>,
+    pub started_at: i64,
+    pub finished_at: i64,
+    pub duration_ms: i64,
+}
+
+/// A GitHub issue imported as a backlog task.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct IssueLink {
+    pub issue_number: i64,
+    pub task_id: String,
+    pub imported_at: i64,
+    /// Set once the issue was closed or commented on after the task's PR merged.
+    pub resolved_at: Option<i64>,
+}
+
+/// A finished run to record; the store adds the run id and duration.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct NewTaskOutcome {
+    pub task_id: String,
+    pub title: String,
+    pub worker_id: String,
+    pub attempt: i64,
+    pub final_state: String,
+    pub pr_number: Option<i64>,
+    pub branch: Option<String>,
+    pub merge_sha: Option<String>,
+    pub review_loops: i64,
+    pub failure_reason: Option<String>,
+    pub started_at: i64,
+    pub finished_at: i64,
+}
+
+/// Open tasks in one scope whose wording marks them as the same work.
+/// Duplicates are ready tasks that `merge_duplicate` can fold into the keeper.
+#[derive(Debug, Clone, PartialEq)]
+pub struct DuplicateCluster {
+    pub keeper: BacklogTask,
+    /// Each duplicate with its title similarity to the keeper.
+    pub duplicates: Vec<(BacklogTask, f64)>,
+}
+
+/// How `BacklogStore::import_jsonl` treats rows already in the backlog.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ImportMode {
+    /// Adds missing rows and overwrites stored rows that are not newer than the
+    /// record; rows absent from the file are left alone.
+    #[default]
+    Merge,
+    /// Makes the backlog match the file: records always win and rows absent
+    /// from the file are removed.
+    Replace,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ImportAction {
+    Add,
+    Update,
+    Unchanged,
+    /// The stored row wins, because it is newer or currently leased.
+    Keep,
+    Remove,
+}
+
+impl ImportAction {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Add => "add",
+            Self::Update => "update",
+            Self::Unchanged => "unchanged",
+            Self::Keep => "keep",
+            Self::Remove => "remove",
+        }
+    }
+}
+
+/// One row of an import diff.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ImportChange {
+    pub task_id: String,
+    pub title: String,
+    pub action: ImportAction,
+    /// Fields whose stored value differs from the record.
+    pub fields: Vec<&'static str>,
+    pub note: Option<String>,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ImportReport {
+    pub changes: Vec<ImportChange>,
+    /// False for a dry run, which computes the same diff and then rolls back.
+    pub applied: bool,
+}
+
+impl ImportReport {
+    pub fn count(&self, action: ImportAction) -> usize {
+        self.changes
+            .iter()
+            .filter(|change| change.action == action)
+            .count()
+    }
+}
+
+/// Restricts which tasks a query returns. Empty lists match everything.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct TaskFilter {
+    pub task_ids: Vec<String>,
+    pub statuses: Vec<TaskStatus>,
+    pub priorities: Vec<Priority>,
+    pub kinds: Vec<TaskKind>,
+    /// Scope prefixes, so `backlog` matches `backlog/store`.
+    pub scopes: Vec<String>,
+    /// Source prefixes, so `seed` matches `seed_runner_v2_gen_3`.
+    pub sources: Vec<String>,
+}
+
+impl TaskFilter {
+    /// Renders `AND ...` clauses for the filter, numbering placeholders after the
+    /// parameters already in `params` and appending its own values.
+    fn sql_clause(&self, params: &mut Vec<String>) -> String {
+        self.sql_clause_after(0, params)
+    }
+
+    /// Like `sql_clause`, for a statement that binds `bound` typed parameters
+    /// ahead of the string ones collected in `params`.
+    fn sql_clause_after(&self, bound: usize, params: &mut Vec<String>) -> String {
+        let mut clause = String::new();
+        let mut push_in = |column: &str, values: Vec<String>, params: &mut Vec<String>| {
+            if values.is_empty() {
+                return;
+            }
+            let placeholders = values
+                .into_iter()
+                .map(|value| {
+                    params.push(value);
+                    format!("?{}", bound + params.len())
+                })
+                .collect::<Vec<_>>()
+                .join(", ");
+            clause.push_str(&format!(" AND {column} IN ({placeholders})"));
+        };
+        push_in("task_id", self.task_ids.clone(), params);
+        push_in(
+            "status",
+            self.statuses.iter().map(|s| s.as_str().to_string()).collect(),
+            params,
+        );
+        push_in(
+            "priority",
+            self.priorities.iter().map(|p| p.as_str().to_string()).collect(),
+            params,
+        );
+        push_in(
+            "kind",
+            self.kinds.iter().map(|k| k.as_str().to_string()).collect(),
+            params,
+        );
+        let mut push_prefixes = |column: &str, prefixes: &[String], params: &mut Vec<String>| {
+            if prefixes.is_empty() {
+                return;
+            }
+            let matches = prefixes
+                .iter()
+                .map(|prefix| {
+                    params.push(prefix.clone());
+                    format!("instr({column}, ?{}) = 1", bound + params.len())
+                })
+                .collect::<Vec<_>>()
+                .join(" OR ");
+            clause.push_str(&format!(" AND ({matches})"));
+        };
+        push_prefixes("scope_key", &self.scopes, params);
+        push_prefixes("source", &self.sources, params);
+        clause
+    }
+}
+
+/// One operator edit in a batch applied by [`BacklogStore::apply_changes`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum BacklogChange {
+    Reprioritize { task_id: String, priority: Priority },
+    Cancel { task_id: String, reason: String },
+    Requeue { task_id: String },
+    Add(NewTask),
+}
+
+/// What one [`BacklogChange`] did.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ChangeOutcome {
+    Applied,
+    /// The task was added under this id.
+    Added(String),
+    /// This task already holds the identity, live or archived, or is the open
+    /// near-duplicate that absorbed it; nothing new was added.
+    AlreadyPresent(String),
+    /// The task moved on (e.g. a worker leased it), so the change no longer
+    /// applies.
+    Stale,
+}
+
+/// Fields an operator may change on an existing task. Changing the kind, title or
+/// scope changes the task's identity, so the row is re-keyed to the new task_id.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct TaskEdit {
+    pub kind: Option<TaskKind>,
+    pub title: Option<String>,
+    pub details: Option<String>,
+    pub rationale: Option<String>,
+    pub scope_key: Option<String>,
 }
 
 #[derive(Debug, Clone, PartialEq, Eq)]
@@ -83,21 +485,31 @@ pub struct NewTask {
     pub source: String,
     pub related_pr: Option<i64>,
     pub related_branch: Option<String>,
+    pub blocked_by: Vec<String>,
 }
 
 #[derive(Debug)]
 enum WriteCmd {
     Upsert {
         task: NewTask,
+        absorb_near_duplicates: bool,
         now: i64,
         reply: oneshot::Sender<StoreResult<BacklogTask>>,
     },
     ClaimNext {
         lease_owner: String,
         lease_expires_at: i64,
+        options: ClaimOptions,
         now: i64,
         reply: oneshot::Sender<StoreResult<Option<BacklogTask>>>,
     },
+    PreviewClaims {
+        limit: usize,
+        parallelism: usize,
+        options: ClaimOptions,
+        now: i64,
+        reply: oneshot::Sender<StoreResult<Vec<BacklogTask>>>,
+    },
     MarkInProgress {
         task_id: String,
         lease_owner: String,
@@ -107,6 +519,7 @@ enum WriteCmd {
     MarkComplete {
         task_id: String,
         lease_owner: String,
+        context: TransitionContext,
         now: i64,
         reply: oneshot::Sender<StoreResult<bool>>,
     },
@@ -114,18 +527,108 @@ enum WriteCmd {
         now: i64,
         reply: oneshot::Sender<StoreResult<usize>>,
     },
+    ScheduleRetry {
+        task_id: String,
+        lease_owner: String,
+        not_before: i64,
+        context: TransitionContext,
+        now: i64,
+        reply: oneshot::Sender<StoreResult<bool>>,
+    },
+    MarkExhausted {
+        task_id: String,
+        lease_owner: String,
+        context: TransitionContext,
+        now: i64,
+        reply: oneshot::Sender<StoreResult<bool>>,
+    },
+    RenewLease {
+        task_id: String,
+        lease_owner: String,
+        lease_expires_at: i64,
+        reply: oneshot::Sender<StoreResult<bool>>,
+    },
     ReleaseLease {
         task_id: String,
         lease_owner: String,
+        context: TransitionContext,
         now: i64,
         reply: oneshot::Sender<StoreResult<bool>>,
     },
     MarkUnresolved {
         task_id: String,
         lease_owner: String,
+        context: TransitionContext,
+        now: i64,
+        reply: oneshot::Sender<StoreResult<bool>>,
+    },
+    MoveNamespace {
+        from_namespace: String,
+        to_namespace: String,
+        now: i64,
+        reply: oneshot::Sender<StoreResult<usize>>,
+    },
+    EditTask {
+        task_id: String,
+        edit: TaskEdit,
+        now: i64,
+        reply: oneshot::Sender<StoreResult<Option<BacklogTask>>>,
+    },
+    SetPriority {
+        task_id: String,
+        priority: Priority,
+        now: i64,
+        reply: oneshot::Sender<StoreResult<bool>>,
+    },
+    CancelTask {
+        task_id: String,
+        reason: String,
+        now: i64,
+        reply: oneshot::Sender<StoreResult<bool>>,
+    },
+    RequeueTask {
+        task_id: String,
+        now: i64,
+        reply: oneshot::Sender<StoreResult<bool>>,
+    },
+    ApplyChanges {
+        changes: Vec<BacklogChange>,
+        now: i64,
+        reply: oneshot::Sender<StoreResult<Vec<ChangeOutcome>>>,
+    },
+    ImportIssue {
+        issue_number: i64,
+        task: NewTask,
+        now: i64,
+        reply: oneshot::Sender<StoreResult<Option<BacklogTask>>>,
+    },
+    ResolveIssue {
+        issue_number: i64,
+        now: i64,
+        reply: oneshot::Sender<StoreResult<bool>>,
+    },
+    RecordOutcome {
+        outcome: NewTaskOutcome,
+        reply: oneshot::Sender<StoreResult<i64>>,
+    },
+    ArchiveFinished {
+        cutoff: i64,
+        now: i64,
+        reply: oneshot::Sender<StoreResult<Vec<String>>>,
+    },
+    MergeDuplicate {
+        keeper_id: String,
+        duplicate_id: String,
         now: i64,
         reply: oneshot::Sender<StoreResult<bool>>,
     },
+    ImportRecords {
+        records: Vec<BacklogTaskRecord>,
+        mode: ImportMode,
+        dry_run: bool,
+        now: i64,
+        reply: oneshot::Sender<StoreResult<ImportReport>>,
+    },
 }
 
 pub struct BacklogStore {
@@ -133,6 +636,7 @@ pub struct BacklogStore {
     read_pool: ReadPool,
     writer_join: Option<thread::JoinHandle<()>>,
     db_path: PathBuf,
+    namespace: String,
 }
 
 impl Drop for BacklogStore {
@@ -157,12 +661,82 @@ impl BacklogStore {
             .ok_or_else(|| GardenerError::Database("store is closed".to_string()))
     }
 
+    /// Sends a command to the writer thread and waits for its reply.
+    fn request<T>(
+        &self,
+        build: impl FnOnce(oneshot::Sender<StoreResult<T>>) -> WriteCmd,
+    ) -> StoreResult<T> {
+        let (reply_tx, reply_rx) = oneshot::channel();
+        let unavailable = |error: String| {
+            append_run_log(
+                "error",
+                "backlog_store.writer_unavailable",
+                json!({ "error": error }),
+            );
+            GardenerError::Database(error)
+        };
+        self.sender()?
+            .blocking_send(build(reply_tx))
+            .map_err(|e| unavailable(e.to_string()))?;
+        reply_rx
+            .blocking_recv()
+            .map_err(|e| unavailable(e.to_string()))?
+    }
+
     pub fn open(path: impl AsRef<Path>) -> StoreResult<Self> {
-        let path = path.as_ref().to_path_buf();
+        Self::open_in_namespace(path.as_ref(), LEGACY_NAMESPACE, None)
+    }
+
+    pub fn open_for_repo(path: impl AsRef<Path>, identity: &RepoIdentity) -> StoreResult<Self> {
+        Self::open_in_namespace(path.as_ref(), &identity.namespace, Some(identity))
+    }
+
+    /// Opens a throwaway copy of the backlog at `path`, written to
+    /// `snapshot_path`, in `identity`'s namespace. The original is only read,
+    /// so a preview gets the usual open (migrations, namespace registration,
+    /// stale lease recovery) and can simulate claims without touching it.
+    pub fn open_snapshot_for_repo(
+        path: impl AsRef<Path>,
+        snapshot_path: impl AsRef<Path>,
+        identity: &RepoIdentity,
+    ) -> StoreResult<Self> {
+        let (path, snapshot_path) = (path.as_ref(), snapshot_path.as_ref());
+        append_run_log(
+            "info",
+            "backlog_store.snapshot",
+            json!({
+                "path": path.display().to_string(),
+                "snapshot_path": snapshot_path.display().to_string(),
+            }),
+        );
+        if let Some(parent) = snapshot_path.parent() {
+            std::fs::create_dir_all(parent).map_err(|e| GardenerError::Database(e.to_string()))?;
+        }
+        let source =
+            Connection::open_with_flags(path, OpenFlags::SQLITE_OPEN_READ_ONLY).map_err(db_err)?;
+        source
+            .busy_timeout(std::time::Duration::from_secs(3))
+            .map_err(db_err)?;
+        source
+            .execute(
+                "VACUUM INTO ?1",
+                params![snapshot_path.to_string_lossy().into_owned()],
+            )
+            .map_err(db_err)?;
+        drop(source);
+        Self::open_for_repo(snapshot_path, identity)
+    }
+
+    fn open_in_namespace(
+        path: &Path,
+        namespace: &str,
+        identity: Option<&RepoIdentity>,
+    ) -> StoreResult<Self> {
+        let path = path.to_path_buf();
         append_run_log(
             "info",
             "backlog_store.open",
-            json!({ "path": path.display().to_string() }),
+            json!({ "path": path.display().to_string(), "namespace": namespace }),
         );
         if let Some(parent) = path.parent() {
             std::fs::create_dir_all(parent).map_err(|e| GardenerError::Database(e.to_string()))?;
@@ -216,30 +790,94 @@ impl BacklogStore {
         }
 
         run_migrations(&mut write_conn)?;
+        if let Some(identity) = identity {
+            register_namespace(&write_conn, identity, system_time_unix())?;
+            // Rows from before namespacing cannot be told apart by repository,
+            // so they stay put until an operator adopts them explicitly.
+            let legacy: i64 = write_conn
+                .query_row(
+                    "SELECT COUNT(*) FROM backlog_tasks WHERE repo_namespace = ?1",
+                    params![LEGACY_NAMESPACE],
+                    |row| row.get(0),
+                )
+                .map_err(db_err)?;
+            if legacy > 0 {
+                append_run_log(
+                    "warn",
+                    "backlog_store.namespace.legacy_pending",
+                    json!({
+                        "namespace": namespace,
+                        "legacy_tasks": legacy,
+                        "hint": format!("gardener --adopt-namespace {LEGACY_NAMESPACE}"),
+                    }),
+                );
+            }
+        }
 
+        let writer_namespace = namespace.to_string();
         let (write_tx, mut write_rx) = mpsc::channel(128);
         let writer_join = thread::spawn(move || {
+            let namespace = writer_namespace.as_str();
             while let Some(cmd) = write_rx.blocking_recv() {
                 match cmd {
-                    WriteCmd::Upsert { task, now, reply } => {
-                        let result = upsert_task(&write_conn, &task, now).and_then(|_| {
-                            fetch_task(&write_conn, &compute_task_id_from_new_task(&task))?
-                                .ok_or_else(|| {
-                                    GardenerError::Database(
-                                        "row missing after upsert".to_string(),
-                                    )
-                                })
+                    WriteCmd::Upsert {
+                        task,
+                        absorb_near_duplicates,
+                        now,
+                        reply,
+                    } => {
+                        let result = upsert_task(
+                            &mut write_conn,
+                            namespace,
+                            &task,
+                            now,
+                            absorb_near_duplicates,
+                        )
+                        .and_then(|task_id| {
+                            // An archived id is reserved: the upsert lands on the
+                            // archived row and leaves it untouched.
+                            let row = match fetch_task(&write_conn, namespace, &task_id)? {
+                                Some(row) => Some(row),
+                                None => fetch_archived_task(&write_conn, namespace, &task_id)?,
+                            };
+                            row.ok_or_else(|| {
+                                GardenerError::Database("row missing after upsert".to_string())
+                            })
                         });
                         let _ = reply.send(result);
                     }
                     WriteCmd::ClaimNext {
                         lease_owner,
                         lease_expires_at,
+                        options,
                         now,
                         reply,
                     } => {
-                        let result =
-                            claim_next(&mut write_conn, &lease_owner, lease_expires_at, now);
+                        let result = claim_next(
+                            &mut write_conn,
+                            namespace,
+                            &lease_owner,
+                            lease_expires_at,
+                            &options,
+                            now,
+                        );
+                        let _ = reply.send(result);
+                    }
+                    WriteCmd::PreviewClaims {
+                        limit,
+                        parallelism,
+                        options,
+                        now,
+                        reply,
+                    } => {
+                        let result = preview_claims(
+                            &mut write_conn,
+                            namespace,
+                            limit,
+                            parallelism,
+                            &options,
+                            now,
+                        );
                         let _ = reply.send(result);
                     }
                     WriteCmd::MarkInProgress {
@@ -248,79 +886,294 @@ impl BacklogStore {
                         now,
                         reply,
                     } => {
-                        let result = mark_in_progress(&write_conn, &task_id, &lease_owner, now);
+                        let result = mark_in_progress(
+                            &mut write_conn,
+                            namespace,
+                            &task_id,
+                            &lease_owner,
+                            now,
+                        );
                         let _ = reply.send(result);
                     }
                     WriteCmd::MarkComplete {
                         task_id,
                         lease_owner,
+                        context,
                         now,
                         reply,
                     } => {
-                        let result = mark_complete(&write_conn, &task_id, &lease_owner, now);
+                        let result = mark_complete(
+                            &mut write_conn,
+                            namespace,
+                            &task_id,
+                            &lease_owner,
+                            &context,
+                            now,
+                        );
                         let _ = reply.send(result);
                     }
                     WriteCmd::RecoverStale { now, reply } => {
-                        let result = recover_stale(&write_conn, now);
+                        let result = recover_stale(&mut write_conn, namespace, now);
+                        let _ = reply.send(result);
+                    }
+                    WriteCmd::ScheduleRetry {
+                        task_id,
+                        lease_owner,
+                        not_before,
+                        context,
+                        now,
+                        reply,
+                    } => {
+                        let result = schedule_retry(
+                            &mut write_conn,
+                            namespace,
+                            &task_id,
+                            &lease_owner,
+                            not_before,
+                            &context,
+                            now,
+                        );
+                        let _ = reply.send(result);
+                    }
+                    WriteCmd::MarkExhausted {
+                        task_id,
+                        lease_owner,
+                        context,
+                        now,
+                        reply,
+                    } => {
+                        let result = mark_exhausted(
+                            &mut write_conn,
+                            namespace,
+                            &task_id,
+                            &lease_owner,
+                            &context,
+                            now,
+                        );
+                        let _ = reply.send(result);
+                    }
+                    WriteCmd::RenewLease {
+                        task_id,
+                        lease_owner,
+                        lease_expires_at,
+                        reply,
+                    } => {
+                        let result = renew_lease(
+                            &write_conn,
+                            namespace,
+                            &task_id,
+                            &lease_owner,
+                            lease_expires_at,
+                        );
                         let _ = reply.send(result);
                     }
                     WriteCmd::ReleaseLease {
                         task_id,
                         lease_owner,
+                        context,
                         now,
                         reply,
                     } => {
-                let result = release_lease(&write_conn, &task_id, &lease_owner, now);
-                let _ = reply.send(result);
-            }
-            WriteCmd::MarkUnresolved {
-                task_id,
-                lease_owner,
-                now,
-                reply,
-            } => {
-                let result = mark_unresolved(&write_conn, &task_id, &lease_owner, now);
-                let _ = reply.send(result);
-            }
-        }
-            }
-        });
-
-        let read_pool = ReadPool::open(&path, READ_POOL_SIZE)?;
-        let store = Self {
-            write_tx: Some(write_tx),
-            read_pool,
-            writer_join: Some(writer_join),
-            db_path: path.clone(),
-        };
-
-        let recovered = store.recover_stale_leases(system_time_unix())?;
-        let (p0, p1, p2) = store.count_tasks_by_priority().unwrap_or((0, 0, 0));
-        append_run_log(
-            "info",
-            "backlog_store.opened",
-            json!({
-                "path": path.display().to_string(),
-                "stale_recovered": recovered,
-                "task_count": { "p0": p0, "p1": p1, "p2": p2, "total": p0 + p1 + p2 },
-            }),
-        );
-
-        Ok(store)
-    }
-
-    pub fn db_path(&self) -> &Path {
-        &self.db_path
-    }
-
-    pub fn upsert_task(&self, task: NewTask) -> StoreResult<BacklogTask> {
-        append_run_log(
-            "debug",
-            "backlog.task.upsert_client_started",
-            json!({
+                        let result = release_lease(
+                            &mut write_conn,
+                            namespace,
+                            &task_id,
+                            &lease_owner,
+                            &context,
+                            now,
+                        );
+                        let _ = reply.send(result);
+                    }
+                    WriteCmd::MarkUnresolved {
+                        task_id,
+                        lease_owner,
+                        context,
+                        now,
+                        reply,
+                    } => {
+                        let result = mark_unresolved(
+                            &mut write_conn,
+                            namespace,
+                            &task_id,
+                            &lease_owner,
+                            &context,
+                            now,
+                        );
+                        let _ = reply.send(result);
+                    }
+                    WriteCmd::MoveNamespace {
+                        from_namespace,
+                        to_namespace,
+                        now,
+                        reply,
+                    } => {
+                        let result = move_namespace(
+                            &mut write_conn,
+                            &from_namespace,
+                            &to_namespace,
+                            now,
+                        );
+                        let _ = reply.send(result);
+                    }
+                    WriteCmd::EditTask {
+                        task_id,
+                        edit,
+                        now,
+                        reply,
+                    } => {
+                        let result = edit_task(&mut write_conn, namespace, &task_id, &edit, now);
+                        let _ = reply.send(result);
+                    }
+                    WriteCmd::SetPriority {
+                        task_id,
+                        priority,
+                        now,
+                        reply,
+                    } => {
+                        let result =
+                            set_priority(&mut write_conn, namespace, &task_id, priority, now);
+                        let _ = reply.send(result);
+                    }
+                    WriteCmd::CancelTask {
+                        task_id,
+                        reason,
+                        now,
+                        reply,
+                    } => {
+                        let result = cancel_task(&mut write_conn, namespace, &task_id, &reason, now);
+                        let _ = reply.send(result);
+                    }
+                    WriteCmd::RequeueTask { task_id, now, reply } => {
+                        let result = requeue_task(&mut write_conn, namespace, &task_id, now);
+                        let _ = reply.send(result);
+                    }
+                    WriteCmd::ApplyChanges {
+                        changes,
+                        now,
+                        reply,
+                    } => {
+                        let result = apply_changes(&mut write_conn, namespace, &changes, now);
+                        let _ = reply.send(result);
+                    }
+                    WriteCmd::ImportIssue {
+                        issue_number,
+                        task,
+                        now,
+                        reply,
+                    } => {
+                        let result =
+                            import_issue(&mut write_conn, namespace, issue_number, &task, now);
+                        let _ = reply.send(result);
+                    }
+                    WriteCmd::ResolveIssue {
+                        issue_number,
+                        now,
+                        reply,
+                    } => {
+                        let result = resolve_issue(&write_conn, namespace, issue_number, now);
+                        let _ = reply.send(result);
+                    }
+                    WriteCmd::RecordOutcome { outcome, reply } => {
+                        let result = record_outcome(&write_conn, namespace, &outcome);
+                        let _ = reply.send(result);
+                    }
+                    WriteCmd::ArchiveFinished { cutoff, now, reply } => {
+                        let result =
+                            archive_finished_tasks(&mut write_conn, namespace, cutoff, now);
+                        let _ = reply.send(result);
+                    }
+                    WriteCmd::MergeDuplicate {
+                        keeper_id,
+                        duplicate_id,
+                        now,
+                        reply,
+                    } => {
+                        let result = merge_duplicate(
+                            &mut write_conn,
+                            namespace,
+                            &keeper_id,
+                            &duplicate_id,
+                            now,
+                        );
+                        let _ = reply.send(result);
+                    }
+                    WriteCmd::ImportRecords {
+                        records,
+                        mode,
+                        dry_run,
+                        now,
+                        reply,
+                    } => {
+                        let result = import_records(
+                            &mut write_conn,
+                            namespace,
+                            &records,
+                            mode,
+                            dry_run,
+                            now,
+                        );
+                        let _ = reply.send(result);
+                    }
+                }
+            }
+        });
+
+        let read_pool = ReadPool::open(&path, READ_POOL_SIZE)?;
+        let store = Self {
+            write_tx: Some(write_tx),
+            read_pool,
+            writer_join: Some(writer_join),
+            db_path: path.clone(),
+            namespace: namespace.to_string(),
+        };
+
+        let recovered = store.recover_stale_leases(system_time_unix())?;
+        let (p0, p1, p2) = store.count_tasks_by_priority().unwrap_or((0, 0, 0));
+        append_run_log(
+            "info",
+            "backlog_store.opened",
+            json!({
+                "path": path.display().to_string(),
+                "namespace": namespace,
+                "stale_recovered": recovered,
+                "task_count": { "p0": p0, "p1": p1, "p2": p2, "total": p0 + p1 + p2 },
+            }),
+        );
+
+        Ok(store)
+    }
+
+    pub fn db_path(&self) -> &Path {
+        &self.db_path
+    }
+
+    pub fn namespace(&self) -> &str {
+        &self.namespace
+    }
+
+    pub fn upsert_task(&self, task: NewTask) -> StoreResult<BacklogTask> {
+        self.upsert_task_with(task, true)
+    }
+
+    /// Upserts `task` on its own id even when a ready task with a near-duplicate
+    /// title exists, for callers that go on to select exactly that row.
+    pub fn upsert_task_exact(&self, task: NewTask) -> StoreResult<BacklogTask> {
+        self.upsert_task_with(task, false)
+    }
+
+    fn upsert_task_with(
+        &self,
+        task: NewTask,
+        absorb_near_duplicates: bool,
+    ) -> StoreResult<BacklogTask> {
+        append_run_log(
+            "debug",
+            "backlog.task.upsert_client_started",
+            json!({
                 "scope_key": task.scope_key,
                 "priority": task.priority.as_str(),
                 "source": task.source,
+                "absorb_near_duplicates": absorb_near_duplicates,
             }),
         );
         let now = system_time_unix();
@@ -333,19 +1186,22 @@ impl BacklogStore {
                 "scope_key": task.scope_key,
                 "priority": task.priority.as_str(),
                 "source": task.source,
+                "blocked_by": task.blocked_by,
             }),
         );
-        let (reply_tx, reply_rx) = oneshot::channel();
-        self.sender()?
-            .blocking_send(WriteCmd::Upsert {
-                task,
-                now,
-                reply: reply_tx,
-            })
-            .map_err(|e| GardenerError::Database(e.to_string()))?;
-        let result = reply_rx
-            .blocking_recv()
-            .map_err(|e| GardenerError::Database(e.to_string()))?;
+        let result = self.request(|reply| WriteCmd::Upsert {
+            task,
+            absorb_near_duplicates,
+            now,
+            reply,
+        });
+        if let Err(ref e) = result {
+            append_run_log(
+                "warn",
+                "backlog.task.upsert_rejected",
+                json!({ "error": e.to_string() }),
+            );
+        }
         if let Ok(ref row) = result {
             append_run_log(
                 "info",
@@ -358,6 +1214,7 @@ impl BacklogStore {
                     "priority": row.priority.as_str(),
                     "status": row.status.as_str(),
                     "source": row.source,
+                    "blocked_by": row.blocked_by,
                 }),
             );
         }
@@ -368,6 +1225,17 @@ impl BacklogStore {
         &self,
         lease_owner: &str,
         lease_duration_secs: i64,
+    ) -> StoreResult<Option<BacklogTask>> {
+        self.claim_next_with(lease_owner, lease_duration_secs, &ClaimOptions::default())
+    }
+
+    /// Claims the next ready task, skipping tasks whose scope is held by an
+    /// active task under `options`.
+    pub fn claim_next_with(
+        &self,
+        lease_owner: &str,
+        lease_duration_secs: i64,
+        options: &ClaimOptions,
     ) -> StoreResult<Option<BacklogTask>> {
         let now = system_time_unix();
         let lease_expires_at = now.saturating_add(lease_duration_secs.saturating_mul(1000));
@@ -379,18 +1247,13 @@ impl BacklogStore {
                 "lease_duration_secs": lease_duration_secs,
             }),
         );
-        let (reply_tx, reply_rx) = oneshot::channel();
-        self.sender()?
-            .blocking_send(WriteCmd::ClaimNext {
-                lease_owner: lease_owner.to_string(),
-                lease_expires_at,
-                now,
-                reply: reply_tx,
-            })
-            .map_err(|e| GardenerError::Database(e.to_string()))?;
-        let result = reply_rx
-            .blocking_recv()
-            .map_err(|e| GardenerError::Database(e.to_string()))?;
+        let result = self.request(|reply| WriteCmd::ClaimNext {
+            lease_owner: lease_owner.to_string(),
+            lease_expires_at,
+            options: options.clone(),
+            now,
+            reply,
+        });
         match &result {
             Ok(Some(task)) => {
                 append_run_log(
@@ -430,18 +1293,12 @@ impl BacklogStore {
     }
 
     pub fn mark_in_progress(&self, task_id: &str, lease_owner: &str) -> StoreResult<bool> {
-        let (reply_tx, reply_rx) = oneshot::channel();
-        self.sender()?
-            .blocking_send(WriteCmd::MarkInProgress {
-                task_id: task_id.to_string(),
-                lease_owner: lease_owner.to_string(),
-                now: system_time_unix(),
-                reply: reply_tx,
-            })
-            .map_err(|e| GardenerError::Database(e.to_string()))?;
-        let result = reply_rx
-            .blocking_recv()
-            .map_err(|e| GardenerError::Database(e.to_string()))?;
+        let result = self.request(|reply| WriteCmd::MarkInProgress {
+            task_id: task_id.to_string(),
+            lease_owner: lease_owner.to_string(),
+            now: system_time_unix(),
+            reply,
+        });
         match &result {
             Ok(true) => {
                 append_run_log(
@@ -469,18 +1326,22 @@ impl BacklogStore {
     }
 
     pub fn mark_complete(&self, task_id: &str, lease_owner: &str) -> StoreResult<bool> {
-        let (reply_tx, reply_rx) = oneshot::channel();
-        self.sender()?
-            .blocking_send(WriteCmd::MarkComplete {
-                task_id: task_id.to_string(),
-                lease_owner: lease_owner.to_string(),
-                now: system_time_unix(),
-                reply: reply_tx,
-            })
-            .map_err(|e| GardenerError::Database(e.to_string()))?;
-        let result = reply_rx
-            .blocking_recv()
-            .map_err(|e| GardenerError::Database(e.to_string()))?;
+        self.mark_complete_with(task_id, lease_owner, TransitionContext::default())
+    }
+
+    pub fn mark_complete_with(
+        &self,
+        task_id: &str,
+        lease_owner: &str,
+        context: TransitionContext,
+    ) -> StoreResult<bool> {
+        let result = self.request(|reply| WriteCmd::MarkComplete {
+            task_id: task_id.to_string(),
+            lease_owner: lease_owner.to_string(),
+            context,
+            now: system_time_unix(),
+            reply,
+        });
         match &result {
             Ok(true) => {
                 append_run_log(
@@ -508,18 +1369,22 @@ impl BacklogStore {
     }
 
     pub fn release_lease(&self, task_id: &str, lease_owner: &str) -> StoreResult<bool> {
-        let (reply_tx, reply_rx) = oneshot::channel();
-        self.sender()?
-            .blocking_send(WriteCmd::ReleaseLease {
-                task_id: task_id.to_string(),
-                lease_owner: lease_owner.to_string(),
-                now: system_time_unix(),
-                reply: reply_tx,
-            })
-            .map_err(|e| GardenerError::Database(e.to_string()))?;
-        let result = reply_rx
-            .blocking_recv()
-            .map_err(|e| GardenerError::Database(e.to_string()))?;
+        self.release_lease_with(task_id, lease_owner, TransitionContext::default())
+    }
+
+    pub fn release_lease_with(
+        &self,
+        task_id: &str,
+        lease_owner: &str,
+        context: TransitionContext,
+    ) -> StoreResult<bool> {
+        let result = self.request(|reply| WriteCmd::ReleaseLease {
+            task_id: task_id.to_string(),
+            lease_owner: lease_owner.to_string(),
+            context,
+            now: system_time_unix(),
+            reply,
+        });
         match &result {
             Ok(true) => {
                 append_run_log(
@@ -547,18 +1412,22 @@ impl BacklogStore {
     }
 
     pub fn mark_unresolved(&self, task_id: &str, lease_owner: &str) -> StoreResult<bool> {
-        let (reply_tx, reply_rx) = oneshot::channel();
-        self.sender()?
-            .blocking_send(WriteCmd::MarkUnresolved {
-                task_id: task_id.to_string(),
-                lease_owner: lease_owner.to_string(),
-                now: system_time_unix(),
-                reply: reply_tx,
-            })
-            .map_err(|e| GardenerError::Database(e.to_string()))?;
-        let result = reply_rx
-            .blocking_recv()
-            .map_err(|e| GardenerError::Database(e.to_string()))?;
+        self.mark_unresolved_with(task_id, lease_owner, TransitionContext::default())
+    }
+
+    pub fn mark_unresolved_with(
+        &self,
+        task_id: &str,
+        lease_owner: &str,
+        context: TransitionContext,
+    ) -> StoreResult<bool> {
+        let result = self.request(|reply| WriteCmd::MarkUnresolved {
+            task_id: task_id.to_string(),
+            lease_owner: lease_owner.to_string(),
+            context,
+            now: system_time_unix(),
+            reply,
+        });
         match &result {
             Ok(true) => {
                 append_run_log(
@@ -585,17 +1454,120 @@ impl BacklogStore {
         result
     }
 
+    /// Returns a failed task to `ready` but keeps it out of `claim_next` until
+    /// `not_before` (unix millis).
+    pub fn schedule_retry(
+        &self,
+        task_id: &str,
+        lease_owner: &str,
+        not_before: i64,
+        context: TransitionContext,
+    ) -> StoreResult<bool> {
+        let result = self.request(|reply| WriteCmd::ScheduleRetry {
+            task_id: task_id.to_string(),
+            lease_owner: lease_owner.to_string(),
+            not_before,
+            context,
+            now: system_time_unix(),
+            reply,
+        });
+        log_lease_transition("retry_scheduled", task_id, lease_owner, &result);
+        result
+    }
+
+    /// Moves a task whose retry budget is spent to the terminal `exhausted`
+    /// status. Dependents are blocked as they are for unresolved prerequisites.
+    pub fn mark_exhausted(
+        &self,
+        task_id: &str,
+        lease_owner: &str,
+        context: TransitionContext,
+    ) -> StoreResult<bool> {
+        let result = self.request(|reply| WriteCmd::MarkExhausted {
+            task_id: task_id.to_string(),
+            lease_owner: lease_owner.to_string(),
+            context,
+            now: system_time_unix(),
+            reply,
+        });
+        log_lease_transition("exhausted", task_id, lease_owner, &result);
+        result
+    }
+
+    /// Extends a lease the caller still owns. Returns `false` once ownership has
+    /// been lost (released, recovered, or completed elsewhere); the caller must
+    /// then stop working on the task.
+    pub fn renew_lease(
+        &self,
+        task_id: &str,
+        lease_owner: &str,
+        lease_expires_at: i64,
+    ) -> StoreResult<bool> {
+        let result = self.request(|reply| WriteCmd::RenewLease {
+            task_id: task_id.to_string(),
+            lease_owner: lease_owner.to_string(),
+            lease_expires_at,
+            reply,
+        });
+        match &result {
+            Ok(true) => append_run_log(
+                "debug",
+                "backlog.lease.renewed",
+                json!({
+                    "task_id": task_id,
+                    "lease_owner": lease_owner,
+                    "lease_expires_at": lease_expires_at,
+                }),
+            ),
+            Ok(false) => append_run_log(
+                "warn",
+                "backlog.lease.renewal_rejected",
+                json!({ "task_id": task_id, "lease_owner": lease_owner }),
+            ),
+            Err(e) => append_run_log(
+                "error",
+                "backlog.lease.renewal_failed",
+                json!({
+                    "task_id": task_id,
+                    "lease_owner": lease_owner,
+                    "error": e.to_string(),
+                }),
+            ),
+        }
+        result
+    }
+
+    /// The first `limit` tasks a pool of `parallelism` workers would claim, in
+    /// claim order, without claiming them. Claims run in waves of `parallelism`
+    /// so scope exclusivity holds within a wave, and each wave counts as
+    /// complete before the next so dependents become claimable. Every change is
+    /// rolled back.
+    pub fn preview_claims(
+        &self,
+        limit: usize,
+        parallelism: usize,
+        options: &ClaimOptions,
+    ) -> StoreResult<Vec<BacklogTask>> {
+        let now = system_time_unix();
+        append_run_log(
+            "debug",
+            "backlog.task.preview_claims",
+            json!({ "limit": limit, "parallelism": parallelism }),
+        );
+        self.request(|reply| WriteCmd::PreviewClaims {
+            limit,
+            parallelism,
+            options: options.clone(),
+            now,
+            reply,
+        })
+    }
+
     pub fn recover_stale_leases(&self, now: i64) -> StoreResult<usize> {
-        let (reply_tx, reply_rx) = oneshot::channel();
-        self.sender()?
-            .blocking_send(WriteCmd::RecoverStale {
-                now,
-                reply: reply_tx,
-            })
-            .map_err(|e| GardenerError::Database(e.to_string()))?;
-        let result = reply_rx
-            .blocking_recv()
-            .map_err(|e| GardenerError::Database(e.to_string()))?;
+        let result = self.request(|reply| WriteCmd::RecoverStale {
+            now,
+            reply,
+        });
         match &result {
             Ok(count) if *count > 0 => {
                 append_run_log(
@@ -623,22 +1595,28 @@ impl BacklogStore {
     }
 
     pub fn list_tasks(&self) -> StoreResult<Vec<BacklogTask>> {
+        self.list_tasks_filtered(&TaskFilter::default())
+    }
+
+    pub fn list_tasks_filtered(&self, filter: &TaskFilter) -> StoreResult<Vec<BacklogTask>> {
+        let mut params = vec![self.namespace.clone()];
+        let filter_clause = filter.sql_clause(&mut params);
         self.read_pool.with_conn(|conn| {
             let mut statement = conn
-                .prepare(
-                    "SELECT task_id, kind, title, details, scope_key, priority, status, last_updated, \
-                            lease_owner, lease_expires_at, source, related_pr, related_branch, rationale, attempt_count, created_at \
-                     FROM backlog_tasks \
+                .prepare(&format!(
+                    "SELECT {TASK_COLUMNS}
+                     FROM backlog_tasks
+                     WHERE repo_namespace = ?1{filter_clause}
                      ORDER BY
                         CASE priority WHEN 'P0' THEN 0 WHEN 'P1' THEN 1 ELSE 2 END,
                         CASE WHEN attempt_count > 0 THEN 0 ELSE 1 END,
                         attempt_count DESC,
                         last_updated ASC,
-                        created_at ASC",
-                )
+                        created_at ASC"
+                ))
                 .map_err(db_err)?;
             let rows = statement
-                .query_map([], row_to_task)
+                .query_map(rusqlite::params_from_iter(params.iter()), row_to_task)
                 .map_err(db_err)?
                 .collect::<Result<Vec<_>, _>>()
                 .map_err(db_err)?;
@@ -646,58 +1624,588 @@ impl BacklogStore {
         })
     }
 
-    pub fn count_tasks_by_priority(&self) -> StoreResult<(usize, usize, usize)> {
-        append_run_log(
-            "debug",
-            "backlog.tasks.count_by_priority.started",
-            json!({}),
-        );
-        self.read_pool.with_conn(|conn| {
+    /// Full-text search over title, details and rationale, best match first.
+    /// Every word in `query` must appear, each matched as a prefix, so `flak tes`
+    /// finds "Flaky test". A query with no words returns nothing.
+    pub fn search(&self, query: &str, filter: &TaskFilter) -> StoreResult<Vec<BacklogTask>> {
+        let Some(match_expr) = fts_match_expression(query) else {
+            return Ok(Vec::new());
+        };
+        let mut params = vec![self.namespace.clone(), match_expr];
+        let filter_clause = filter.sql_clause(&mut params);
+        let tasks = self.read_pool.with_conn(|conn| {
             let mut statement = conn
-                .prepare(
-                    "SELECT
-                        COALESCE(SUM(CASE WHEN priority = 'P0' THEN 1 ELSE 0 END), 0) AS p0,
-                        COALESCE(SUM(CASE WHEN priority = 'P1' THEN 1 ELSE 0 END), 0) AS p1,
-                        COALESCE(SUM(CASE WHEN priority = 'P2' THEN 1 ELSE 0 END), 0) AS p2
+                .prepare(&format!(
+                    "WITH hits AS (
+                        SELECT task_id AS hit_id,
+                               bm25(backlog_tasks_fts, 0.0, 0.0, 10.0, 2.0, 1.0) AS score
+                        FROM backlog_tasks_fts
+                        WHERE backlog_tasks_fts MATCH ?2 AND repo_namespace = ?1
+                     )
+                     SELECT {TASK_COLUMNS}
                      FROM backlog_tasks
-                    WHERE status <> 'complete'"
-                )
+                     JOIN hits ON hits.hit_id = backlog_tasks.task_id
+                     WHERE repo_namespace = ?1{filter_clause}
+                     ORDER BY hits.score ASC, created_at ASC"
+                ))
                 .map_err(db_err)?;
-            statement
-                .query_row([], |row| {
-                    let p0: i64 = row.get(0)?;
-                    let p1: i64 = row.get(1)?;
-                    let p2: i64 = row.get(2)?;
-                    Ok((p0 as usize, p1 as usize, p2 as usize))
-                })
-                .map_err(db_err)
-        })
-    }
-
-    pub fn count_active_tasks(&self) -> StoreResult<usize> {
+            let rows = statement
+                .query_map(rusqlite::params_from_iter(params.iter()), row_to_task)
+                .map_err(db_err)?
+                .collect::<Result<Vec<_>, _>>()
+                .map_err(db_err)?;
+            Ok(rows)
+        })?;
         append_run_log(
             "debug",
-            "backlog.tasks.count_active.started",
-            json!({}),
+            "backlog.tasks.searched",
+            json!({
+                "namespace": self.namespace,
+                "query": query,
+                "result_count": tasks.len()
+            }),
         );
-        self.read_pool.with_conn(|conn| {
-            let mut statement = conn
-                .prepare("SELECT COUNT(*) FROM backlog_tasks WHERE status NOT IN ('complete', 'failed')")
-                .map_err(db_err)?;
-            statement
-                .query_row([], |row| {
-                    let count: i64 = row.get(0)?;
-                    Ok(count as usize)
-                })
-                .map_err(db_err)
-        })
+        Ok(tasks)
     }
 
-    pub fn get_task(&self, task_id: &str) -> StoreResult<Option<BacklogTask>> {
-        self.read_pool.with_conn(|conn| fetch_task(conn, task_id))
-    }
-}
-
+    /// Applies an operator edit. Returns `None` when the task does not exist.
+    pub fn edit_task(&self, task_id: &str, edit: TaskEdit) -> StoreResult<Option<BacklogTask>> {
+        let result = self.request(|reply| WriteCmd::EditTask {
+            task_id: task_id.to_string(),
+            edit,
+            now: system_time_unix(),
+            reply,
+        });
+        match &result {
+            Ok(Some(row)) => append_run_log(
+                "info",
+                "backlog.task.edited",
+                json!({ "task_id": task_id, "new_task_id": row.task_id, "title": row.title }),
+            ),
+            Ok(None) => append_run_log(
+                "warn",
+                "backlog.task.edit.rejected",
+                json!({ "task_id": task_id }),
+            ),
+            Err(e) => append_run_log(
+                "error",
+                "backlog.task.edit.failed",
+                json!({ "task_id": task_id, "error": e.to_string() }),
+            ),
+        }
+        result
+    }
+
+    /// Sets the priority outright; unlike `upsert_task` this may lower it.
+    pub fn reprioritize_task(&self, task_id: &str, priority: Priority) -> StoreResult<bool> {
+        let result = self.request(|reply| WriteCmd::SetPriority {
+            task_id: task_id.to_string(),
+            priority,
+            now: system_time_unix(),
+            reply,
+        });
+        log_operator_mutation("reprioritized", task_id, &result);
+        result
+    }
+
+    /// Marks a task that is not currently leased `cancelled`; `reason` must not be
+    /// blank. Dependents are marked blocked with a reason naming the cancelled
+    /// prerequisite.
+    pub fn cancel_task(&self, task_id: &str, reason: &str) -> StoreResult<bool> {
+        let result = self.request(|reply| WriteCmd::CancelTask {
+            task_id: task_id.to_string(),
+            reason: reason.to_string(),
+            now: system_time_unix(),
+            reply,
+        });
+        log_operator_mutation("cancelled", task_id, &result);
+        result
+    }
+
+    /// Returns a complete, failed, unresolved, exhausted or cancelled task to
+    /// `ready` with a fresh retry budget.
+    pub fn requeue_task(&self, task_id: &str) -> StoreResult<bool> {
+        let result = self.request(|reply| WriteCmd::RequeueTask {
+            task_id: task_id.to_string(),
+            now: system_time_unix(),
+            reply,
+        });
+        log_operator_mutation("requeued", task_id, &result);
+        result
+    }
+
+    /// Applies `changes` in order, in one transaction. When any of them is
+    /// [`ChangeOutcome::Stale`] nothing is written and the outcomes say which.
+    /// An add whose identity a live or archived task already holds is left out,
+    /// so applying the same batch twice does not reopen finished work.
+    pub fn apply_changes(&self, changes: Vec<BacklogChange>) -> StoreResult<Vec<ChangeOutcome>> {
+        let count = changes.len();
+        let result = self.request(|reply| WriteCmd::ApplyChanges {
+            changes,
+            now: system_time_unix(),
+            reply,
+        });
+        append_run_log(
+            if result.is_ok() { "info" } else { "warn" },
+            "backlog.changes.applied",
+            json!({
+                "namespace": self.namespace,
+                "changes": count,
+                "stale": result.as_ref().ok().map(|outcomes| outcomes
+                    .iter()
+                    .filter(|outcome| **outcome == ChangeOutcome::Stale)
+                    .count()),
+                "error": result.as_ref().err().map(ToString::to_string),
+            }),
+        );
+        result
+    }
+
+    /// Moves complete and cancelled tasks untouched for more than
+    /// `older_than_days` days to `archived_tasks` and returns their ids. A
+    /// cancelled task stays live while open tasks depend on it, so they stay
+    /// blocked. Archived ids remain reserved against upserts.
+    pub fn archive_finished_tasks(&self, older_than_days: u32) -> StoreResult<Vec<String>> {
+        let now = system_time_unix();
+        let cutoff = now.saturating_sub(i64::from(older_than_days) * 86_400_000);
+        let result = self.request(|reply| WriteCmd::ArchiveFinished { cutoff, now, reply });
+        append_run_log(
+            if result.is_ok() { "info" } else { "warn" },
+            "backlog.tasks.archived",
+            json!({
+                "namespace": self.namespace,
+                "older_than_days": older_than_days,
+                "archived": result.as_ref().map(Vec::len).ok(),
+                "error": result.as_ref().err().map(ToString::to_string),
+            }),
+        );
+        result
+    }
+
+    pub fn get_archived_task(&self, task_id: &str) -> StoreResult<Option<BacklogTask>> {
+        self.read_pool
+            .with_conn(|conn| fetch_archived_task(conn, &self.namespace, task_id))
+    }
+
+    /// Groups open tasks that `upsert_task` would have merged had they arrived
+    /// in the other order. The keeper of a cluster is an active task if there is
+    /// one, otherwise the most urgent and then oldest task.
+    pub fn find_duplicate_clusters(&self) -> StoreResult<Vec<DuplicateCluster>> {
+        let mut by_scope: BTreeMap<String, Vec<BacklogTask>> = BTreeMap::new();
+        for task in self.list_tasks()? {
+            if is_open_for_merge(task.status) {
+                by_scope.entry(task.scope_key.clone()).or_default().push(task);
+            }
+        }
+        let mut clusters = Vec::new();
+        for tasks in by_scope.into_values() {
+            let mut group = (0..tasks.len()).collect::<Vec<_>>();
+            for i in 0..tasks.len() {
+                for j in (i + 1)..tasks.len() {
+                    if tasks_are_near_duplicates(&tasks[i], &tasks[j]) {
+                        let (from, to) = (group[j], group[i]);
+                        for slot in group.iter_mut().filter(|slot| **slot == from) {
+                            *slot = to;
+                        }
+                    }
+                }
+            }
+            let mut members: BTreeMap<usize, Vec<&BacklogTask>> = BTreeMap::new();
+            for (idx, root) in group.iter().enumerate() {
+                members.entry(*root).or_default().push(&tasks[idx]);
+            }
+            for mut cluster in members.into_values().filter(|members| members.len() > 1) {
+                cluster.sort_by_key(|task| {
+                    (
+                        task.status == TaskStatus::Ready,
+                        task.priority.rank(),
+                        task.created_at,
+                    )
+                });
+                let keeper = cluster[0].clone();
+                let duplicates = cluster[1..]
+                    .iter()
+                    .filter(|task| task.status == TaskStatus::Ready)
+                    .map(|task| ((*task).clone(), title_similarity(&keeper.title, &task.title)))
+                    .collect::<Vec<_>>();
+                if !duplicates.is_empty() {
+                    clusters.push(DuplicateCluster { keeper, duplicates });
+                }
+            }
+        }
+        append_run_log(
+            "info",
+            "backlog.tasks.duplicate_clusters_found",
+            json!({
+                "namespace": self.namespace,
+                "clusters": clusters.len(),
+                "duplicates": clusters.iter().map(|c| c.duplicates.len()).sum::<usize>(),
+            }),
+        );
+        Ok(clusters)
+    }
+
+    /// Folds a ready duplicate into `keeper_id`: the keeper takes the more urgent
+    /// priority and the duplicate's dependency edges, and the duplicate is retired
+    /// with a note naming the keeper.
+    pub fn merge_duplicate(&self, keeper_id: &str, duplicate_id: &str) -> StoreResult<bool> {
+        let result = self.request(|reply| WriteCmd::MergeDuplicate {
+            keeper_id: keeper_id.to_string(),
+            duplicate_id: duplicate_id.to_string(),
+            now: system_time_unix(),
+            reply,
+        });
+        match &result {
+            Ok(merged) => append_run_log(
+                "info",
+                "backlog.task.duplicate_merged",
+                json!({ "keeper_id": keeper_id, "duplicate_id": duplicate_id, "merged": merged }),
+            ),
+            Err(e) => append_run_log(
+                "error",
+                "backlog.task.duplicate_merge.failed",
+                json!({
+                    "keeper_id": keeper_id,
+                    "duplicate_id": duplicate_id,
+                    "error": e.to_string(),
+                }),
+            ),
+        }
+        result
+    }
+
+    /// Writes every task matching `filter` as one `BacklogTaskRecord` per line.
+    /// The output round-trips through `import_jsonl` without losing columns.
+    pub fn export_jsonl(&self, filter: &TaskFilter) -> StoreResult<String> {
+        let tasks = self.list_tasks_filtered(filter)?;
+        append_run_log(
+            "info",
+            "backlog.tasks.exported",
+            json!({ "namespace": self.namespace, "task_count": tasks.len() }),
+        );
+        let mut out = String::new();
+        for task in tasks {
+            let line = serde_json::to_string(&BacklogTaskRecord::from(task))
+                .map_err(|e| GardenerError::Database(e.to_string()))?;
+            out.push_str(&line);
+            out.push('\n');
+        }
+        Ok(out)
+    }
+
+    /// Reconciles the backlog with JSONL written by `export_jsonl` in a single
+    /// transaction. Every record's task_id must match its identity. A dry run
+    /// returns the diff without keeping any change.
+    pub fn import_jsonl(
+        &self,
+        contents: &str,
+        mode: ImportMode,
+        dry_run: bool,
+    ) -> StoreResult<ImportReport> {
+        let records = parse_task_records(contents)?;
+        let record_count = records.len();
+        let result = self.request(|reply| WriteCmd::ImportRecords {
+            records,
+            mode,
+            dry_run,
+            now: system_time_unix(),
+            reply,
+        });
+        match &result {
+            Ok(report) => append_run_log(
+                "info",
+                "backlog.tasks.imported",
+                json!({
+                    "namespace": self.namespace,
+                    "records": record_count,
+                    "mode": format!("{mode:?}"),
+                    "dry_run": dry_run,
+                    "added": report.count(ImportAction::Add),
+                    "updated": report.count(ImportAction::Update),
+                    "kept": report.count(ImportAction::Keep),
+                    "removed": report.count(ImportAction::Remove),
+                }),
+            ),
+            Err(e) => append_run_log(
+                "error",
+                "backlog.tasks.import.failed",
+                json!({
+                    "namespace": self.namespace,
+                    "records": record_count,
+                    "error": e.to_string(),
+                }),
+            ),
+        }
+        result
+    }
+
+    pub fn count_tasks_by_priority(&self) -> StoreResult<(usize, usize, usize)> {
+        append_run_log(
+            "debug",
+            "backlog.tasks.count_by_priority.started",
+            json!({}),
+        );
+        self.read_pool.with_conn(|conn| {
+            let mut statement = conn
+                .prepare(&format!(
+                    "SELECT
+                        COALESCE(SUM(CASE WHEN priority = 'P0' THEN 1 ELSE 0 END), 0) AS p0,
+                        COALESCE(SUM(CASE WHEN priority = 'P1' THEN 1 ELSE 0 END), 0) AS p1,
+                        COALESCE(SUM(CASE WHEN priority = 'P2' THEN 1 ELSE 0 END), 0) AS p2
+                     FROM backlog_tasks
+                    WHERE repo_namespace = ?1
+                      AND status NOT IN {CLOSED_STATUSES_SQL}"
+                ))
+                .map_err(db_err)?;
+            statement
+                .query_row([&self.namespace], |row| {
+                    let p0: i64 = row.get(0)?;
+                    let p1: i64 = row.get(1)?;
+                    let p2: i64 = row.get(2)?;
+                    Ok((p0 as usize, p1 as usize, p2 as usize))
+                })
+                .map_err(db_err)
+        })
+    }
+
+    pub fn count_active_tasks(&self) -> StoreResult<usize> {
+        append_run_log(
+            "debug",
+            "backlog.tasks.count_active.started",
+            json!({}),
+        );
+        self.read_pool.with_conn(|conn| {
+            let mut statement = conn
+                .prepare(&format!(
+                    "SELECT COUNT(*) FROM backlog_tasks
+                     WHERE repo_namespace = ?1 AND status NOT IN {CLOSED_STATUSES_SQL}",
+                ))
+                .map_err(db_err)?;
+            statement
+                .query_row([&self.namespace], |row| {
+                    let count: i64 = row.get(0)?;
+                    Ok(count as usize)
+                })
+                .map_err(db_err)
+        })
+    }
+
+    pub fn get_task(&self, task_id: &str) -> StoreResult<Option<BacklogTask>> {
+        self.read_pool
+            .with_conn(|conn| fetch_task(conn, &self.namespace, task_id))
+    }
+
+    /// Adds the task for a GitHub issue unless the issue was imported before, or
+    /// its task was archived, in which case the backlog is left alone and `None`
+    /// is returned. The issue
+    /// gets a task of its own rather than being merged into a near-duplicate,
+    /// and the task and its link are written together.
+    pub fn import_issue(
+        &self,
+        issue_number: i64,
+        task: NewTask,
+    ) -> StoreResult<Option<BacklogTask>> {
+        let now = system_time_unix();
+        let result = self.request(|reply| WriteCmd::ImportIssue {
+            issue_number,
+            task,
+            now,
+            reply,
+        });
+        append_run_log(
+            if result.is_ok() { "info" } else { "warn" },
+            "backlog.issue.imported",
+            json!({
+                "issue_number": issue_number,
+                "task_id": result.as_ref().ok().and_then(|row| row.as_ref().map(|t| &t.task_id)),
+                "already_linked": matches!(result, Ok(None)),
+                "error": result.as_ref().err().map(ToString::to_string),
+            }),
+        );
+        result
+    }
+
+    /// The GitHub issue a task was imported from, if any.
+    pub fn issue_link_for_task(&self, task_id: &str) -> StoreResult<Option<IssueLink>> {
+        append_run_log(
+            "debug",
+            "backlog.issue.link_lookup",
+            json!({ "task_id": task_id }),
+        );
+        self.read_pool.with_conn(|conn| {
+            conn.query_row(
+                "SELECT issue_number, task_id, imported_at, resolved_at
+                 FROM github_issue_links
+                 WHERE repo_namespace = ?1 AND task_id = ?2
+                 ORDER BY issue_number ASC
+                 LIMIT 1",
+                params![self.namespace, task_id],
+                |row| {
+                    Ok(IssueLink {
+                        issue_number: row.get(0)?,
+                        task_id: row.get(1)?,
+                        imported_at: row.get(2)?,
+                        resolved_at: row.get(3)?,
+                    })
+                },
+            )
+            .optional()
+            .map_err(db_err)
+        })
+    }
+
+    /// Records that an issue was closed or commented on. Returns false when the
+    /// issue is unknown or was already resolved.
+    pub fn mark_issue_resolved(&self, issue_number: i64) -> StoreResult<bool> {
+        let now = system_time_unix();
+        let result = self.request(|reply| WriteCmd::ResolveIssue {
+            issue_number,
+            now,
+            reply,
+        });
+        append_run_log(
+            "info",
+            "backlog.issue.resolved",
+            json!({ "issue_number": issue_number, "changed": result.as_ref().ok() }),
+        );
+        result
+    }
+
+    /// Stores the outcome of a finished worker run under the current run id and
+    /// returns its outcome_id.
+    pub fn record_outcome(&self, outcome: NewTaskOutcome) -> StoreResult<i64> {
+        let task_id = outcome.task_id.clone();
+        let result = self.request(|reply| WriteCmd::RecordOutcome { outcome, reply });
+        append_run_log(
+            if result.is_ok() { "info" } else { "warn" },
+            "backlog.task.outcome_recorded",
+            json!({
+                "task_id": task_id,
+                "outcome_id": result.as_ref().ok(),
+                "error": result.as_ref().err().map(ToString::to_string),
+            }),
+        );
+        result
+    }
+
+    /// Outcomes recorded for a task, oldest first.
+    pub fn outcomes_for_task(&self, task_id: &str) -> StoreResult<Vec<TaskOutcome>> {
+        append_run_log(
+            "debug",
+            "backlog.task.outcomes.started",
+            json!({ "task_id": task_id }),
+        );
+        self.read_pool.with_conn(|conn| {
+            fetch_outcomes(conn, "task_id = ?2", params![self.namespace, task_id])
+        })
+    }
+
+    /// Outcomes recorded during one gardener run, oldest first.
+    pub fn outcomes_for_run(&self, run_id: &str) -> StoreResult<Vec<TaskOutcome>> {
+        append_run_log(
+            "debug",
+            "backlog.run.outcomes.started",
+            json!({ "run_id": run_id }),
+        );
+        self.read_pool.with_conn(|conn| {
+            fetch_outcomes(conn, "run_id = ?2", params![self.namespace, run_id])
+        })
+    }
+
+    /// Every recorded transition for a task, oldest first.
+    pub fn history(&self, task_id: &str) -> StoreResult<Vec<TaskEvent>> {
+        append_run_log(
+            "debug",
+            "backlog.task.history.started",
+            json!({ "task_id": task_id }),
+        );
+        self.read_pool
+            .with_conn(|conn| fetch_history(conn, &self.namespace, task_id))
+    }
+
+    pub fn list_namespaces(&self) -> StoreResult<Vec<NamespaceSummary>> {
+        append_run_log(
+            "debug",
+            "backlog.namespaces.list.started",
+            json!({ "namespace": self.namespace }),
+        );
+        self.read_pool.with_conn(|conn| {
+            let mut statement = conn
+                .prepare(&format!(
+                    "SELECT ns.repo_namespace, r.repo_root, r.remote_url,
+                            COUNT(t.task_id),
+                            COALESCE(SUM(
+                                CASE WHEN t.status NOT IN {CLOSED_STATUSES_SQL} THEN 1 ELSE 0 END
+                            ), 0)
+                     FROM (
+                        SELECT repo_namespace FROM backlog_tasks
+                        UNION
+                        SELECT repo_namespace FROM repo_namespaces
+                     ) ns
+                     LEFT JOIN repo_namespaces r ON r.repo_namespace = ns.repo_namespace
+                     LEFT JOIN backlog_tasks t ON t.repo_namespace = ns.repo_namespace
+                     GROUP BY ns.repo_namespace, r.repo_root, r.remote_url
+                     ORDER BY ns.repo_namespace",
+                ))
+                .map_err(db_err)?;
+            let rows = statement
+                .query_map([], |row| {
+                    let task_count: i64 = row.get(3)?;
+                    let active_count: i64 = row.get(4)?;
+                    Ok(NamespaceSummary {
+                        repo_namespace: row.get(0)?,
+                        repo_root: row.get(1)?,
+                        remote_url: row.get(2)?,
+                        task_count: task_count as usize,
+                        active_count: active_count as usize,
+                    })
+                })
+                .map_err(db_err)?
+                .collect::<Result<Vec<_>, _>>()
+                .map_err(db_err)?;
+            Ok(rows)
+        })
+    }
+
+    pub fn move_namespace_tasks(
+        &self,
+        from_namespace: &str,
+        to_namespace: &str,
+    ) -> StoreResult<usize> {
+        let result = self.request(|reply| WriteCmd::MoveNamespace {
+            from_namespace: from_namespace.to_string(),
+            to_namespace: to_namespace.to_string(),
+            now: system_time_unix(),
+            reply,
+        });
+        match &result {
+            Ok(moved) => {
+                append_run_log(
+                    "info",
+                    "backlog.namespace.moved",
+                    json!({
+                        "from_namespace": from_namespace,
+                        "to_namespace": to_namespace,
+                        "moved": moved,
+                    }),
+                );
+            }
+            Err(e) => {
+                append_run_log(
+                    "error",
+                    "backlog.namespace.move_failed",
+                    json!({
+                        "from_namespace": from_namespace,
+                        "to_namespace": to_namespace,
+                        "error": e.to_string(),
+                    }),
+                );
+            }
+        }
+        result
+    }
+}
+
 #[derive(Clone)]
 struct ReadPool {
     conns: Arc<Vec<Mutex<Connection>>>,
@@ -745,6 +2253,15 @@ fn run_migrations(conn: &mut Connection) -> StoreResult<()> {
         (1_i64, include_str!("../migrations/0001_backlog.sql")),
         (2_i64, include_str!("../migrations/0002_backlog.sql")),
         (3_i64, include_str!("../migrations/0003_backlog.sql")),
+        (4_i64, include_str!("../migrations/0004_backlog.sql")),
+        (5_i64, include_str!("../migrations/0005_backlog.sql")),
+        (6_i64, include_str!("../migrations/0006_backlog.sql")),
+        (7_i64, include_str!("../migrations/0007_backlog.sql")),
+        (8_i64, include_str!("../migrations/0008_backlog.sql")),
+        (9_i64, include_str!("../migrations/0009_backlog.sql")),
+        (10_i64, include_str!("../migrations/0010_backlog.sql")),
+        (11_i64, include_str!("../migrations/0011_backlog.sql")),
+        (12_i64, include_str!("../migrations/0012_backlog.sql")),
     ];
 
     conn.execute_batch("BEGIN IMMEDIATE; CREATE TABLE IF NOT EXISTS schema_migrations (version INTEGER PRIMARY KEY, applied_at INTEGER NOT NULL); COMMIT;")
@@ -788,32 +2305,197 @@ fn run_migrations(conn: &mut Connection) -> StoreResult<()> {
     Ok(())
 }
 
-fn upsert_task(conn: &Connection, task: &NewTask, now: i64) -> StoreResult<()> {
+fn register_namespace(conn: &Connection, identity: &RepoIdentity, now: i64) -> StoreResult<()> {
     append_run_log(
         "debug",
-        "backlog_store.upsert_task.started",
+        "backlog_store.register_namespace.started",
         json!({
-            "task_id": compute_task_id_from_new_task(task),
-            "scope_key": task.scope_key,
+            "namespace": identity.namespace,
+            "repo_root": identity.repo_root.display().to_string(),
         }),
     );
-    let task_id = compute_task_id_from_new_task(task);
     conn.execute(
-        "INSERT INTO backlog_tasks (
-            task_id, kind, title, details, scope_key, priority, status, last_updated, lease_owner,
-            lease_expires_at, source, related_pr, related_branch, rationale, attempt_count, created_at
-        ) VALUES (
-            ?1, ?2, ?3, ?4, ?5, ?6, 'ready', ?7, NULL, NULL, ?8, ?9, ?10, ?11, 0, ?12
+        "INSERT INTO repo_namespaces (repo_namespace, repo_root, remote_url, registered_at, last_opened_at)
+         VALUES (?1, ?2, ?3, ?4, ?4)
+         ON CONFLICT(repo_namespace) DO UPDATE SET
+            repo_root = excluded.repo_root,
+            remote_url = excluded.remote_url,
+            last_opened_at = excluded.last_opened_at",
+        params![
+            identity.namespace,
+            identity.repo_root.display().to_string(),
+            identity.remote_url,
+            now,
+        ],
+    )
+    .map_err(db_err)?;
+    Ok(())
+}
+
+/// Moves every live task that is not leased or in progress, and every archived
+/// one, whose id the target namespace does not hold yet, together with its
+/// dependencies, events, outcomes and issue links. Rows that stay behind keep
+/// their history. Returns how many live tasks moved.
+fn move_namespace(
+    conn: &mut Connection,
+    from_namespace: &str,
+    to_namespace: &str,
+    now: i64,
+) -> StoreResult<usize> {
+    append_run_log(
+        "debug",
+        "backlog_store.move_namespace.started",
+        json!({
+            "from_namespace": from_namespace,
+            "to_namespace": to_namespace,
+        }),
+    );
+    if from_namespace == to_namespace {
+        return Ok(0);
+    }
+    let tx = conn.transaction().map_err(db_err)?;
+    let free_in_target = "NOT EXISTS (
+            SELECT 1 FROM backlog_tasks target
+            WHERE target.repo_namespace = ?2 AND target.task_id = source.task_id
+        ) AND NOT EXISTS (
+            SELECT 1 FROM archived_tasks target
+            WHERE target.repo_namespace = ?2 AND target.task_id = source.task_id
+        )";
+    let collect = |sql: String| -> StoreResult<Vec<String>> {
+        let mut statement = tx.prepare(&sql).map_err(db_err)?;
+        let ids = statement
+            .query_map(params![from_namespace, to_namespace], |row| row.get(0))
+            .map_err(db_err)?
+            .collect::<Result<Vec<String>, _>>()
+            .map_err(db_err)?;
+        Ok(ids)
+    };
+    let live = collect(format!(
+        "SELECT task_id FROM backlog_tasks source
+         WHERE repo_namespace = ?1 AND status NOT IN ('leased', 'in_progress')
+           AND {free_in_target}"
+    ))?;
+    let archived = collect(format!(
+        "SELECT task_id FROM archived_tasks source
+         WHERE repo_namespace = ?1 AND {free_in_target}"
+    ))?;
+    for task_id in &live {
+        tx.execute(
+            "UPDATE backlog_tasks SET repo_namespace = ?2, last_updated = ?3
+             WHERE repo_namespace = ?1 AND task_id = ?4",
+            params![from_namespace, to_namespace, now, task_id],
         )
-        ON CONFLICT(task_id) DO UPDATE SET
-            title = excluded.title,
-            details = excluded.details,
-            scope_key = excluded.scope_key,
-            priority = CASE
-                WHEN CASE excluded.priority
-                    WHEN 'P0' THEN 0
-                    WHEN 'P1' THEN 1
-                    ELSE 2
+        .map_err(db_err)?;
+    }
+    for task_id in &archived {
+        tx.execute(
+            "UPDATE archived_tasks SET repo_namespace = ?2
+             WHERE repo_namespace = ?1 AND task_id = ?3",
+            params![from_namespace, to_namespace, task_id],
+        )
+        .map_err(db_err)?;
+    }
+    for task_id in live.iter().chain(&archived) {
+        // An issue already linked in the target keeps that link.
+        for table in [
+            "task_dependencies",
+            "task_events",
+            "task_outcomes",
+            "github_issue_links",
+        ] {
+            tx.execute(
+                &format!(
+                    "UPDATE OR IGNORE {table} SET repo_namespace = ?2
+                     WHERE repo_namespace = ?1 AND task_id = ?3"
+                ),
+                params![from_namespace, to_namespace, task_id],
+            )
+            .map_err(db_err)?;
+        }
+    }
+    tx.commit().map_err(db_err)?;
+    append_run_log(
+        "debug",
+        "backlog_store.move_namespace.finished",
+        json!({
+            "from_namespace": from_namespace,
+            "to_namespace": to_namespace,
+            "live": live.len(),
+            "archived": archived.len(),
+        }),
+    );
+    Ok(live.len())
+}
+
+/// Inserts or refreshes a task and returns the id it landed on: its own id, or
+/// the id of an open near-duplicate in the same scope that absorbed it.
+fn upsert_task(
+    conn: &mut Connection,
+    namespace: &str,
+    task: &NewTask,
+    now: i64,
+    absorb_near_duplicates: bool,
+) -> StoreResult<String> {
+    let tx = conn.transaction().map_err(db_err)?;
+    let task_id = upsert_task_in_tx(&tx, namespace, task, now, absorb_near_duplicates)?;
+    tx.commit().map_err(db_err)?;
+    Ok(task_id)
+}
+
+/// The body of `upsert_task`, inside the caller's transaction. With
+/// `absorb_near_duplicates` off the task always lands on its own id, for callers
+/// that must know exactly which row they created.
+fn upsert_task_in_tx(
+    tx: &Transaction<'_>,
+    namespace: &str,
+    task: &NewTask,
+    now: i64,
+    absorb_near_duplicates: bool,
+) -> StoreResult<String> {
+    append_run_log(
+        "debug",
+        "backlog_store.upsert_task.started",
+        json!({
+            "task_id": compute_task_id_from_new_task(task),
+            "namespace": namespace,
+            "scope_key": task.scope_key,
+            "blocked_by": task.blocked_by,
+        }),
+    );
+    let task_id = compute_task_id_from_new_task(task);
+    let previous_status = task_status(tx, namespace, &task_id)?;
+    if previous_status.is_none() && fetch_archived_task(tx, namespace, &task_id)?.is_some() {
+        append_run_log(
+            "info",
+            "backlog_store.upsert_task.archived_id_reserved",
+            json!({ "namespace": namespace, "task_id": task_id }),
+        );
+        return Ok(task_id);
+    }
+    if previous_status.is_none() && absorb_near_duplicates {
+        if let Some((keeper, score)) = find_near_duplicate(tx, namespace, task)? {
+            absorb_near_duplicate(tx, namespace, &keeper, task, score, now)?;
+            return Ok(keeper.task_id);
+        }
+    }
+    validate_dependencies(tx, namespace, &task_id, &task.blocked_by)?;
+    tx.execute(
+        "INSERT INTO backlog_tasks (
+            task_id, kind, title, details, scope_key, priority, status, last_updated, lease_owner,
+            lease_expires_at, source, related_pr, related_branch, rationale, attempt_count, created_at,
+            repo_namespace
+        ) VALUES (
+            ?1, ?2, ?3, ?4, ?5, ?6, 'ready', ?7, NULL, NULL, ?8, ?9, ?10, ?11, 0, ?12, ?13
+        )
+        ON CONFLICT(repo_namespace, task_id) DO UPDATE SET
+            title = excluded.title,
+            details = excluded.details,
+            scope_key = excluded.scope_key,
+            priority = CASE
+                WHEN CASE excluded.priority
+                    WHEN 'P0' THEN 0
+                    WHEN 'P1' THEN 1
+                    ELSE 2
                 END < CASE backlog_tasks.priority
                     WHEN 'P0' THEN 0
                     WHEN 'P1' THEN 1
@@ -822,7 +2504,8 @@ fn upsert_task(conn: &Connection, task: &NewTask, now: i64) -> StoreResult<()> {
                 ELSE backlog_tasks.priority
             END,
             status = CASE
-                WHEN backlog_tasks.status IN ('leased', 'in_progress') THEN backlog_tasks.status
+                WHEN backlog_tasks.status IN ('leased', 'in_progress', 'exhausted', 'cancelled')
+                    THEN backlog_tasks.status
                 ELSE 'ready'
             END,
             last_updated = excluded.last_updated,
@@ -834,6 +2517,11 @@ fn upsert_task(conn: &Connection, task: &NewTask, now: i64) -> StoreResult<()> {
                 WHEN backlog_tasks.status IN ('leased', 'in_progress') THEN backlog_tasks.lease_expires_at
                 ELSE NULL
             END,
+            status_note = CASE
+                WHEN backlog_tasks.status IN ('leased', 'in_progress', 'exhausted', 'cancelled')
+                    THEN backlog_tasks.status_note
+                ELSE NULL
+            END,
             source = excluded.source,
             related_pr = excluded.related_pr,
             related_branch = excluded.related_branch,
@@ -851,85 +2539,1762 @@ fn upsert_task(conn: &Connection, task: &NewTask, now: i64) -> StoreResult<()> {
             task.related_branch,
             task.rationale,
             now,
+            namespace,
         ],
     )
     .map_err(db_err)?;
+    for prerequisite in &task.blocked_by {
+        tx.execute(
+            "INSERT OR IGNORE INTO task_dependencies (repo_namespace, task_id, depends_on, created_at)
+             VALUES (?1, ?2, ?3, ?4)",
+            params![namespace, task_id, prerequisite, now],
+        )
+        .map_err(db_err)?;
+    }
+    // A re-upsert puts an unresolved task back in the queue, so its dependents
+    // are no longer blocked on it.
+    clear_prerequisite_block(tx, namespace, &task_id, now)?;
+    let status = task_status(tx, namespace, &task_id)?.unwrap_or_else(|| "ready".to_string());
+    record_event(
+        tx,
+        namespace,
+        &task_id,
+        TaskEventInput {
+            event_type: if previous_status.is_some() {
+                "upserted"
+            } else {
+                "created"
+            },
+            from_status: previous_status.as_deref(),
+            to_status: &status,
+            actor: &task.source,
+            context: &TransitionContext::default(),
+        },
+        now,
+    )?;
+    Ok(task_id)
+}
+
+fn is_open_for_merge(status: TaskStatus) -> bool {
+    matches!(
+        status,
+        TaskStatus::Ready | TaskStatus::Leased | TaskStatus::InProgress
+    )
+}
+
+/// Tasks tied to different PRs are different work however they are worded.
+fn tasks_are_near_duplicates(a: &BacklogTask, b: &BacklogTask) -> bool {
+    let same_pr = a.related_pr.is_none() || b.related_pr.is_none() || a.related_pr == b.related_pr;
+    a.kind == b.kind
+        && a.scope_key == b.scope_key
+        && same_pr
+        && near_duplicate_score(&a.title, &a.details, &b.title, &b.details).is_some()
+}
+
+/// The most similar open task of the new task's kind and scope, if any clears
+/// the near-duplicate threshold.
+fn find_near_duplicate(
+    tx: &Transaction<'_>,
+    namespace: &str,
+    task: &NewTask,
+) -> StoreResult<Option<(BacklogTask, f64)>> {
+    append_run_log(
+        "debug",
+        "backlog_store.find_near_duplicate.started",
+        json!({ "namespace": namespace, "scope_key": task.scope_key, "title": task.title }),
+    );
+    let mut statement = tx
+        .prepare(&format!(
+            "SELECT {TASK_COLUMNS}
+             FROM backlog_tasks
+             WHERE repo_namespace = ?1 AND scope_key = ?2 AND kind = ?3
+               AND status IN ('ready', 'leased', 'in_progress')
+             ORDER BY created_at ASC"
+        ))
+        .map_err(db_err)?;
+    let candidates = statement
+        .query_map(params![namespace, task.scope_key, task.kind.as_str()], row_to_task)
+        .map_err(db_err)?
+        .collect::<Result<Vec<_>, _>>()
+        .map_err(db_err)?;
+    let mut best: Option<(BacklogTask, f64)> = None;
+    for candidate in candidates {
+        let same_pr = task.related_pr.is_none()
+            || candidate.related_pr.is_none()
+            || task.related_pr == candidate.related_pr;
+        if !same_pr {
+            continue;
+        }
+        let Some(score) =
+            near_duplicate_score(&task.title, &task.details, &candidate.title, &candidate.details)
+        else {
+            continue;
+        };
+        if best.as_ref().is_none_or(|(_, best_score)| score > *best_score) {
+            best = Some((candidate, score));
+        }
+    }
+    Ok(best)
+}
+
+/// Folds an incoming task into an existing near-duplicate instead of inserting
+/// it: the keeper takes the more urgent priority and any new prerequisites.
+fn absorb_near_duplicate(
+    tx: &Transaction<'_>,
+    namespace: &str,
+    keeper: &BacklogTask,
+    task: &NewTask,
+    score: f64,
+    now: i64,
+) -> StoreResult<()> {
+    validate_dependencies(tx, namespace, &keeper.task_id, &task.blocked_by)?;
+    let priority = keeper.priority.max_urgency(task.priority);
+    tx.execute(
+        "UPDATE backlog_tasks SET priority = ?3, last_updated = ?4
+         WHERE repo_namespace = ?1 AND task_id = ?2",
+        params![namespace, keeper.task_id, priority.as_str(), now],
+    )
+    .map_err(db_err)?;
+    for prerequisite in &task.blocked_by {
+        tx.execute(
+            "INSERT OR IGNORE INTO task_dependencies (repo_namespace, task_id, depends_on, created_at)
+             VALUES (?1, ?2, ?3, ?4)",
+            params![namespace, keeper.task_id, prerequisite, now],
+        )
+        .map_err(db_err)?;
+    }
+    let status = keeper.status.as_str();
+    record_event(
+        tx,
+        namespace,
+        &keeper.task_id,
+        TaskEventInput {
+            event_type: "merged_duplicate",
+            from_status: Some(status),
+            to_status: status,
+            actor: &task.source,
+            context: &TransitionContext {
+                reason: Some(format!(
+                    "near-duplicate \"{}\" (similarity {score:.2})",
+                    task.title
+                )),
+                ..TransitionContext::default()
+            },
+        },
+        now,
+    )?;
+    append_run_log(
+        "info",
+        "backlog_store.upsert_task.near_duplicate_merged",
+        json!({
+            "namespace": namespace,
+            "keeper_id": keeper.task_id,
+            "keeper_title": keeper.title,
+            "incoming_task_id": compute_task_id_from_new_task(task),
+            "incoming_title": task.title,
+            "scope_key": task.scope_key,
+            "similarity": score,
+            "priority": priority.as_str(),
+        }),
+    );
     Ok(())
 }
 
-fn claim_next(
+fn merge_duplicate(
     conn: &mut Connection,
-    lease_owner: &str,
-    lease_expires_at: i64,
+    namespace: &str,
+    keeper_id: &str,
+    duplicate_id: &str,
+    now: i64,
+) -> StoreResult<bool> {
+    append_run_log(
+        "debug",
+        "backlog_store.merge_duplicate.started",
+        json!({ "namespace": namespace, "keeper_id": keeper_id, "duplicate_id": duplicate_id }),
+    );
+    let tx = conn.transaction().map_err(db_err)?;
+    let (Some(keeper), Some(duplicate)) = (
+        fetch_task(&tx, namespace, keeper_id)?,
+        fetch_task(&tx, namespace, duplicate_id)?,
+    ) else {
+        return Ok(false);
+    };
+    if keeper_id == duplicate_id || duplicate.status != TaskStatus::Ready {
+        return Ok(false);
+    }
+
+    let prerequisites = duplicate
+        .blocked_by
+        .iter()
+        .filter(|id| id.as_str() != keeper_id)
+        .cloned()
+        .collect::<Vec<_>>();
+    tx.execute(
+        "DELETE FROM task_dependencies
+         WHERE repo_namespace = ?1
+           AND ((task_id = ?2 AND depends_on = ?3) OR task_id = ?3)",
+        params![namespace, keeper_id, duplicate_id],
+    )
+    .map_err(db_err)?;
+    validate_dependencies(&tx, namespace, keeper_id, &prerequisites)?;
+    for prerequisite in &prerequisites {
+        tx.execute(
+            "INSERT OR IGNORE INTO task_dependencies (repo_namespace, task_id, depends_on, created_at)
+             VALUES (?1, ?2, ?3, ?4)",
+            params![namespace, keeper_id, prerequisite, now],
+        )
+        .map_err(db_err)?;
+    }
+    let dependents = {
+        let mut statement = tx
+            .prepare(
+                "SELECT task_id FROM task_dependencies
+                 WHERE repo_namespace = ?1 AND depends_on = ?2",
+            )
+            .map_err(db_err)?;
+        let rows = statement
+            .query_map(params![namespace, duplicate_id], |row| row.get::<_, String>(0))
+            .map_err(db_err)?
+            .collect::<Result<Vec<_>, _>>()
+            .map_err(db_err)?;
+        rows
+    };
+    tx.execute(
+        "DELETE FROM task_dependencies WHERE repo_namespace = ?1 AND depends_on = ?2",
+        params![namespace, duplicate_id],
+    )
+    .map_err(db_err)?;
+    for dependent in &dependents {
+        validate_dependencies(&tx, namespace, dependent, &[keeper_id.to_string()])?;
+        tx.execute(
+            "INSERT OR IGNORE INTO task_dependencies (repo_namespace, task_id, depends_on, created_at)
+             VALUES (?1, ?2, ?3, ?4)",
+            params![namespace, dependent, keeper_id, now],
+        )
+        .map_err(db_err)?;
+    }
+
+    let priority = keeper.priority.max_urgency(duplicate.priority);
+    tx.execute(
+        "UPDATE backlog_tasks SET priority = ?3, last_updated = ?4
+         WHERE repo_namespace = ?1 AND task_id = ?2",
+        params![namespace, keeper_id, priority.as_str(), now],
+    )
+    .map_err(db_err)?;
+    tx.execute(
+        "UPDATE backlog_tasks
+         SET status = 'cancelled', status_note = ?3, last_updated = ?4
+         WHERE repo_namespace = ?1 AND task_id = ?2",
+        params![namespace, duplicate_id, format!("duplicate of {keeper_id}"), now],
+    )
+    .map_err(db_err)?;
+    let keeper_status = keeper.status.as_str();
+    for (task_id, event_type, from_status, to_status, reason) in [
+        (
+            keeper_id,
+            "merged_duplicate",
+            keeper_status,
+            keeper_status,
+            format!("absorbed {duplicate_id} \"{}\"", duplicate.title),
+        ),
+        (
+            duplicate_id,
+            "merged_into",
+            "ready",
+            "cancelled",
+            format!("duplicate of {keeper_id}"),
+        ),
+    ] {
+        record_event(
+            &tx,
+            namespace,
+            task_id,
+            TaskEventInput {
+                event_type,
+                from_status: Some(from_status),
+                to_status,
+                actor: OPERATOR_ACTOR,
+                context: &TransitionContext {
+                    reason: Some(reason),
+                    ..TransitionContext::default()
+                },
+            },
+            now,
+        )?;
+    }
+    tx.commit().map_err(db_err)?;
+    Ok(true)
+}
+
+fn validate_dependencies(
+    tx: &Transaction<'_>,
+    namespace: &str,
+    task_id: &str,
+    blocked_by: &[String],
+) -> StoreResult<()> {
+    append_run_log(
+        "debug",
+        "backlog_store.validate_dependencies.started",
+        json!({
+            "namespace": namespace,
+            "task_id": task_id,
+            "blocked_by": blocked_by,
+        }),
+    );
+    for prerequisite in blocked_by {
+        if prerequisite == task_id {
+            return Err(GardenerError::Database(format!(
+                "task {task_id} cannot depend on itself"
+            )));
+        }
+        let exists = tx
+            .query_row(
+                "SELECT 1 FROM backlog_tasks WHERE repo_namespace = ?1 AND task_id = ?2",
+                params![namespace, prerequisite],
+                |_| Ok(()),
+            )
+            .optional()
+            .map_err(db_err)?
+            .is_some();
+        if !exists {
+            return Err(GardenerError::Database(format!(
+                "task {task_id} depends on unknown task {prerequisite}"
+            )));
+        }
+        // Adding task -> prerequisite closes a cycle if the prerequisite already
+        // (transitively) depends on the task.
+        let closes_cycle = tx
+            .query_row(
+                "WITH RECURSIVE reachable(id) AS (
+                    SELECT ?2
+                    UNION
+                    SELECT dep.depends_on
+                    FROM task_dependencies dep
+                    JOIN reachable ON dep.task_id = reachable.id
+                    WHERE dep.repo_namespace = ?1
+                 )
+                 SELECT 1 FROM reachable WHERE id = ?3 LIMIT 1",
+                params![namespace, prerequisite, task_id],
+                |_| Ok(()),
+            )
+            .optional()
+            .map_err(db_err)?
+            .is_some();
+        if closes_cycle {
+            append_run_log(
+                "warn",
+                "backlog_store.dependency_cycle_rejected",
+                json!({
+                    "namespace": namespace,
+                    "task_id": task_id,
+                    "prerequisite": prerequisite,
+                }),
+            );
+            return Err(GardenerError::Database(format!(
+                "dependency cycle: task {prerequisite} already depends on task {task_id}"
+            )));
+        }
+    }
+    Ok(())
+}
+
+fn log_operator_mutation(action: &str, task_id: &str, result: &StoreResult<bool>) {
+    match result {
+        Ok(true) => append_run_log(
+            "info",
+            &format!("backlog.task.{action}"),
+            json!({ "task_id": task_id }),
+        ),
+        Ok(false) => append_run_log(
+            "warn",
+            &format!("backlog.task.{action}.rejected"),
+            json!({ "task_id": task_id }),
+        ),
+        Err(e) => append_run_log(
+            "error",
+            &format!("backlog.task.{action}.failed"),
+            json!({ "task_id": task_id, "error": e.to_string() }),
+        ),
+    }
+}
+
+const OPERATOR_ACTOR: &str = "operator";
+const LEASE_RECOVERY_ACTOR: &str = "lease_recovery";
+
+struct TaskEventInput<'a> {
+    event_type: &'a str,
+    from_status: Option<&'a str>,
+    to_status: &'a str,
+    actor: &'a str,
+    context: &'a TransitionContext,
+}
+
+/// Appends a history row. Callers pass the transaction that performed the
+/// mutation so the event and the state change commit together.
+fn record_event(
+    conn: &Connection,
+    namespace: &str,
+    task_id: &str,
+    event: TaskEventInput<'_>,
+    now: i64,
+) -> StoreResult<()> {
+    append_run_log(
+        "debug",
+        "backlog_store.record_event.started",
+        json!({
+            "namespace": namespace,
+            "task_id": task_id,
+            "event_type": event.event_type,
+            "actor": event.actor,
+        }),
+    );
+    conn.execute(
+        "INSERT INTO task_events (
+            repo_namespace, task_id, event_type, from_status, to_status, actor, reason, run_id,
+            pr_number, failure_reason, created_at
+        ) VALUES (
+            ?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8,
+            COALESCE(?9, (SELECT related_pr FROM backlog_tasks
+                          WHERE repo_namespace = ?1 AND task_id = ?2)),
+            ?10, ?11
+        )",
+        params![
+            namespace,
+            task_id,
+            event.event_type,
+            event.from_status,
+            event.to_status,
+            event.actor,
+            event.context.reason,
+            current_run_id(),
+            event.context.pr_number,
+            event.context.failure_reason,
+            now,
+        ],
+    )
+    .map_err(db_err)?;
+    Ok(())
+}
+
+fn task_status(conn: &Connection, namespace: &str, task_id: &str) -> StoreResult<Option<String>> {
+    conn.query_row(
+        "SELECT status FROM backlog_tasks WHERE repo_namespace = ?1 AND task_id = ?2",
+        params![namespace, task_id],
+        |row| row.get(0),
+    )
+    .optional()
+    .map_err(db_err)
+}
+
+/// Kind and scope of every task currently leased or in progress.
+fn active_scopes(conn: &Connection, namespace: &str) -> StoreResult<Vec<(TaskKind, String)>> {
+    let mut statement = conn
+        .prepare(
+            "SELECT kind, scope_key FROM backlog_tasks
+             WHERE repo_namespace = ?1 AND status IN ('leased', 'in_progress')",
+        )
+        .map_err(db_err)?;
+    let rows = statement
+        .query_map(params![namespace], |row| {
+            Ok((row.get::<_, String>(0)?, row.get::<_, String>(1)?))
+        })
+        .map_err(db_err)?;
+    let mut scopes = Vec::new();
+    for row in rows {
+        let (kind, scope_key) = row.map_err(db_err)?;
+        scopes.push((stored_kind(&kind)?, scope_key));
+    }
+    Ok(scopes)
+}
+
+fn stored_kind(kind: &str) -> StoreResult<TaskKind> {
+    task_kind_from_db(kind)
+        .ok_or_else(|| GardenerError::Database(format!("invalid task kind in backlog: {kind}")))
+}
+
+/// Turns free text into an FTS5 expression of quoted prefix terms, so operator
+/// input never reaches the query syntax (`NOT`, `:`, unbalanced quotes).
+fn fts_match_expression(query: &str) -> Option<String> {
+    let terms = query
+        .split(|ch: char| !ch.is_alphanumeric())
+        .filter(|word| !word.is_empty())
+        .map(|word| format!("\"{}\"*", word.to_lowercase()))
+        .collect::<Vec<_>>();
+    if terms.is_empty() {
+        None
+    } else {
+        Some(terms.join(" "))
+    }
+}
+
+fn import_issue(
+    conn: &mut Connection,
+    namespace: &str,
+    issue_number: i64,
+    task: &NewTask,
     now: i64,
 ) -> StoreResult<Option<BacklogTask>> {
+    append_run_log(
+        "debug",
+        "backlog_store.import_issue.started",
+        json!({ "namespace": namespace, "issue_number": issue_number }),
+    );
+    // The task and its link land together, so a failed or interrupted import
+    // leaves nothing behind for the next run to duplicate.
     let tx = conn.transaction().map_err(db_err)?;
-    let maybe = claim_next_in_tx(&tx, lease_owner, lease_expires_at, now)?;
+    let linked = tx
+        .query_row(
+            "SELECT task_id FROM github_issue_links
+             WHERE repo_namespace = ?1 AND issue_number = ?2",
+            params![namespace, issue_number],
+            |row| row.get::<_, String>(0),
+        )
+        .optional()
+        .map_err(db_err)?;
+    if linked.is_some() {
+        return Ok(None);
+    }
+    let link = |task_id: &str| {
+        tx.execute(
+            "INSERT INTO github_issue_links (repo_namespace, issue_number, task_id, imported_at)
+             VALUES (?1, ?2, ?3, ?4)",
+            params![namespace, issue_number, task_id, now],
+        )
+        .map_err(db_err)
+    };
+    // The issue's task was finished and archived before its link went away:
+    // relink it and leave the backlog alone rather than reopen finished work.
+    let archived_id = compute_task_id_from_new_task(task);
+    if fetch_archived_task(&tx, namespace, &archived_id)?.is_some() {
+        link(&archived_id)?;
+        tx.commit().map_err(db_err)?;
+        append_run_log(
+            "info",
+            "backlog_store.import_issue.archived",
+            json!({ "namespace": namespace, "issue_number": issue_number, "task_id": archived_id }),
+        );
+        return Ok(None);
+    }
+    // An issue is its own piece of work: it is never folded into a similarly
+    // worded task, which would tie the issue to unrelated work.
+    let task_id = upsert_task_in_tx(&tx, namespace, task, now, false)?;
+    link(&task_id)?;
+    let row = fetch_task(&tx, namespace, &task_id)?
+        .ok_or_else(|| GardenerError::Database("row missing after issue import".to_string()))?;
     tx.commit().map_err(db_err)?;
-    Ok(maybe)
+    Ok(Some(row))
+}
+
+fn resolve_issue(
+    conn: &Connection,
+    namespace: &str,
+    issue_number: i64,
+    now: i64,
+) -> StoreResult<bool> {
+    append_run_log(
+        "debug",
+        "backlog_store.resolve_issue.started",
+        json!({ "namespace": namespace, "issue_number": issue_number }),
+    );
+    let changed = conn
+        .execute(
+            "UPDATE github_issue_links SET resolved_at = ?3
+             WHERE repo_namespace = ?1 AND issue_number = ?2 AND resolved_at IS NULL",
+            params![namespace, issue_number, now],
+        )
+        .map_err(db_err)?;
+    Ok(changed > 0)
+}
+
+fn record_outcome(
+    conn: &Connection,
+    namespace: &str,
+    outcome: &NewTaskOutcome,
+) -> StoreResult<i64> {
+    append_run_log(
+        "debug",
+        "backlog_store.record_outcome.started",
+        json!({
+            "namespace": namespace,
+            "task_id": outcome.task_id,
+            "final_state": outcome.final_state,
+        }),
+    );
+    conn.execute(
+        "INSERT INTO task_outcomes (
+            repo_namespace, task_id, title, run_id, worker_id, attempt, final_state, pr_number,
+            branch, merge_sha, review_loops, failure_reason, started_at, finished_at, duration_ms
+        ) VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12, ?13, ?14, ?15)",
+        params![
+            namespace,
+            outcome.task_id,
+            outcome.title,
+            current_run_id(),
+            outcome.worker_id,
+            outcome.attempt,
+            outcome.final_state,
+            outcome.pr_number,
+            outcome.branch,
+            outcome.merge_sha,
+            outcome.review_loops,
+            outcome.failure_reason,
+            outcome.started_at,
+            outcome.finished_at,
+            outcome.finished_at.saturating_sub(outcome.started_at).max(0),
+        ],
+    )
+    .map_err(db_err)?;
+    Ok(conn.last_insert_rowid())
+}
+
+fn fetch_outcomes(
+    conn: &Connection,
+    condition: &str,
+    params: impl rusqlite::Params,
+) -> StoreResult<Vec<TaskOutcome>> {
+    let mut statement = conn
+        .prepare(&format!(
+            "SELECT outcome_id, task_id, title, run_id, worker_id, attempt, final_state, pr_number,
+                    branch, merge_sha, review_loops, failure_reason, started_at, finished_at,
+                    duration_ms
+             FROM task_outcomes
+             WHERE repo_namespace = ?1 AND {condition}
+             ORDER BY outcome_id ASC"
+        ))
+        .map_err(db_err)?;
+    let rows = statement
+        .query_map(params, |row| {
+            Ok(TaskOutcome {
+                outcome_id: row.get(0)?,
+                task_id: row.get(1)?,
+                title: row.get(2)?,
+                run_id: row.get(3)?,
+                worker_id: row.get(4)?,
+                attempt: row.get(5)?,
+                final_state: row.get(6)?,
+                pr_number: row.get(7)?,
+                branch: row.get(8)?,
+                merge_sha: row.get(9)?,
+                review_loops: row.get(10)?,
+                failure_reason: row.get(11)?,
+                started_at: row.get(12)?,
+                finished_at: row.get(13)?,
+                duration_ms: row.get(14)?,
+            })
+        })
+        .map_err(db_err)?
+        .collect::<Result<Vec<_>, _>>()
+        .map_err(db_err)?;
+    Ok(rows)
+}
+
+fn fetch_history(conn: &Connection, namespace: &str, task_id: &str) -> StoreResult<Vec<TaskEvent>> {
+    let mut statement = conn
+        .prepare(
+            "SELECT event_id, task_id, event_type, from_status, to_status, actor, reason, run_id,
+                    pr_number, failure_reason, created_at
+             FROM task_events
+             WHERE repo_namespace = ?1 AND task_id = ?2
+             ORDER BY event_id ASC",
+        )
+        .map_err(db_err)?;
+    let rows = statement
+        .query_map(params![namespace, task_id], |row| {
+            Ok(TaskEvent {
+                event_id: row.get(0)?,
+                task_id: row.get(1)?,
+                event_type: row.get(2)?,
+                from_status: row.get(3)?,
+                to_status: row.get(4)?,
+                actor: row.get(5)?,
+                reason: row.get(6)?,
+                run_id: row.get(7)?,
+                pr_number: row.get(8)?,
+                failure_reason: row.get(9)?,
+                created_at: row.get(10)?,
+            })
+        })
+        .map_err(db_err)?
+        .collect::<Result<Vec<_>, _>>()
+        .map_err(db_err)?;
+    Ok(rows)
+}
+
+fn log_lease_transition(action: &str, task_id: &str, lease_owner: &str, result: &StoreResult<bool>) {
+    match result {
+        Ok(true) => append_run_log(
+            "info",
+            &format!("backlog.task.{action}"),
+            json!({ "task_id": task_id, "lease_owner": lease_owner }),
+        ),
+        Ok(false) => append_run_log(
+            "warn",
+            &format!("backlog.task.{action}.owner_mismatch"),
+            json!({ "task_id": task_id, "lease_owner": lease_owner }),
+        ),
+        Err(e) => append_run_log(
+            "error",
+            &format!("backlog.task.{action}.failed"),
+            json!({ "task_id": task_id, "lease_owner": lease_owner, "error": e.to_string() }),
+        ),
+    }
+}
+
+fn unresolved_prerequisite_reason(task_id: &str) -> String {
+    format!("prerequisite {task_id} is unresolved")
+}
+
+fn cancelled_prerequisite_reason(task_id: &str) -> String {
+    format!("prerequisite {task_id} was cancelled")
+}
+
+fn exhausted_prerequisite_reason(task_id: &str) -> String {
+    format!("prerequisite {task_id} exhausted its retries")
+}
+
+fn cascade_prerequisite_block(
+    conn: &Connection,
+    namespace: &str,
+    task_id: &str,
+    reason: &str,
+    now: i64,
+) -> StoreResult<usize> {
+    append_run_log(
+        "debug",
+        "backlog_store.cascade_prerequisite_block.started",
+        json!({ "namespace": namespace, "task_id": task_id }),
+    );
+    let changed = conn
+        .execute(
+            "WITH RECURSIVE dependents(id) AS (
+                SELECT task_id FROM task_dependencies
+                WHERE repo_namespace = ?1 AND depends_on = ?2
+                UNION
+                SELECT dep.task_id
+                FROM task_dependencies dep
+                JOIN dependents ON dep.depends_on = dependents.id
+                WHERE dep.repo_namespace = ?1
+             )
+             UPDATE backlog_tasks
+             SET blocked_reason = ?3, last_updated = ?4
+             WHERE repo_namespace = ?1
+               AND task_id IN (SELECT id FROM dependents)
+               AND status NOT IN ('complete', 'cancelled', 'exhausted')",
+            params![namespace, task_id, reason, now],
+        )
+        .map_err(db_err)?;
+    Ok(changed)
+}
+
+fn clear_prerequisite_block(
+    conn: &Connection,
+    namespace: &str,
+    task_id: &str,
+    now: i64,
+) -> StoreResult<usize> {
+    append_run_log(
+        "debug",
+        "backlog_store.clear_prerequisite_block.started",
+        json!({ "namespace": namespace, "task_id": task_id }),
+    );
+    let changed = conn
+        .execute(
+            "UPDATE backlog_tasks
+             SET blocked_reason = NULL, last_updated = ?3
+             WHERE repo_namespace = ?1 AND blocked_reason IN (?2, ?4, ?5)",
+            params![
+                namespace,
+                unresolved_prerequisite_reason(task_id),
+                now,
+                cancelled_prerequisite_reason(task_id),
+                exhausted_prerequisite_reason(task_id),
+            ],
+        )
+        .map_err(db_err)?;
+    Ok(changed)
+}
+
+fn edit_task(
+    conn: &mut Connection,
+    namespace: &str,
+    task_id: &str,
+    edit: &TaskEdit,
+    now: i64,
+) -> StoreResult<Option<BacklogTask>> {
+    append_run_log(
+        "debug",
+        "backlog_store.edit_task.started",
+        json!({ "namespace": namespace, "task_id": task_id }),
+    );
+    let tx = conn.transaction().map_err(db_err)?;
+    let Some(current) = fetch_task(&tx, namespace, task_id)? else {
+        return Ok(None);
+    };
+    if matches!(current.status, TaskStatus::Leased | TaskStatus::InProgress) {
+        return Err(GardenerError::Database(format!(
+            "task {task_id} is {} and cannot be edited until its lease ends",
+            current.status.as_str()
+        )));
+    }
+    let kind = edit.kind.unwrap_or(current.kind);
+    let title = edit.title.clone().unwrap_or(current.title);
+    let details = edit.details.clone().unwrap_or(current.details);
+    let rationale = edit.rationale.clone().unwrap_or(current.rationale);
+    let scope_key = edit.scope_key.clone().unwrap_or(current.scope_key);
+    let new_task_id = compute_task_id(TaskIdentity {
+        kind,
+        title: title.clone(),
+        scope_key: scope_key.clone(),
+        related_pr: current.related_pr,
+        related_branch: current.related_branch,
+    });
+    if new_task_id != task_id && fetch_task(&tx, namespace, &new_task_id)?.is_some() {
+        return Err(GardenerError::Database(format!(
+            "edit would give task {task_id} the identity of existing task {new_task_id}"
+        )));
+    }
+    tx.execute(
+        "UPDATE backlog_tasks
+         SET task_id = ?3, kind = ?4, title = ?5, details = ?6, rationale = ?7, scope_key = ?8,
+             last_updated = ?9
+         WHERE repo_namespace = ?1 AND task_id = ?2",
+        params![
+            namespace,
+            task_id,
+            new_task_id,
+            kind.as_str(),
+            title,
+            details,
+            rationale,
+            scope_key,
+            now,
+        ],
+    )
+    .map_err(db_err)?;
+    if new_task_id != task_id {
+        tx.execute(
+            "UPDATE task_dependencies SET task_id = ?3 WHERE repo_namespace = ?1 AND task_id = ?2",
+            params![namespace, task_id, new_task_id],
+        )
+        .map_err(db_err)?;
+        tx.execute(
+            "UPDATE task_dependencies SET depends_on = ?3
+             WHERE repo_namespace = ?1 AND depends_on = ?2",
+            params![namespace, task_id, new_task_id],
+        )
+        .map_err(db_err)?;
+        for (old_reason, new_reason) in [
+            (
+                unresolved_prerequisite_reason(task_id),
+                unresolved_prerequisite_reason(&new_task_id),
+            ),
+            (
+                cancelled_prerequisite_reason(task_id),
+                cancelled_prerequisite_reason(&new_task_id),
+            ),
+            (
+                exhausted_prerequisite_reason(task_id),
+                exhausted_prerequisite_reason(&new_task_id),
+            ),
+        ] {
+            tx.execute(
+                "UPDATE backlog_tasks SET blocked_reason = ?3
+                 WHERE repo_namespace = ?1 AND blocked_reason = ?2",
+                params![namespace, old_reason, new_reason],
+            )
+            .map_err(db_err)?;
+        }
+        for table in ["task_events", "task_outcomes", "github_issue_links"] {
+            tx.execute(
+                &format!(
+                    "UPDATE {table} SET task_id = ?3 WHERE repo_namespace = ?1 AND task_id = ?2"
+                ),
+                params![namespace, task_id, new_task_id],
+            )
+            .map_err(db_err)?;
+        }
+    }
+    let status = current.status.as_str();
+    record_event(
+        &tx,
+        namespace,
+        &new_task_id,
+        TaskEventInput {
+            event_type: "edited",
+            from_status: Some(status),
+            to_status: status,
+            actor: OPERATOR_ACTOR,
+            context: &TransitionContext {
+                reason: (new_task_id != task_id).then(|| format!("re-keyed from {task_id}")),
+                ..TransitionContext::default()
+            },
+        },
+        now,
+    )?;
+    let updated = fetch_task(&tx, namespace, &new_task_id)?;
+    tx.commit().map_err(db_err)?;
+    Ok(updated)
+}
+
+fn set_priority(
+    conn: &mut Connection,
+    namespace: &str,
+    task_id: &str,
+    priority: Priority,
+    now: i64,
+) -> StoreResult<bool> {
+    let tx = conn.transaction().map_err(db_err)?;
+    let changed = set_priority_in_tx(&tx, namespace, task_id, priority, now)?;
+    tx.commit().map_err(db_err)?;
+    Ok(changed)
+}
+
+fn set_priority_in_tx(
+    tx: &Transaction<'_>,
+    namespace: &str,
+    task_id: &str,
+    priority: Priority,
+    now: i64,
+) -> StoreResult<bool> {
+    append_run_log(
+        "debug",
+        "backlog_store.set_priority.started",
+        json!({ "namespace": namespace, "task_id": task_id, "priority": priority.as_str() }),
+    );
+    let Some(current) = fetch_task(tx, namespace, task_id)? else {
+        return Ok(false);
+    };
+    tx.execute(
+        "UPDATE backlog_tasks SET priority = ?3, last_updated = ?4
+         WHERE repo_namespace = ?1 AND task_id = ?2",
+        params![namespace, task_id, priority.as_str(), now],
+    )
+    .map_err(db_err)?;
+    let status = current.status.as_str();
+    record_event(
+        tx,
+        namespace,
+        task_id,
+        TaskEventInput {
+            event_type: "reprioritized",
+            from_status: Some(status),
+            to_status: status,
+            actor: OPERATOR_ACTOR,
+            context: &TransitionContext {
+                reason: Some(format!(
+                    "{} -> {}",
+                    current.priority.as_str(),
+                    priority.as_str()
+                )),
+                ..TransitionContext::default()
+            },
+        },
+        now,
+    )?;
+    Ok(true)
+}
+
+fn cancel_task(
+    conn: &mut Connection,
+    namespace: &str,
+    task_id: &str,
+    reason: &str,
+    now: i64,
+) -> StoreResult<bool> {
+    let tx = conn.transaction().map_err(db_err)?;
+    let changed = cancel_task_in_tx(&tx, namespace, task_id, reason, now)?;
+    tx.commit().map_err(db_err)?;
+    Ok(changed)
+}
+
+fn cancel_task_in_tx(
+    tx: &Transaction<'_>,
+    namespace: &str,
+    task_id: &str,
+    reason: &str,
+    now: i64,
+) -> StoreResult<bool> {
+    append_run_log(
+        "debug",
+        "backlog_store.cancel_task.started",
+        json!({ "namespace": namespace, "task_id": task_id, "reason": reason }),
+    );
+    let reason = reason.trim();
+    if reason.is_empty() {
+        return Err(GardenerError::Database(format!(
+            "cancelling task {task_id} requires a reason"
+        )));
+    }
+    let previous_status = task_status(tx, namespace, task_id)?;
+    let changed = tx
+        .execute(
+            "UPDATE backlog_tasks
+             SET status = 'cancelled', lease_owner = NULL, lease_expires_at = NULL,
+                 status_note = ?3, not_before = NULL, last_updated = ?4
+             WHERE repo_namespace = ?1 AND task_id = ?2
+               AND status NOT IN ('leased', 'in_progress')",
+            params![namespace, task_id, reason, now],
+        )
+        .map_err(db_err)?;
+    if changed > 0 {
+        record_event(
+            tx,
+            namespace,
+            task_id,
+            TaskEventInput {
+                event_type: "cancelled",
+                from_status: previous_status.as_deref(),
+                to_status: "cancelled",
+                actor: OPERATOR_ACTOR,
+                context: &TransitionContext {
+                    reason: Some(reason.to_string()),
+                    ..TransitionContext::default()
+                },
+            },
+            now,
+        )?;
+        cascade_prerequisite_block(
+            tx,
+            namespace,
+            task_id,
+            &cancelled_prerequisite_reason(task_id),
+            now,
+        )?;
+    }
+    Ok(changed > 0)
+}
+
+/// Columns of `archived_tasks` in `TASK_COLUMNS` order. Archived rows keep no
+/// dependency edges.
+const ARCHIVED_TASK_COLUMNS: &str = "task_id, kind, title, details, scope_key, priority, status,
+    last_updated, lease_owner, lease_expires_at, source, related_pr, related_branch, rationale,
+    attempt_count, created_at, repo_namespace, blocked_reason, status_note, NULL AS blocked_by,
+    not_before, failure_reason";
+
+fn fetch_archived_task(
+    conn: &Connection,
+    namespace: &str,
+    task_id: &str,
+) -> StoreResult<Option<BacklogTask>> {
+    conn.query_row(
+        &format!(
+            "SELECT {ARCHIVED_TASK_COLUMNS} FROM archived_tasks
+             WHERE repo_namespace = ?1 AND task_id = ?2"
+        ),
+        params![namespace, task_id],
+        row_to_task,
+    )
+    .optional()
+    .map_err(db_err)
+}
+
+fn archive_finished_tasks(
+    conn: &mut Connection,
+    namespace: &str,
+    cutoff: i64,
+    now: i64,
+) -> StoreResult<Vec<String>> {
+    append_run_log(
+        "debug",
+        "backlog_store.archive_finished_tasks.started",
+        json!({ "namespace": namespace, "cutoff": cutoff }),
+    );
+    let tx = conn.transaction().map_err(db_err)?;
+    let candidates = {
+        let mut statement = tx
+            .prepare(
+                "SELECT task_id, status FROM backlog_tasks
+                 WHERE repo_namespace = ?1 AND last_updated <= ?2
+                   AND (
+                        status = 'complete'
+                        OR (status = 'cancelled' AND NOT EXISTS (
+                            SELECT 1
+                            FROM task_dependencies dep
+                            JOIN backlog_tasks dependent
+                              ON dependent.repo_namespace = dep.repo_namespace
+                             AND dependent.task_id = dep.task_id
+                            WHERE dep.repo_namespace = backlog_tasks.repo_namespace
+                              AND dep.depends_on = backlog_tasks.task_id
+                              AND dependent.status NOT IN ('complete', 'cancelled', 'exhausted')
+                        ))
+                   )
+                 ORDER BY last_updated ASC, task_id ASC",
+            )
+            .map_err(db_err)?;
+        let rows = statement
+            .query_map(params![namespace, cutoff], |row| {
+                Ok((row.get::<_, String>(0)?, row.get::<_, String>(1)?))
+            })
+            .map_err(db_err)?
+            .collect::<Result<Vec<_>, _>>()
+            .map_err(db_err)?;
+        rows
+    };
+    for (task_id, status) in &candidates {
+        tx.execute(
+            "INSERT INTO archived_tasks (
+                repo_namespace, task_id, kind, title, details, scope_key, priority, status,
+                last_updated, lease_owner, lease_expires_at, source, related_pr, related_branch,
+                rationale, attempt_count, created_at, blocked_reason, status_note, not_before,
+                failure_reason, archived_at
+             )
+             SELECT
+                repo_namespace, task_id, kind, title, details, scope_key, priority, status,
+                last_updated, lease_owner, lease_expires_at, source, related_pr, related_branch,
+                rationale, attempt_count, created_at, blocked_reason, status_note, not_before,
+                failure_reason, ?3
+             FROM backlog_tasks
+             WHERE repo_namespace = ?1 AND task_id = ?2",
+            params![namespace, task_id, now],
+        )
+        .map_err(db_err)?;
+        // Recorded while the live row still carries its PR.
+        record_event(
+            &tx,
+            namespace,
+            task_id,
+            TaskEventInput {
+                event_type: "archived",
+                from_status: Some(status),
+                to_status: status,
+                actor: OPERATOR_ACTOR,
+                context: &TransitionContext::default(),
+            },
+            now,
+        )?;
+        // Edges onto a complete task are satisfied; edges onto an archived
+        // cancelled task only link finished work.
+        tx.execute(
+            "DELETE FROM task_dependencies
+             WHERE repo_namespace = ?1 AND (task_id = ?2 OR depends_on = ?2)",
+            params![namespace, task_id],
+        )
+        .map_err(db_err)?;
+        tx.execute(
+            "DELETE FROM backlog_tasks WHERE repo_namespace = ?1 AND task_id = ?2",
+            params![namespace, task_id],
+        )
+        .map_err(db_err)?;
+    }
+    tx.commit().map_err(db_err)?;
+    Ok(candidates.into_iter().map(|(task_id, _)| task_id).collect())
+}
+
+fn requeue_task(
+    conn: &mut Connection,
+    namespace: &str,
+    task_id: &str,
+    now: i64,
+) -> StoreResult<bool> {
+    let tx = conn.transaction().map_err(db_err)?;
+    let changed = requeue_task_in_tx(&tx, namespace, task_id, now)?;
+    tx.commit().map_err(db_err)?;
+    Ok(changed)
+}
+
+fn requeue_task_in_tx(
+    tx: &Transaction<'_>,
+    namespace: &str,
+    task_id: &str,
+    now: i64,
+) -> StoreResult<bool> {
+    append_run_log(
+        "debug",
+        "backlog_store.requeue_task.started",
+        json!({ "namespace": namespace, "task_id": task_id }),
+    );
+    let previous_status = task_status(tx, namespace, task_id)?;
+    let changed = tx
+        .execute(
+            "UPDATE backlog_tasks
+             SET status = 'ready', lease_owner = NULL, lease_expires_at = NULL,
+                 status_note = NULL, not_before = NULL, attempt_count = 0, last_updated = ?3
+             WHERE repo_namespace = ?1 AND task_id = ?2
+               AND status IN ('complete', 'failed', 'unresolved', 'exhausted', 'cancelled')",
+            params![namespace, task_id, now],
+        )
+        .map_err(db_err)?;
+    if changed > 0 {
+        record_event(
+            tx,
+            namespace,
+            task_id,
+            TaskEventInput {
+                event_type: "requeued",
+                from_status: previous_status.as_deref(),
+                to_status: "ready",
+                actor: OPERATOR_ACTOR,
+                context: &TransitionContext::default(),
+            },
+            now,
+        )?;
+        clear_prerequisite_block(tx, namespace, task_id, now)?;
+    }
+    Ok(changed > 0)
+}
+
+fn apply_changes(
+    conn: &mut Connection,
+    namespace: &str,
+    changes: &[BacklogChange],
+    now: i64,
+) -> StoreResult<Vec<ChangeOutcome>> {
+    append_run_log(
+        "debug",
+        "backlog_store.apply_changes.started",
+        json!({ "namespace": namespace, "changes": changes.len() }),
+    );
+    let tx = conn.transaction().map_err(db_err)?;
+    let mut outcomes = Vec::with_capacity(changes.len());
+    for change in changes {
+        let applied = match change {
+            BacklogChange::Reprioritize { task_id, priority } => {
+                set_priority_in_tx(&tx, namespace, task_id, *priority, now)?
+            }
+            BacklogChange::Cancel { task_id, reason } => {
+                cancel_task_in_tx(&tx, namespace, task_id, reason, now)?
+            }
+            BacklogChange::Requeue { task_id } => {
+                requeue_task_in_tx(&tx, namespace, task_id, now)?
+            }
+            BacklogChange::Add(task) => {
+                let task_id = compute_task_id_from_new_task(task);
+                let present = task_status(&tx, namespace, &task_id)?.is_some()
+                    || fetch_archived_task(&tx, namespace, &task_id)?.is_some();
+                let landed = if present {
+                    task_id.clone()
+                } else {
+                    upsert_task_in_tx(&tx, namespace, task, now, true)?
+                };
+                outcomes.push(if present || landed != task_id {
+                    ChangeOutcome::AlreadyPresent(landed)
+                } else {
+                    ChangeOutcome::Added(landed)
+                });
+                continue;
+            }
+        };
+        outcomes.push(if applied {
+            ChangeOutcome::Applied
+        } else {
+            ChangeOutcome::Stale
+        });
+    }
+    if outcomes.contains(&ChangeOutcome::Stale) {
+        // Dropping the transaction rolls every change back.
+        return Ok(outcomes);
+    }
+    tx.commit().map_err(db_err)?;
+    Ok(outcomes)
+}
+
+const IMPORT_ACTOR: &str = "import";
+
+fn parse_task_records(contents: &str) -> StoreResult<Vec<BacklogTaskRecord>> {
+    contents
+        .lines()
+        .enumerate()
+        .filter(|(_, line)| !line.trim().is_empty())
+        .map(|(idx, line)| {
+            serde_json::from_str::<BacklogTaskRecord>(line).map_err(|e| {
+                GardenerError::Database(format!("invalid backlog record on line {}: {e}", idx + 1))
+            })
+        })
+        .collect()
+}
+
+/// Rejects records whose task_id does not match their identity, duplicated
+/// ids and unknown statuses before anything is written.
+fn validate_import_records(records: &[BacklogTaskRecord]) -> StoreResult<()> {
+    let mut seen = HashSet::new();
+    for record in records {
+        let expected = compute_task_id(TaskIdentity {
+            kind: record.kind,
+            title: record.title.clone(),
+            scope_key: record.scope_key.clone(),
+            related_pr: record.related_pr,
+            related_branch: record.related_branch.clone(),
+        });
+        if expected != record.task_id {
+            return Err(GardenerError::Database(format!(
+                "record {} does not match its kind, title and scope (expected task_id {expected})",
+                record.task_id
+            )));
+        }
+        if !seen.insert(record.task_id.as_str()) {
+            return Err(GardenerError::Database(format!(
+                "record {} appears more than once",
+                record.task_id
+            )));
+        }
+        if TaskStatus::from_db(&record.status).is_none() {
+            return Err(GardenerError::Database(format!(
+                "record {} has unknown status {}",
+                record.task_id, record.status
+            )));
+        }
+    }
+    Ok(())
+}
+
+fn changed_fields(stored: &BacklogTaskRecord, record: &BacklogTaskRecord) -> Vec<&'static str> {
+    let sorted = |ids: &[String]| {
+        let mut ids = ids.to_vec();
+        ids.sort();
+        ids
+    };
+    [
+        ("details", stored.details != record.details),
+        ("rationale", stored.rationale != record.rationale),
+        ("priority", stored.priority != record.priority),
+        ("status", stored.status != record.status),
+        ("last_updated", stored.last_updated != record.last_updated),
+        ("lease_owner", stored.lease_owner != record.lease_owner),
+        ("lease_expires_at", stored.lease_expires_at != record.lease_expires_at),
+        ("source", stored.source != record.source),
+        ("attempt_count", stored.attempt_count != record.attempt_count),
+        ("created_at", stored.created_at != record.created_at),
+        ("blocked_by", sorted(&stored.blocked_by) != sorted(&record.blocked_by)),
+        ("blocked_reason", stored.blocked_reason != record.blocked_reason),
+        ("status_note", stored.status_note != record.status_note),
+        ("not_before", stored.not_before != record.not_before),
+        ("failure_reason", stored.failure_reason != record.failure_reason),
+    ]
+    .into_iter()
+    .filter_map(|(name, differs)| differs.then_some(name))
+    .collect()
+}
+
+fn active_lease_note(task: &BacklogTask) -> Option<String> {
+    matches!(task.status, TaskStatus::Leased | TaskStatus::InProgress).then(|| {
+        format!(
+            "{} by {}",
+            task.status.as_str(),
+            task.lease_owner.as_deref().unwrap_or("unknown")
+        )
+    })
+}
+
+fn import_records(
+    conn: &mut Connection,
+    namespace: &str,
+    records: &[BacklogTaskRecord],
+    mode: ImportMode,
+    dry_run: bool,
+    now: i64,
+) -> StoreResult<ImportReport> {
+    append_run_log(
+        "debug",
+        "backlog_store.import_records.started",
+        json!({
+            "namespace": namespace,
+            "records": records.len(),
+            "mode": format!("{mode:?}"),
+            "dry_run": dry_run,
+        }),
+    );
+    validate_import_records(records)?;
+    let tx = conn.transaction().map_err(db_err)?;
+    let mut stored = {
+        let mut statement = tx
+            .prepare(&format!(
+                "SELECT {TASK_COLUMNS} FROM backlog_tasks WHERE repo_namespace = ?1"
+            ))
+            .map_err(db_err)?;
+        let rows = statement
+            .query_map(params![namespace], row_to_task)
+            .map_err(db_err)?;
+        let mut stored = HashMap::new();
+        for row in rows {
+            let task = row.map_err(db_err)?;
+            stored.insert(task.task_id.clone(), task);
+        }
+        stored
+    };
+
+    let mut changes = Vec::new();
+    let mut writes = Vec::new();
+    for record in records {
+        let change = |action, fields, note| ImportChange {
+            task_id: record.task_id.clone(),
+            title: record.title.clone(),
+            action,
+            fields,
+            note,
+        };
+        let Some(task) = stored.remove(&record.task_id) else {
+            if fetch_archived_task(&tx, namespace, &record.task_id)?.is_some() {
+                changes.push(change(ImportAction::Keep, Vec::new(), Some("archived".to_string())));
+                continue;
+            }
+            changes.push(change(ImportAction::Add, Vec::new(), None));
+            writes.push((record, None));
+            continue;
+        };
+        let previous_status = task.status.as_str();
+        let fields = changed_fields(&BacklogTaskRecord::from(task.clone()), record);
+        let keep_note = active_lease_note(&task).or_else(|| {
+            (mode == ImportMode::Merge && task.last_updated > record.last_updated)
+                .then(|| "stored row is newer".to_string())
+        });
+        if fields.is_empty() {
+            changes.push(change(ImportAction::Unchanged, fields, None));
+        } else if let Some(note) = keep_note {
+            changes.push(change(ImportAction::Keep, fields, Some(note)));
+        } else {
+            changes.push(change(ImportAction::Update, fields, None));
+            writes.push((record, Some(previous_status)));
+        }
+    }
+
+    let mut removed = Vec::new();
+    if mode == ImportMode::Replace {
+        let mut leftovers = stored.into_values().collect::<Vec<_>>();
+        leftovers.sort_by(|a, b| a.task_id.cmp(&b.task_id));
+        for task in leftovers {
+            let note = active_lease_note(&task);
+            let action = if note.is_some() {
+                ImportAction::Keep
+            } else {
+                removed.push((task.task_id.clone(), task.status));
+                ImportAction::Remove
+            };
+            changes.push(ImportChange {
+                task_id: task.task_id,
+                title: task.title,
+                action,
+                fields: Vec::new(),
+                note,
+            });
+        }
+    }
+
+    for (task_id, status) in &removed {
+        // The history stays behind to say where the row went; outcomes and
+        // issue links would otherwise point at a task that no longer exists.
+        record_event(
+            &tx,
+            namespace,
+            task_id,
+            TaskEventInput {
+                event_type: "deleted",
+                from_status: Some(status.as_str()),
+                to_status: status.as_str(),
+                actor: IMPORT_ACTOR,
+                context: &TransitionContext::default(),
+            },
+            now,
+        )?;
+        for table in ["task_outcomes", "github_issue_links", "task_dependencies"] {
+            tx.execute(
+                &format!("DELETE FROM {table} WHERE repo_namespace = ?1 AND task_id = ?2"),
+                params![namespace, task_id],
+            )
+            .map_err(db_err)?;
+        }
+        tx.execute(
+            "DELETE FROM task_dependencies WHERE repo_namespace = ?1 AND depends_on = ?2",
+            params![namespace, task_id],
+        )
+        .map_err(db_err)?;
+        tx.execute(
+            "DELETE FROM backlog_tasks WHERE repo_namespace = ?1 AND task_id = ?2",
+            params![namespace, task_id],
+        )
+        .map_err(db_err)?;
+    }
+    for (record, _) in &writes {
+        tx.execute(
+            "INSERT INTO backlog_tasks (
+                repo_namespace, task_id, kind, title, details, scope_key, priority, status,
+                last_updated, lease_owner, lease_expires_at, source, related_pr, related_branch,
+                rationale, attempt_count, created_at, blocked_reason, status_note, not_before,
+                failure_reason
+            ) VALUES (
+                ?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12, ?13, ?14, ?15, ?16, ?17, ?18,
+                ?19, ?20, ?21
+            )
+            ON CONFLICT(repo_namespace, task_id) DO UPDATE SET
+                details = excluded.details,
+                priority = excluded.priority,
+                status = excluded.status,
+                last_updated = excluded.last_updated,
+                lease_owner = excluded.lease_owner,
+                lease_expires_at = excluded.lease_expires_at,
+                source = excluded.source,
+                rationale = excluded.rationale,
+                attempt_count = excluded.attempt_count,
+                created_at = excluded.created_at,
+                blocked_reason = excluded.blocked_reason,
+                status_note = excluded.status_note,
+                not_before = excluded.not_before,
+                failure_reason = excluded.failure_reason",
+            params![
+                namespace,
+                record.task_id,
+                record.kind.as_str(),
+                record.title,
+                record.details,
+                record.scope_key,
+                record.priority.as_str(),
+                record.status,
+                record.last_updated,
+                record.lease_owner,
+                record.lease_expires_at,
+                record.source,
+                record.related_pr,
+                record.related_branch,
+                record.rationale,
+                record.attempt_count,
+                record.created_at,
+                record.blocked_reason,
+                record.status_note,
+                record.not_before,
+                record.failure_reason,
+            ],
+        )
+        .map_err(db_err)?;
+    }
+    // Edges are rewritten once every row exists, so records may depend on
+    // tasks that appear later in the file.
+    for (record, previous_status) in &writes {
+        tx.execute(
+            "DELETE FROM task_dependencies WHERE repo_namespace = ?1 AND task_id = ?2",
+            params![namespace, record.task_id],
+        )
+        .map_err(db_err)?;
+        validate_dependencies(&tx, namespace, &record.task_id, &record.blocked_by)?;
+        for prerequisite in &record.blocked_by {
+            tx.execute(
+                "INSERT OR IGNORE INTO task_dependencies (repo_namespace, task_id, depends_on, created_at)
+                 VALUES (?1, ?2, ?3, ?4)",
+                params![namespace, record.task_id, prerequisite, now],
+            )
+            .map_err(db_err)?;
+        }
+        record_event(
+            &tx,
+            namespace,
+            &record.task_id,
+            TaskEventInput {
+                event_type: "imported",
+                from_status: *previous_status,
+                to_status: &record.status,
+                actor: IMPORT_ACTOR,
+                context: &TransitionContext::default(),
+            },
+            now,
+        )?;
+    }
+    for (task_id, _) in &removed {
+        append_run_log(
+            "info",
+            "backlog_store.import_records.removed",
+            json!({ "namespace": namespace, "task_id": task_id, "dry_run": dry_run }),
+        );
+    }
+
+    if dry_run {
+        tx.rollback().map_err(db_err)?;
+    } else {
+        tx.commit().map_err(db_err)?;
+    }
+    Ok(ImportReport {
+        changes,
+        applied: !dry_run,
+    })
+}
+
+fn claim_next(
+    conn: &mut Connection,
+    namespace: &str,
+    lease_owner: &str,
+    lease_expires_at: i64,
+    options: &ClaimOptions,
+    now: i64,
+) -> StoreResult<Option<BacklogTask>> {
+    let tx = conn.transaction().map_err(db_err)?;
+    let maybe = claim_next_in_tx(&tx, namespace, lease_owner, lease_expires_at, options, now)?;
+    tx.commit().map_err(db_err)?;
+    Ok(maybe)
+}
+
+fn preview_claims(
+    conn: &mut Connection,
+    namespace: &str,
+    limit: usize,
+    parallelism: usize,
+    options: &ClaimOptions,
+    now: i64,
+) -> StoreResult<Vec<BacklogTask>> {
+    // Dropping the transaction without committing rolls every claim back.
+    let tx = conn.transaction().map_err(db_err)?;
+    let parallelism = parallelism.max(1);
+    let mut claimed = Vec::new();
+    while claimed.len() < limit {
+        let mut wave = Vec::new();
+        for slot in 0..parallelism.min(limit - claimed.len()) {
+            let worker_id = format!("worker-{}", slot + 1);
+            match claim_next_in_tx(&tx, namespace, &worker_id, now, options, now)? {
+                Some(task) => wave.push(task),
+                None => break,
+            }
+        }
+        if wave.is_empty() {
+            break;
+        }
+        for task in &wave {
+            tx.execute(
+                "UPDATE backlog_tasks SET status = 'complete'
+                 WHERE repo_namespace = ?1 AND task_id = ?2",
+                params![namespace, task.task_id],
+            )
+            .map_err(db_err)?;
+        }
+        claimed.extend(wave);
+    }
+    append_run_log(
+        "debug",
+        "backlog_store.preview_claims.finished",
+        json!({ "namespace": namespace, "claimed": claimed.len() }),
+    );
+    Ok(claimed)
 }
 
 fn claim_next_in_tx(
     tx: &Transaction<'_>,
+    namespace: &str,
     lease_owner: &str,
     lease_expires_at: i64,
+    options: &ClaimOptions,
     now: i64,
 ) -> StoreResult<Option<BacklogTask>> {
     append_run_log(
         "debug",
         "backlog_store.claim_next_in_tx.started",
         json!({
+            "namespace": namespace,
             "lease_owner": lease_owner,
             "lease_expires_at": lease_expires_at
         }),
     );
-    let mut candidate = tx
-        .prepare(
-            "SELECT task_id
+    let active_scopes = if options.restricts_scopes() {
+        active_scopes(tx, namespace)?
+    } else {
+        Vec::new()
+    };
+    let filter = TaskFilter {
+        statuses: Vec::new(),
+        ..options.filter.clone()
+    };
+    let mut filter_params = Vec::new();
+    let filter_clause = filter.sql_clause_after(4, &mut filter_params);
+    let mut candidates = tx
+        .prepare(&format!(
+            "SELECT task_id, kind, scope_key
              FROM backlog_tasks
-             WHERE status = 'ready'
+             WHERE repo_namespace = ?1 AND status = 'ready'{filter_clause}
+               AND (not_before IS NULL OR not_before <= ?2)
+               AND NOT EXISTS (
+                    SELECT 1
+                    FROM task_dependencies dep
+                    LEFT JOIN backlog_tasks prerequisite
+                      ON prerequisite.repo_namespace = dep.repo_namespace
+                     AND prerequisite.task_id = dep.depends_on
+                    WHERE dep.repo_namespace = backlog_tasks.repo_namespace
+                      AND dep.task_id = backlog_tasks.task_id
+                      AND COALESCE(prerequisite.status, '') <> 'complete'
+               )
              ORDER BY
-                CASE priority WHEN 'P0' THEN 0 WHEN 'P1' THEN 1 ELSE 2 END,
+                CASE
+                    WHEN priority = 'P0' THEN 0
+                    WHEN priority = 'P1' OR created_at <= ?4 THEN
+                        CASE WHEN created_at <= ?3 THEN 0 ELSE 1 END
+                    ELSE 2
+                END,
                 CASE WHEN attempt_count > 0 THEN 0 ELSE 1 END,
                 attempt_count DESC,
                 last_updated ASC,
-                created_at ASC
-             LIMIT 1",
-        )
+                created_at ASC"
+        ))
         .map_err(db_err)?;
-    let Some(task_id) = candidate
-        .query_row([], |row| row.get::<_, String>(0))
-        .optional()
-        .map_err(db_err)?
-    else {
+    // Aged tasks compete with native tasks of their effective priority on the
+    // remaining keys, so a long-waiting task wins on `last_updated`.
+    let (p0_cutoff, p1_cutoff) = options.priority_aging.cutoffs(now);
+    let mut bound: Vec<&dyn rusqlite::ToSql> = vec![&namespace, &now, &p0_cutoff, &p1_cutoff];
+    bound.extend(filter_params.iter().map(|param| param as &dyn rusqlite::ToSql));
+    let mut rows = candidates.query(bound.as_slice()).map_err(db_err)?;
+    let mut chosen = None;
+    while let Some(row) = rows.next().map_err(db_err)? {
+        let task_id: String = row.get(0).map_err(db_err)?;
+        let kind = stored_kind(&row.get::<_, String>(1).map_err(db_err)?)?;
+        let scope_key: String = row.get(2).map_err(db_err)?;
+        let holder = active_scopes.iter().find(|(active_kind, active_scope)| {
+            options.scopes_conflict(kind, &scope_key, *active_kind, active_scope)
+        });
+        if let Some((_, active_scope)) = holder {
+            append_run_log(
+                "debug",
+                "backlog.task.claim_skipped_scope_conflict",
+                json!({
+                    "namespace": namespace,
+                    "task_id": task_id,
+                    "scope_key": scope_key,
+                    "held_by_scope": active_scope,
+                    "lease_owner": lease_owner,
+                }),
+            );
+            continue;
+        }
+        chosen = Some(task_id);
+        break;
+    }
+    drop(rows);
+    let Some(task_id) = chosen else {
         return Ok(None);
     };
 
     let mut stmt = tx
-        .prepare(
+        .prepare(&format!(
             "UPDATE backlog_tasks
              SET status = 'leased',
                  lease_owner = ?2,
                  lease_expires_at = ?3,
                  last_updated = ?4,
                  attempt_count = attempt_count + 1
-             WHERE task_id = ?1 AND status = 'ready'
-             RETURNING task_id, kind, title, details, scope_key, priority, status, last_updated,
-                       lease_owner, lease_expires_at, source, related_pr, related_branch, rationale,
-                       attempt_count, created_at",
-        )
+             WHERE repo_namespace = ?5 AND task_id = ?1 AND status = 'ready'
+             RETURNING {TASK_COLUMNS}"
+        ))
         .map_err(db_err)?;
 
-    stmt.query_row(
-        params![task_id, lease_owner, lease_expires_at, now],
-        row_to_task,
-    )
-    .optional()
-    .map_err(db_err)
+    let claimed = stmt
+        .query_row(
+            params![task_id, lease_owner, lease_expires_at, now, namespace],
+            row_to_task,
+        )
+        .optional()
+        .map_err(db_err)?;
+    if claimed.is_some() {
+        record_event(
+            tx,
+            namespace,
+            &task_id,
+            TaskEventInput {
+                event_type: "claimed",
+                from_status: Some("ready"),
+                to_status: "leased",
+                actor: lease_owner,
+                context: &TransitionContext::default(),
+            },
+            now,
+        )?;
+    }
+    Ok(claimed)
 }
 
 fn mark_in_progress(
-    conn: &Connection,
+    conn: &mut Connection,
+    namespace: &str,
     task_id: &str,
     lease_owner: &str,
     now: i64,
@@ -938,81 +4303,338 @@ fn mark_in_progress(
         "debug",
         "backlog_store.mark_in_progress.started",
         json!({
+            "namespace": namespace,
             "task_id": task_id,
             "lease_owner": lease_owner,
         }),
     );
-    let changed = conn
+    let tx = conn.transaction().map_err(db_err)?;
+    let changed = tx
         .execute(
             "UPDATE backlog_tasks
              SET status = 'in_progress', last_updated = ?1
-             WHERE task_id = ?2 AND status = 'leased' AND lease_owner = ?3",
-            params![now, task_id, lease_owner],
+             WHERE repo_namespace = ?4 AND task_id = ?2 AND status = 'leased' AND lease_owner = ?3",
+            params![now, task_id, lease_owner, namespace],
         )
         .map_err(db_err)?;
+    if changed > 0 {
+        record_event(
+            &tx,
+            namespace,
+            task_id,
+            TaskEventInput {
+                event_type: "in_progress",
+                from_status: Some("leased"),
+                to_status: "in_progress",
+                actor: lease_owner,
+                context: &TransitionContext::default(),
+            },
+            now,
+        )?;
+    }
+    tx.commit().map_err(db_err)?;
     Ok(changed > 0)
 }
 
 fn mark_complete(
-    conn: &Connection,
+    conn: &mut Connection,
+    namespace: &str,
     task_id: &str,
     lease_owner: &str,
+    context: &TransitionContext,
     now: i64,
 ) -> StoreResult<bool> {
     append_run_log(
         "debug",
         "backlog_store.mark_complete.started",
         json!({
+            "namespace": namespace,
             "task_id": task_id,
             "lease_owner": lease_owner,
         }),
     );
-    let changed = conn
+    let tx = conn.transaction().map_err(db_err)?;
+    let previous_status = task_status(&tx, namespace, task_id)?;
+    let changed = tx
         .execute(
             "UPDATE backlog_tasks
-             SET status = 'complete', lease_owner = NULL, lease_expires_at = NULL, last_updated = ?1
-             WHERE task_id = ?2 AND lease_owner = ?3 AND status IN ('leased', 'in_progress')",
-            params![now, task_id, lease_owner],
+             SET status = 'complete', lease_owner = NULL, lease_expires_at = NULL,
+                 blocked_reason = NULL, last_updated = ?1
+             WHERE repo_namespace = ?4 AND task_id = ?2 AND lease_owner = ?3
+               AND status IN ('leased', 'in_progress')",
+            params![now, task_id, lease_owner, namespace],
         )
         .map_err(db_err)?;
+    if changed > 0 {
+        record_event(
+            &tx,
+            namespace,
+            task_id,
+            TaskEventInput {
+                event_type: "completed",
+                from_status: previous_status.as_deref(),
+                to_status: "complete",
+                actor: lease_owner,
+                context,
+            },
+            now,
+        )?;
+        clear_prerequisite_block(&tx, namespace, task_id, now)?;
+    }
+    tx.commit().map_err(db_err)?;
     Ok(changed > 0)
 }
 
 fn release_lease(
-    conn: &Connection,
+    conn: &mut Connection,
+    namespace: &str,
     task_id: &str,
     lease_owner: &str,
+    context: &TransitionContext,
     now: i64,
 ) -> StoreResult<bool> {
     append_run_log(
         "debug",
         "backlog_store.release_lease.started",
         json!({
+            "namespace": namespace,
             "task_id": task_id,
             "lease_owner": lease_owner,
         }),
     );
-    let changed = conn
+    let tx = conn.transaction().map_err(db_err)?;
+    let previous_status = task_status(&tx, namespace, task_id)?;
+    let changed = tx
         .execute(
             "UPDATE backlog_tasks
-             SET status = 'ready', lease_owner = NULL, lease_expires_at = NULL, last_updated = ?1
-             WHERE task_id = ?2 AND lease_owner = ?3 AND status IN ('leased', 'in_progress')",
-            params![now, task_id, lease_owner],
+             SET status = 'ready', lease_owner = NULL, lease_expires_at = NULL,
+                 failure_reason = COALESCE(?5, failure_reason),
+                 status_note = COALESCE(?6, status_note), last_updated = ?1
+             WHERE repo_namespace = ?4 AND task_id = ?2 AND lease_owner = ?3
+               AND status IN ('leased', 'in_progress')",
+            params![
+                now,
+                task_id,
+                lease_owner,
+                namespace,
+                context.failure_reason,
+                context.reason
+            ],
         )
         .map_err(db_err)?;
+    if changed > 0 {
+        record_event(
+            &tx,
+            namespace,
+            task_id,
+            TaskEventInput {
+                event_type: "released",
+                from_status: previous_status.as_deref(),
+                to_status: "ready",
+                actor: lease_owner,
+                context,
+            },
+            now,
+        )?;
+    }
+    tx.commit().map_err(db_err)?;
     Ok(changed > 0)
 }
 
 fn mark_unresolved(
-    conn: &Connection,
+    conn: &mut Connection,
+    namespace: &str,
     task_id: &str,
     lease_owner: &str,
+    context: &TransitionContext,
     now: i64,
 ) -> StoreResult<bool> {
     append_run_log(
         "debug",
         "backlog_store.mark_unresolved.started",
         json!({
+            "namespace": namespace,
+            "task_id": task_id,
+            "lease_owner": lease_owner,
+        }),
+    );
+    let tx = conn.transaction().map_err(db_err)?;
+    let previous_status = task_status(&tx, namespace, task_id)?;
+    let changed = tx
+        .execute(
+            "UPDATE backlog_tasks
+             SET status = 'unresolved', lease_owner = NULL, lease_expires_at = NULL,
+                 failure_reason = COALESCE(?5, failure_reason), last_updated = ?1
+             WHERE repo_namespace = ?4 AND task_id = ?2 AND lease_owner = ?3
+               AND status IN ('leased', 'in_progress')",
+            params![now, task_id, lease_owner, namespace, context.failure_reason],
+        )
+        .map_err(db_err)?;
+    if changed > 0 {
+        record_event(
+            &tx,
+            namespace,
+            task_id,
+            TaskEventInput {
+                event_type: "unresolved",
+                from_status: previous_status.as_deref(),
+                to_status: "unresolved",
+                actor: lease_owner,
+                context,
+            },
+            now,
+        )?;
+        let blocked = cascade_prerequisite_block(
+            &tx,
+            namespace,
+            task_id,
+            &unresolved_prerequisite_reason(task_id),
+            now,
+        )?;
+        if blocked > 0 {
+            append_run_log(
+                "warn",
+                "backlog_store.dependents_blocked",
+                json!({ "namespace": namespace, "task_id": task_id, "blocked": blocked }),
+            );
+        }
+    }
+    tx.commit().map_err(db_err)?;
+    Ok(changed > 0)
+}
+
+fn schedule_retry(
+    conn: &mut Connection,
+    namespace: &str,
+    task_id: &str,
+    lease_owner: &str,
+    not_before: i64,
+    context: &TransitionContext,
+    now: i64,
+) -> StoreResult<bool> {
+    append_run_log(
+        "debug",
+        "backlog_store.schedule_retry.started",
+        json!({
+            "namespace": namespace,
+            "task_id": task_id,
+            "lease_owner": lease_owner,
+            "not_before": not_before,
+        }),
+    );
+    let tx = conn.transaction().map_err(db_err)?;
+    let previous_status = task_status(&tx, namespace, task_id)?;
+    let changed = tx
+        .execute(
+            "UPDATE backlog_tasks
+             SET status = 'ready', lease_owner = NULL, lease_expires_at = NULL,
+                 not_before = ?5, failure_reason = COALESCE(?6, failure_reason), last_updated = ?1
+             WHERE repo_namespace = ?4 AND task_id = ?2 AND lease_owner = ?3
+               AND status IN ('leased', 'in_progress')",
+            params![
+                now,
+                task_id,
+                lease_owner,
+                namespace,
+                not_before,
+                context.failure_reason
+            ],
+        )
+        .map_err(db_err)?;
+    if changed > 0 {
+        let context = TransitionContext {
+            reason: context
+                .reason
+                .clone()
+                .or_else(|| Some(format!("retry not before {not_before}"))),
+            ..context.clone()
+        };
+        record_event(
+            &tx,
+            namespace,
+            task_id,
+            TaskEventInput {
+                event_type: "retry_scheduled",
+                from_status: previous_status.as_deref(),
+                to_status: "ready",
+                actor: lease_owner,
+                context: &context,
+            },
+            now,
+        )?;
+    }
+    tx.commit().map_err(db_err)?;
+    Ok(changed > 0)
+}
+
+fn mark_exhausted(
+    conn: &mut Connection,
+    namespace: &str,
+    task_id: &str,
+    lease_owner: &str,
+    context: &TransitionContext,
+    now: i64,
+) -> StoreResult<bool> {
+    append_run_log(
+        "debug",
+        "backlog_store.mark_exhausted.started",
+        json!({
+            "namespace": namespace,
+            "task_id": task_id,
+            "lease_owner": lease_owner,
+        }),
+    );
+    let tx = conn.transaction().map_err(db_err)?;
+    let previous_status = task_status(&tx, namespace, task_id)?;
+    let changed = tx
+        .execute(
+            "UPDATE backlog_tasks
+             SET status = 'exhausted', lease_owner = NULL, lease_expires_at = NULL,
+                 not_before = NULL, failure_reason = COALESCE(?5, failure_reason),
+                 last_updated = ?1
+             WHERE repo_namespace = ?4 AND task_id = ?2 AND lease_owner = ?3
+               AND status IN ('leased', 'in_progress')",
+            params![now, task_id, lease_owner, namespace, context.failure_reason],
+        )
+        .map_err(db_err)?;
+    if changed > 0 {
+        record_event(
+            &tx,
+            namespace,
+            task_id,
+            TaskEventInput {
+                event_type: "exhausted",
+                from_status: previous_status.as_deref(),
+                to_status: "exhausted",
+                actor: lease_owner,
+                context,
+            },
+            now,
+        )?;
+        cascade_prerequisite_block(
+            &tx,
+            namespace,
+            task_id,
+            &exhausted_prerequisite_reason(task_id),
+            now,
+        )?;
+    }
+    tx.commit().map_err(db_err)?;
+    Ok(changed > 0)
+}
+
+fn renew_lease(
+    conn: &Connection,
+    namespace: &str,
+    task_id: &str,
+    lease_owner: &str,
+    lease_expires_at: i64,
+) -> StoreResult<bool> {
+    append_run_log(
+        "debug",
+        "backlog_store.renew_lease.started",
+        json!({
+            "namespace": namespace,
             "task_id": task_id,
             "lease_owner": lease_owner,
         }),
@@ -1020,50 +4642,102 @@ fn mark_unresolved(
     let changed = conn
         .execute(
             "UPDATE backlog_tasks
-             SET status = 'unresolved', lease_owner = NULL, lease_expires_at = NULL, last_updated = ?1
-             WHERE task_id = ?2 AND lease_owner = ?3 AND status IN ('leased', 'in_progress')",
-            params![now, task_id, lease_owner],
+             SET lease_expires_at = MAX(COALESCE(lease_expires_at, 0), ?4)
+             WHERE repo_namespace = ?1 AND task_id = ?2 AND lease_owner = ?3
+               AND status IN ('leased', 'in_progress')",
+            params![namespace, task_id, lease_owner, lease_expires_at],
         )
         .map_err(db_err)?;
     Ok(changed > 0)
 }
 
-fn recover_stale(conn: &Connection, now: i64) -> StoreResult<usize> {
+fn recover_stale(conn: &mut Connection, namespace: &str, now: i64) -> StoreResult<usize> {
     append_run_log(
         "debug",
         "backlog_store.recover_stale.started",
-        json!({ "now": now }),
+        json!({ "namespace": namespace, "now": now }),
     );
-    let changed = conn
+    let tx = conn.transaction().map_err(db_err)?;
+    let stale = {
+        let mut statement = tx
+            .prepare(
+                "SELECT task_id, status, lease_owner
+                 FROM backlog_tasks
+                 WHERE repo_namespace = ?2
+                   AND status IN ('leased', 'in_progress')
+                   AND (lease_expires_at IS NULL OR lease_expires_at < ?1)",
+            )
+            .map_err(db_err)?;
+        let rows = statement
+            .query_map(params![now, namespace], |row| {
+                Ok((
+                    row.get::<_, String>(0)?,
+                    row.get::<_, String>(1)?,
+                    row.get::<_, Option<String>>(2)?,
+                ))
+            })
+            .map_err(db_err)?
+            .collect::<Result<Vec<_>, _>>()
+            .map_err(db_err)?;
+        rows
+    };
+    let changed = tx
         .execute(
             "UPDATE backlog_tasks
              SET status = 'ready',
                  lease_owner = NULL,
                  lease_expires_at = NULL,
                  last_updated = ?1
-             WHERE status = 'in_progress'
-                OR (status = 'leased' AND (lease_expires_at IS NULL OR lease_expires_at < ?1))",
-            [now],
+             WHERE repo_namespace = ?2
+               AND status IN ('leased', 'in_progress')
+               AND (lease_expires_at IS NULL OR lease_expires_at < ?1)",
+            params![now, namespace],
         )
         .map_err(db_err)?;
+    for (task_id, status, lease_owner) in &stale {
+        record_event(
+            &tx,
+            namespace,
+            task_id,
+            TaskEventInput {
+                event_type: "lease_recovered",
+                from_status: Some(status),
+                to_status: "ready",
+                actor: LEASE_RECOVERY_ACTOR,
+                context: &TransitionContext {
+                    reason: lease_owner
+                        .as_ref()
+                        .map(|owner| format!("lease held by {owner} expired")),
+                    ..TransitionContext::default()
+                },
+            },
+            now,
+        )?;
+    }
+    tx.commit().map_err(db_err)?;
     Ok(changed)
 }
 
-fn fetch_task(conn: &Connection, task_id: &str) -> StoreResult<Option<BacklogTask>> {
+fn fetch_task(
+    conn: &Connection,
+    namespace: &str,
+    task_id: &str,
+) -> StoreResult<Option<BacklogTask>> {
     append_run_log(
         "debug",
         "backlog_store.fetch_task.started",
         json!({
+            "namespace": namespace,
             "task_id": task_id,
         }),
     );
     conn.query_row(
-        "SELECT task_id, kind, title, details, scope_key, priority, status, last_updated,
-                lease_owner, lease_expires_at, source, related_pr, related_branch, rationale,
-                attempt_count, created_at
-         FROM backlog_tasks
-         WHERE task_id = ?1",
-        [task_id],
+        &format!(
+            "SELECT {TASK_COLUMNS}
+             FROM backlog_tasks
+             WHERE repo_namespace = ?1 AND task_id = ?2"
+        ),
+        [namespace, task_id],
         row_to_task,
     )
     .optional()
@@ -1119,6 +4793,15 @@ fn row_to_task(row: &rusqlite::Row<'_>) -> rusqlite::Result<BacklogTask> {
         related_branch: row.get(12)?,
         attempt_count: row.get(14)?,
         created_at: row.get(15)?,
+        repo_namespace: row.get(16)?,
+        blocked_reason: row.get(17)?,
+        status_note: row.get(18)?,
+        blocked_by: row
+            .get::<_, Option<String>>(19)?
+            .map(|ids| ids.split(',').map(str::to_string).collect())
+            .unwrap_or_default(),
+        not_before: row.get(20)?,
+        failure_reason: row.get(21)?,
     })
 }
 
@@ -1135,7 +4818,7 @@ fn task_kind_from_db(value: &str) -> Option<TaskKind> {
     }
 }
 
-fn compute_task_id_from_new_task(task: &NewTask) -> String {
+pub fn compute_task_id_from_new_task(task: &NewTask) -> String {
     compute_task_id(TaskIdentity {
         kind: task.kind,
         title: task.title.clone(),
@@ -1173,8 +4856,14 @@ mod tests {
     use rusqlite::Connection;
     use tempfile::TempDir;
 
-    use super::{db_err, task_kind_from_db, BacklogStore, NewTask, TaskStatus};
-    use crate::priority::Priority;
+    use super::{
+        db_err, task_kind_from_db, BacklogChange, BacklogStore, BacklogTask, ChangeOutcome,
+        ClaimOptions, ImportAction, ImportMode, NewTask, NewTaskOutcome, TaskEdit, TaskFilter,
+        TaskStatus, TransitionContext, LEGACY_NAMESPACE,
+    };
+    use crate::config::ScopeExclusivity;
+    use crate::priority::{Priority, PriorityAging};
+    use crate::repo_identity::RepoIdentity;
     use crate::task_identity::{compute_task_id, TaskIdentity, TaskKind};
 
     fn temp_store() -> (BacklogStore, TempDir) {
@@ -1194,6 +4883,7 @@ mod tests {
             source: "test".to_string(),
             related_pr: None,
             related_branch: None,
+            blocked_by: Vec::new(),
         }
     }
 
@@ -1352,6 +5042,328 @@ mod tests {
         assert_eq!(round_trip.lease_expires_at, None);
     }
 
+    #[test]
+    fn renewed_leases_survive_recovery_until_they_expire() {
+        let (store, _dir) = temp_store();
+        let row = store
+            .upsert_task(task("long-running", Priority::P1))
+            .expect("seed");
+        let leased = store
+            .claim_next("worker", 1)
+            .expect("claim")
+            .expect("leased row");
+        assert!(store
+            .mark_in_progress(&row.task_id, "worker")
+            .expect("in progress"));
+        let initial_expiry = leased.lease_expires_at.expect("expiry");
+
+        assert!(!store
+            .renew_lease(&row.task_id, "someone-else", initial_expiry + 60_000)
+            .expect("foreign renewal"));
+        assert!(store
+            .renew_lease(&row.task_id, "worker", initial_expiry + 60_000)
+            .expect("renewal"));
+        // A late renewal never shortens the lease.
+        assert!(store
+            .renew_lease(&row.task_id, "worker", initial_expiry)
+            .expect("stale renewal"));
+        let renewed = store.get_task(&row.task_id).expect("fetch").expect("task");
+        assert_eq!(renewed.lease_expires_at, Some(initial_expiry + 60_000));
+
+        assert_eq!(
+            store
+                .recover_stale_leases(initial_expiry + 1)
+                .expect("recover"),
+            0
+        );
+        assert_eq!(
+            store
+                .get_task(&row.task_id)
+                .expect("fetch")
+                .expect("task")
+                .status,
+            TaskStatus::InProgress
+        );
+
+        assert_eq!(
+            store
+                .recover_stale_leases(initial_expiry + 60_001)
+                .expect("recover"),
+            1
+        );
+        assert!(!store
+            .renew_lease(&row.task_id, "worker", initial_expiry + 120_000)
+            .expect("renewal after loss"));
+    }
+
+    #[test]
+    fn retries_wait_for_not_before_and_exhausted_tasks_stay_terminal() {
+        let (store, dir) = temp_store();
+        let prerequisite = store
+            .upsert_task(task("flaky", Priority::P0))
+            .expect("seed");
+        let dependent_row = store
+            .upsert_task(dependent("after flaky", &[&prerequisite.task_id]))
+            .expect("dependent");
+        let claimed = store.claim_next("worker", 60).expect("claim").expect("task");
+        assert_eq!(claimed.task_id, prerequisite.task_id);
+
+        let not_before = super::system_time_unix() + 3_600_000;
+        assert!(store
+            .schedule_retry(
+                &claimed.task_id,
+                "worker",
+                not_before,
+                TransitionContext {
+                    failure_reason: Some("tests failed".to_string()),
+                    ..TransitionContext::default()
+                },
+            )
+            .expect("retry"));
+        let waiting = store.get_task(&claimed.task_id).expect("get").expect("row");
+        assert_eq!(waiting.status, TaskStatus::Ready);
+        assert_eq!(waiting.not_before, Some(not_before));
+        assert_eq!(waiting.failure_reason.as_deref(), Some("tests failed"));
+        assert!(store.claim_next("worker", 60).expect("claim").is_none());
+
+        // Simulate the backoff elapsing.
+        drop(store);
+        let db = dir.path().join("backlog.sqlite");
+        Connection::open(&db)
+            .expect("raw")
+            .execute("UPDATE backlog_tasks SET not_before = 0", [])
+            .expect("rewind");
+        let store = BacklogStore::open(&db).expect("reopen");
+        let retried = store.claim_next("worker", 60).expect("claim").expect("task");
+        assert_eq!(retried.task_id, prerequisite.task_id);
+        assert_eq!(retried.attempt_count, 2);
+
+        assert!(store
+            .mark_exhausted(&retried.task_id, "worker", TransitionContext::default())
+            .expect("exhaust"));
+        let exhausted = store.get_task(&retried.task_id).expect("get").expect("row");
+        assert_eq!(exhausted.status, TaskStatus::Exhausted);
+        assert_eq!(exhausted.failure_reason.as_deref(), Some("tests failed"));
+        assert_eq!(
+            store
+                .get_task(&dependent_row.task_id)
+                .expect("get")
+                .expect("row")
+                .blocked_reason,
+            Some(format!("prerequisite {} exhausted its retries", retried.task_id))
+        );
+
+        // Rediscovering the task does not bring it back; an operator requeue does.
+        store.upsert_task(task("flaky", Priority::P0)).expect("re-upsert");
+        assert_eq!(
+            store
+                .get_task(&retried.task_id)
+                .expect("get")
+                .expect("row")
+                .status,
+            TaskStatus::Exhausted
+        );
+        assert!(store.requeue_task(&retried.task_id).expect("requeue"));
+        let requeued = store.get_task(&retried.task_id).expect("get").expect("row");
+        assert_eq!(requeued.status, TaskStatus::Ready);
+        assert_eq!(requeued.attempt_count, 0);
+        assert_eq!(requeued.not_before, None);
+        assert_eq!(
+            store
+                .get_task(&dependent_row.task_id)
+                .expect("get")
+                .expect("row")
+                .blocked_reason,
+            None
+        );
+    }
+
+    #[test]
+    fn claim_filters_restrict_which_ready_tasks_are_claimed() {
+        let (store, _dir) = temp_store();
+        let p0 = store.upsert_task(task("urgent", Priority::P0)).expect("p0");
+        let bug = store
+            .upsert_task(NewTask {
+                kind: TaskKind::Bugfix,
+                scope_key: "backlog:store".to_string(),
+                source: "seed_runner_v2".to_string(),
+                ..task("store bug", Priority::P1)
+            })
+            .expect("bug");
+        let p2 = store.upsert_task(task("later", Priority::P2)).expect("p2");
+        let claim = |filter: TaskFilter| {
+            let options = ClaimOptions {
+                filter,
+                ..ClaimOptions::default()
+            };
+            store
+                .claim_next_with("w1", 60, &options)
+                .expect("claim")
+                .map(|task| task.title)
+        };
+
+        assert_eq!(
+            claim(TaskFilter {
+                task_ids: vec![p2.task_id],
+                ..TaskFilter::default()
+            })
+            .as_deref(),
+            Some("later")
+        );
+        for filter in [
+            TaskFilter {
+                kinds: vec![TaskKind::Bugfix],
+                ..TaskFilter::default()
+            },
+            TaskFilter {
+                scopes: vec!["backlog".to_string()],
+                sources: vec!["seed".to_string()],
+                ..TaskFilter::default()
+            },
+        ] {
+            assert_eq!(claim(filter).as_deref(), Some("store bug"));
+            store.release_lease(&bug.task_id, "w1").expect("release");
+        }
+        let p1_only = TaskFilter {
+            priorities: vec![Priority::P1],
+            scopes: vec!["domain".to_string()],
+            ..TaskFilter::default()
+        };
+        assert_eq!(claim(p1_only), None);
+        assert_eq!(
+            store.get_task(&p0.task_id).expect("get").expect("row").status,
+            TaskStatus::Ready
+        );
+    }
+
+    #[test]
+    fn scope_exclusivity_holds_back_conflicting_claims() {
+        let scoped = |title: &str, scope: &str, priority: Priority| NewTask {
+            scope_key: scope.to_string(),
+            ..task(title, priority)
+        };
+        let seed = |store: &BacklogStore| {
+            for new_task in [
+                scoped("dashboard layout", "tui:dashboard", Priority::P0),
+                scoped("dashboard colors", "tui:dashboard", Priority::P0),
+                scoped("tui keymap", "tui", Priority::P1),
+                scoped("api pagination", "api", Priority::P2),
+            ] {
+                store.upsert_task(new_task).expect("seed");
+            }
+        };
+        let options = |mode: ScopeExclusivity| ClaimOptions {
+            scope_exclusivity: [("feature".to_string(), mode)].into_iter().collect(),
+            scope_separator: ":".to_string(),
+            ..ClaimOptions::default()
+        };
+        let claim = |store: &BacklogStore, owner: &str, options: &ClaimOptions| {
+            store
+                .claim_next_with(owner, 60, options)
+                .expect("claim")
+                .map(|task| task.title)
+        };
+
+        let (store, _dir) = temp_store();
+        seed(&store);
+        let exact = options(ScopeExclusivity::Exact);
+        assert_eq!(claim(&store, "w1", &exact).as_deref(), Some("dashboard layout"));
+        assert_eq!(exact.held_back(&store.list_tasks().expect("list")), 1);
+        assert_eq!(claim(&store, "w2", &exact).as_deref(), Some("tui keymap"));
+        assert_eq!(claim(&store, "w3", &exact).as_deref(), Some("api pagination"));
+        assert_eq!(claim(&store, "w4", &exact), None);
+
+        let (store, _dir) = temp_store();
+        seed(&store);
+        let prefix = options(ScopeExclusivity::Prefix);
+        assert_eq!(claim(&store, "w1", &prefix).as_deref(), Some("dashboard layout"));
+        assert_eq!(claim(&store, "w2", &prefix).as_deref(), Some("api pagination"));
+        assert_eq!(prefix.held_back(&store.list_tasks().expect("list")), 2);
+        assert_eq!(claim(&store, "w3", &prefix), None);
+
+        let (store, _dir) = temp_store();
+        seed(&store);
+        assert_eq!(
+            claim(&store, "w1", &ClaimOptions::default()).as_deref(),
+            Some("dashboard layout")
+        );
+        assert_eq!(
+            claim(&store, "w2", &ClaimOptions::default()).as_deref(),
+            Some("dashboard colors")
+        );
+
+        assert!(!prefix.scopes_conflict(
+            TaskKind::Feature,
+            "tui:dashboards",
+            TaskKind::Feature,
+            "tui:dashboard"
+        ));
+        assert!(exact.scopes_conflict(
+            TaskKind::Bugfix,
+            "tui:dashboard",
+            TaskKind::Feature,
+            "tui:dashboard"
+        ));
+        assert!(!exact.scopes_conflict(
+            TaskKind::Bugfix,
+            "tui:dashboard",
+            TaskKind::Bugfix,
+            "tui:dashboard"
+        ));
+        assert!(!exact.scopes_conflict(TaskKind::Feature, "", TaskKind::Feature, ""));
+    }
+
+    #[test]
+    fn aged_tasks_claim_ahead_of_newer_higher_priority_work() {
+        let (store, dir) = temp_store();
+        let old = store.upsert_task(task("old polish", Priority::P2)).expect("p2");
+        store.upsert_task(task("fresh gap", Priority::P1)).expect("p1");
+        store.upsert_task(task("fresh gap two", Priority::P1)).expect("p1");
+
+        // Backdate the P2 task by two days.
+        drop(store);
+        let db = dir.path().join("backlog.sqlite");
+        Connection::open(&db)
+            .expect("raw")
+            .execute(
+                "UPDATE backlog_tasks
+                 SET created_at = created_at - 172800000, last_updated = last_updated - 172800000
+                 WHERE task_id = ?1",
+                [&old.task_id],
+            )
+            .expect("backdate");
+        let store = BacklogStore::open(&db).expect("reopen");
+
+        let strict = store.claim_next("w1", 60).expect("claim").expect("task");
+        assert_eq!(strict.title, "fresh gap");
+
+        let aging = ClaimOptions {
+            priority_aging: PriorityAging {
+                p2_to_p1_seconds: 86_400,
+                p1_to_p0_seconds: 0,
+            },
+            ..ClaimOptions::default()
+        };
+        let aged = store
+            .claim_next_with("w2", 60, &aging)
+            .expect("claim")
+            .expect("task");
+        assert_eq!(aged.task_id, old.task_id);
+
+        let promoted_to_p0 = ClaimOptions {
+            priority_aging: PriorityAging {
+                p2_to_p1_seconds: 86_400,
+                p1_to_p0_seconds: 86_400,
+            },
+            ..ClaimOptions::default()
+        };
+        let next = store
+            .claim_next_with("w3", 60, &promoted_to_p0)
+            .expect("claim")
+            .expect("task");
+        assert_eq!(next.title, "fresh gap two");
+    }
+
     #[test]
     fn mark_complete_requires_owner_match() {
         let (store, _dir) = temp_store();
@@ -1427,6 +5439,7 @@ mod tests {
         assert_eq!(TaskStatus::Unresolved.as_str(), "unresolved");
         assert_eq!(TaskStatus::from_db("failed"), Some(TaskStatus::Failed));
         assert_eq!(TaskStatus::from_db("unresolved"), Some(TaskStatus::Unresolved));
+        assert_eq!(TaskStatus::from_db("cancelled"), Some(TaskStatus::Cancelled));
         assert_eq!(TaskStatus::from_db("unknown"), None);
 
         let (store, _dir) = temp_store();
@@ -1450,6 +5463,7 @@ mod tests {
                 source: "t".to_string(),
                 related_pr: None,
                 related_branch: None,
+                blocked_by: Vec::new(),
             })
             .expect("bugfix insert");
         let _ = store
@@ -1463,6 +5477,7 @@ mod tests {
                 source: "t".to_string(),
                 related_pr: None,
                 related_branch: None,
+                blocked_by: Vec::new(),
             })
             .expect("maintenance insert");
         let _ = store
@@ -1476,6 +5491,7 @@ mod tests {
                 source: "t".to_string(),
                 related_pr: None,
                 related_branch: None,
+                blocked_by: Vec::new(),
             })
             .expect("infra insert");
 
@@ -1500,21 +5516,21 @@ mod tests {
 
         let conversion_conn = Connection::open_in_memory().expect("open memory");
         let bad_kind = conversion_conn.query_row(
-            "SELECT 'id', 'invalid', 'title', '', 'global', 'P1', 'ready', 1, NULL, NULL, 'src', NULL, NULL, '', 0, 1",
+            "SELECT 'id', 'invalid', 'title', '', 'global', 'P1', 'ready', 1, NULL, NULL, 'src', NULL, NULL, '', 0, 1, 'legacy', NULL, NULL, NULL, NULL, NULL",
             [],
             super::row_to_task,
         );
         assert!(bad_kind.is_err());
 
         let bad_priority = conversion_conn.query_row(
-            "SELECT 'id', 'feature', 'title', '', 'global', 'PX', 'ready', 1, NULL, NULL, 'src', NULL, NULL, '', 0, 1",
+            "SELECT 'id', 'feature', 'title', '', 'global', 'PX', 'ready', 1, NULL, NULL, 'src', NULL, NULL, '', 0, 1, 'legacy', NULL, NULL, NULL, NULL, NULL",
             [],
             super::row_to_task,
         );
         assert!(bad_priority.is_err());
 
         let bad_status = conversion_conn.query_row(
-            "SELECT 'id', 'feature', 'title', '', 'global', 'P1', 'unknown', 1, NULL, NULL, 'src', NULL, NULL, '', 0, 1",
+            "SELECT 'id', 'feature', 'title', '', 'global', 'P1', 'unknown', 1, NULL, NULL, 'src', NULL, NULL, '', 0, 1, 'legacy', NULL, NULL, NULL, NULL, NULL",
             [],
             super::row_to_task,
         );
@@ -1602,4 +5618,1005 @@ mod tests {
             Ok(_) => panic!("expected error, got Ok"),
         }
     }
+
+    #[test]
+    fn repo_namespaces_isolate_claims_and_counts() {
+        let dir = TempDir::new().expect("tempdir");
+        let db = dir.path().join("backlog.sqlite");
+        let app = RepoIdentity::new("/work/app", Some("git@github.com:acme/app.git".to_string()));
+        let lib = RepoIdentity::new("/work/lib", Some("git@github.com:acme/lib.git".to_string()));
+        let app_store = BacklogStore::open_for_repo(&db, &app).expect("open app");
+        let lib_store = BacklogStore::open_for_repo(&db, &lib).expect("open lib");
+        assert_eq!(app_store.namespace(), app.namespace);
+
+        let app_task = app_store
+            .upsert_task(task("shared title", Priority::P1))
+            .expect("app insert");
+        let lib_task = lib_store
+            .upsert_task(task("shared title", Priority::P0))
+            .expect("lib insert");
+        app_store
+            .upsert_task(task("app only", Priority::P2))
+            .expect("second app insert");
+        assert_eq!(app_task.task_id, lib_task.task_id);
+        assert_eq!(app_task.repo_namespace, app.namespace);
+        assert_eq!(lib_task.priority, Priority::P0);
+        assert_eq!(
+            app_store.get_task(&app_task.task_id).expect("get").map(|t| t.priority),
+            Some(Priority::P1)
+        );
+
+        let claimed = app_store
+            .claim_next("app-worker", 60)
+            .expect("claim")
+            .expect("app task claimable");
+        assert_eq!(claimed.repo_namespace, app.namespace);
+        assert_eq!(claimed.task_id, app_task.task_id);
+        assert_eq!(app_store.count_active_tasks().expect("active"), 2);
+        assert_eq!(lib_store.count_active_tasks().expect("active"), 1);
+        assert_eq!(lib_store.count_tasks_by_priority().expect("counts"), (1, 0, 0));
+        assert!(!lib_store
+            .mark_in_progress(&claimed.task_id, "app-worker")
+            .expect("cross-namespace transition"));
+        assert_eq!(lib_store.list_tasks().expect("list").len(), 1);
+        let lib_claim = lib_store
+            .claim_next("lib-worker", 60)
+            .expect("claim")
+            .expect("lib task claimable");
+        assert_eq!(lib_claim.repo_namespace, lib.namespace);
+        assert!(lib_store.claim_next("lib-worker", 60).expect("claim").is_none());
+    }
+
+    #[test]
+    fn legacy_tasks_are_only_adopted_explicitly_and_namespaces_are_listed() {
+        let dir = TempDir::new().expect("tempdir");
+        let db = dir.path().join("backlog.sqlite");
+        {
+            let legacy = BacklogStore::open(&db).expect("open legacy");
+            assert_eq!(legacy.namespace(), LEGACY_NAMESPACE);
+            legacy
+                .upsert_task(task("seeded before repo open", Priority::P1))
+                .expect("seed");
+        }
+
+        // Opening for a repo leaves legacy rows alone; another repo sharing the
+        // database must not find them in its own namespace either.
+        let app = RepoIdentity::new("/work/app", None);
+        let app_store = BacklogStore::open_for_repo(&db, &app).expect("open app");
+        assert!(app_store.list_tasks().expect("list").is_empty());
+        let lib = RepoIdentity::new("/work/lib", None);
+        let lib_store = BacklogStore::open_for_repo(&db, &lib).expect("open lib");
+        assert!(lib_store.list_tasks().expect("list").is_empty());
+
+        let adopted = app_store
+            .move_namespace_tasks(LEGACY_NAMESPACE, &app.namespace)
+            .expect("adopt");
+        assert_eq!(adopted, 1);
+        let tasks = app_store.list_tasks().expect("list");
+        assert_eq!(tasks.len(), 1);
+        assert_eq!(tasks[0].repo_namespace, app.namespace);
+        assert!(lib_store.list_tasks().expect("list").is_empty());
+
+        let namespaces = lib_store.list_namespaces().expect("namespaces");
+        let app_summary = namespaces
+            .iter()
+            .find(|summary| summary.repo_namespace == app.namespace)
+            .expect("app namespace listed");
+        assert_eq!(app_summary.repo_root.as_deref(), Some("/work/app"));
+        assert_eq!(app_summary.task_count, 1);
+        assert!(namespaces
+            .iter()
+            .any(|summary| summary.repo_namespace == lib.namespace && summary.task_count == 0));
+
+        let moved = lib_store
+            .move_namespace_tasks(&app.namespace, &lib.namespace)
+            .expect("move");
+        assert_eq!(moved, 1);
+        assert_eq!(lib_store.list_tasks().expect("list").len(), 1);
+        assert!(app_store.list_tasks().expect("list").is_empty());
+    }
+
+    #[test]
+    fn applying_changes_is_all_or_nothing_and_adds_only_new_tasks() {
+        let (store, _dir) = temp_store();
+        let bump = store.upsert_task(task("bump", Priority::P2)).expect("bump");
+        let ready = store.upsert_task(task("ready", Priority::P2)).expect("ready");
+
+        let outcomes = store
+            .apply_changes(vec![
+                BacklogChange::Reprioritize {
+                    task_id: bump.task_id.clone(),
+                    priority: Priority::P0,
+                },
+                BacklogChange::Requeue {
+                    task_id: ready.task_id,
+                },
+            ])
+            .expect("apply");
+        assert_eq!(outcomes, vec![ChangeOutcome::Applied, ChangeOutcome::Stale]);
+        let bump_now = store.get_task(&bump.task_id).expect("get").expect("bump");
+        assert_eq!(bump_now.priority, Priority::P2);
+
+        let add = || BacklogChange::Add(task("new work", Priority::P1));
+        let outcomes = store.apply_changes(vec![add()]).expect("add");
+        let ChangeOutcome::Added(added) = &outcomes[0] else {
+            panic!("expected a new task, got {outcomes:?}");
+        };
+        let claimed = store.claim_next("worker-1", 60).expect("claim").expect("task");
+        assert_eq!(&claimed.task_id, added);
+        assert!(store.mark_complete(added, "worker-1").expect("complete"));
+
+        let outcomes = store.apply_changes(vec![add()]).expect("re-add");
+        assert_eq!(outcomes, vec![ChangeOutcome::AlreadyPresent(added.clone())]);
+        let after = store.get_task(added).expect("get").expect("added");
+        assert_eq!(after.status, TaskStatus::Complete);
+    }
+
+    #[test]
+    fn moving_a_namespace_takes_history_only_with_the_rows_that_moved() {
+        let dir = TempDir::new().expect("tempdir");
+        let db = dir.path().join("backlog.sqlite");
+        let app = RepoIdentity::new("/work/app", None);
+        let legacy = BacklogStore::open(&db).expect("open legacy");
+        let app_store = BacklogStore::open_for_repo(&db, &app).expect("open app");
+
+        let done = legacy.upsert_task(task("done", Priority::P1)).expect("done");
+        legacy.claim_next("worker-1", 60).expect("claim").expect("done");
+        assert!(legacy.mark_complete(&done.task_id, "worker-1").expect("complete"));
+        assert_eq!(legacy.archive_finished_tasks(0).expect("archive"), vec![done.task_id.clone()]);
+        let busy = legacy.upsert_task(task("busy", Priority::P0)).expect("busy");
+        legacy.claim_next("worker-1", 60).expect("claim").expect("busy");
+        let shared = legacy.upsert_task(task("shared", Priority::P1)).expect("shared");
+        let moves = legacy.upsert_task(task("moves", Priority::P2)).expect("moves");
+        app_store.upsert_task(task("shared", Priority::P1)).expect("app copy");
+        let history = |store: &BacklogStore, task_id: &str| {
+            store.history(task_id).expect("history").len()
+        };
+        let (busy_events, shared_events) =
+            (history(&legacy, &busy.task_id), history(&legacy, &shared.task_id));
+        let (app_shared_events, moves_events) =
+            (history(&app_store, &shared.task_id), history(&legacy, &moves.task_id));
+
+        let moved = app_store
+            .move_namespace_tasks(LEGACY_NAMESPACE, &app.namespace)
+            .expect("adopt");
+        assert_eq!(moved, 1);
+        // The leased task and the one the target already holds stay, with
+        // their history.
+        let left = legacy.list_tasks().expect("list");
+        assert_eq!(left.len(), 2);
+        assert_eq!(history(&legacy, &busy.task_id), busy_events);
+        assert_eq!(history(&legacy, &shared.task_id), shared_events);
+        assert_eq!(history(&app_store, &shared.task_id), app_shared_events);
+        assert_eq!(history(&app_store, &moves.task_id), moves_events);
+        assert_eq!(history(&legacy, &moves.task_id), 0);
+        assert!(app_store.get_archived_task(&done.task_id).expect("get").is_some());
+        assert!(legacy.get_archived_task(&done.task_id).expect("get").is_none());
+        assert!(history(&app_store, &done.task_id) > 0);
+    }
+
+    fn dependent(title: &str, blocked_by: &[&str]) -> NewTask {
+        NewTask {
+            blocked_by: blocked_by.iter().map(|id| id.to_string()).collect(),
+            ..task(title, Priority::P0)
+        }
+    }
+
+    #[test]
+    fn blocked_tasks_are_skipped_until_prerequisites_complete() {
+        let (store, _dir) = temp_store();
+        let base = store
+            .upsert_task(task("base", Priority::P2))
+            .expect("insert base");
+        let follow_up = store
+            .upsert_task(dependent("follow up", &[&base.task_id]))
+            .expect("insert dependent");
+        assert_eq!(follow_up.blocked_by, vec![base.task_id.clone()]);
+
+        // The dependent outranks its prerequisite but must not be claimed first.
+        let first = store
+            .claim_next("worker-1", 60)
+            .expect("claim")
+            .expect("base claimable");
+        assert_eq!(first.task_id, base.task_id);
+        assert!(store.claim_next("worker-2", 60).expect("claim").is_none());
+
+        assert!(store
+            .mark_complete(&base.task_id, "worker-1")
+            .expect("complete base"));
+        let second = store
+            .claim_next("worker-2", 60)
+            .expect("claim")
+            .expect("dependent unblocked");
+        assert_eq!(second.task_id, follow_up.task_id);
+        assert_eq!(second.blocked_by, vec![base.task_id]);
+    }
+
+    #[test]
+    fn unresolved_prerequisite_cascades_blocked_reason() {
+        let (store, _dir) = temp_store();
+        let base = store
+            .upsert_task(task("base", Priority::P1))
+            .expect("insert base");
+        let middle = store
+            .upsert_task(dependent("middle", &[&base.task_id]))
+            .expect("insert middle");
+        let leaf = store
+            .upsert_task(dependent("leaf", &[&middle.task_id]))
+            .expect("insert leaf");
+
+        store.claim_next("worker-1", 60).expect("claim").expect("base");
+        assert!(store
+            .mark_unresolved(&base.task_id, "worker-1")
+            .expect("unresolve base"));
+
+        let reason = format!("prerequisite {} is unresolved", base.task_id);
+        for task_id in [&middle.task_id, &leaf.task_id] {
+            let row = store.get_task(task_id).expect("get").expect("row");
+            assert_eq!(row.blocked_reason.as_deref(), Some(reason.as_str()));
+        }
+        assert!(store.claim_next("worker-1", 60).expect("claim").is_none());
+
+        // Requeueing the prerequisite lifts the cascaded reason.
+        store
+            .upsert_task(task("base", Priority::P1))
+            .expect("requeue base");
+        let row = store.get_task(&leaf.task_id).expect("get").expect("row");
+        assert_eq!(row.blocked_reason, None);
+    }
+
+    #[test]
+    fn dependency_cycles_and_unknown_prerequisites_are_rejected() {
+        let (store, _dir) = temp_store();
+        let a = store.upsert_task(task("a", Priority::P1)).expect("insert a");
+        let b = store
+            .upsert_task(dependent("b", &[&a.task_id]))
+            .expect("insert b");
+        let c = store
+            .upsert_task(dependent("c", &[&b.task_id]))
+            .expect("insert c");
+
+        let cycle = store
+            .upsert_task(NewTask {
+                blocked_by: vec![c.task_id],
+                ..task("a", Priority::P1)
+            })
+            .expect_err("cycle rejected");
+        assert!(cycle.to_string().contains("dependency cycle"), "{cycle}");
+        assert!(store
+            .get_task(&a.task_id)
+            .expect("get")
+            .expect("row")
+            .blocked_by
+            .is_empty());
+
+        let self_edge = store
+            .upsert_task(dependent("self", &[&compute_task_id(TaskIdentity {
+                kind: TaskKind::Feature,
+                title: "self".to_string(),
+                scope_key: "domain:core".to_string(),
+                related_pr: None,
+                related_branch: None,
+            })]))
+            .expect_err("self edge rejected");
+        assert!(self_edge.to_string().contains("itself"), "{self_edge}");
+
+        let unknown = store
+            .upsert_task(dependent("orphan", &["missing"]))
+            .expect_err("unknown prerequisite rejected");
+        assert!(unknown.to_string().contains("unknown task missing"), "{unknown}");
+        assert_eq!(store.list_tasks().expect("list").len(), 3);
+    }
+
+    #[test]
+    fn history_records_every_transition_with_context() {
+        let (store, dir) = temp_store();
+        let row = store
+            .upsert_task(task("tracked", Priority::P1))
+            .expect("seed");
+        store.claim_next("worker-1", 60).expect("claim").expect("task");
+        store
+            .mark_in_progress(&row.task_id, "worker-1")
+            .expect("in progress");
+        store
+            .release_lease_with(
+                &row.task_id,
+                "worker-1",
+                TransitionContext {
+                    reason: Some("worker stopped in state reviewing".to_string()),
+                    pr_number: Some(42),
+                    failure_reason: None,
+                },
+            )
+            .expect("release");
+        // The release reason stays on the task so `backlog list` shows why it
+        // is back in the queue.
+        assert_eq!(
+            store
+                .get_task(&row.task_id)
+                .expect("get")
+                .expect("task")
+                .status_note
+                .as_deref(),
+            Some("worker stopped in state reviewing")
+        );
+        store.claim_next("worker-2", 1).expect("claim").expect("task");
+        assert_eq!(store.recover_stale_leases(i64::MAX).expect("recover"), 1);
+        store.claim_next("worker-3", 60).expect("claim").expect("task");
+        store
+            .mark_unresolved_with(
+                &row.task_id,
+                "worker-3",
+                TransitionContext {
+                    failure_reason: Some("validation failed".to_string()),
+                    ..TransitionContext::default()
+                },
+            )
+            .expect("unresolved");
+
+        let history = store.history(&row.task_id).expect("history");
+        let steps = history
+            .iter()
+            .map(|event| {
+                (
+                    event.event_type.as_str(),
+                    event.from_status.as_deref(),
+                    event.to_status.as_str(),
+                    event.actor.as_str(),
+                )
+            })
+            .collect::<Vec<_>>();
+        assert_eq!(
+            steps,
+            vec![
+                ("created", None, "ready", "test"),
+                ("claimed", Some("ready"), "leased", "worker-1"),
+                ("in_progress", Some("leased"), "in_progress", "worker-1"),
+                ("released", Some("in_progress"), "ready", "worker-1"),
+                ("claimed", Some("ready"), "leased", "worker-2"),
+                ("lease_recovered", Some("leased"), "ready", "lease_recovery"),
+                ("claimed", Some("ready"), "leased", "worker-3"),
+                ("unresolved", Some("leased"), "unresolved", "worker-3"),
+            ]
+        );
+        assert_eq!(history[3].pr_number, Some(42));
+        assert_eq!(
+            history[3].reason.as_deref(),
+            Some("worker stopped in state reviewing")
+        );
+        assert_eq!(
+            history[5].reason.as_deref(),
+            Some("lease held by worker-2 expired")
+        );
+        assert_eq!(
+            history[7].failure_reason.as_deref(),
+            Some("validation failed")
+        );
+
+        // Owner mismatches change nothing and record nothing.
+        assert!(!store.mark_complete(&row.task_id, "intruder").expect("noop"));
+        assert_eq!(store.history(&row.task_id).expect("history").len(), 8);
+
+        drop(store);
+        let conn = Connection::open(dir.path().join("backlog.sqlite")).expect("open raw");
+        let update = conn
+            .execute("UPDATE task_events SET actor = 'someone else'", [])
+            .expect_err("update rejected");
+        assert!(update.to_string().contains("append-only"), "{update}");
+        let delete = conn
+            .execute("DELETE FROM task_events", [])
+            .expect_err("delete rejected");
+        assert!(delete.to_string().contains("append-only"), "{delete}");
+    }
+
+    #[test]
+    fn edits_carry_history_to_the_new_task_id() {
+        let (store, _dir) = temp_store();
+        let row = store
+            .upsert_task(task("original", Priority::P2))
+            .expect("seed");
+        assert!(store
+            .reprioritize_task(&row.task_id, Priority::P0)
+            .expect("reprioritize"));
+        let edited = store
+            .edit_task(
+                &row.task_id,
+                super::TaskEdit {
+                    title: Some("renamed".to_string()),
+                    ..super::TaskEdit::default()
+                },
+            )
+            .expect("edit")
+            .expect("row");
+        assert_ne!(edited.task_id, row.task_id);
+        assert!(store.history(&row.task_id).expect("old").is_empty());
+        let history = store.history(&edited.task_id).expect("history");
+        let types = history
+            .iter()
+            .map(|event| event.event_type.as_str())
+            .collect::<Vec<_>>();
+        assert_eq!(types, vec!["created", "reprioritized", "edited"]);
+        assert_eq!(history[1].reason.as_deref(), Some("P2 -> P0"));
+        assert_eq!(history[1].actor, "operator");
+        assert_eq!(
+            history[2].reason,
+            Some(format!("re-keyed from {}", row.task_id))
+        );
+    }
+    #[test]
+    fn jsonl_export_round_trips_full_rows() {
+        let (source, dir) = temp_store();
+        let base = source.upsert_task(task("base", Priority::P1)).expect("base");
+        source
+            .upsert_task(dependent("follow-up", &[&base.task_id]))
+            .expect("dependent");
+        let claimed = source.claim_next("worker-1", 600).expect("claim").expect("task");
+        assert!(source
+            .schedule_retry(
+                &claimed.task_id,
+                "worker-1",
+                super::system_time_unix() + 60_000,
+                TransitionContext {
+                    failure_reason: Some("gates failed".to_string()),
+                    ..TransitionContext::default()
+                },
+            )
+            .expect("retry"));
+        let exported = source.export_jsonl(&TaskFilter::default()).expect("export");
+        assert_eq!(exported.lines().count(), 2);
+
+        let target = BacklogStore::open(dir.path().join("target.sqlite")).expect("target");
+        let preview = target
+            .import_jsonl(&exported, ImportMode::Merge, true)
+            .expect("dry run");
+        assert!(!preview.applied);
+        assert_eq!(preview.count(ImportAction::Add), 2);
+        assert!(target.list_tasks().expect("list").is_empty());
+
+        let report = target
+            .import_jsonl(&exported, ImportMode::Merge, false)
+            .expect("import");
+        assert!(report.applied);
+        assert_eq!(
+            target.list_tasks().expect("list"),
+            source.list_tasks().expect("list")
+        );
+        let again = target
+            .import_jsonl(&exported, ImportMode::Merge, false)
+            .expect("reimport");
+        assert_eq!(again.count(ImportAction::Unchanged), 2);
+        let history = target.history(&claimed.task_id).expect("history");
+        assert_eq!(history.len(), 1);
+        assert_eq!(history[0].event_type, "imported");
+        assert_eq!(history[0].actor, "import");
+    }
+
+    #[test]
+    fn jsonl_import_modes_and_identity_checks() {
+        let (store, _dir) = temp_store();
+        let kept = store.upsert_task(task("kept", Priority::P2)).expect("kept");
+        let stale = store.upsert_task(task("stale", Priority::P2)).expect("stale");
+        let snapshot = store.export_jsonl(&TaskFilter::default()).expect("export");
+
+        // The stored copy of `stale` moves on after the export, so a merge keeps it.
+        thread::sleep(std::time::Duration::from_millis(5));
+        store
+            .reprioritize_task(&stale.task_id, Priority::P0)
+            .expect("reprioritize");
+        let extra = store
+            .import_issue(7, task("extra", Priority::P1))
+            .expect("import issue")
+            .expect("extra");
+        store
+            .record_outcome(NewTaskOutcome {
+                task_id: extra.task_id.clone(),
+                title: extra.title.clone(),
+                worker_id: "worker-1".to_string(),
+                attempt: 1,
+                final_state: "failed".to_string(),
+                pr_number: None,
+                branch: None,
+                merge_sha: None,
+                review_loops: 0,
+                failure_reason: None,
+                started_at: 1_000,
+                finished_at: 2_000,
+            })
+            .expect("outcome");
+        let merged = store
+            .import_jsonl(&snapshot, ImportMode::Merge, false)
+            .expect("merge");
+        let keep = merged
+            .changes
+            .iter()
+            .find(|change| change.task_id == stale.task_id)
+            .expect("stale change");
+        assert_eq!(keep.action, ImportAction::Keep);
+        assert!(keep.fields.contains(&"priority"));
+        assert_eq!(keep.note.as_deref(), Some("stored row is newer"));
+        assert!(store.get_task(&extra.task_id).expect("get").is_some());
+
+        let replaced = store
+            .import_jsonl(&snapshot, ImportMode::Replace, false)
+            .expect("replace");
+        assert_eq!(replaced.count(ImportAction::Update), 1);
+        assert_eq!(replaced.count(ImportAction::Remove), 1);
+        assert_eq!(
+            store
+                .get_task(&stale.task_id)
+                .expect("get")
+                .expect("row")
+                .priority,
+            Priority::P2
+        );
+        assert!(store.get_task(&extra.task_id).expect("get").is_none());
+        assert!(store.get_task(&kept.task_id).expect("get").is_some());
+        let history = store.history(&extra.task_id).expect("history");
+        let deleted = history.last().expect("event");
+        assert_eq!((deleted.event_type.as_str(), deleted.actor.as_str()), ("deleted", "import"));
+        assert!(store.issue_link_for_task(&extra.task_id).expect("link").is_none());
+        assert!(store.outcomes_for_task(&extra.task_id).expect("outcomes").is_empty());
+
+        let tampered = snapshot.replace("\"title\":\"kept\"", "\"title\":\"renamed\"");
+        let err = store
+            .import_jsonl(&tampered, ImportMode::Merge, true)
+            .expect_err("mismatched id");
+        assert!(err.to_string().contains("does not match"), "{err}");
+        let err = store
+            .import_jsonl("{not json}", ImportMode::Merge, true)
+            .expect_err("bad line");
+        assert!(err.to_string().contains("line 1"), "{err}");
+    }
+    #[test]
+    fn reworded_upserts_merge_into_open_near_duplicates() {
+        let (store, _dir) = temp_store();
+        let original = store
+            .upsert_task(NewTask {
+                details: String::new(),
+                ..task("Add tests for backlog store", Priority::P2)
+            })
+            .expect("original");
+        let reworded = store
+            .upsert_task(NewTask {
+                details: String::new(),
+                ..task("Add unit tests to backlog_store", Priority::P0)
+            })
+            .expect("reworded");
+        assert_eq!(reworded.task_id, original.task_id);
+        assert_eq!(reworded.title, "Add tests for backlog store");
+        assert_eq!(reworded.priority, Priority::P0);
+        assert_eq!(store.list_tasks().expect("list").len(), 1);
+        let history = store.history(&original.task_id).expect("history");
+        let merged = history.last().expect("event");
+        assert_eq!(merged.event_type, "merged_duplicate");
+        assert!(merged
+            .reason
+            .as_deref()
+            .is_some_and(|reason| reason.starts_with("near-duplicate \"Add unit tests")));
+
+        // Another scope is different work.
+        let elsewhere = store
+            .upsert_task(NewTask {
+                details: String::new(),
+                scope_key: "domain:tui".to_string(),
+                ..task("Add unit tests to backlog_store", Priority::P1)
+            })
+            .expect("other scope");
+        assert_ne!(elsewhere.task_id, original.task_id);
+
+        // So is another kind in the same scope.
+        let bugfix = store
+            .upsert_task(NewTask {
+                details: String::new(),
+                kind: TaskKind::Bugfix,
+                ..task("Add unit tests to backlog_store", Priority::P1)
+            })
+            .expect("other kind");
+        assert_ne!(bugfix.task_id, original.task_id);
+        assert_eq!(bugfix.kind, TaskKind::Bugfix);
+        let original_now = store.get_task(&original.task_id).expect("get").expect("row");
+        assert_eq!(original_now.kind, TaskKind::Feature);
+    }
+
+    #[test]
+    fn issue_imports_get_their_own_task_and_land_with_their_link_or_not_at_all() {
+        let (store, dir) = temp_store();
+        let existing = store
+            .upsert_task(NewTask {
+                details: String::new(),
+                ..task("Add tests for backlog store", Priority::P2)
+            })
+            .expect("existing");
+        let imported = store
+            .import_issue(
+                7,
+                NewTask {
+                    details: String::new(),
+                    ..task("Add unit tests to backlog_store", Priority::P1)
+                },
+            )
+            .expect("import")
+            .expect("new link");
+        assert_ne!(imported.task_id, existing.task_id);
+        assert_eq!(
+            store
+                .issue_link_for_task(&imported.task_id)
+                .expect("link")
+                .map(|link| link.issue_number),
+            Some(7)
+        );
+        assert!(store
+            .issue_link_for_task(&existing.task_id)
+            .expect("link")
+            .is_none());
+
+        // A link that cannot be written takes the task down with it.
+        Connection::open(dir.path().join("backlog.sqlite"))
+            .expect("raw")
+            .execute_batch(
+                "CREATE TRIGGER reject_links BEFORE INSERT ON github_issue_links
+                 BEGIN SELECT RAISE(ABORT, 'link rejected'); END;",
+            )
+            .expect("trigger");
+        store
+            .import_issue(8, task("Handle rate limits", Priority::P1))
+            .expect_err("link insert fails");
+        assert_eq!(store.list_tasks().expect("list").len(), 2);
+    }
+
+    #[test]
+    fn dedupe_clusters_fold_duplicates_and_their_edges_into_the_keeper() {
+        let (store, _dir) = temp_store();
+        let first = store
+            .upsert_task(NewTask {
+                details: "explain backoff".to_string(),
+                ..task("Document retry backoff", Priority::P1)
+            })
+            .expect("first");
+        let second = store
+            .upsert_task(NewTask {
+                details: "unrelated wording here".to_string(),
+                ..task("Document the retry backoffs", Priority::P0)
+            })
+            .expect("distinct details");
+        assert_ne!(second.task_id, first.task_id);
+        let follow_up = store
+            .upsert_task(dependent("publish docs", &[&first.task_id]))
+            .expect("dependent");
+        assert!(store.find_duplicate_clusters().expect("clusters").is_empty());
+
+        store
+            .edit_task(
+                &second.task_id,
+                super::TaskEdit {
+                    details: Some("explain backoff settings".to_string()),
+                    ..super::TaskEdit::default()
+                },
+            )
+            .expect("edit")
+            .expect("row");
+        // The more urgent task keeps its id; the other folds into it.
+        let clusters = store.find_duplicate_clusters().expect("clusters");
+        assert_eq!(clusters.len(), 1);
+        assert_eq!(clusters[0].keeper.task_id, second.task_id);
+        assert_eq!(clusters[0].duplicates.len(), 1);
+        assert_eq!(clusters[0].duplicates[0].0.task_id, first.task_id);
+
+        assert!(store
+            .merge_duplicate(&second.task_id, &first.task_id)
+            .expect("merge"));
+        let retired = store.get_task(&first.task_id).expect("get").expect("row");
+        assert_eq!(retired.status, TaskStatus::Cancelled);
+        assert_eq!(
+            retired.status_note,
+            Some(format!("duplicate of {}", second.task_id))
+        );
+        let follow_up = store.get_task(&follow_up.task_id).expect("get").expect("row");
+        assert_eq!(follow_up.blocked_by, vec![second.task_id.clone()]);
+        assert!(store.find_duplicate_clusters().expect("clusters").is_empty());
+        assert!(!store
+            .merge_duplicate(&second.task_id, &first.task_id)
+            .expect("already merged"));
+    }
+
+    #[test]
+    fn search_ranks_title_hits_first_and_tracks_edits() {
+        let (store, _dir) = temp_store();
+        let in_details = store
+            .upsert_task(NewTask {
+                details: "the flaky retry test times out on CI".to_string(),
+                ..task("Stabilize worker pool", Priority::P0)
+            })
+            .expect("details hit");
+        let in_title = store
+            .upsert_task(NewTask {
+                kind: TaskKind::Bugfix,
+                details: "reproduce locally first".to_string(),
+                ..task("Fix flaky tests in scheduler", Priority::P2)
+            })
+            .expect("title hit");
+        store
+            .upsert_task(task("Document config loading", Priority::P1))
+            .expect("unrelated");
+
+        let ids = |tasks: Vec<BacklogTask>| {
+            tasks.into_iter().map(|task| task.task_id).collect::<Vec<_>>()
+        };
+        let all = TaskFilter::default();
+        assert_eq!(
+            ids(store.search("flaky test", &all).expect("search")),
+            vec![in_title.task_id.clone(), in_details.task_id.clone()]
+        );
+        assert_eq!(
+            ids(store.search("FLAK", &all).expect("prefix")),
+            vec![in_title.task_id.clone(), in_details.task_id.clone()]
+        );
+        let bugfixes = TaskFilter {
+            kinds: vec![TaskKind::Bugfix],
+            ..TaskFilter::default()
+        };
+        assert_eq!(
+            ids(store.search("flaky", &bugfixes).expect("filtered")),
+            vec![in_title.task_id]
+        );
+        assert!(store.search("  \"NOT:  ", &all).expect("no words").is_empty());
+
+        store
+            .edit_task(
+                &in_details.task_id,
+                TaskEdit {
+                    rationale: Some("blocks the nightly release".to_string()),
+                    ..TaskEdit::default()
+                },
+            )
+            .expect("edit")
+            .expect("exists");
+        assert_eq!(
+            ids(store.search("nightly", &all).expect("edited")),
+            vec![in_details.task_id]
+        );
+    }
+
+    #[test]
+    fn cancelled_tasks_keep_their_reason_until_requeued() {
+        let (store, _dir) = temp_store();
+        let prerequisite = store
+            .upsert_task(task("Migrate legacy config", Priority::P1))
+            .expect("prerequisite");
+        let dependent = store
+            .upsert_task(NewTask {
+                blocked_by: vec![prerequisite.task_id.clone()],
+                ..task("Delete legacy loader", Priority::P1)
+            })
+            .expect("dependent");
+        let dropped = store
+            .upsert_task(NewTask {
+                blocked_by: vec![prerequisite.task_id.clone()],
+                ..task("Document legacy loader", Priority::P0)
+            })
+            .expect("dropped dependent");
+        assert!(store.cancel_task(&dropped.task_id, "docs are moving").expect("cancel"));
+        assert_eq!(store.count_tasks_by_priority().expect("counts"), (0, 2, 0));
+
+        let err = store
+            .cancel_task(&prerequisite.task_id, "   ")
+            .expect_err("blank reason");
+        assert!(err.to_string().contains("requires a reason"), "{err}");
+        assert!(store
+            .cancel_task(&prerequisite.task_id, "won't fix: config stays")
+            .expect("cancel"));
+        let cancelled = store
+            .get_task(&prerequisite.task_id)
+            .expect("get")
+            .expect("row");
+        assert_eq!(cancelled.status, TaskStatus::Cancelled);
+        assert_eq!(cancelled.status_note.as_deref(), Some("won't fix: config stays"));
+        assert_eq!(
+            store
+                .get_task(&dependent.task_id)
+                .expect("get")
+                .expect("row")
+                .blocked_reason,
+            Some(format!("prerequisite {} was cancelled", prerequisite.task_id))
+        );
+        // Finished dependents are not blocked, and cancelled work is not counted.
+        assert_eq!(
+            store
+                .get_task(&dropped.task_id)
+                .expect("get")
+                .expect("row")
+                .blocked_reason,
+            None
+        );
+        assert_eq!(store.count_tasks_by_priority().expect("counts"), (0, 1, 0));
+
+        // Rediscovering the task does not reopen it.
+        store
+            .upsert_task(task("Migrate legacy config", Priority::P0))
+            .expect("re-upsert");
+        let still = store
+            .get_task(&prerequisite.task_id)
+            .expect("get")
+            .expect("row");
+        assert_eq!(still.status, TaskStatus::Cancelled);
+        assert_eq!(still.status_note, cancelled.status_note);
+
+        assert!(store.requeue_task(&prerequisite.task_id).expect("requeue"));
+        let requeued = store
+            .get_task(&prerequisite.task_id)
+            .expect("get")
+            .expect("row");
+        assert_eq!(requeued.status, TaskStatus::Ready);
+        assert_eq!(requeued.status_note, None);
+    }
+
+    #[test]
+    fn archival_moves_old_finished_tasks_and_reserves_their_ids() {
+        let (store, dir) = temp_store();
+        let done = store
+            .upsert_task(NewTask {
+                related_pr: Some(42),
+                ..task("Ship search", Priority::P1)
+            })
+            .expect("done");
+        let claimed = store.claim_next("worker", 60).expect("claim").expect("task");
+        assert_eq!(claimed.task_id, done.task_id);
+        assert!(store.mark_complete(&done.task_id, "worker").expect("complete"));
+        let dropped = store
+            .upsert_task(task("Rewrite in another language", Priority::P2))
+            .expect("dropped");
+        assert!(store.cancel_task(&dropped.task_id, "not worth it").expect("cancel"));
+        let blocker = store
+            .upsert_task(task("Old approach", Priority::P2))
+            .expect("blocker");
+        let waiting = store
+            .upsert_task(NewTask {
+                blocked_by: vec![blocker.task_id.clone()],
+                ..task("Follow old approach", Priority::P2)
+            })
+            .expect("waiting");
+        assert!(store.cancel_task(&blocker.task_id, "superseded").expect("cancel"));
+        let dead_end = store
+            .upsert_task(task("Older idea", Priority::P2))
+            .expect("dead end");
+        let gave_up = store
+            .upsert_task(NewTask {
+                blocked_by: vec![dead_end.task_id.clone()],
+                ..task("Follow older idea", Priority::P2)
+            })
+            .expect("gave up");
+        assert!(store.cancel_task(&dead_end.task_id, "abandoned").expect("cancel"));
+        let follow_up = store
+            .upsert_task(NewTask {
+                blocked_by: vec![done.task_id.clone()],
+                ..task("Tune search ranking", Priority::P2)
+            })
+            .expect("follow-up");
+
+        // Nothing is old enough yet.
+        assert!(store.archive_finished_tasks(30).expect("archive").is_empty());
+
+        drop(store);
+        let db = dir.path().join("backlog.sqlite");
+        let raw = Connection::open(&db).expect("raw");
+        raw.execute("UPDATE backlog_tasks SET last_updated = 0", [])
+            .expect("age rows");
+        raw.execute(
+            "UPDATE backlog_tasks SET status = 'exhausted' WHERE task_id = ?1",
+            [&gave_up.task_id],
+        )
+        .expect("exhaust dependent");
+        drop(raw);
+        let store = BacklogStore::open(&db).expect("reopen");
+
+        let mut archived = store.archive_finished_tasks(30).expect("archive");
+        archived.sort();
+        let mut expected = vec![
+            done.task_id.clone(),
+            dropped.task_id.clone(),
+            dead_end.task_id,
+        ];
+        expected.sort();
+        assert_eq!(archived, expected, "a cancelled prerequisite of open work stays live");
+        assert!(store.get_task(&done.task_id).expect("get").is_none());
+        assert!(store.get_task(&blocker.task_id).expect("get").is_some());
+        assert_eq!(
+            store
+                .get_archived_task(&dropped.task_id)
+                .expect("archived")
+                .expect("row")
+                .status_note
+                .as_deref(),
+            Some("not worth it")
+        );
+        assert!(store.search("search", &TaskFilter::default()).expect("search").iter().all(
+            |task| task.task_id != done.task_id
+        ));
+        let history = store.history(&done.task_id).expect("history");
+        let archived_event = history
+            .iter()
+            .find(|event| event.event_type == "archived")
+            .expect("archived event");
+        assert_eq!(archived_event.pr_number, Some(42));
+
+        // The archived prerequisite was complete, so its dependent is claimable.
+        let next = store.claim_next("worker", 60).expect("claim").expect("task");
+        assert_eq!(next.task_id, follow_up.task_id);
+        assert_ne!(next.task_id, waiting.task_id);
+
+        // Re-adding archived work lands on the archived row instead of a new task.
+        let again = store
+            .upsert_task(NewTask {
+                related_pr: Some(42),
+                ..task("Ship search", Priority::P0)
+            })
+            .expect("re-upsert");
+        assert_eq!(again.task_id, done.task_id);
+        assert_eq!(again.status, TaskStatus::Complete);
+        assert!(store.get_task(&done.task_id).expect("get").is_none());
+    }
+
+    #[test]
+    fn outcomes_are_queryable_per_task_and_run_and_follow_re_keys() {
+        let (store, dir) = temp_store();
+        let row = store
+            .upsert_task(task("Ship outcomes", Priority::P1))
+            .expect("upsert");
+        let outcome = |final_state: &str, pr_number, merge_sha: Option<&str>| NewTaskOutcome {
+            task_id: row.task_id.clone(),
+            title: row.title.clone(),
+            worker_id: "worker-1".to_string(),
+            attempt: 1,
+            final_state: final_state.to_string(),
+            pr_number,
+            branch: Some("gardener/ship-outcomes".to_string()),
+            merge_sha: merge_sha.map(str::to_string),
+            review_loops: 2,
+            failure_reason: None,
+            started_at: 1_000,
+            finished_at: 61_000,
+        };
+        let first = store
+            .record_outcome(outcome("failed", None, None))
+            .expect("record");
+        let second = store
+            .record_outcome(outcome("complete", Some(42), Some("abc123")))
+            .expect("record");
+        assert!(second > first);
+
+        let recorded = store.outcomes_for_task(&row.task_id).expect("outcomes");
+        assert_eq!(recorded.len(), 2);
+        assert_eq!(recorded[1].pr_number, Some(42));
+        assert_eq!(recorded[1].merge_sha.as_deref(), Some("abc123"));
+        assert_eq!(recorded[1].duration_ms, 60_000);
+
+        drop(store);
+        let db = dir.path().join("backlog.sqlite");
+        Connection::open(&db)
+            .expect("raw")
+            .execute(
+                "UPDATE task_outcomes SET run_id = 'run-a' WHERE outcome_id = ?1",
+                [second],
+            )
+            .expect("stamp run");
+        let store = BacklogStore::open(&db).expect("reopen");
+        let run = store.outcomes_for_run("run-a").expect("run outcomes");
+        assert_eq!(run.len(), 1);
+        assert_eq!(run[0].final_state, "complete");
+        assert!(store.outcomes_for_run("run-b").expect("run outcomes").is_empty());
+
+        let renamed = store
+            .edit_task(
+                &row.task_id,
+                TaskEdit {
+                    title: Some("Ship outcome records".to_string()),
+                    ..TaskEdit::default()
+                },
+            )
+            .expect("edit")
+            .expect("row");
+        assert_ne!(renamed.task_id, row.task_id);
+        assert_eq!(store.outcomes_for_task(&renamed.task_id).expect("outcomes").len(), 2);
+        assert!(store.outcomes_for_task(&row.task_id).expect("outcomes").is_empty());
+    }
 }
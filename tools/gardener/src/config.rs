@@ -1,6 +1,8 @@
 use crate::errors::GardenerError;
 use crate::logging::append_run_log;
+use crate::priority::PriorityAging;
 use crate::runtime::{FileSystem, ProcessRequest, ProcessRunner};
+use crate::task_identity::TaskKind;
 use crate::types::{AgentKind, RuntimeScope, ValidationCommandResolution, WorkerState};
 use serde::{Deserialize, Serialize};
 use serde_json::json;
@@ -24,6 +26,8 @@ pub struct CliOverrides {
     pub retriage: bool,
     pub triage_only: bool,
     pub sync_only: bool,
+    pub max_cost_usd: Option<f64>,
+    pub max_tokens: Option<u64>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
@@ -35,12 +39,19 @@ pub struct AppConfig {
     pub agent: AgentConfig,
     pub states: BTreeMap<String, StateConfig>,
     pub scheduler: SchedulerConfig,
+    pub retry: RetryConfig,
     pub prompts: PromptsConfig,
     pub learning: LearningConfig,
     pub seeding: SeedingConfig,
     pub execution: ExecutionConfig,
     pub triage: TriageConfig,
     pub quality_report: QualityReportConfig,
+    pub github_issues: GithubIssuesConfig,
+    pub watch: WatchConfig,
+    /// Prices per million tokens, keyed by model id.
+    pub pricing: BTreeMap<String, ModelPrice>,
+    pub budget: BudgetConfig,
+    pub merge_queue: MergeQueueConfig,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
@@ -62,11 +73,21 @@ pub struct StartupConfig {
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
 pub struct ValidationConfig {
     pub command: String,
+    /// The post-merge validation command is killed after this many seconds.
+    pub timeout_seconds: u64,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
 pub struct AgentConfig {
     pub default: Option<AgentKind>,
+    /// Most agent turns that may run at once per backend (`codex`, `claude`).
+    /// Backends that are not listed are only bounded by the worker count.
+    #[serde(default)]
+    pub concurrency: BTreeMap<String, u32>,
+    /// Most agent turns that may run at once per model, on top of the
+    /// backend cap.
+    #[serde(default)]
+    pub model_concurrency: BTreeMap<String, u32>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
@@ -79,11 +100,69 @@ pub struct StateConfig {
 pub struct SchedulerConfig {
     pub lease_timeout_seconds: u64,
     pub heartbeat_interval_seconds: u64,
+    /// Per-kind scope exclusivity, keyed by kind (`bugfix`, `feature`, ...).
+    /// Kinds that are not listed may run alongside anything sharing their scope.
+    pub scope_exclusivity: BTreeMap<String, ScopeExclusivity>,
+    /// Separates the levels of a hierarchical `scope_key` such as `tui:dashboard`.
+    pub scope_separator: String,
+    pub priority_aging: PriorityAging,
+}
+
+impl SchedulerConfig {
+    pub fn scope_exclusivity_for(&self, kind: TaskKind) -> ScopeExclusivity {
+        self.scope_exclusivity
+            .get(kind.as_str())
+            .copied()
+            .unwrap_or_default()
+    }
+}
+
+/// Whether a task holds its `scope_key` exclusively while leased or in progress.
+/// `Exact` only holds back tasks with the same key; `Prefix` also holds back
+/// tasks whose key is an ancestor or descendant in the separator hierarchy.
+#[derive(
+    Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Default, Serialize, Deserialize,
+)]
+#[serde(rename_all = "snake_case")]
+pub enum ScopeExclusivity {
+    #[default]
+    Off,
+    Exact,
+    Prefix,
+}
+
+/// How failed tasks are retried. Attempt `n` (1-based) that fails is retried
+/// after `backoff_base_seconds * 2^(n-1)`, capped at `backoff_max_seconds`, until
+/// the kind's attempt limit is reached and the task becomes `exhausted`.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct RetryConfig {
+    pub max_attempts: u32,
+    pub backoff_base_seconds: u64,
+    pub backoff_max_seconds: u64,
+    /// Per-kind overrides of `max_attempts`, keyed by kind (`bugfix`, `merge_conflict`, ...).
+    pub max_attempts_by_kind: BTreeMap<String, u32>,
+}
+
+impl RetryConfig {
+    pub fn max_attempts_for(&self, kind: TaskKind) -> u32 {
+        self.max_attempts_by_kind
+            .get(kind.as_str())
+            .copied()
+            .unwrap_or(self.max_attempts)
+    }
+
+    pub fn backoff_seconds(&self, failed_attempts: i64) -> u64 {
+        let exponent = failed_attempts.saturating_sub(1).clamp(0, 32) as u32;
+        self.backoff_base_seconds
+            .saturating_mul(2_u64.saturating_pow(exponent))
+            .min(self.backoff_max_seconds)
+    }
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
 pub struct PromptsConfig {
     pub turn_budget: TurnBudgetConfig,
+    pub turn_timeout_seconds: TurnTimeoutConfig,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
@@ -96,6 +175,18 @@ pub struct TurnBudgetConfig {
     pub merging: u32,
 }
 
+/// Wall-clock seconds an agent turn may run in each state before its process
+/// group is killed and the task is released for a retry.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct TurnTimeoutConfig {
+    pub understand: u64,
+    pub planning: u64,
+    pub doing: u64,
+    pub gitting: u64,
+    pub reviewing: u64,
+    pub merging: u64,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
 pub struct LearningConfig {
     pub confidence_decay_per_day: f64,
@@ -130,6 +221,84 @@ pub struct QualityReportConfig {
     pub stale_if_head_commit_differs: bool,
 }
 
+/// Imports open GitHub issues carrying `label` as backlog tasks at startup. When
+/// an imported task's PR merges, the issue is closed or commented on. Tasks get
+/// `scope_key` unless the issue carries a `scope:<key>` label.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct GithubIssuesConfig {
+    pub enabled: bool,
+    pub label: String,
+    pub limit: u32,
+    pub on_merge: IssueMergeAction,
+    pub scope_key: String,
+}
+
+/// Long-running `--watch` mode: how long to wait between backlog polls while
+/// there is no work, how often to re-run the startup audits, and where and how
+/// often to write the health file.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct WatchConfig {
+    pub idle_backoff_seconds: u64,
+    pub max_idle_backoff_seconds: u64,
+    pub audit_interval_seconds: u64,
+    pub health_path: String,
+    pub health_interval_seconds: u64,
+}
+
+/// What a model costs in USD per million tokens. A turn on a model without a
+/// price costs what its backend reported, or nothing.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq)]
+pub struct ModelPrice {
+    pub input_per_mtok: f64,
+    /// Rate for cached input tokens; they cost `input_per_mtok` when unset.
+    #[serde(default)]
+    pub cached_input_per_mtok: Option<f64>,
+    pub output_per_mtok: f64,
+}
+
+/// Run budgets. Once the run's agent turns reach either one, the pool stops
+/// claiming and drains.
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq)]
+pub struct BudgetConfig {
+    pub max_cost_usd: Option<f64>,
+    pub max_tokens: Option<u64>,
+}
+
+/// How PRs ready to merge take their turn. `batch_size` above one lets PRs
+/// that change disjoint files merge together in one turn.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct MergeQueueConfig {
+    pub order: MergeQueueOrder,
+    pub batch_size: u32,
+}
+
+impl Default for MergeQueueConfig {
+    fn default() -> Self {
+        Self {
+            order: MergeQueueOrder::Fifo,
+            batch_size: 1,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum MergeQueueOrder {
+    /// In the order PRs became ready.
+    #[default]
+    Fifo,
+    /// By task priority, then in the order PRs became ready.
+    Priority,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum IssueMergeAction {
+    #[default]
+    Close,
+    Comment,
+}
+
 impl Default for AppConfig {
     fn default() -> Self {
         Self {
@@ -141,14 +310,29 @@ impl Default for AppConfig {
             },
             validation: ValidationConfig {
                 command: "npm run validate".to_string(),
+                timeout_seconds: 1800,
             },
             agent: AgentConfig {
                 default: Some(AgentKind::Codex),
+                concurrency: BTreeMap::new(),
+                model_concurrency: BTreeMap::new(),
             },
             states: BTreeMap::new(),
             scheduler: SchedulerConfig {
                 lease_timeout_seconds: 900,
                 heartbeat_interval_seconds: 15,
+                scope_exclusivity: BTreeMap::new(),
+                scope_separator: ":".to_string(),
+                priority_aging: PriorityAging {
+                    p2_to_p1_seconds: 86_400,
+                    p1_to_p0_seconds: 259_200,
+                },
+            },
+            retry: RetryConfig {
+                max_attempts: 3,
+                backoff_base_seconds: 60,
+                backoff_max_seconds: 3600,
+                max_attempts_by_kind: BTreeMap::new(),
             },
             prompts: PromptsConfig {
                 turn_budget: TurnBudgetConfig {
@@ -159,6 +343,14 @@ impl Default for AppConfig {
                     reviewing: 100,
                     merging: 100,
                 },
+                turn_timeout_seconds: TurnTimeoutConfig {
+                    understand: 900,
+                    planning: 1800,
+                    doing: 3600,
+                    gitting: 900,
+                    reviewing: 1800,
+                    merging: 1800,
+                },
             },
             learning: LearningConfig {
                 confidence_decay_per_day: 0.01,
@@ -184,6 +376,23 @@ impl Default for AppConfig {
                 stale_after_days: 7,
                 stale_if_head_commit_differs: true,
             },
+            github_issues: GithubIssuesConfig {
+                enabled: false,
+                label: "gardener".to_string(),
+                limit: 100,
+                on_merge: IssueMergeAction::Close,
+                scope_key: "runtime".to_string(),
+            },
+            watch: WatchConfig {
+                idle_backoff_seconds: 5,
+                max_idle_backoff_seconds: 300,
+                audit_interval_seconds: 3600,
+                health_path: ".cache/gardener/watch-health.json".to_string(),
+                health_interval_seconds: 30,
+            },
+            pricing: BTreeMap::new(),
+            budget: BudgetConfig::default(),
+            merge_queue: MergeQueueConfig::default(),
         }
     }
 }
@@ -197,12 +406,18 @@ struct PartialAppConfig {
     agent: Option<AgentConfig>,
     states: Option<BTreeMap<String, StateConfig>>,
     scheduler: Option<PartialSchedulerConfig>,
+    retry: Option<PartialRetryConfig>,
     prompts: Option<PartialPromptsConfig>,
     learning: Option<PartialLearningConfig>,
     seeding: Option<PartialSeedingConfig>,
     execution: Option<PartialExecutionConfig>,
     triage: Option<PartialTriageConfig>,
     quality_report: Option<PartialQualityReportConfig>,
+    github_issues: Option<PartialGithubIssuesConfig>,
+    watch: Option<PartialWatchConfig>,
+    pricing: Option<BTreeMap<String, ModelPrice>>,
+    budget: Option<PartialBudgetConfig>,
+    merge_queue: Option<PartialMergeQueueConfig>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, Default)]
@@ -224,17 +439,36 @@ struct PartialStartupConfig {
 #[derive(Debug, Clone, Serialize, Deserialize, Default)]
 struct PartialValidationConfig {
     command: Option<String>,
+    timeout_seconds: Option<u64>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, Default)]
 struct PartialSchedulerConfig {
     lease_timeout_seconds: Option<u64>,
     heartbeat_interval_seconds: Option<u64>,
+    scope_exclusivity: Option<BTreeMap<String, ScopeExclusivity>>,
+    scope_separator: Option<String>,
+    priority_aging: Option<PartialPriorityAgingConfig>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+struct PartialPriorityAgingConfig {
+    p2_to_p1_seconds: Option<u64>,
+    p1_to_p0_seconds: Option<u64>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+struct PartialRetryConfig {
+    max_attempts: Option<u32>,
+    backoff_base_seconds: Option<u64>,
+    backoff_max_seconds: Option<u64>,
+    max_attempts_by_kind: Option<BTreeMap<String, u32>>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, Default)]
 struct PartialPromptsConfig {
     turn_budget: Option<PartialTurnBudgetConfig>,
+    turn_timeout_seconds: Option<PartialTurnTimeoutConfig>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, Default)]
@@ -247,6 +481,16 @@ struct PartialTurnBudgetConfig {
     merging: Option<u32>,
 }
 
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+struct PartialTurnTimeoutConfig {
+    understand: Option<u64>,
+    planning: Option<u64>,
+    doing: Option<u64>,
+    gitting: Option<u64>,
+    reviewing: Option<u64>,
+    merging: Option<u64>,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize, Default)]
 struct PartialLearningConfig {
     confidence_decay_per_day: Option<f64>,
@@ -281,6 +525,36 @@ struct PartialQualityReportConfig {
     stale_if_head_commit_differs: Option<bool>,
 }
 
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+struct PartialGithubIssuesConfig {
+    enabled: Option<bool>,
+    label: Option<String>,
+    limit: Option<u32>,
+    on_merge: Option<IssueMergeAction>,
+    scope_key: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+struct PartialWatchConfig {
+    idle_backoff_seconds: Option<u64>,
+    max_idle_backoff_seconds: Option<u64>,
+    audit_interval_seconds: Option<u64>,
+    health_path: Option<String>,
+    health_interval_seconds: Option<u64>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+struct PartialBudgetConfig {
+    max_cost_usd: Option<f64>,
+    max_tokens: Option<u64>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+struct PartialMergeQueueConfig {
+    order: Option<MergeQueueOrder>,
+    batch_size: Option<u32>,
+}
+
 pub fn load_config(
     overrides: &CliOverrides,
     process_cwd: &Path,
@@ -415,6 +689,9 @@ fn merge_partial_config(cfg: &mut AppConfig, partial: PartialAppConfig) {
         if let Some(command) = validation.command {
             cfg.validation.command = command;
         }
+        if let Some(value) = validation.timeout_seconds {
+            cfg.validation.timeout_seconds = value;
+        }
     }
 
     if let Some(agent) = partial.agent {
@@ -432,6 +709,35 @@ fn merge_partial_config(cfg: &mut AppConfig, partial: PartialAppConfig) {
         if let Some(value) = scheduler.heartbeat_interval_seconds {
             cfg.scheduler.heartbeat_interval_seconds = value;
         }
+        if let Some(value) = scheduler.scope_exclusivity {
+            cfg.scheduler.scope_exclusivity.extend(value);
+        }
+        if let Some(value) = scheduler.scope_separator {
+            cfg.scheduler.scope_separator = value;
+        }
+        if let Some(aging) = scheduler.priority_aging {
+            if let Some(value) = aging.p2_to_p1_seconds {
+                cfg.scheduler.priority_aging.p2_to_p1_seconds = value;
+            }
+            if let Some(value) = aging.p1_to_p0_seconds {
+                cfg.scheduler.priority_aging.p1_to_p0_seconds = value;
+            }
+        }
+    }
+
+    if let Some(retry) = partial.retry {
+        if let Some(value) = retry.max_attempts {
+            cfg.retry.max_attempts = value;
+        }
+        if let Some(value) = retry.backoff_base_seconds {
+            cfg.retry.backoff_base_seconds = value;
+        }
+        if let Some(value) = retry.backoff_max_seconds {
+            cfg.retry.backoff_max_seconds = value;
+        }
+        if let Some(value) = retry.max_attempts_by_kind {
+            cfg.retry.max_attempts_by_kind.extend(value);
+        }
     }
 
     if let Some(prompts) = partial.prompts {
@@ -455,6 +761,26 @@ fn merge_partial_config(cfg: &mut AppConfig, partial: PartialAppConfig) {
                 cfg.prompts.turn_budget.merging = value;
             }
         }
+        if let Some(timeouts) = prompts.turn_timeout_seconds {
+            if let Some(value) = timeouts.understand {
+                cfg.prompts.turn_timeout_seconds.understand = value;
+            }
+            if let Some(value) = timeouts.planning {
+                cfg.prompts.turn_timeout_seconds.planning = value;
+            }
+            if let Some(value) = timeouts.doing {
+                cfg.prompts.turn_timeout_seconds.doing = value;
+            }
+            if let Some(value) = timeouts.gitting {
+                cfg.prompts.turn_timeout_seconds.gitting = value;
+            }
+            if let Some(value) = timeouts.reviewing {
+                cfg.prompts.turn_timeout_seconds.reviewing = value;
+            }
+            if let Some(value) = timeouts.merging {
+                cfg.prompts.turn_timeout_seconds.merging = value;
+            }
+        }
     }
 
     if let Some(learning) = partial.learning {
@@ -513,6 +839,64 @@ fn merge_partial_config(cfg: &mut AppConfig, partial: PartialAppConfig) {
             cfg.quality_report.stale_if_head_commit_differs = value;
         }
     }
+
+    if let Some(issues) = partial.github_issues {
+        if let Some(value) = issues.enabled {
+            cfg.github_issues.enabled = value;
+        }
+        if let Some(value) = issues.label {
+            cfg.github_issues.label = value;
+        }
+        if let Some(value) = issues.limit {
+            cfg.github_issues.limit = value;
+        }
+        if let Some(value) = issues.on_merge {
+            cfg.github_issues.on_merge = value;
+        }
+        if let Some(value) = issues.scope_key {
+            cfg.github_issues.scope_key = value;
+        }
+    }
+
+    if let Some(watch) = partial.watch {
+        if let Some(value) = watch.idle_backoff_seconds {
+            cfg.watch.idle_backoff_seconds = value;
+        }
+        if let Some(value) = watch.max_idle_backoff_seconds {
+            cfg.watch.max_idle_backoff_seconds = value;
+        }
+        if let Some(value) = watch.audit_interval_seconds {
+            cfg.watch.audit_interval_seconds = value;
+        }
+        if let Some(value) = watch.health_path {
+            cfg.watch.health_path = value;
+        }
+        if let Some(value) = watch.health_interval_seconds {
+            cfg.watch.health_interval_seconds = value;
+        }
+    }
+
+    if let Some(pricing) = partial.pricing {
+        cfg.pricing.extend(pricing);
+    }
+
+    if let Some(budget) = partial.budget {
+        if let Some(value) = budget.max_cost_usd {
+            cfg.budget.max_cost_usd = Some(value);
+        }
+        if let Some(value) = budget.max_tokens {
+            cfg.budget.max_tokens = Some(value);
+        }
+    }
+
+    if let Some(merge_queue) = partial.merge_queue {
+        if let Some(value) = merge_queue.order {
+            cfg.merge_queue.order = value;
+        }
+        if let Some(value) = merge_queue.batch_size {
+            cfg.merge_queue.batch_size = value;
+        }
+    }
 }
 
 fn apply_cli_overrides(cfg: &mut AppConfig, overrides: &CliOverrides) {
@@ -525,6 +909,12 @@ fn apply_cli_overrides(cfg: &mut AppConfig, overrides: &CliOverrides) {
     if let Some(validation_command) = &overrides.validation_command {
         cfg.validation.command = validation_command.clone();
     }
+    if let Some(max_cost_usd) = overrides.max_cost_usd {
+        cfg.budget.max_cost_usd = Some(max_cost_usd);
+    }
+    if let Some(max_tokens) = overrides.max_tokens {
+        cfg.budget.max_tokens = Some(max_tokens);
+    }
 }
 
 pub fn resolve_scope(
@@ -577,6 +967,7 @@ fn detect_repo_root(process_cwd: &Path, process_runner: &dyn ProcessRunner) -> O
             program: "git".to_string(),
             args: vec!["rev-parse".to_string(), "--show-toplevel".to_string()],
             cwd: Some(process_cwd.to_path_buf()),
+            timeout: None,
         })
         .ok()?;
 
@@ -708,6 +1099,156 @@ fn validate_config(cfg: &AppConfig) -> Result<(), GardenerError> {
         }
     }
 
+    for (backend, cap) in &cfg.agent.concurrency {
+        if ![AgentKind::Claude, AgentKind::Codex]
+            .iter()
+            .any(|known| known.as_str() == backend)
+        {
+            return Err(GardenerError::InvalidConfig(format!(
+                "agent.concurrency.{backend} is not an agent backend"
+            )));
+        }
+        if *cap == 0 {
+            return Err(GardenerError::InvalidConfig(format!(
+                "agent.concurrency.{backend} must be greater than zero"
+            )));
+        }
+    }
+    for (model, cap) in &cfg.agent.model_concurrency {
+        if *cap == 0 {
+            return Err(GardenerError::InvalidConfig(format!(
+                "agent.model_concurrency.{model} must be greater than zero"
+            )));
+        }
+    }
+
+    for kind in cfg.scheduler.scope_exclusivity.keys() {
+        if !TaskKind::ALL.iter().any(|known| known.as_str() == kind) {
+            return Err(GardenerError::InvalidConfig(format!(
+                "scheduler.scope_exclusivity.{kind} is not a task kind"
+            )));
+        }
+    }
+
+    if cfg.scheduler.scope_separator.is_empty() {
+        return Err(GardenerError::InvalidConfig(
+            "scheduler.scope_separator must not be empty".to_string(),
+        ));
+    }
+
+    if cfg.retry.max_attempts == 0 {
+        return Err(GardenerError::InvalidConfig(
+            "retry.max_attempts must be greater than zero".to_string(),
+        ));
+    }
+
+    for (kind, attempts) in &cfg.retry.max_attempts_by_kind {
+        if !TaskKind::ALL.iter().any(|known| known.as_str() == kind) {
+            return Err(GardenerError::InvalidConfig(format!(
+                "retry.max_attempts_by_kind.{kind} is not a task kind"
+            )));
+        }
+        if *attempts == 0 {
+            return Err(GardenerError::InvalidConfig(format!(
+                "retry.max_attempts_by_kind.{kind} must be greater than zero"
+            )));
+        }
+    }
+
+    if cfg.retry.backoff_base_seconds > cfg.retry.backoff_max_seconds {
+        return Err(GardenerError::InvalidConfig(
+            "retry.backoff_base_seconds must not exceed retry.backoff_max_seconds".to_string(),
+        ));
+    }
+
+    let timeouts = &cfg.prompts.turn_timeout_seconds;
+    if [
+        timeouts.understand,
+        timeouts.planning,
+        timeouts.doing,
+        timeouts.gitting,
+        timeouts.reviewing,
+        timeouts.merging,
+        cfg.validation.timeout_seconds,
+    ]
+    .contains(&0)
+    {
+        return Err(GardenerError::InvalidConfig(
+            "prompts.turn_timeout_seconds and validation.timeout_seconds must be greater than zero"
+                .to_string(),
+        ));
+    }
+
+    if cfg.github_issues.enabled
+        && (cfg.github_issues.label.trim().is_empty()
+            || cfg.github_issues.scope_key.trim().is_empty()
+            || cfg.github_issues.limit == 0)
+    {
+        return Err(GardenerError::InvalidConfig(
+            "github_issues needs a non-empty label and scope_key and a limit above zero when \
+             enabled"
+                .to_string(),
+        ));
+    }
+
+    if cfg.watch.idle_backoff_seconds == 0
+        || cfg.watch.audit_interval_seconds == 0
+        || cfg.watch.health_interval_seconds == 0
+    {
+        return Err(GardenerError::InvalidConfig(
+            "watch.idle_backoff_seconds, watch.audit_interval_seconds and \
+             watch.health_interval_seconds must be greater than zero"
+                .to_string(),
+        ));
+    }
+
+    if cfg.watch.idle_backoff_seconds > cfg.watch.max_idle_backoff_seconds {
+        return Err(GardenerError::InvalidConfig(
+            "watch.idle_backoff_seconds must not exceed watch.max_idle_backoff_seconds"
+                .to_string(),
+        ));
+    }
+
+    if cfg.watch.health_path.trim().is_empty() {
+        return Err(GardenerError::InvalidConfig(
+            "watch.health_path must not be empty".to_string(),
+        ));
+    }
+
+    for (model, price) in &cfg.pricing {
+        let rates = [
+            Some(price.input_per_mtok),
+            price.cached_input_per_mtok,
+            Some(price.output_per_mtok),
+        ];
+        if rates
+            .into_iter()
+            .flatten()
+            .any(|rate| !rate.is_finite() || rate < 0.0)
+        {
+            return Err(GardenerError::InvalidConfig(format!(
+                "pricing.{model} rates must be zero or more"
+            )));
+        }
+    }
+
+    if cfg
+        .budget
+        .max_cost_usd
+        .is_some_and(|max| !max.is_finite() || max <= 0.0)
+        || cfg.budget.max_tokens == Some(0)
+    {
+        return Err(GardenerError::InvalidConfig(
+            "budget.max_cost_usd and budget.max_tokens must be greater than zero".to_string(),
+        ));
+    }
+
+    if cfg.merge_queue.batch_size == 0 {
+        return Err(GardenerError::InvalidConfig(
+            "merge_queue.batch_size must be at least 1".to_string(),
+        ));
+    }
+
     if model_is_invalid(&cfg.seeding.model) {
         return Err(GardenerError::InvalidConfig(
             "seeding.model must be a real model id".to_string(),
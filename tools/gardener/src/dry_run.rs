@@ -0,0 +1,416 @@
+use crate::backlog_cli::RunSelection;
+use crate::backlog_store::{
+    compute_task_id_from_new_task, BacklogStore, BacklogTask, ClaimOptions,
+};
+use crate::config::AppConfig;
+use crate::errors::GardenerError;
+use crate::logging::append_run_log;
+use crate::repo_identity::resolve_repo_identity;
+use crate::runtime::ProductionRuntime;
+use crate::startup::{backlog_db_path, preview_startup_audits};
+use crate::triage::{triage_needed, TriageDecision};
+use crate::types::RuntimeScope;
+use crate::worker::{plan_task_live, PlannedStep, TaskPlan};
+use serde_json::json;
+use std::path::PathBuf;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DryRunReport {
+    /// Tasks in the order the pool would claim them, as they would look once
+    /// claimed.
+    pub claims: Vec<BacklogTask>,
+    pub plans: Vec<TaskPlan>,
+}
+
+/// Prints what a run with `target` tasks (`None` for `--watch`) would do, and
+/// changes nothing: triage and the startup audits are only checked, claims are
+/// simulated on a copy of the backlog, prompts are rendered but not sent, and
+/// git and gh commands are listed but not run.
+pub fn run_dry_run(
+    runtime: &ProductionRuntime,
+    scope: &RuntimeScope,
+    cfg: &mut AppConfig,
+    target: Option<usize>,
+    selection: &RunSelection,
+) -> Result<DryRunReport, GardenerError> {
+    append_run_log(
+        "info",
+        "dry_run.started",
+        json!({ "target": target, "selection": format!("{selection:?}") }),
+    );
+    let out = |line: &str| runtime.terminal.write_line(line);
+    out("dry run: nothing is claimed, committed, pushed, merged or sent to an agent")?;
+
+    match triage_needed(scope, cfg, runtime, false)? {
+        TriageDecision::Needed => {
+            out("triage: needed; the run would start with the triage interview")?;
+        }
+        TriageDecision::NotNeeded => out("triage: repository profile is current")?,
+    }
+
+    let db_path = backlog_db_path(cfg, scope);
+    // Opening the backlog migrates it, registers the namespace and recovers
+    // stale leases, so the preview runs on a copy and the real file is only read.
+    let snapshot = SnapshotFile::new();
+    let store = if db_path.exists() {
+        let identity = resolve_repo_identity(scope, runtime.process_runner.as_ref());
+        Some(BacklogStore::open_snapshot_for_repo(
+            &db_path,
+            &snapshot.0,
+            &identity,
+        )?)
+    } else {
+        None
+    };
+    if !cfg.execution.test_mode {
+        for line in preview_startup_audits(runtime, cfg, scope, store.as_ref())? {
+            out(&format!("audit: {line}"))?;
+        }
+    }
+
+    // An ad-hoc task the backlog does not hold yet would be added and run on
+    // its own; plan it without adding it.
+    let pending_adhoc = match &selection.adhoc {
+        Some(task) => {
+            let task_id = compute_task_id_from_new_task(task);
+            let stored = match &store {
+                Some(store) => store.get_task(&task_id)?,
+                None => None,
+            };
+            stored.is_none().then_some((task_id, task))
+        }
+        None => None,
+    };
+    let mut plans = Vec::new();
+    if let Some((task_id, task)) = pending_adhoc {
+        out(&format!("backlog: would add ad-hoc task {task_id} and run only it"))?;
+        let plan = plan_task_live(cfg, scope, "worker-1", &task_id, &task.title, 1)?;
+        let header = format!(
+            "[1] worker-1 claims {} {} {task_id}: {}",
+            task.priority.as_str(),
+            task.kind.as_str(),
+            task.title
+        );
+        print_plan(&out, &header, &plan)?;
+        plans.push(plan);
+        return Ok(DryRunReport {
+            claims: Vec::new(),
+            plans,
+        });
+    }
+    let Some(store) = store else {
+        out(&format!("backlog: no database at {}", db_path.display()))?;
+        return Ok(DryRunReport {
+            claims: Vec::new(),
+            plans,
+        });
+    };
+    let options = ClaimOptions {
+        filter: selection.preview_filter(&store)?.unwrap_or_default(),
+        ..ClaimOptions::from(&cfg.scheduler)
+    };
+    let limit = target.unwrap_or(usize::MAX);
+    let parallelism = (cfg.orchestrator.parallelism.max(1) as usize).min(limit.max(1));
+    let claims = store.preview_claims(limit, parallelism, &options)?;
+    out(&format!(
+        "backlog: {} task(s) would be claimed by {parallelism} worker(s)",
+        claims.len()
+    ))?;
+
+    for (index, task) in claims.iter().enumerate() {
+        let worker_id = task.lease_owner.as_deref().unwrap_or("worker-1");
+        let plan = plan_task_live(
+            cfg,
+            scope,
+            worker_id,
+            &task.task_id,
+            &task.title,
+            task.attempt_count,
+        )?;
+        let header = format!(
+            "[{}] {worker_id} claims {} {} {}: {}",
+            index + 1,
+            task.priority.as_str(),
+            task.kind.as_str(),
+            task.task_id,
+            task.title
+        );
+        print_plan(&out, &header, &plan)?;
+        plans.push(plan);
+    }
+    append_run_log(
+        "info",
+        "dry_run.finished",
+        json!({ "claims": claims.len() }),
+    );
+    Ok(DryRunReport { claims, plans })
+}
+
+/// The dry run's copy of the backlog under the temp dir, removed with its WAL
+/// files once the preview is done.
+struct SnapshotFile(PathBuf);
+
+impl SnapshotFile {
+    fn new() -> Self {
+        let nanos = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map_or(0, |elapsed| elapsed.as_nanos());
+        Self(std::env::temp_dir().join(format!(
+            "gardener-dry-run-{}-{nanos}.sqlite",
+            std::process::id()
+        )))
+    }
+}
+
+impl Drop for SnapshotFile {
+    fn drop(&mut self) {
+        append_run_log(
+            "debug",
+            "dry_run.snapshot.removed",
+            json!({ "path": self.0.display().to_string() }),
+        );
+        for suffix in ["", "-wal", "-shm"] {
+            let mut path = self.0.clone().into_os_string();
+            path.push(suffix);
+            let _ = std::fs::remove_file(path);
+        }
+    }
+}
+
+fn print_plan(
+    out: &dyn Fn(&str) -> Result<(), GardenerError>,
+    header: &str,
+    plan: &TaskPlan,
+) -> Result<(), GardenerError> {
+    out("")?;
+    out(header)?;
+    out(&format!(
+        "    branch {} in {}",
+        plan.branch,
+        plan.worktree_path.display()
+    ))?;
+    if let Some(state) = plan.resume_from {
+        out(&format!(
+            "    resumes at {} from a checkpoint{}",
+            state.as_str(),
+            plan.pr_number
+                .map_or_else(String::new, |number| format!(" with PR #{number}"))
+        ))?;
+    }
+    for step in &plan.steps {
+        for line in render_step(step) {
+            out(&line)?;
+        }
+    }
+    Ok(())
+}
+
+fn render_step(step: &PlannedStep) -> Vec<String> {
+    match step {
+        PlannedStep::AgentTurn {
+            state,
+            backend,
+            model,
+            prompt_version,
+            prompt,
+            only_if,
+        } => {
+            let backend = backend.map_or("none", |backend| backend.as_str());
+            let mut lines = vec![format!(
+                "  {}: agent turn backend={backend} model={model} prompt={prompt_version}{}",
+                state.as_str(),
+                only_if_suffix(*only_if)
+            )];
+            lines.extend(prompt.lines().map(|line| format!("      | {line}")));
+            lines
+        }
+        PlannedStep::Command {
+            state,
+            cwd,
+            argv,
+            only_if,
+        } => vec![format!(
+            "  {}: $ {}  (in {}){}",
+            state.as_str(),
+            argv.iter()
+                .map(|arg| shell_quote(arg))
+                .collect::<Vec<_>>()
+                .join(" "),
+            cwd.display(),
+            only_if_suffix(*only_if)
+        )],
+    }
+}
+
+fn only_if_suffix(only_if: Option<&str>) -> String {
+    only_if.map_or_else(String::new, |condition| format!(" only if {condition}"))
+}
+
+fn shell_quote(arg: &str) -> String {
+    let plain = !arg.is_empty()
+        && arg
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || "-_./=:,@%+<>".contains(c));
+    if plain {
+        arg.to_string()
+    } else {
+        format!("'{}'", arg.replace('\'', r"'\''"))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{run_dry_run, shell_quote};
+    use crate::backlog_cli::RunSelection;
+    use crate::backlog_store::{BacklogStore, NewTask, TaskStatus};
+    use crate::config::AppConfig;
+    use crate::priority::Priority;
+    use crate::repo_identity::resolve_repo_identity;
+    use crate::runtime::{
+        FakeClock, FakeProcessRunner, FakeTerminal, ProductionFileSystem, ProductionRuntime,
+    };
+    use crate::task_identity::TaskKind;
+    use crate::types::RuntimeScope;
+    use crate::worker::PlannedStep;
+    use sha2::{Digest, Sha256};
+    use std::sync::Arc;
+    use tempfile::TempDir;
+
+    fn task(title: &str, priority: Priority) -> NewTask {
+        NewTask {
+            kind: TaskKind::Maintenance,
+            title: title.to_string(),
+            details: "details".to_string(),
+            scope_key: String::new(),
+            rationale: "seeded for the dry run".to_string(),
+            priority,
+            source: "test".to_string(),
+            related_pr: None,
+            related_branch: None,
+            blocked_by: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn dry_run_plans_claims_in_order_and_leaves_the_backlog_untouched() {
+        let dir = TempDir::new().expect("tempdir");
+        let scope = RuntimeScope {
+            process_cwd: dir.path().to_path_buf(),
+            repo_root: Some(dir.path().to_path_buf()),
+            working_dir: dir.path().to_path_buf(),
+        };
+        let mut cfg = AppConfig::default();
+        cfg.execution.test_mode = true;
+        cfg.orchestrator.parallelism = 2;
+        let process_runner = FakeProcessRunner::default();
+        let db_path = dir.path().join(".cache/gardener/backlog.sqlite");
+        let identity = resolve_repo_identity(&scope, &process_runner);
+        let store = BacklogStore::open_for_repo(&db_path, &identity).expect("open store");
+        let first = store.upsert_task(task("urgent fix", Priority::P0)).expect("p0");
+        let mut blocked = task("follow-up", Priority::P0);
+        blocked.blocked_by = vec![first.task_id];
+        store.upsert_task(blocked).expect("blocked");
+        store.upsert_task(task("later cleanup", Priority::P2)).expect("p2");
+        let before = store.list_tasks().expect("list");
+        drop(store);
+
+        let terminal = FakeTerminal::new(false);
+        let runtime = ProductionRuntime {
+            clock: Arc::new(FakeClock::default()),
+            file_system: Arc::new(ProductionFileSystem),
+            process_runner: Arc::new(process_runner.clone()),
+            terminal: Arc::new(terminal.clone()),
+        };
+        let report = run_dry_run(&runtime, &scope, &mut cfg, Some(3), &RunSelection::default()).expect("dry run");
+
+        let titles = report
+            .claims
+            .iter()
+            .map(|task| task.title.as_str())
+            .collect::<Vec<_>>();
+        assert_eq!(titles, ["urgent fix", "later cleanup", "follow-up"]);
+        assert_eq!(report.claims[2].lease_owner.as_deref(), Some("worker-1"));
+        let plan = &report.plans[0];
+        assert!(plan.steps.iter().any(|step| matches!(
+            step,
+            PlannedStep::AgentTurn { prompt, .. } if prompt.contains("urgent fix")
+        )));
+        let lines = terminal.written_lines();
+        assert!(lines.iter().any(|line| line.contains("understand: agent turn")));
+        assert!(lines
+            .iter()
+            .any(|line| line.contains(&format!("$ git push origin {}", plan.branch))));
+        assert!(lines.iter().any(|line| line.contains("$ gh pr merge <pr> --squash")));
+
+        let store = BacklogStore::open_for_repo(&db_path, &identity).expect("reopen store");
+        let after = store.list_tasks().expect("list");
+        assert_eq!(after, before);
+        assert!(after.iter().all(|task| task.status == TaskStatus::Ready));
+        // Only local reads: no agent, git push or gh call.
+        assert!(process_runner
+            .spawned()
+            .iter()
+            .all(|request| request.program == "git" && request.args[0] != "push"));
+    }
+
+    #[test]
+    fn dry_run_reads_the_backlog_file_without_writing_to_it() {
+        let dir = TempDir::new().expect("tempdir");
+        let scope = RuntimeScope {
+            process_cwd: dir.path().to_path_buf(),
+            repo_root: Some(dir.path().to_path_buf()),
+            working_dir: dir.path().to_path_buf(),
+        };
+        let mut cfg = AppConfig::default();
+        cfg.execution.test_mode = true;
+        let process_runner = FakeProcessRunner::default();
+        let db_path = dir.path().join(".cache/gardener/backlog.sqlite");
+        let identity = resolve_repo_identity(&scope, &process_runner);
+        let adhoc = RunSelection::new(Some("Fix the flaky login test"), None, Default::default());
+        let store = BacklogStore::open_for_repo(&db_path, &identity).expect("open store");
+        store.upsert_task(task("stale lease", Priority::P0)).expect("stale");
+        // A lease that already ran out: a real open would hand it back.
+        store.claim_next("worker-9", -60).expect("claim").expect("leased");
+        let stored = store
+            .upsert_task(adhoc.adhoc.clone().expect("adhoc task"))
+            .expect("adhoc");
+        drop(store);
+        // Writes may sit in the WAL until a checkpoint, so it counts as the file.
+        let checksum = || {
+            let mut hasher = Sha256::new();
+            hasher.update(std::fs::read(&db_path).expect("read db"));
+            hasher.update(std::fs::read(db_path.with_extension("sqlite-wal")).unwrap_or_default());
+            hasher.finalize().to_vec()
+        };
+        let before = checksum();
+
+        let terminal = FakeTerminal::new(false);
+        let runtime = ProductionRuntime {
+            clock: Arc::new(FakeClock::default()),
+            file_system: Arc::new(ProductionFileSystem),
+            process_runner: Arc::new(process_runner),
+            terminal: Arc::new(terminal),
+        };
+        let report = run_dry_run(&runtime, &scope, &mut cfg, None, &RunSelection::default())
+            .expect("dry run");
+        assert!(report.claims.iter().any(|task| task.title == "stale lease"));
+        assert_eq!(checksum(), before);
+
+        let report = run_dry_run(&runtime, &scope, &mut cfg, None, &adhoc).expect("ad-hoc run");
+        assert_eq!(report.claims.len(), 1);
+        assert_eq!(report.claims[0].task_id, stored.task_id);
+        assert_eq!(checksum(), before);
+
+        let store = BacklogStore::open_for_repo(&db_path, &identity).expect("reopen store");
+        assert_eq!(store.get_task(&stored.task_id).expect("get"), Some(stored));
+    }
+
+    #[test]
+    fn shell_quote_leaves_plain_words_and_quotes_the_rest() {
+        assert_eq!(shell_quote("origin/main"), "origin/main");
+        assert_eq!(shell_quote("fix the bug"), "'fix the bug'");
+        assert_eq!(shell_quote("it's"), r"'it'\''s'");
+        assert_eq!(shell_quote("<pr>"), "<pr>");
+    }
+}
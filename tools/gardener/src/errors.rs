@@ -12,6 +12,9 @@ pub enum GardenerError {
     Cli(String),
     #[error("process error: {0}")]
     Process(String),
+    /// A process ran past its `ProcessRequest::timeout` and was killed.
+    #[error("process timed out: {0}")]
+    Timeout(String),
     #[error("output envelope error: {0}")]
     OutputEnvelope(String),
     #[error("database error: {0}")]
@@ -51,6 +51,21 @@ pub struct PrMergeability {
     pub merge_state_status: MergeStateStatus,
 }
 
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+pub struct IssueSummary {
+    pub number: u64,
+    pub title: String,
+    #[serde(default)]
+    pub body: String,
+    #[serde(default)]
+    pub labels: Vec<IssueLabel>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+pub struct IssueLabel {
+    pub name: String,
+}
+
 pub struct GhClient<'a> {
     runner: &'a dyn ProcessRunner,
     cwd: PathBuf,
@@ -81,6 +96,7 @@ impl<'a> GhClient<'a> {
                 body.to_string(),
             ],
             cwd: Some(self.cwd.clone()),
+            timeout: None,
         })?;
         if out.exit_code != 0 {
             append_run_log(
@@ -139,6 +155,7 @@ impl<'a> GhClient<'a> {
                 "mergedAt,mergeCommit,headRefName,state".to_string(),
             ],
             cwd: Some(self.cwd.clone()),
+            timeout: None,
         })?;
         if out.exit_code != 0 {
             append_run_log(
@@ -189,6 +206,7 @@ impl<'a> GhClient<'a> {
                 "mergeable,mergeStateStatus".to_string(),
             ],
             cwd: Some(self.cwd.clone()),
+            timeout: None,
         })?;
         if out.exit_code != 0 {
             return Err(GardenerError::Process(format!(
@@ -254,6 +272,7 @@ impl<'a> GhClient<'a> {
                 "--squash".to_string(),
             ],
             cwd: Some(self.cwd.clone()),
+            timeout: None,
         })?;
         if squash.exit_code == 0 {
             append_run_log(
@@ -282,6 +301,7 @@ impl<'a> GhClient<'a> {
                 "--merge".to_string(),
             ],
             cwd: Some(self.cwd.clone()),
+            timeout: None,
         })?;
         if merge.exit_code == 0 {
             append_run_log(
@@ -306,6 +326,116 @@ impl<'a> GhClient<'a> {
         )))
     }
 
+    pub fn list_issues(&self, label: &str, limit: u32) -> Result<Vec<IssueSummary>, GardenerError> {
+        append_run_log(
+            "info",
+            "gh.issue.list.started",
+            json!({ "cwd": self.cwd.display().to_string(), "label": label, "limit": limit }),
+        );
+        let out = self.runner.run(ProcessRequest {
+            program: "gh".to_string(),
+            args: vec![
+                "issue".to_string(),
+                "list".to_string(),
+                "--label".to_string(),
+                label.to_string(),
+                "--state".to_string(),
+                "open".to_string(),
+                "--limit".to_string(),
+                limit.to_string(),
+                "--json".to_string(),
+                "number,title,body,labels".to_string(),
+            ],
+            cwd: Some(self.cwd.clone()),
+            timeout: None,
+        })?;
+        if out.exit_code != 0 {
+            append_run_log(
+                "error",
+                "gh.issue.list.failed",
+                json!({
+                    "cwd": self.cwd.display().to_string(),
+                    "label": label,
+                    "exit_code": out.exit_code,
+                    "stderr": out.stderr
+                }),
+            );
+            return Err(GardenerError::Process(format!(
+                "gh issue list failed: {}",
+                out.stderr
+            )));
+        }
+        let issues: Vec<IssueSummary> = serde_json::from_str(&out.stdout)
+            .map_err(|e| GardenerError::Process(format!("invalid gh issue list json: {e}")))?;
+        append_run_log(
+            "info",
+            "gh.issue.list.fetched",
+            json!({ "label": label, "issue_count": issues.len() }),
+        );
+        Ok(issues)
+    }
+
+    /// Closes an issue, leaving `comment` on it.
+    pub fn close_issue(&self, issue_number: u64, comment: &str) -> Result<(), GardenerError> {
+        self.run_issue_command("close", issue_number, "--comment", comment)
+    }
+
+    pub fn comment_issue(&self, issue_number: u64, comment: &str) -> Result<(), GardenerError> {
+        self.run_issue_command("comment", issue_number, "--body", comment)
+    }
+
+    fn run_issue_command(
+        &self,
+        action: &str,
+        issue_number: u64,
+        comment_flag: &str,
+        comment: &str,
+    ) -> Result<(), GardenerError> {
+        append_run_log(
+            "info",
+            "gh.issue.update.started",
+            json!({
+                "cwd": self.cwd.display().to_string(),
+                "action": action,
+                "issue_number": issue_number
+            }),
+        );
+        let out = self.runner.run(ProcessRequest {
+            program: "gh".to_string(),
+            args: vec![
+                "issue".to_string(),
+                action.to_string(),
+                issue_number.to_string(),
+                comment_flag.to_string(),
+                comment.to_string(),
+            ],
+            cwd: Some(self.cwd.clone()),
+            timeout: None,
+        })?;
+        if out.exit_code != 0 {
+            append_run_log(
+                "error",
+                "gh.issue.update.failed",
+                json!({
+                    "action": action,
+                    "issue_number": issue_number,
+                    "exit_code": out.exit_code,
+                    "stderr": out.stderr
+                }),
+            );
+            return Err(GardenerError::Process(format!(
+                "gh issue {action} failed: {}",
+                out.stderr
+            )));
+        }
+        append_run_log(
+            "info",
+            "gh.issue.update.succeeded",
+            json!({ "action": action, "issue_number": issue_number }),
+        );
+        Ok(())
+    }
+
     pub fn verify_merged_and_validated(
         &self,
         git: &GitClient,
@@ -369,7 +499,7 @@ impl<'a> GhClient<'a> {
             ));
         }
 
-        git.run_validation_command(validation_command)?;
+        git.run_validation_command(validation_command, None)?;
         append_run_log(
             "info",
             "gh.pr.verify.succeeded",
@@ -404,6 +534,7 @@ pub fn generate_pr_title_body(
             "--format=%s".to_string(),
         ],
         cwd: Some(cwd.to_path_buf()),
+        timeout: None,
     })?;
     let subjects: Vec<&str> = log_out
         .stdout
@@ -3,6 +3,7 @@ use crate::logging::append_run_log;
 use crate::runtime::{ProcessRequest, ProcessRunner};
 use serde_json::json;
 use std::path::{Path, PathBuf};
+use std::time::Duration;
 
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub enum MergeMode {
@@ -116,6 +117,25 @@ impl<'a> GitClient<'a> {
         }
     }
 
+    pub fn remote_url(&self, remote: &str) -> Result<Option<String>, GardenerError> {
+        let out = self.run(["git", "remote", "get-url", remote])?;
+        if out.exit_code == 0 {
+            let url = out.stdout.trim().to_string();
+            Ok(if url.is_empty() { None } else { Some(url) })
+        } else {
+            append_run_log(
+                "debug",
+                "git.remote_url.missing",
+                json!({
+                    "cwd": self.cwd.display().to_string(),
+                    "remote": remote,
+                    "stderr": out.stderr
+                }),
+            );
+            Ok(None)
+        }
+    }
+
     pub fn verify_ancestor(
         &self,
         maybe_ancestor: &str,
@@ -280,6 +300,58 @@ impl<'a> GitClient<'a> {
         Ok(())
     }
 
+    /// Pushes a rebased branch over its old remote head, refusing if the
+    /// remote moved since it was last fetched.
+    pub fn force_push_with_lease(&self, branch: &str) -> Result<(), GardenerError> {
+        let out = self.run(["git", "push", "--force-with-lease", "origin", branch])?;
+        append_run_log(
+            if out.exit_code == 0 { "info" } else { "error" },
+            "git.push.force_with_lease",
+            json!({
+                "cwd": self.cwd.display().to_string(),
+                "branch": branch,
+                "exit_code": out.exit_code,
+                "stderr": out.stderr
+            }),
+        );
+        if out.exit_code != 0 {
+            return Err(GardenerError::Process(format!(
+                "git push --force-with-lease origin {branch} failed: {}",
+                out.stderr
+            )));
+        }
+        Ok(())
+    }
+
+    /// Paths the branch changes relative to where it forked from
+    /// `origin/<base_branch>`.
+    pub fn changed_files(&self, base_branch: &str) -> Result<Vec<String>, GardenerError> {
+        let range = format!("origin/{base_branch}...HEAD");
+        let out = self.run(["git", "diff", "--name-only", &range])?;
+        append_run_log(
+            "debug",
+            "git.diff.changed_files",
+            json!({
+                "cwd": self.cwd.display().to_string(),
+                "range": range,
+                "exit_code": out.exit_code
+            }),
+        );
+        if out.exit_code != 0 {
+            return Err(GardenerError::Process(format!(
+                "git diff --name-only {range} failed: {}",
+                out.stderr
+            )));
+        }
+        Ok(out
+            .stdout
+            .lines()
+            .map(str::trim)
+            .filter(|line| !line.is_empty())
+            .map(str::to_string)
+            .collect())
+    }
+
     pub fn rebase_onto_local(&self, base: &str) -> Result<(), GardenerError> {
         append_run_log(
             "info",
@@ -445,7 +517,11 @@ impl<'a> GitClient<'a> {
         Ok(())
     }
 
-    pub fn run_validation_command(&self, command: &str) -> Result<(), GardenerError> {
+    pub fn run_validation_command(
+        &self,
+        command: &str,
+        timeout: Option<Duration>,
+    ) -> Result<(), GardenerError> {
         append_run_log(
             "info",
             "git.validation.started",
@@ -454,7 +530,12 @@ impl<'a> GitClient<'a> {
                 "command": command
             }),
         );
-        let out = self.run(["sh", "-lc", command])?;
+        let out = self.runner.run(ProcessRequest {
+            program: "sh".to_string(),
+            args: vec!["-lc".to_string(), command.to_string()],
+            cwd: Some(self.cwd.clone()),
+            timeout,
+        })?;
         if out.exit_code != 0 {
             append_run_log(
                 "error",
@@ -505,6 +586,7 @@ impl<'a> GitClient<'a> {
             program,
             args: argv,
             cwd: Some(self.cwd.clone()),
+            timeout: None,
         })
     }
 }
@@ -518,7 +600,9 @@ fn is_rebase_conflict(stderr: &str) -> bool {
 mod tests {
     use super::GitClient;
     use super::RebaseResult;
+    use crate::errors::GardenerError;
     use crate::runtime::{FakeProcessRunner, ProcessOutput};
+    use std::time::Duration;
 
     #[test]
     fn push_rebase_recovery_path() {
@@ -567,6 +651,24 @@ mod tests {
         assert!(spawned[1].args.contains(&"rebase".to_string()));
     }
 
+    #[test]
+    fn changed_files_lists_paths_against_the_merge_base() {
+        let runner = FakeProcessRunner::default();
+        runner.push_response(Ok(ProcessOutput {
+            exit_code: 0,
+            stdout: "src/a.rs\n\ndocs/b.md\n".to_string(),
+            stderr: String::new(),
+        }));
+        let files = GitClient::new(&runner, "/repo")
+            .changed_files("main")
+            .expect("diff");
+        assert_eq!(files, ["src/a.rs", "docs/b.md"]);
+        assert_eq!(
+            runner.spawned()[0].args,
+            ["diff", "--name-only", "origin/main...HEAD"]
+        );
+    }
+
     #[test]
     fn rebase_onto_main_aborts_on_conflict() {
         let runner = FakeProcessRunner::default();
@@ -758,11 +860,22 @@ mod tests {
             stderr: "failed validation".to_string(),
         }));
         let err = GitClient::new(&runner, "/repo")
-            .run_validation_command("npm run validate")
+            .run_validation_command("npm run validate", None)
             .expect_err("validation failed");
         assert!(err.to_string().contains("post-merge validation command failed"));
     }
 
+    #[test]
+    fn run_validation_command_times_out_when_the_command_hangs() {
+        let runner = FakeProcessRunner::default();
+        runner.push_hang();
+        let err = GitClient::new(&runner, "/repo")
+            .run_validation_command("npm run validate", Some(Duration::from_secs(30)))
+            .expect_err("validation hung");
+        assert!(matches!(err, GardenerError::Timeout(_)), "{err}");
+        assert_eq!(runner.spawned()[0].timeout, Some(Duration::from_secs(30)));
+    }
+
     #[test]
     fn rebase_local_recovery_paths_are_exercised() {
         let runner = FakeProcessRunner::default();
@@ -15,9 +15,10 @@ pub enum HotkeyAction {
     ViewReport,
     RegenerateReport,
     Back,
+    Search,
 }
 
-pub const DASHBOARD_BINDINGS: [HotkeyBinding; 5] = [
+pub const DASHBOARD_BINDINGS: [HotkeyBinding; 6] = [
     HotkeyBinding {
         key: 'q',
         action: "quit",
@@ -38,6 +39,10 @@ pub const DASHBOARD_BINDINGS: [HotkeyBinding; 5] = [
         key: 'g',
         action: "regenerate",
     },
+    HotkeyBinding {
+        key: '/',
+        action: "search",
+    },
 ];
 
 pub const OPERATOR_BINDINGS: [HotkeyBinding; 3] = [
@@ -97,6 +102,7 @@ pub fn action_for_key_with_mode(key: char, operator_hotkeys: bool) -> Option<Hot
         'v' => Some(HotkeyAction::ViewReport),
         'g' => Some(HotkeyAction::RegenerateReport),
         'b' => Some(HotkeyAction::Back),
+        '/' => Some(HotkeyAction::Search),
         _ => None,
     }
 }
@@ -0,0 +1,353 @@
+use crate::backlog_store::{BacklogStore, NewTask};
+use crate::config::{GithubIssuesConfig, IssueMergeAction};
+use crate::errors::GardenerError;
+use crate::gh::{GhClient, IssueSummary};
+use crate::logging::append_run_log;
+use crate::priority::Priority;
+use crate::task_identity::TaskKind;
+use serde_json::json;
+
+/// `source` of every task imported from a GitHub issue.
+pub const ISSUE_SOURCE: &str = "github_issue";
+/// Label prefix that sets an imported task's scope, as in `scope:cli`.
+const SCOPE_LABEL_PREFIX: &str = "scope:";
+const BUG_LABELS: &[&str] = &["bug", "bugfix"];
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct IssueImportSummary {
+    pub listed: usize,
+    pub imported: usize,
+    pub already_linked: usize,
+}
+
+/// Pulls open issues carrying the configured label into the backlog. Issues
+/// imported on an earlier run are skipped, so running this repeatedly is safe.
+pub fn import_labeled_issues(
+    store: &BacklogStore,
+    gh: &GhClient<'_>,
+    cfg: &GithubIssuesConfig,
+) -> Result<IssueImportSummary, GardenerError> {
+    append_run_log(
+        "info",
+        "issue_import.started",
+        json!({ "label": cfg.label, "limit": cfg.limit }),
+    );
+    let issues = gh.list_issues(&cfg.label, cfg.limit)?;
+    let mut summary = IssueImportSummary {
+        listed: issues.len(),
+        ..IssueImportSummary::default()
+    };
+    for issue in &issues {
+        match store.import_issue(issue.number as i64, issue_task(issue, cfg))? {
+            Some(_) => summary.imported += 1,
+            None => summary.already_linked += 1,
+        }
+    }
+    append_run_log(
+        "info",
+        "issue_import.completed",
+        json!({
+            "label": cfg.label,
+            "listed": summary.listed,
+            "imported": summary.imported,
+            "already_linked": summary.already_linked,
+        }),
+    );
+    Ok(summary)
+}
+
+/// Closes or comments on the issue a completed task was imported from. Returns
+/// the issue number when one was resolved; tasks without an issue, and issues
+/// resolved before, are left alone.
+pub fn resolve_issue_for_merged_task(
+    store: &BacklogStore,
+    gh: &GhClient<'_>,
+    cfg: &GithubIssuesConfig,
+    task_id: &str,
+    pr_number: Option<u64>,
+    merge_sha: Option<&str>,
+) -> Result<Option<i64>, GardenerError> {
+    let Some(link) = store.issue_link_for_task(task_id)? else {
+        return Ok(None);
+    };
+    if link.resolved_at.is_some() {
+        append_run_log(
+            "debug",
+            "issue_import.resolve.already_resolved",
+            json!({ "task_id": task_id, "issue_number": link.issue_number }),
+        );
+        return Ok(None);
+    }
+    let comment = merge_comment(pr_number, merge_sha);
+    append_run_log(
+        "info",
+        "issue_import.resolve.started",
+        json!({
+            "task_id": task_id,
+            "issue_number": link.issue_number,
+            "on_merge": format!("{:?}", cfg.on_merge),
+        }),
+    );
+    let issue_number = link.issue_number as u64;
+    match cfg.on_merge {
+        IssueMergeAction::Close => gh.close_issue(issue_number, &comment)?,
+        IssueMergeAction::Comment => gh.comment_issue(issue_number, &comment)?,
+    }
+    store.mark_issue_resolved(link.issue_number)?;
+    Ok(Some(link.issue_number))
+}
+
+/// The task for an issue. The issue number is part of the title, so two issues
+/// with the same title still get a task each.
+fn issue_task(issue: &IssueSummary, cfg: &GithubIssuesConfig) -> NewTask {
+    let has_label = |names: &[&str]| {
+        issue
+            .labels
+            .iter()
+            .any(|label| names.iter().any(|name| label.name.eq_ignore_ascii_case(name)))
+    };
+    let kind = if has_label(BUG_LABELS) {
+        TaskKind::Bugfix
+    } else {
+        TaskKind::Feature
+    };
+    let priority = issue
+        .labels
+        .iter()
+        .find_map(|label| Priority::from_db(&label.name.to_ascii_uppercase()))
+        .unwrap_or(Priority::P1);
+    let scope_key = issue
+        .labels
+        .iter()
+        .filter_map(|label| label.name.strip_prefix(SCOPE_LABEL_PREFIX))
+        .map(str::trim)
+        .find(|scope| !scope.is_empty())
+        .unwrap_or(&cfg.scope_key);
+    let body = issue.body.trim();
+    NewTask {
+        kind,
+        title: format!("{} (#{})", issue.title.trim(), issue.number),
+        details: if body.is_empty() {
+            format!("See GitHub issue #{}.", issue.number)
+        } else {
+            body.to_string()
+        },
+        rationale: format!("Imported from GitHub issue #{}.", issue.number),
+        scope_key: scope_key.to_string(),
+        priority,
+        source: ISSUE_SOURCE.to_string(),
+        related_pr: None,
+        related_branch: None,
+        blocked_by: Vec::new(),
+    }
+}
+
+fn merge_comment(pr_number: Option<u64>, merge_sha: Option<&str>) -> String {
+    let pr = pr_number.map_or_else(|| "the gardener".to_string(), |pr| format!("#{pr}"));
+    match merge_sha {
+        Some(sha) => format!("Resolved by {pr}, merged as {sha}."),
+        None => format!("Resolved by {pr}."),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{import_labeled_issues, resolve_issue_for_merged_task, ISSUE_SOURCE};
+    use crate::backlog_store::BacklogStore;
+    use crate::config::{GithubIssuesConfig, IssueMergeAction};
+    use crate::gh::GhClient;
+    use crate::priority::Priority;
+    use crate::runtime::{FakeProcessRunner, ProcessOutput};
+    use crate::task_identity::TaskKind;
+    use tempfile::TempDir;
+
+    const ISSUES_JSON: &str = r#"[
+        {"number": 12, "title": "Crash on empty config", "body": "Panics at load.",
+         "labels": [{"name": "gardener"}, {"name": "bug"}, {"name": "p0"}]},
+        {"number": 15, "title": "Add a --json flag to status", "body": "",
+         "labels": [{"name": "gardener"}]}
+    ]"#;
+
+    fn ok(stdout: &str) -> ProcessOutput {
+        ProcessOutput {
+            exit_code: 0,
+            stdout: stdout.to_string(),
+            stderr: String::new(),
+        }
+    }
+
+    fn config(on_merge: IssueMergeAction) -> GithubIssuesConfig {
+        GithubIssuesConfig {
+            enabled: true,
+            label: "gardener".to_string(),
+            limit: 50,
+            on_merge,
+            scope_key: "runtime".to_string(),
+        }
+    }
+
+    #[test]
+    fn labeled_issues_import_once_as_feature_and_bugfix_tasks() {
+        let dir = TempDir::new().expect("tempdir");
+        let store = BacklogStore::open(dir.path().join("backlog.sqlite")).expect("store");
+        let runner = FakeProcessRunner::default();
+        runner.push_response(Ok(ok(ISSUES_JSON)));
+        runner.push_response(Ok(ok(ISSUES_JSON)));
+        let gh = GhClient::new(&runner, "/repo");
+        let cfg = config(IssueMergeAction::Close);
+
+        let first = import_labeled_issues(&store, &gh, &cfg).expect("import");
+        assert_eq!((first.listed, first.imported, first.already_linked), (2, 2, 0));
+        let second = import_labeled_issues(&store, &gh, &cfg).expect("re-import");
+        assert_eq!((second.imported, second.already_linked), (0, 2));
+
+        let tasks = store.list_tasks().expect("list");
+        assert_eq!(tasks.len(), 2);
+        let bug = tasks
+            .iter()
+            .find(|task| task.title == "Crash on empty config (#12)")
+            .expect("bug task");
+        assert_eq!(bug.kind, TaskKind::Bugfix);
+        assert_eq!(bug.priority, Priority::P0);
+        assert_eq!(bug.source, ISSUE_SOURCE);
+        let feature = tasks
+            .iter()
+            .find(|task| task.title == "Add a --json flag to status (#15)")
+            .expect("feature task");
+        assert_eq!(feature.kind, TaskKind::Feature);
+        assert_eq!(feature.details, "See GitHub issue #15.");
+        assert_eq!(
+            store
+                .issue_link_for_task(&bug.task_id)
+                .expect("link")
+                .map(|link| link.issue_number),
+            Some(12)
+        );
+
+        let spawned = runner.spawned();
+        assert_eq!(spawned[0].program, "gh");
+        assert_eq!(&spawned[0].args[..4], ["issue", "list", "--label", "gardener"]);
+    }
+
+    #[test]
+    fn merged_tasks_close_or_comment_on_their_issue_exactly_once() {
+        let dir = TempDir::new().expect("tempdir");
+        let store = BacklogStore::open(dir.path().join("backlog.sqlite")).expect("store");
+        let runner = FakeProcessRunner::default();
+        runner.push_response(Ok(ok(ISSUES_JSON)));
+        runner.push_response(Ok(ok("")));
+        runner.push_response(Ok(ok("")));
+        let gh = GhClient::new(&runner, "/repo");
+        import_labeled_issues(&store, &gh, &config(IssueMergeAction::Close)).expect("import");
+        let tasks = store.list_tasks().expect("list");
+        let bug = tasks
+            .iter()
+            .find(|task| task.title == "Crash on empty config (#12)")
+            .expect("bug task");
+        let feature = tasks
+            .iter()
+            .find(|task| task.title != bug.title)
+            .expect("feature task");
+
+        let resolved = resolve_issue_for_merged_task(
+            &store,
+            &gh,
+            &config(IssueMergeAction::Close),
+            &bug.task_id,
+            Some(41),
+            Some("abc123"),
+        )
+        .expect("resolve");
+        assert_eq!(resolved, Some(12));
+        let again = resolve_issue_for_merged_task(
+            &store,
+            &gh,
+            &config(IssueMergeAction::Close),
+            &bug.task_id,
+            Some(41),
+            Some("abc123"),
+        )
+        .expect("resolve again");
+        assert_eq!(again, None);
+        resolve_issue_for_merged_task(
+            &store,
+            &gh,
+            &config(IssueMergeAction::Comment),
+            &feature.task_id,
+            None,
+            None,
+        )
+        .expect("comment");
+
+        let spawned = runner.spawned();
+        assert_eq!(spawned.len(), 3, "the resolved issue is not touched twice");
+        assert_eq!(
+            spawned[1].args,
+            ["issue", "close", "12", "--comment", "Resolved by #41, merged as abc123."]
+        );
+        assert_eq!(spawned[2].args[..3], ["issue", "comment", "15"]);
+    }
+
+    #[test]
+    fn each_issue_gets_its_own_task_and_archived_issue_tasks_stay_archived() {
+        let dir = TempDir::new().expect("tempdir");
+        let db = dir.path().join("backlog.sqlite");
+        let store = BacklogStore::open(&db).expect("store");
+        let runner = FakeProcessRunner::default();
+        runner.push_response(Ok(ok(ISSUES_JSON)));
+        runner.push_response(Ok(ok(r#"[
+            {"number": 13, "title": "Crash on empty config", "body": "Seen in the CLI too.",
+             "labels": [{"name": "gardener"}, {"name": "scope:cli"}]},
+            {"number": 15, "title": "Add a --json flag to status", "body": "",
+             "labels": [{"name": "gardener"}]}
+        ]"#)));
+        let gh = GhClient::new(&runner, "/repo");
+        let cfg = GithubIssuesConfig {
+            scope_key: "issues".to_string(),
+            ..config(IssueMergeAction::Close)
+        };
+        import_labeled_issues(&store, &gh, &cfg).expect("import");
+        let feature = store
+            .list_tasks()
+            .expect("list")
+            .into_iter()
+            .find(|task| task.title == "Add a --json flag to status (#15)")
+            .expect("feature task");
+        assert_eq!(feature.scope_key, "issues");
+
+        // The feature's task finished and was archived, and its link was lost.
+        let raw = rusqlite::Connection::open(&db).expect("raw");
+        raw.execute(
+            "UPDATE backlog_tasks SET status = 'complete' WHERE task_id = ?1",
+            [&feature.task_id],
+        )
+        .expect("complete");
+        raw.execute("DELETE FROM github_issue_links WHERE issue_number = 15", [])
+            .expect("unlink");
+        drop(raw);
+        let archived = store.archive_finished_tasks(0).expect("archive");
+        assert_eq!(archived, vec![feature.task_id.clone()]);
+
+        let second = import_labeled_issues(&store, &gh, &cfg).expect("re-import");
+        assert_eq!((second.imported, second.already_linked), (1, 1));
+        let tasks = store.list_tasks().expect("list");
+        let crashes = tasks
+            .iter()
+            .filter(|task| task.title.starts_with("Crash on empty config"))
+            .collect::<Vec<_>>();
+        assert_eq!(crashes.len(), 2, "same title, different issues");
+        let cli = crashes
+            .iter()
+            .find(|task| task.title.ends_with("(#13)"))
+            .expect("issue 13");
+        assert_eq!(cli.scope_key, "cli");
+        assert!(tasks.iter().all(|task| task.task_id != feature.task_id));
+        assert_eq!(
+            store
+                .issue_link_for_task(&feature.task_id)
+                .expect("link")
+                .map(|link| link.issue_number),
+            Some(15)
+        );
+    }
+}
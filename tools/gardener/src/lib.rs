@@ -1,17 +1,21 @@
 #![deny(clippy::redundant_clone)]
 
 pub mod agent;
+pub mod backlog_cli;
 pub mod backlog_snapshot;
 pub mod backlog_store;
 pub mod config;
+pub mod dry_run;
 pub mod errors;
 pub mod fsm;
 pub mod gh;
 pub mod git;
 pub mod hotkeys;
+pub mod issue_import;
 pub mod learning_loop;
 pub mod log_retention;
 pub mod logging;
+pub mod merge_queue;
 pub mod output_envelope;
 pub mod postmerge_analysis;
 pub mod postmortem;
@@ -23,6 +27,7 @@ pub mod prompt_registry;
 pub mod prompts;
 pub mod protocol;
 pub mod replay;
+pub mod repo_identity;
 pub mod quality_domain_catalog;
 pub mod quality_evidence;
 pub mod quality_grades;
@@ -33,13 +38,17 @@ pub mod seed_runner;
 pub mod seeding;
 pub mod startup;
 pub mod task_identity;
+pub mod task_similarity;
 pub mod triage;
 pub mod triage_agent_detection;
 pub mod triage_discovery;
 pub mod triage_interview;
 pub mod tui;
 pub mod types;
+pub mod usage;
+pub mod watch;
 pub mod worker;
+pub mod worker_checkpoint;
 pub mod worker_identity;
 pub mod worker_pool;
 pub mod worktree;
@@ -47,10 +56,11 @@ pub mod worktree_audit;
 
 use agent::factory::AdapterFactory;
 use agent::{probe_and_persist, validate_model};
-use backlog_snapshot::export_markdown_snapshot;
+use backlog_snapshot::{export_markdown_snapshot, SNAPSHOT_PATH};
 use backlog_store::BacklogStore;
-use clap::{error::ErrorKind, CommandFactory, Parser, ValueEnum};
+use clap::{error::ErrorKind, CommandFactory, Parser, Subcommand, ValueEnum};
 use config::{load_config, resolve_validation_command, CliOverrides};
+use dry_run::run_dry_run;
 use errors::GardenerError;
 use logging::{
     append_run_log, clear_run_logger, default_run_log_path, init_run_logger, set_run_working_dir,
@@ -58,13 +68,18 @@ use logging::{
 };
 use replay::recorder::emit_record;
 use replay::recording::{BacklogSnapshotRecord, BacklogTaskRecord, RecordEntry};
-use runtime::{clear_interrupt, ProcessRequest, ProductionRuntime};
+use repo_identity::resolve_repo_identity;
+use runtime::{
+    clear_interrupt, clear_shutdown_requests, install_shutdown_signal_handlers, ProcessRequest,
+    ProductionRuntime,
+};
 use serde_json::json;
 use startup::{backlog_db_path, run_startup_audits, run_startup_audits_with_progress};
 use triage::{ensure_profile_for_run, triage_needed, TriageDecision};
 use triage_agent_detection::{is_non_interactive, EnvMap};
 use tui::{BacklogView, QueueStats, WorkerRow};
 use types::{AgentKind, RuntimeScope, ValidationCommandResolution};
+use watch::run_watch;
 use worker_pool::run_worker_pool_fsm;
 
 #[derive(Debug, Clone, Parser)]
@@ -77,10 +92,32 @@ pub struct Cli {
     pub working_dir: Option<std::path::PathBuf>,
     #[arg(long)]
     pub parallelism: Option<u32>,
-    #[arg(long)]
+    /// Add this ad-hoc task to the backlog and run only it.
+    #[arg(
+        long,
+        conflicts_with_all = ["task_id", "priorities", "kinds", "scopes", "sources"]
+    )]
     pub task: Option<String>,
+    /// Run only this backlog task, given by full id or unique prefix.
+    #[arg(long = "task-id", value_name = "ID")]
+    pub task_id: Option<String>,
+    #[command(flatten)]
+    pub claim_filter: backlog_cli::ClaimFilterArgs,
     #[arg(long = "quit-after")]
     pub target: Option<u32>,
+    /// Stop claiming and drain once agent turns have cost this many USD.
+    #[arg(long = "max-cost", value_name = "USD")]
+    pub max_cost: Option<f64>,
+    /// Stop claiming and drain once agent turns have used this many tokens.
+    #[arg(long = "max-tokens", value_name = "N")]
+    pub max_tokens: Option<u64>,
+    /// Keep running: poll the backlog with an idle backoff, re-run the startup
+    /// audits on a schedule, and retry failed tasks instead of stopping.
+    #[arg(long, default_value_t = false)]
+    pub watch: bool,
+    /// Show what the run would claim, prompt and run, without changing anything.
+    #[arg(long, default_value_t = false)]
+    pub dry_run: bool,
     #[arg(long, default_value_t = false)]
     pub prune_only: bool,
     #[arg(long, default_value_t = false)]
@@ -99,9 +136,26 @@ pub struct Cli {
     pub triage_only: bool,
     #[arg(long, default_value_t = false)]
     pub sync_only: bool,
+    /// List the per-repository backlog namespaces stored in the backlog database.
+    #[arg(long, default_value_t = false)]
+    pub list_namespaces: bool,
+    /// Move the tasks of another namespace into the current repository's namespace.
+    #[arg(long = "adopt-namespace", value_name = "NAMESPACE")]
+    pub adopt_namespace: Option<String>,
     /// Write a JSONL session recording to this path (also via GARDENER_RECORD_SESSION env var).
     #[arg(long = "record-session")]
     pub record_session: Option<std::path::PathBuf>,
+    #[command(subcommand)]
+    pub command: Option<Command>,
+}
+
+#[derive(Debug, Clone, Subcommand)]
+pub enum Command {
+    /// Inspect and edit this repository's backlog queue.
+    Backlog {
+        #[command(subcommand)]
+        command: backlog_cli::BacklogCommand,
+    },
 }
 
 #[derive(Debug, Clone, Copy, ValueEnum)]
@@ -139,6 +193,7 @@ pub fn run() -> Result<i32, GardenerError> {
     let env = std::env::vars_os().collect::<Vec<_>>();
     let cwd = std::env::current_dir().map_err(|e| GardenerError::Io(e.to_string()))?;
     let runtime = ProductionRuntime::new();
+    install_shutdown_signal_handlers()?;
     run_with_runtime(&args, &env, &cwd, &runtime)
 }
 
@@ -149,6 +204,7 @@ pub fn run_with_runtime(
     runtime: &ProductionRuntime,
 ) -> Result<i32, GardenerError> {
     clear_interrupt();
+    clear_shutdown_requests();
     let run_log_path = default_run_log_path(cwd);
     let run_id = init_run_logger(&run_log_path, cwd);
     let _run_log_guard = RunLogGuard;
@@ -180,10 +236,17 @@ pub fn run_with_runtime(
             json!({
                 "config_override": cli.config.as_ref().map(|p| p.display().to_string()),
                 "validate": cli.validate,
-                "task_override": cli.task,
+                "task": cli.task,
+                "task_id": cli.task_id,
                 "target": cli.target,
+                "max_cost": cli.max_cost,
+                "max_tokens": cli.max_tokens,
+                "watch": cli.watch,
+                "dry_run": cli.dry_run,
                 "triage_only": cli.triage_only,
-                "sync_only": cli.sync_only
+                "sync_only": cli.sync_only,
+                "list_namespaces": cli.list_namespaces,
+                "adopt_namespace": cli.adopt_namespace
             }),
         );
 
@@ -214,6 +277,8 @@ pub fn run_with_runtime(
             retriage: cli.retriage,
             triage_only: cli.triage_only,
             sync_only: cli.sync_only,
+            max_cost_usd: cli.max_cost,
+            max_tokens: cli.max_tokens,
         };
 
         let (cfg, scope) = load_config(
@@ -276,6 +341,7 @@ pub fn run_with_runtime(
                 program: "sh".to_string(),
                 args: vec!["-lc".to_string(), startup.validation.command.clone()],
                 cwd: Some(startup.scope.working_dir.clone()),
+                timeout: Some(std::time::Duration::from_secs(cfg.validation.timeout_seconds)),
             })?;
             append_run_log(
                 "info",
@@ -328,21 +394,64 @@ pub fn run_with_runtime(
             return Ok(0);
         }
 
+        if let Some(Command::Backlog { command }) = &cli.command {
+            let identity = resolve_repo_identity(&startup.scope, runtime.process_runner.as_ref());
+            let store = BacklogStore::open_for_repo(backlog_db_path(&cfg, &startup.scope), &identity)?;
+            return backlog_cli::run_backlog_command(
+                &store,
+                command,
+                runtime.terminal.as_ref(),
+                runtime.file_system.as_ref(),
+            );
+        }
+
+        if cli.list_namespaces || cli.adopt_namespace.is_some() {
+            let identity = resolve_repo_identity(&startup.scope, runtime.process_runner.as_ref());
+            let store = BacklogStore::open_for_repo(backlog_db_path(&cfg, &startup.scope), &identity)?;
+            if let Some(from_namespace) = cli.adopt_namespace.as_deref() {
+                let moved = store.move_namespace_tasks(from_namespace, store.namespace())?;
+                runtime.terminal.write_line(&format!(
+                    "moved {moved} task(s) from {from_namespace} into {}",
+                    store.namespace()
+                ))?;
+            }
+            if cli.list_namespaces {
+                for summary in store.list_namespaces()? {
+                    let marker = if summary.repo_namespace == store.namespace() {
+                        "*"
+                    } else {
+                        " "
+                    };
+                    runtime.terminal.write_line(&format!(
+                        "{marker} {} tasks={} active={} root={} remote={}",
+                        summary.repo_namespace,
+                        summary.task_count,
+                        summary.active_count,
+                        summary.repo_root.as_deref().unwrap_or("-"),
+                        summary.remote_url.as_deref().unwrap_or("-"),
+                    ))?;
+                }
+            }
+            return Ok(0);
+        }
+
         if cli.sync_only {
             let mut cfg_for_startup = cfg;
             if !cfg_for_startup.execution.test_mode {
                 let _ = run_startup_audits(runtime, &mut cfg_for_startup, &startup.scope, false)?;
             }
             let db_path = backlog_db_path(&cfg_for_startup, &startup.scope);
-            let snapshot_path = startup
-                .scope
-                .working_dir
-                .join(".cache/gardener/backlog-snapshot.md");
+            let snapshot_path = startup.scope.working_dir.join(SNAPSHOT_PATH);
             if let Some(parent) = snapshot_path.parent() {
                 runtime.file_system.create_dir_all(parent)?;
             }
-            let store = BacklogStore::open(db_path)?;
-            let _ = export_markdown_snapshot(&store, &snapshot_path)?;
+            let identity = resolve_repo_identity(&startup.scope, runtime.process_runner.as_ref());
+            let store = BacklogStore::open_for_repo(db_path, &identity)?;
+            let _ = export_markdown_snapshot(
+                &store,
+                &snapshot_path,
+                &cfg_for_startup.scheduler.priority_aging,
+            )?;
             runtime.terminal.write_line(&format!(
                 "sync complete: snapshot={}",
                 snapshot_path.display()
@@ -351,6 +460,7 @@ pub fn run_with_runtime(
         }
 
         let default_quit_after = if cli.target.is_none()
+            && !cli.watch
             && !cli.prune_only
             && !cli.backlog_only
             && !cli.quality_grades_only
@@ -363,7 +473,35 @@ pub fn run_with_runtime(
             None
         };
 
-        if let Some(target) = cli.target.or(default_quit_after) {
+        let run_target = cli.target.or(default_quit_after);
+        let selection = backlog_cli::RunSelection::new(
+            cli.task.as_deref(),
+            cli.task_id.as_deref(),
+            (&cli.claim_filter).into(),
+        );
+        if cli.dry_run {
+            let mut cfg_for_dry_run = cfg;
+            let profile_loc = triage::profile_path(&startup.scope, &cfg_for_dry_run);
+            let profile = runtime
+                .file_system
+                .exists(&profile_loc)
+                .then(|| repo_intelligence::read_profile(runtime.file_system.as_ref(), &profile_loc))
+                .transpose()?;
+            apply_profile_runtime_preferences(
+                &mut cfg_for_dry_run,
+                profile.as_ref(),
+                cli.parallelism,
+            );
+            run_dry_run(
+                runtime,
+                &startup.scope,
+                &mut cfg_for_dry_run,
+                run_target.map(|target| target as usize),
+                &selection,
+            )?;
+            return Ok(0);
+        }
+        if cli.watch || run_target.is_some() {
             let mut cfg_for_startup = cfg;
             draw_boot_stage(
                 runtime,
@@ -443,7 +581,9 @@ pub fn run_with_runtime(
                 )?;
             }
             let db_path = backlog_db_path(&cfg_for_startup, &startup.scope);
-            let store = BacklogStore::open(db_path)?;
+            let identity = resolve_repo_identity(&startup.scope, runtime.process_runner.as_ref());
+            let store = BacklogStore::open_for_repo(db_path, &identity)?;
+            let claim_filter = selection.claim_filter(&store)?;
             let startup_backlog = store.list_tasks()?;
             emit_record(RecordEntry::BacklogSnapshot(BacklogSnapshotRecord {
                 tasks: startup_backlog
@@ -474,26 +614,58 @@ pub fn run_with_runtime(
                 "WORKING",
                 "Dispatching tasks to workers and streaming progress",
             )?;
-            let completed = run_worker_pool_fsm(
-                runtime,
-                &startup.scope,
-                &cfg_for_startup,
-                &store,
-                runtime.terminal.as_ref(),
-                target as usize,
-                cli.task.as_deref(),
-            )?;
+            usage::reset_run_usage();
+            let completed = if cli.watch {
+                run_watch(
+                    runtime,
+                    &startup.scope,
+                    &cfg_for_startup,
+                    &store,
+                    runtime.terminal.as_ref(),
+                    run_target.map(|target| target as usize),
+                    claim_filter.as_ref(),
+                )?
+                .tasks_completed
+            } else {
+                run_worker_pool_fsm(
+                    runtime,
+                    &startup.scope,
+                    &cfg_for_startup,
+                    &store,
+                    runtime.terminal.as_ref(),
+                    run_target.unwrap_or(1) as usize,
+                    claim_filter.as_ref(),
+                )?
+            };
+            let run_usage = usage::run_usage();
+            append_run_log(
+                "info",
+                "run.usage",
+                json!({
+                    "turns": run_usage.turns,
+                    "input_tokens": run_usage.input_tokens,
+                    "cached_input_tokens": run_usage.cached_input_tokens,
+                    "output_tokens": run_usage.output_tokens,
+                    "cost_usd": run_usage.cost_usd,
+                }),
+            );
             if !runtime.terminal.stdin_is_tty() {
+                let target = run_target.map_or_else(|| "none".to_string(), |t| t.to_string());
                 runtime.terminal.write_line(&structured_fallback_line(
                     "pool",
                     "complete",
-                    &format!("target={target} completed={completed}"),
+                    &format!(
+                        "target={target} completed={completed} tokens={} cost_usd={:.2}",
+                        run_usage.total_tokens(),
+                        run_usage.cost_usd
+                    ),
                 ))?;
             }
             if record_path.is_some() {
                 emit_record(RecordEntry::SessionEnd(replay::recording::SessionEndRecord {
                     completed_tasks: completed as u64,
                     total_duration_ns: 0, // wall-clock timing not tracked at this layer
+                    usage: run_usage,
                 }));
                 replay::recorder::clear_session_recorder();
                 append_run_log("info", "session.recording.finished", json!({}));
@@ -561,10 +733,14 @@ fn draw_boot_stage(
         p0: 0,
         p1: 0,
         p2: 0,
+        held_by_scope: 0,
+        pool_status: None,
+        usage: None,
     };
     let backlog = BacklogView {
         in_progress: vec![format!("INP SYS {stage}")],
         queued: vec![],
+        search: None,
     };
     runtime.terminal.draw_dashboard(&workers, &stats, &backlog)
 }
@@ -663,7 +839,8 @@ fn persist_agent_default(
 
 #[cfg(test)]
 mod tests {
-    use super::{config, repo_intelligence, runtime, triage_discovery};
+    use super::{config, repo_intelligence, runtime, triage_discovery, Cli};
+    use clap::{error::ErrorKind, Parser};
     use std::path::Path;
 
     fn sample_profile(preferred_parallelism: Option<u32>) -> repo_intelligence::RepoIntelligenceProfile {
@@ -695,4 +872,21 @@ mod tests {
 
         assert_eq!(cfg.orchestrator.parallelism, 4);
     }
+
+    #[test]
+    fn adhoc_task_rejects_claim_filters() {
+        let task = ["gardener", "--task", "Fix the login test"];
+        assert!(Cli::try_parse_from(task).is_ok());
+        let filters = [
+            ["--kind", "bugfix"],
+            ["--priority", "P0"],
+            ["--scope", "ui"],
+            ["--source", "ci"],
+        ];
+        for filter in filters {
+            let err = Cli::try_parse_from(task.iter().chain(filter.iter()))
+                .expect_err("filter conflicts with --task");
+            assert_eq!(err.kind(), ErrorKind::ArgumentConflict);
+        }
+    }
 }
@@ -389,10 +389,12 @@ fn recent_worker_tool_commands_nolock(
     events.split_off(events.len() - max_lines)
 }
 
+/// Turn starts per worker, as `(line, worker_id, state, queued_backend)`;
+/// `queued_backend` is set while the turn waits for an agent concurrency slot.
 pub fn recent_worker_state_events(
     from_line: usize,
     max_lines: usize,
-) -> Vec<(usize, String, String)> {
+) -> Vec<(usize, String, String, Option<String>)> {
     let _guard = run_log_activity_lock()
         .lock()
         .expect("run log activity lock");
@@ -402,7 +404,7 @@ pub fn recent_worker_state_events(
 fn recent_worker_state_events_nolock(
     from_line: usize,
     max_lines: usize,
-) -> Vec<(usize, String, String)> {
+) -> Vec<(usize, String, String, Option<String>)> {
     let _ = structured_fallback_line("logging", "recent_worker_state_events_nolock", "starting");
     if max_lines == 0 {
         return Vec::new();
@@ -430,7 +432,7 @@ fn recent_worker_state_events_nolock(
             .get("event_type")
             .and_then(Value::as_str)
             .unwrap_or("");
-        if event_type != "agent.turn.started" {
+        if event_type != "agent.turn.started" && event_type != "agent.turn.queued" {
             continue;
         }
 
@@ -450,7 +452,15 @@ fn recent_worker_state_events_nolock(
             Some(state) if !state.is_empty() => state.to_string(),
             _ => continue,
         };
-        events.push((idx, worker_id, state));
+        let queued_backend = (event_type == "agent.turn.queued").then(|| {
+            value
+                .get("payload")
+                .and_then(|p| p.get("backend"))
+                .and_then(Value::as_str)
+                .unwrap_or("agent")
+                .to_string()
+        });
+        events.push((idx, worker_id, state, queued_backend));
     }
 
     if events.len() <= max_lines {
@@ -779,17 +789,22 @@ mod tests {
             r#"{"event_type":"agent.turn.started","payload":{"worker_id":"worker-1","state":"understand"}}"#,
             r#"{"event_type":"adapter.tool","payload":{"worker_id":"worker-1","kind":"ToolCall","command":"ignored"}}"#,
             r#"{"event_type":"agent.turn.started","payload":{"worker_id":"worker-2","state":"gitting"}}"#,
+            r#"{"event_type":"agent.turn.queued","payload":{"worker_id":"worker-1","state":"doing","backend":"codex"}}"#,
         ];
         std::fs::write(&path, log_lines.join("\n")).expect("seed log");
 
         let lines = super::recent_worker_state_events_nolock(0, 8);
-        assert_eq!(lines.len(), 2);
+        assert_eq!(lines.len(), 3);
         assert_eq!(lines[0].0, 0);
         assert_eq!(lines[0].1, "worker-1");
         assert_eq!(lines[0].2, "understand");
+        assert_eq!(lines[0].3, None);
         assert_eq!(lines[1].0, 2);
         assert_eq!(lines[1].1, "worker-2");
         assert_eq!(lines[1].2, "gitting");
+        assert_eq!(lines[2].0, 3);
+        assert_eq!(lines[2].2, "doing");
+        assert_eq!(lines[2].3.as_deref(), Some("codex"));
         clear_run_logger_nolock();
     }
 
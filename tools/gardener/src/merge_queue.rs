@@ -0,0 +1,578 @@
+use crate::config::{MergeQueueConfig, MergeQueueOrder};
+use crate::logging::append_run_log;
+use crate::priority::Priority;
+use serde_json::json;
+use std::collections::BTreeSet;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Condvar, Mutex, MutexGuard, OnceLock, PoisonError};
+use std::time::Duration;
+
+/// How often a waiting entry re-checks whether its worker was told to stop.
+const TURN_POLL_INTERVAL: Duration = Duration::from_millis(200);
+
+/// PRs waiting to merge, taken in order one turn (or one batch) at a time.
+///
+/// An entry holding the turn rebases onto main, polls mergeability and merges
+/// while the rest wait, so each PR is checked against the main it will land
+/// on. Members of a batch take those steps one after another, each once the
+/// member before it has landed. An entry whose merge fails hands the turn
+/// back while its agent fixes the branch, and rejoins at its old place once it
+/// is ready again.
+#[derive(Debug, Default)]
+pub struct MergeQueue {
+    entries: Mutex<Vec<MergeEntry>>,
+    changed: Condvar,
+    next_seq: AtomicU64,
+    /// Serializes pulling and validating main in the shared checkout.
+    main_checkout: Mutex<()>,
+}
+
+#[derive(Debug, Clone)]
+struct MergeEntry {
+    seq: u64,
+    worker_id: String,
+    task_id: String,
+    pr_number: u64,
+    priority: Priority,
+    /// Files the PR changes; entries can share a batch only when these are
+    /// known and disjoint.
+    files: BTreeSet<String>,
+    status: EntryStatus,
+    /// Set once the entry's PR has merged during its current turn.
+    landed: bool,
+    failed_attempts: u32,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum EntryStatus {
+    Waiting,
+    Merging,
+    /// Out of line while its agent fixes the branch after a failed merge.
+    Remediating,
+}
+
+/// Where a worker's PR stands in the queue, for the dashboard.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum QueueSlot {
+    /// 1-based place among the entries waiting for a turn.
+    Waiting { position: usize, of: usize },
+    Merging { pr_number: u64 },
+    Remediating { pr_number: u64, failed_attempts: u32 },
+}
+
+impl QueueSlot {
+    pub fn label(&self) -> String {
+        match self {
+            Self::Waiting { position, of } => format!("merge queue {position} of {of}"),
+            Self::Merging { pr_number } => format!("merging PR #{pr_number}"),
+            Self::Remediating {
+                pr_number,
+                failed_attempts,
+            } => format!("fixing PR #{pr_number} after {failed_attempts} failed merge(s)"),
+        }
+    }
+}
+
+/// A PR's place in the queue. Dropping it leaves the queue.
+#[derive(Debug)]
+pub struct MergeTicket<'a> {
+    queue: &'a MergeQueue,
+    seq: u64,
+}
+
+/// The right to merge, held from the rebase until the merged PR is validated on
+/// main. Dropping it passes the turn on.
+#[derive(Debug)]
+pub struct MergeTurn<'a> {
+    queue: &'a MergeQueue,
+    seq: u64,
+}
+
+/// The queue shared by every worker in this process.
+pub fn merge_queue() -> &'static MergeQueue {
+    static QUEUE: OnceLock<MergeQueue> = OnceLock::new();
+    QUEUE.get_or_init(MergeQueue::default)
+}
+
+impl MergeQueue {
+    fn lock(&self) -> MutexGuard<'_, Vec<MergeEntry>> {
+        self.entries.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+
+    pub fn enqueue(
+        &self,
+        worker_id: &str,
+        task_id: &str,
+        pr_number: u64,
+        priority: Priority,
+        files: Vec<String>,
+    ) -> MergeTicket<'_> {
+        let seq = self.next_seq.fetch_add(1, Ordering::Relaxed);
+        let mut entries = self.lock();
+        entries.push(MergeEntry {
+            seq,
+            worker_id: worker_id.to_string(),
+            task_id: task_id.to_string(),
+            pr_number,
+            priority,
+            files: files.into_iter().collect(),
+            status: EntryStatus::Waiting,
+            landed: false,
+            failed_attempts: 0,
+        });
+        append_run_log(
+            "info",
+            "merge_queue.enqueued",
+            json!({
+                "worker_id": worker_id,
+                "task_id": task_id,
+                "pr_number": pr_number,
+                "priority": priority.as_str(),
+                "queue_len": entries.len(),
+            }),
+        );
+        drop(entries);
+        self.changed.notify_all();
+        MergeTicket { queue: self, seq }
+    }
+
+    /// Where each worker with an entry stands, in queue order.
+    pub fn slots(&self, order: MergeQueueOrder) -> Vec<(String, QueueSlot)> {
+        let entries = self.lock();
+        let waiting = ordered(&entries, order, EntryStatus::Waiting);
+        entries
+            .iter()
+            .map(|entry| {
+                let slot = match entry.status {
+                    EntryStatus::Waiting => QueueSlot::Waiting {
+                        position: waiting
+                            .iter()
+                            .position(|seq| *seq == entry.seq)
+                            .map_or(0, |idx| idx + 1),
+                        of: waiting.len(),
+                    },
+                    EntryStatus::Merging => QueueSlot::Merging {
+                        pr_number: entry.pr_number,
+                    },
+                    EntryStatus::Remediating => QueueSlot::Remediating {
+                        pr_number: entry.pr_number,
+                        failed_attempts: entry.failed_attempts,
+                    },
+                };
+                (entry.worker_id.clone(), slot)
+            })
+            .collect()
+    }
+
+    fn set_status(&self, seq: u64, status: EntryStatus) {
+        let mut entries = self.lock();
+        if let Some(entry) = entries.iter_mut().find(|entry| entry.seq == seq) {
+            entry.status = status;
+        }
+        drop(entries);
+        self.changed.notify_all();
+    }
+
+    fn remove(&self, seq: u64) {
+        let mut entries = self.lock();
+        if let Some(idx) = entries.iter().position(|entry| entry.seq == seq) {
+            let entry = entries.remove(idx);
+            append_run_log(
+                "info",
+                "merge_queue.left",
+                json!({
+                    "worker_id": entry.worker_id,
+                    "task_id": entry.task_id,
+                    "pr_number": entry.pr_number,
+                    "failed_attempts": entry.failed_attempts,
+                    "queue_len": entries.len(),
+                }),
+            );
+        }
+        drop(entries);
+        self.changed.notify_all();
+    }
+}
+
+impl<'a> MergeTicket<'a> {
+    /// Blocks until this entry is at the head of the queue, or in the batch
+    /// admitted with the head, no earlier batch is still merging, and every
+    /// member ahead of it in its batch has landed. Returns
+    /// without the turn once `stop` turns true; the worker's next process
+    /// spawn then reports why it stopped.
+    pub fn wait_turn(&self, cfg: &MergeQueueConfig, stop: &dyn Fn() -> bool) -> MergeTurn<'a> {
+        let queue = self.queue;
+        let mut entries = queue.lock();
+        if let Some(entry) = entries.iter_mut().find(|entry| entry.seq == self.seq) {
+            if entry.status == EntryStatus::Remediating {
+                entry.status = EntryStatus::Waiting;
+            }
+        }
+        queue.changed.notify_all();
+        loop {
+            if !entries
+                .iter()
+                .any(|entry| entry.status == EntryStatus::Merging)
+            {
+                admit_next_batch(&mut entries, cfg);
+                queue.changed.notify_all();
+            }
+            let Some(entry) = entries.iter().find(|entry| entry.seq == self.seq) else {
+                break;
+            };
+            if entry.status == EntryStatus::Merging
+                && earlier_batch_members_landed(&entries, cfg.order, self.seq)
+            {
+                append_run_log(
+                    "info",
+                    "merge_queue.turn.started",
+                    json!({
+                        "worker_id": entry.worker_id,
+                        "task_id": entry.task_id,
+                        "pr_number": entry.pr_number,
+                        "batch": entries
+                            .iter()
+                            .filter(|other| other.status == EntryStatus::Merging)
+                            .map(|other| other.pr_number)
+                            .collect::<Vec<_>>(),
+                    }),
+                );
+                break;
+            }
+            if stop() {
+                append_run_log(
+                    "warn",
+                    "merge_queue.wait_abandoned",
+                    json!({
+                        "worker_id": entry.worker_id,
+                        "task_id": entry.task_id,
+                        "pr_number": entry.pr_number,
+                    }),
+                );
+                break;
+            }
+            entries = queue
+                .changed
+                .wait_timeout(entries, TURN_POLL_INTERVAL)
+                .unwrap_or_else(PoisonError::into_inner)
+                .0;
+        }
+        MergeTurn {
+            queue,
+            seq: self.seq,
+        }
+    }
+
+    /// Replaces the files the PR changes, after a remediation commit, so the
+    /// next batch is formed from what the branch touches now.
+    pub fn update_files(&self, files: Vec<String>) {
+        let mut entries = self.queue.lock();
+        if let Some(entry) = entries.iter_mut().find(|entry| entry.seq == self.seq) {
+            entry.files = files.into_iter().collect();
+            append_run_log(
+                "debug",
+                "merge_queue.files_updated",
+                json!({
+                    "worker_id": entry.worker_id,
+                    "task_id": entry.task_id,
+                    "pr_number": entry.pr_number,
+                    "files": entry.files.len(),
+                }),
+            );
+        }
+    }
+
+    /// Counts a failed merge against this entry and returns the total so far.
+    /// The count follows the entry through every turn it takes.
+    pub fn record_failed_attempt(&self) -> u32 {
+        let mut entries = self.queue.lock();
+        entries
+            .iter_mut()
+            .find(|entry| entry.seq == self.seq)
+            .map_or(0, |entry| {
+                entry.failed_attempts += 1;
+                entry.failed_attempts
+            })
+    }
+}
+
+impl Drop for MergeTicket<'_> {
+    fn drop(&mut self) {
+        self.queue.remove(self.seq);
+    }
+}
+
+impl<'a> MergeTurn<'a> {
+    /// Records that this entry's PR merged, letting the next member of its
+    /// batch rebase onto the new main.
+    pub fn mark_landed(&self) {
+        let mut entries = self.queue.lock();
+        if let Some(entry) = entries.iter_mut().find(|entry| entry.seq == self.seq) {
+            entry.landed = true;
+        }
+        drop(entries);
+        self.queue.changed.notify_all();
+    }
+
+    /// Takes the shared main checkout for pulling and validating main; other
+    /// members of the batch wait until the guard is dropped.
+    pub fn lock_main_checkout(&self) -> MutexGuard<'a, ()> {
+        self.queue
+            .main_checkout
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+    }
+
+    /// Hands the turn back so the entry can be fixed out of line; the next
+    /// `wait_turn` puts it back at its place.
+    pub fn yield_for_remediation(self) {
+        self.queue.set_status(self.seq, EntryStatus::Remediating);
+        std::mem::forget(self);
+    }
+}
+
+impl Drop for MergeTurn<'_> {
+    fn drop(&mut self) {
+        let mut entries = self.queue.lock();
+        if let Some(entry) = entries.iter_mut().find(|entry| entry.seq == self.seq) {
+            if entry.status == EntryStatus::Merging {
+                entry.status = EntryStatus::Remediating;
+            }
+        }
+        drop(entries);
+        self.queue.changed.notify_all();
+    }
+}
+
+/// Whether every member of `seq`'s batch that merges before it has landed.
+fn earlier_batch_members_landed(entries: &[MergeEntry], order: MergeQueueOrder, seq: u64) -> bool {
+    ordered(entries, order, EntryStatus::Merging)
+        .into_iter()
+        .take_while(|other| *other != seq)
+        .all(|other| entries.iter().any(|entry| entry.seq == other && entry.landed))
+}
+
+/// Sequence numbers of the entries in `status`, in the order they get a turn.
+fn ordered(entries: &[MergeEntry], order: MergeQueueOrder, status: EntryStatus) -> Vec<u64> {
+    let mut matching = entries
+        .iter()
+        .filter(|entry| entry.status == status)
+        .collect::<Vec<_>>();
+    matching.sort_by_key(|entry| match order {
+        MergeQueueOrder::Fifo => (0, entry.seq),
+        MergeQueueOrder::Priority => (entry.priority.rank(), entry.seq),
+    });
+    matching.iter().map(|entry| entry.seq).collect()
+}
+
+/// Admits the head of the queue, plus the entries right behind it whose files
+/// do not overlap anything already admitted, up to `batch_size`.
+fn admit_next_batch(entries: &mut [MergeEntry], cfg: &MergeQueueConfig) {
+    let order = ordered(entries, cfg.order, EntryStatus::Waiting);
+    let mut admitted_files = BTreeSet::new();
+    for (idx, seq) in order.iter().enumerate() {
+        if idx >= cfg.batch_size.max(1) as usize {
+            break;
+        }
+        let Some(entry) = entries.iter_mut().find(|entry| entry.seq == *seq) else {
+            continue;
+        };
+        if idx > 0 && (entry.files.is_empty() || !entry.files.is_disjoint(&admitted_files)) {
+            break;
+        }
+        entry.status = EntryStatus::Merging;
+        if entry.files.is_empty() {
+            // Nothing is known to be safe alongside a PR with unknown files.
+            break;
+        }
+        admitted_files.extend(entry.files.iter().cloned());
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{MergeQueue, QueueSlot};
+    use crate::config::{MergeQueueConfig, MergeQueueOrder};
+    use crate::priority::Priority;
+    use std::sync::atomic::{AtomicBool, Ordering};
+    use std::sync::{Arc, Mutex};
+    use std::thread;
+    use std::time::Duration;
+
+    fn config(order: MergeQueueOrder, batch_size: u32) -> MergeQueueConfig {
+        MergeQueueConfig { order, batch_size }
+    }
+
+    fn files(paths: &[&str]) -> Vec<String> {
+        paths.iter().map(|path| path.to_string()).collect()
+    }
+
+    /// Queues `prs` behind a held turn, then releases it and returns the order
+    /// the rest merged in.
+    fn merge_order(cfg: MergeQueueConfig, prs: Vec<(u64, Priority)>) -> Vec<u64> {
+        let queue = Arc::new(MergeQueue::default());
+        let head = queue.enqueue("worker-0", "task-0", 1, Priority::P2, Vec::new());
+        let head_turn = head.wait_turn(&cfg, &|| false);
+        let merged = Arc::new(Mutex::new(Vec::new()));
+        let mut handles = Vec::new();
+        for (pr, priority) in prs {
+            let queue = Arc::clone(&queue);
+            let merged = Arc::clone(&merged);
+            let ticket_ready = Arc::new(AtomicBool::new(false));
+            let ready = Arc::clone(&ticket_ready);
+            let cfg = cfg.clone();
+            handles.push(thread::spawn(move || {
+                let ticket = queue.enqueue(
+                    &format!("worker-{pr}"),
+                    &format!("task-{pr}"),
+                    pr,
+                    priority,
+                    Vec::new(),
+                );
+                ready.store(true, Ordering::SeqCst);
+                let _turn = ticket.wait_turn(&cfg, &|| false);
+                merged.lock().expect("merged").push(pr);
+            }));
+            while !ticket_ready.load(Ordering::SeqCst) {
+                thread::yield_now();
+            }
+        }
+        drop(head_turn);
+        drop(head);
+        for handle in handles {
+            handle.join().expect("merge thread");
+        }
+        let order = merged.lock().expect("merged").clone();
+        order
+    }
+
+    #[test]
+    fn fifo_merges_in_arrival_order_and_priority_lets_urgent_prs_ahead() {
+        let prs = vec![(10, Priority::P2), (11, Priority::P0), (12, Priority::P1)];
+        assert_eq!(
+            merge_order(config(MergeQueueOrder::Fifo, 1), prs.clone()),
+            [10, 11, 12]
+        );
+        assert_eq!(
+            merge_order(config(MergeQueueOrder::Priority, 1), prs),
+            [11, 12, 10]
+        );
+    }
+
+    #[test]
+    fn slots_report_positions_and_remediating_entries_step_out_of_line() {
+        let cfg = config(MergeQueueOrder::Fifo, 1);
+        let queue = MergeQueue::default();
+        let first = queue.enqueue("worker-1", "task-1", 7, Priority::P1, Vec::new());
+        let second = queue.enqueue("worker-2", "task-2", 8, Priority::P1, Vec::new());
+        let third = queue.enqueue("worker-3", "task-3", 9, Priority::P1, Vec::new());
+        let turn = first.wait_turn(&cfg, &|| false);
+        assert_eq!(
+            queue.slots(cfg.order),
+            vec![
+                ("worker-1".to_string(), QueueSlot::Merging { pr_number: 7 }),
+                ("worker-2".to_string(), QueueSlot::Waiting { position: 1, of: 2 }),
+                ("worker-3".to_string(), QueueSlot::Waiting { position: 2, of: 2 }),
+            ]
+        );
+
+        // A failed merge hands the turn on without losing the entry's count.
+        assert_eq!(first.record_failed_attempt(), 1);
+        turn.yield_for_remediation();
+        let turn = second.wait_turn(&cfg, &|| false);
+        assert_eq!(
+            queue.slots(cfg.order)[0].1.label(),
+            "fixing PR #7 after 1 failed merge(s)"
+        );
+        drop(turn);
+        drop(second);
+
+        // Back in line, the earlier entry goes ahead of the later one again.
+        let turn = first.wait_turn(&cfg, &|| false);
+        assert_eq!(first.record_failed_attempt(), 2);
+        drop(turn);
+        drop(first);
+        assert_eq!(
+            queue.slots(cfg.order),
+            vec![("worker-3".to_string(), QueueSlot::Waiting { position: 1, of: 1 })]
+        );
+        drop(third);
+        assert!(queue.slots(cfg.order).is_empty());
+    }
+
+    #[test]
+    fn batches_admit_prs_with_disjoint_files_together() {
+        let cfg = config(MergeQueueOrder::Fifo, 3);
+        let queue = MergeQueue::default();
+        let a = queue.enqueue("worker-1", "task-1", 1, Priority::P1, files(&["src/a.rs"]));
+        let b = queue.enqueue("worker-2", "task-2", 2, Priority::P1, files(&["src/b.rs"]));
+        let c = queue.enqueue("worker-3", "task-3", 3, Priority::P1, files(&["src/a.rs"]));
+        let turn_a = a.wait_turn(&cfg, &|| false);
+        turn_a.mark_landed();
+        let turn_b = b.wait_turn(&cfg, &|| false);
+        let merging = queue
+            .slots(cfg.order)
+            .into_iter()
+            .filter(|(_, slot)| matches!(slot, QueueSlot::Merging { .. }))
+            .count();
+        assert_eq!(merging, 2, "c touches a.rs and waits for the next batch");
+
+        let waiter = thread::scope(|scope| {
+            let handle = scope.spawn(|| {
+                let _turn = c.wait_turn(&cfg, &|| false);
+            });
+            thread::sleep(Duration::from_millis(50));
+            assert!(!handle.is_finished(), "c waits while the batch merges");
+            drop(turn_a);
+            drop(a);
+            drop(turn_b);
+            drop(b);
+            handle.join()
+        });
+        assert!(waiter.is_ok());
+    }
+
+    #[test]
+    fn batch_members_merge_one_after_another_on_their_current_files() {
+        let cfg = config(MergeQueueOrder::Fifo, 3);
+        let queue = MergeQueue::default();
+        let a = queue.enqueue("worker-1", "task-1", 1, Priority::P1, files(&["src/a.rs"]));
+        let b = queue.enqueue("worker-2", "task-2", 2, Priority::P1, files(&["src/b.rs"]));
+        let c = queue.enqueue("worker-3", "task-3", 3, Priority::P1, files(&["src/c.rs"]));
+        // A remediation commit made c touch a.rs, so it no longer fits the batch.
+        c.update_files(files(&["src/c.rs", "src/a.rs"]));
+        let turn_a = a.wait_turn(&cfg, &|| false);
+        let merging = queue
+            .slots(cfg.order)
+            .into_iter()
+            .filter(|(_, slot)| matches!(slot, QueueSlot::Merging { .. }))
+            .count();
+        assert_eq!(merging, 2);
+
+        let rebased = thread::scope(|scope| {
+            let handle = scope.spawn(|| {
+                let _turn = b.wait_turn(&cfg, &|| false);
+            });
+            thread::sleep(Duration::from_millis(50));
+            assert!(!handle.is_finished(), "b rebases only after a has merged");
+            turn_a.mark_landed();
+            handle.join()
+        });
+        assert!(rebased.is_ok());
+    }
+
+    #[test]
+    fn stopped_worker_gives_up_waiting() {
+        let cfg = config(MergeQueueOrder::Fifo, 1);
+        let queue = MergeQueue::default();
+        let head = queue.enqueue("worker-1", "task-1", 1, Priority::P1, Vec::new());
+        let _head_turn = head.wait_turn(&cfg, &|| false);
+        let next = queue.enqueue("worker-2", "task-2", 2, Priority::P1, Vec::new());
+        let stop = AtomicBool::new(true);
+        let _turn = next.wait_turn(&cfg, &|| stop.load(Ordering::SeqCst));
+        assert_eq!(
+            queue.slots(cfg.order)[1].1,
+            QueueSlot::Waiting { position: 1, of: 1 }
+        );
+    }
+}
@@ -46,6 +46,7 @@ pub fn reconcile_open_prs() -> PrAuditSummary {
             "number,headRefName".to_string(),
         ],
         cwd: Some(cwd.clone()),
+        timeout: None,
     }) {
         Ok(out) if out.exit_code == 0 => out,
         Ok(out) => {
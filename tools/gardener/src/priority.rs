@@ -16,6 +16,24 @@ impl Priority {
         }
     }
 
+    /// Claim order: lower ranks are claimed first.
+    pub fn rank(self) -> u8 {
+        match self {
+            Self::P0 => 0,
+            Self::P1 => 1,
+            Self::P2 => 2,
+        }
+    }
+
+    /// The more urgent of two priorities.
+    pub fn max_urgency(self, other: Self) -> Self {
+        if other.rank() < self.rank() {
+            other
+        } else {
+            self
+        }
+    }
+
     pub fn from_db(value: &str) -> Option<Self> {
         match value {
             "P0" => Some(Self::P0),
@@ -26,9 +44,46 @@ impl Priority {
     }
 }
 
+/// Promotes waiting tasks so a steady stream of higher-priority work cannot
+/// starve them. Ages are measured from `created_at`; a threshold of zero
+/// disables that step.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub struct PriorityAging {
+    /// Age after which a P2 task is claimed as P1.
+    pub p2_to_p1_seconds: u64,
+    /// Age after which a P1 task, including an aged P2 task, is claimed as P0.
+    pub p1_to_p0_seconds: u64,
+}
+
+impl PriorityAging {
+    pub fn effective(&self, priority: Priority, created_at: i64, now: i64) -> Priority {
+        let (p0_cutoff, p1_cutoff) = self.cutoffs(now);
+        let reached = |cutoff: Option<i64>| cutoff.is_some_and(|cutoff| created_at <= cutoff);
+        let aged = match priority {
+            Priority::P2 if reached(p1_cutoff) => Priority::P1,
+            other => other,
+        };
+        match aged {
+            Priority::P1 if reached(p0_cutoff) => Priority::P0,
+            other => other,
+        }
+    }
+
+    /// The latest `created_at` (unix millis) that still ages a task into P0
+    /// and into P1 respectively, or `None` when that step is disabled.
+    pub fn cutoffs(&self, now: i64) -> (Option<i64>, Option<i64>) {
+        let cutoff = |seconds: u64| {
+            (seconds > 0).then(|| {
+                now.saturating_sub(i64::try_from(seconds).unwrap_or(i64::MAX).saturating_mul(1000))
+            })
+        };
+        (cutoff(self.p1_to_p0_seconds), cutoff(self.p2_to_p1_seconds))
+    }
+}
+
 #[cfg(test)]
 mod tests {
-    use super::Priority;
+    use super::{Priority, PriorityAging};
 
     #[test]
     fn db_round_trip_strings_are_stable() {
@@ -37,4 +92,30 @@ mod tests {
         }
         assert_eq!(Priority::from_db("bad"), None);
     }
+
+    #[test]
+    fn aging_promotes_one_level_per_threshold() {
+        let day = 86_400_000;
+        let aging = PriorityAging {
+            p2_to_p1_seconds: 86_400,
+            p1_to_p0_seconds: 3 * 86_400,
+        };
+        let now = 10 * day;
+        assert_eq!(aging.effective(Priority::P2, now, now), Priority::P2);
+        assert_eq!(aging.effective(Priority::P2, now - day, now), Priority::P1);
+        assert_eq!(aging.effective(Priority::P1, now - 2 * day, now), Priority::P1);
+        assert_eq!(aging.effective(Priority::P2, now - 3 * day, now), Priority::P0);
+        assert_eq!(aging.effective(Priority::P0, now, now), Priority::P0);
+
+        let p2_only = PriorityAging {
+            p2_to_p1_seconds: 86_400,
+            p1_to_p0_seconds: 0,
+        };
+        assert_eq!(p2_only.effective(Priority::P2, 0, now), Priority::P1);
+        assert_eq!(p2_only.cutoffs(now), (None, Some(now - day)));
+        assert_eq!(
+            PriorityAging::default().effective(Priority::P2, 0, now),
+            Priority::P2
+        );
+    }
 }
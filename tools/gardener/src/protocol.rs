@@ -38,6 +38,64 @@ pub struct StepResult {
     pub events: Vec<AgentEvent>,
     pub payload: Value,
     pub diagnostics: Vec<String>,
+    /// Tokens the backend reported for the turn; all zero when it reported none.
+    #[serde(default)]
+    pub usage: TokenUsage,
+}
+
+/// Token counts for one agent turn. `input_tokens` includes the cached ones.
+#[derive(Debug, Clone, Copy, PartialEq, Default, Serialize, Deserialize)]
+pub struct TokenUsage {
+    pub input_tokens: u64,
+    pub cached_input_tokens: u64,
+    pub output_tokens: u64,
+    /// Cost the backend itself reported, when it reports one (claude does).
+    pub reported_cost_usd: Option<f64>,
+}
+
+impl TokenUsage {
+    pub fn total_tokens(&self) -> u64 {
+        self.input_tokens.saturating_add(self.output_tokens)
+    }
+}
+
+/// Usage from claude's terminal `result` event. Its `input_tokens` leave out
+/// cache reads and writes, which are added back in here.
+pub fn claude_usage(result: &Value) -> TokenUsage {
+    let usage = result.get("usage");
+    let count = |field: &str| {
+        usage
+            .and_then(|usage| usage.get(field))
+            .and_then(Value::as_u64)
+            .unwrap_or(0)
+    };
+    let cache_read = count("cache_read_input_tokens");
+    TokenUsage {
+        input_tokens: count("input_tokens")
+            .saturating_add(count("cache_creation_input_tokens"))
+            .saturating_add(cache_read),
+        cached_input_tokens: cache_read,
+        output_tokens: count("output_tokens"),
+        reported_cost_usd: result.get("total_cost_usd").and_then(Value::as_f64),
+    }
+}
+
+/// Usage summed over every codex `turn.completed` event of a run.
+pub fn codex_usage(raw_events: &[Value]) -> TokenUsage {
+    let mut total = TokenUsage::default();
+    for usage in raw_events
+        .iter()
+        .filter(|event| event.get("type").and_then(Value::as_str) == Some("turn.completed"))
+        .filter_map(|event| event.get("usage"))
+    {
+        let count = |field: &str| usage.get(field).and_then(Value::as_u64).unwrap_or(0);
+        total.input_tokens = total.input_tokens.saturating_add(count("input_tokens"));
+        total.cached_input_tokens = total
+            .cached_input_tokens
+            .saturating_add(count("cached_input_tokens"));
+        total.output_tokens = total.output_tokens.saturating_add(count("output_tokens"));
+    }
+    total
 }
 
 pub fn map_codex_event(raw: &Value) -> AgentEvent {
@@ -112,7 +170,10 @@ pub fn parse_json_records(input: &str) -> Result<Vec<Value>, GardenerError> {
 
 #[cfg(test)]
 mod tests {
-    use super::{map_codex_event, parse_jsonl, parse_json_records, AgentEventKind};
+    use super::{
+        claude_usage, codex_usage, map_codex_event, parse_jsonl, parse_json_records,
+        AgentEventKind, TokenUsage,
+    };
     use serde_json::json;
 
     #[test]
@@ -142,4 +203,44 @@ mod tests {
         let err = parse_json_records("{\"type\":\"thread.started\"}\n{bad").expect_err("invalid");
         assert!(format!("{err}").contains("invalid json stream"));
     }
+
+    #[test]
+    fn claude_usage_counts_cache_tokens_as_input() {
+        let result = json!({
+            "type": "result",
+            "subtype": "success",
+            "total_cost_usd": 0.25,
+            "usage": {
+                "input_tokens": 10,
+                "cache_creation_input_tokens": 100,
+                "cache_read_input_tokens": 2000,
+                "output_tokens": 300
+            }
+        });
+        assert_eq!(
+            claude_usage(&result),
+            TokenUsage {
+                input_tokens: 2110,
+                cached_input_tokens: 2000,
+                output_tokens: 300,
+                reported_cost_usd: Some(0.25),
+            }
+        );
+        assert_eq!(claude_usage(&json!({"type": "result"})), TokenUsage::default());
+    }
+
+    #[test]
+    fn codex_usage_sums_turn_completed_events() {
+        let events = [
+            json!({"type": "turn.completed", "usage": {"input_tokens": 1000, "cached_input_tokens": 400, "output_tokens": 50}}),
+            json!({"type": "item.completed", "usage": {"input_tokens": 9999}}),
+            json!({"type": "turn.completed", "usage": {"input_tokens": 500, "output_tokens": 25}}),
+        ];
+        let usage = codex_usage(&events);
+        assert_eq!(usage.input_tokens, 1500);
+        assert_eq!(usage.cached_input_tokens, 400);
+        assert_eq!(usage.output_tokens, 75);
+        assert_eq!(usage.total_tokens(), 1575);
+        assert_eq!(usage.reported_cost_usd, None);
+    }
 }
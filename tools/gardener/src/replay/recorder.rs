@@ -224,6 +224,7 @@ mod tests {
                 program: "echo".to_string(),
                 args: vec!["hello".to_string()],
                 cwd: None,
+                timeout: None,
             })
             .expect("test");
         let out = runner.wait(handle).expect("test");
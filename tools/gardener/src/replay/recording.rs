@@ -4,7 +4,9 @@
 
 use crate::backlog_store::BacklogTask;
 use crate::priority::Priority;
+use crate::protocol::TokenUsage;
 use crate::task_identity::TaskKind;
+use crate::usage::UsageTotals;
 use serde::{Deserialize, Serialize};
 use serde_json::Value;
 
@@ -30,6 +32,16 @@ pub struct BacklogTaskRecord {
     pub related_branch: Option<String>,
     pub attempt_count: i64,
     pub created_at: i64,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub blocked_by: Vec<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub blocked_reason: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub status_note: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub not_before: Option<i64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub failure_reason: Option<String>,
 }
 
 impl From<BacklogTask> for BacklogTaskRecord {
@@ -51,6 +63,11 @@ impl From<BacklogTask> for BacklogTaskRecord {
             related_branch: t.related_branch,
             attempt_count: t.attempt_count,
             created_at: t.created_at,
+            blocked_by: t.blocked_by,
+            blocked_reason: t.blocked_reason,
+            status_note: t.status_note,
+            not_before: t.not_before,
+            failure_reason: t.failure_reason,
         }
     }
 }
@@ -161,6 +178,9 @@ pub struct AgentTurnRecord {
     /// The `step.payload` value consumed by the FSM.
     pub payload: Value,
     pub diagnostic_count: usize,
+    /// Absent in recordings made before usage was tracked.
+    #[serde(default)]
+    pub usage: TokenUsage,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -178,6 +198,9 @@ pub struct BacklogMutationRecord {
 pub struct SessionEndRecord {
     pub completed_tasks: u64,
     pub total_duration_ns: u64,
+    /// Token and cost totals over every agent turn of the session.
+    #[serde(default)]
+    pub usage: UsageTotals,
 }
 
 #[cfg(test)]
@@ -276,6 +276,7 @@ impl ReplayAgentAdapter {
                 events: Vec::new(),
                 payload: t.payload,
                 diagnostics: Vec::new(),
+                usage: t.usage,
             })
             .collect();
         Self {
@@ -382,6 +383,7 @@ pub fn replay_worker_task(
         worker_id,
         &task.task_id,
         &task.title,
+        task.priority,
         task.attempt_count,
     )?;
 
@@ -511,6 +513,7 @@ mod tests {
                 terminal: "success".to_string(),
                 payload: serde_json::json!({ "terminal": "success" }),
                 diagnostic_count: 0,
+                usage: crate::protocol::TokenUsage::default(),
             }),
             RecordEntry::BacklogMutation(BacklogMutationRecord {
                 seq: 4,
@@ -614,6 +617,7 @@ mod tests {
                 program: "echo".to_string(),
                 args: vec!["first".to_string()],
                 cwd: Some("/tmp".into()),
+                timeout: None,
             })
             .expect("spawn");
         let output = runner
@@ -673,6 +677,7 @@ mod tests {
                     program: "printf".to_string(),
                     args: vec!["oops".to_string()],
                     cwd: None,
+                    timeout: None,
                 })
                 .is_ok()
         );
@@ -0,0 +1,137 @@
+use crate::git::GitClient;
+use crate::logging::append_run_log;
+use crate::runtime::ProcessRunner;
+use crate::types::RuntimeScope;
+use serde_json::json;
+use sha2::{Digest, Sha256};
+use std::path::{Path, PathBuf};
+
+const NAMESPACE_HASH_CHARS: usize = 16;
+
+/// Identifies the repository a backlog belongs to. The namespace is derived from
+/// the repo root and the `origin` remote so one shared database can serve several
+/// repositories without tasks leaking between them.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RepoIdentity {
+    pub repo_root: PathBuf,
+    pub remote_url: Option<String>,
+    pub namespace: String,
+}
+
+impl RepoIdentity {
+    pub fn new(repo_root: impl Into<PathBuf>, remote_url: Option<String>) -> Self {
+        let repo_root = repo_root.into();
+        let namespace = namespace_for(&repo_root, remote_url.as_deref());
+        Self {
+            repo_root,
+            remote_url,
+            namespace,
+        }
+    }
+}
+
+pub fn resolve_repo_identity(
+    scope: &RuntimeScope,
+    process_runner: &dyn ProcessRunner,
+) -> RepoIdentity {
+    let repo_root = scope
+        .repo_root
+        .clone()
+        .unwrap_or_else(|| scope.working_dir.clone());
+    let remote_url = match GitClient::new(process_runner, &repo_root).remote_url("origin") {
+        Ok(url) => url,
+        Err(err) => {
+            append_run_log(
+                "warn",
+                "repo_identity.remote_lookup_failed",
+                json!({
+                    "repo_root": repo_root.display().to_string(),
+                    "error": err.to_string(),
+                }),
+            );
+            None
+        }
+    };
+    let identity = RepoIdentity::new(repo_root, remote_url);
+    append_run_log(
+        "info",
+        "repo_identity.resolved",
+        json!({
+            "repo_root": identity.repo_root.display().to_string(),
+            "remote_url": identity.remote_url,
+            "namespace": identity.namespace,
+        }),
+    );
+    identity
+}
+
+pub fn namespace_for(repo_root: &Path, remote_url: Option<&str>) -> String {
+    let mut digest = Sha256::new();
+    digest.update(repo_root.display().to_string().as_bytes());
+    digest.update(b"\n");
+    digest.update(remote_url.unwrap_or_default().trim().as_bytes());
+    let hex = digest
+        .finalize()
+        .iter()
+        .map(|byte| format!("{byte:02x}"))
+        .collect::<String>();
+    format!("repo:{}", &hex[..NAMESPACE_HASH_CHARS])
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{namespace_for, resolve_repo_identity, RepoIdentity};
+    use crate::runtime::{FakeProcessRunner, ProcessOutput};
+    use crate::types::RuntimeScope;
+    use std::path::{Path, PathBuf};
+
+    #[test]
+    fn namespace_depends_on_root_and_remote() {
+        let root = Path::new("/work/app");
+        let with_remote = namespace_for(root, Some("git@github.com:acme/app.git"));
+        assert!(with_remote.starts_with("repo:"));
+        assert_eq!(with_remote.len(), "repo:".len() + 16);
+        assert_eq!(
+            with_remote,
+            namespace_for(root, Some(" git@github.com:acme/app.git\n"))
+        );
+        assert_ne!(with_remote, namespace_for(root, None));
+        assert_ne!(
+            with_remote,
+            namespace_for(Path::new("/work/other"), Some("git@github.com:acme/app.git"))
+        );
+    }
+
+    #[test]
+    fn resolve_reads_origin_remote_and_tolerates_missing_remote() {
+        let scope = RuntimeScope {
+            process_cwd: PathBuf::from("/work/app"),
+            repo_root: Some(PathBuf::from("/work/app")),
+            working_dir: PathBuf::from("/work/app/packages/api"),
+        };
+        let runner = FakeProcessRunner::default();
+        runner.push_response(Ok(ProcessOutput {
+            exit_code: 0,
+            stdout: "https://github.com/acme/app.git\n".to_string(),
+            stderr: String::new(),
+        }));
+        runner.push_response(Ok(ProcessOutput {
+            exit_code: 2,
+            stdout: String::new(),
+            stderr: "error: No such remote 'origin'".to_string(),
+        }));
+
+        let with_remote = resolve_repo_identity(&scope, &runner);
+        assert_eq!(
+            with_remote,
+            RepoIdentity::new("/work/app", Some("https://github.com/acme/app.git".to_string()))
+        );
+        let spawned = runner.spawned();
+        assert_eq!(spawned[0].args, vec!["remote", "get-url", "origin"]);
+        assert_eq!(spawned[0].cwd, Some(PathBuf::from("/work/app")));
+
+        let without_remote = resolve_repo_identity(&scope, &runner);
+        assert_eq!(without_remote.remote_url, None);
+        assert_ne!(without_remote.namespace, with_remote.namespace);
+    }
+}
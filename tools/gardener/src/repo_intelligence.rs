@@ -124,6 +124,7 @@ pub fn current_head_sha(
         program: "git".to_string(),
         args: vec!["rev-parse".to_string(), "HEAD".to_string()],
         cwd: Some(cwd.to_path_buf()),
+        timeout: None,
     })?;
     if out.exit_code != 0 {
         append_run_log(
@@ -162,6 +163,7 @@ pub fn commits_since_profile_head(
             format!("{profile_head}..HEAD"),
         ],
         cwd: Some(cwd.to_path_buf()),
+        timeout: None,
     })?;
     if out.exit_code != 0 {
         append_run_log(
@@ -5,17 +5,23 @@ use crate::tui::{
     render_triage, BacklogView, QueueStats, WorkerRow,
 };
 use serde_json::json;
+use std::cell::RefCell;
 use std::collections::{HashMap, VecDeque};
 use std::io::{Read, Write};
 use std::path::{Path, PathBuf};
 use std::process::{Command, Stdio};
-use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
 use std::sync::{Arc, Mutex, OnceLock};
 use std::thread::JoinHandle;
-use std::time::SystemTime;
+use std::time::{Duration, Instant, SystemTime};
 
 const RESIZE_SENTINEL_KEY: char = '\0';
 pub const INTERRUPT_SENTINEL_KEY: char = '\x03';
+pub const ENTER_KEY: char = '\n';
+pub const ESCAPE_KEY: char = '\x1b';
+pub const BACKSPACE_KEY: char = '\x08';
+pub const LEASE_LOST_MESSAGE: &str = "worker lease lost to another owner";
+pub const INTERRUPT_MESSAGE: &str = "user interrupt requested (q/Ctrl-C)";
 const COPY_SHORTCUT_KEY: char = 'c';
 const DEFAULT_TERMINAL_WIDTH: u16 = 120;
 const DEFAULT_TERMINAL_HEIGHT: u16 = 30;
@@ -25,6 +31,9 @@ pub struct ProcessRequest {
     pub program: String,
     pub args: Vec<String>,
     pub cwd: Option<PathBuf>,
+    /// Wall-clock budget. Once it runs out the whole process group is killed
+    /// and the wait fails with `GardenerError::Timeout`.
+    pub timeout: Option<Duration>,
 }
 
 #[derive(Debug, Clone, PartialEq, Eq)]
@@ -126,7 +135,66 @@ pub trait Terminal: Send + Sync {
 }
 
 static INTERRUPT_REQUESTED: AtomicBool = AtomicBool::new(false);
+/// Shutdown requests (SIGINT/SIGTERM, or `q`/Ctrl+C read by the live key
+/// listener) received while a worker pool could drain.
+static SHUTDOWN_REQUESTS: AtomicUsize = AtomicUsize::new(0);
+/// Number of live `DrainEnabled` guards; while zero, a shutdown request
+/// interrupts or exits right away as it did before drain existed.
+static DRAIN_ENABLED: AtomicUsize = AtomicUsize::new(0);
+/// Process groups of running children, killed on a hard exit.
+static LIVE_PROCESS_GROUPS: OnceLock<Mutex<Vec<u32>>> = OnceLock::new();
+
+thread_local! {
+    static WORKER_ABORT: RefCell<Option<Arc<AtomicBool>>> = const { RefCell::new(None) };
+    static WORKER_SHUTDOWN: RefCell<Option<Arc<PoolShutdown>>> = const { RefCell::new(None) };
+}
+
+/// Drain and interrupt state of one worker pool, shared with its worker
+/// threads. Draining workers stop at the next state boundary; interrupting
+/// ones have their running process tree killed.
+#[derive(Debug, Default)]
+pub struct PoolShutdown {
+    draining: AtomicBool,
+    interrupting: AtomicBool,
+}
+
+impl PoolShutdown {
+    pub fn drain(&self) {
+        self.draining.store(true, Ordering::SeqCst);
+    }
+
+    pub fn interrupt(&self) {
+        self.draining.store(true, Ordering::SeqCst);
+        self.interrupting.store(true, Ordering::SeqCst);
+    }
+
+    pub fn is_draining(&self) -> bool {
+        self.draining.load(Ordering::SeqCst)
+    }
+
+    pub fn is_interrupting(&self) -> bool {
+        self.interrupting.load(Ordering::SeqCst)
+    }
+}
+
+/// Routes shutdown requests through a pool's drain for as long as it lives.
+pub struct DrainEnabled(());
+
+impl DrainEnabled {
+    pub fn acquire() -> Self {
+        DRAIN_ENABLED.fetch_add(1, Ordering::SeqCst);
+        Self(())
+    }
+}
+
+impl Drop for DrainEnabled {
+    fn drop(&mut self) {
+        DRAIN_ENABLED.fetch_sub(1, Ordering::SeqCst);
+    }
+}
 pub static KEY_LISTENER_ACTIVE: AtomicBool = AtomicBool::new(false);
+/// While a text box has focus, `q` is typed rather than treated as quit.
+static TEXT_INPUT_ACTIVE: AtomicBool = AtomicBool::new(false);
 static KEY_QUEUE: OnceLock<Mutex<VecDeque<char>>> = OnceLock::new();
 static KEY_LISTENER: OnceLock<Mutex<Option<KeyListenerState>>> = OnceLock::new();
 
@@ -179,17 +247,14 @@ fn start_key_listener_if_needed() {
                 continue;
             };
             if let crossterm::event::Event::Key(key) = event {
-                if let crossterm::event::KeyCode::Char(c) = key.code {
-                    if key
-                        .modifiers
-                        .contains(crossterm::event::KeyModifiers::CONTROL)
-                        && is_copy_shortcut_key(c)
+                if let Some(c) = key_event_char(key) {
+                    enqueue_key(c);
+                    if c == INTERRUPT_SENTINEL_KEY
+                        || (c == 'q' && !TEXT_INPUT_ACTIVE.load(Ordering::SeqCst))
                     {
-                        enqueue_key(INTERRUPT_SENTINEL_KEY);
-                        request_interrupt();
-                    } else {
-                        enqueue_key(c);
-                        if c == 'q' {
+                        if DRAIN_ENABLED.load(Ordering::SeqCst) > 0 {
+                            request_shutdown();
+                        } else {
                             request_interrupt();
                         }
                     }
@@ -214,6 +279,31 @@ fn stop_key_listener() {
     append_run_log("debug", "runtime.key_listener.stopped", json!({}));
 }
 
+/// Maps a key press to the char handed to `poll_key`: printable keys as
+/// themselves, Ctrl+C as the interrupt sentinel, and Enter, Esc and Backspace
+/// as their control characters.
+fn key_event_char(key: crossterm::event::KeyEvent) -> Option<char> {
+    match key.code {
+        crossterm::event::KeyCode::Char(c)
+            if key
+                .modifiers
+                .contains(crossterm::event::KeyModifiers::CONTROL)
+                && is_copy_shortcut_key(c) =>
+        {
+            Some(INTERRUPT_SENTINEL_KEY)
+        }
+        crossterm::event::KeyCode::Char(c) => Some(c),
+        crossterm::event::KeyCode::Enter => Some(ENTER_KEY),
+        crossterm::event::KeyCode::Esc => Some(ESCAPE_KEY),
+        crossterm::event::KeyCode::Backspace => Some(BACKSPACE_KEY),
+        _ => None,
+    }
+}
+
+pub fn set_text_input_active(active: bool) {
+    TEXT_INPUT_ACTIVE.store(active, Ordering::SeqCst);
+}
+
 pub fn request_interrupt() {
     INTERRUPT_REQUESTED.store(true, Ordering::SeqCst);
 }
@@ -222,6 +312,138 @@ pub fn clear_interrupt() {
     INTERRUPT_REQUESTED.store(false, Ordering::SeqCst);
 }
 
+/// Counts one more shutdown request and returns the total so far: the first
+/// asks a running pool to drain, the second to interrupt its workers.
+pub fn request_shutdown() -> usize {
+    SHUTDOWN_REQUESTS.fetch_add(1, Ordering::SeqCst) + 1
+}
+
+pub fn shutdown_requests() -> usize {
+    SHUTDOWN_REQUESTS.load(Ordering::SeqCst)
+}
+
+pub fn clear_shutdown_requests() {
+    SHUTDOWN_REQUESTS.store(0, Ordering::SeqCst);
+}
+
+/// Routes SIGINT and SIGTERM into `request_shutdown` while a pool can drain.
+/// Otherwise the signal kills any running child process trees and exits, as
+/// the default handler would have.
+#[cfg(unix)]
+pub fn install_shutdown_signal_handlers() -> Result<(), GardenerError> {
+    use signal_hook::consts::{SIGINT, SIGTERM};
+    static INSTALLED: OnceLock<()> = OnceLock::new();
+    append_run_log("debug", "runtime.signals.install", json!({}));
+    if INSTALLED.set(()).is_err() {
+        return Ok(());
+    }
+    let mut signals = signal_hook::iterator::Signals::new([SIGINT, SIGTERM])
+        .map_err(|e| GardenerError::Io(e.to_string()))?;
+    std::thread::spawn(move || {
+        for signal in signals.forever() {
+            if DRAIN_ENABLED.load(Ordering::SeqCst) > 0 {
+                let requests = request_shutdown();
+                append_run_log(
+                    "warn",
+                    "runtime.signal.shutdown_requested",
+                    json!({ "signal": signal, "requests": requests }),
+                );
+                continue;
+            }
+            append_run_log("warn", "runtime.signal.exit", json!({ "signal": signal }));
+            kill_live_process_groups();
+            let _ = close_live_terminal();
+            std::process::exit(128 + signal);
+        }
+    });
+    Ok(())
+}
+
+#[cfg(not(unix))]
+pub fn install_shutdown_signal_handlers() -> Result<(), GardenerError> {
+    Ok(())
+}
+
+/// Installs the abort flag for processes run from the current worker thread.
+/// Unlike `request_interrupt`, raising it only stops this worker.
+pub fn set_worker_abort_flag(flag: Option<Arc<AtomicBool>>) {
+    WORKER_ABORT.with(|cell| *cell.borrow_mut() = flag);
+}
+
+fn worker_abort_requested() -> bool {
+    WORKER_ABORT.with(|cell| {
+        cell.borrow()
+            .as_ref()
+            .is_some_and(|flag| flag.load(Ordering::SeqCst))
+    })
+}
+
+/// Installs the shutdown state of the pool that owns the current worker thread.
+pub fn set_worker_shutdown(shutdown: Option<Arc<PoolShutdown>>) {
+    WORKER_SHUTDOWN.with(|cell| *cell.borrow_mut() = shutdown);
+}
+
+/// True once the pool running the current worker thread has started draining;
+/// the worker should stop at its next state boundary.
+pub fn worker_drain_requested() -> bool {
+    WORKER_SHUTDOWN.with(|cell| cell.borrow().as_ref().is_some_and(|pool| pool.is_draining()))
+}
+
+fn worker_interrupt_requested() -> bool {
+    WORKER_SHUTDOWN.with(|cell| {
+        cell.borrow()
+            .as_ref()
+            .is_some_and(|pool| pool.is_interrupting())
+    })
+}
+
+/// True once the current worker lost its lease or the pool is interrupting;
+/// the next process it spawns would be refused.
+pub fn worker_stop_requested() -> bool {
+    worker_abort_requested() || worker_interrupt_requested()
+}
+
+fn live_process_groups() -> &'static Mutex<Vec<u32>> {
+    LIVE_PROCESS_GROUPS.get_or_init(|| Mutex::new(Vec::new()))
+}
+
+fn forget_process_group(pid: u32) {
+    live_process_groups()
+        .lock()
+        .unwrap_or_else(std::sync::PoisonError::into_inner)
+        .retain(|live| *live != pid);
+}
+
+fn kill_live_process_groups() {
+    let groups = std::mem::take(
+        &mut *live_process_groups()
+            .lock()
+            .unwrap_or_else(std::sync::PoisonError::into_inner),
+    );
+    for pid in groups {
+        signal_process_group(pid);
+    }
+}
+
+#[cfg(unix)]
+fn signal_process_group(pid: u32) {
+    // SAFETY: `killpg` only sends a signal; `pid` leads the group the child was
+    // spawned into, so nothing outside that tree is hit.
+    let _ = unsafe { libc::killpg(pid as libc::pid_t, libc::SIGKILL) };
+}
+
+#[cfg(not(unix))]
+fn signal_process_group(_pid: u32) {}
+
+/// Kills a child together with everything it started. Children run in their
+/// own process group, so agents' tool subprocesses go down with them.
+fn kill_process_tree(child: &mut std::process::Child) {
+    signal_process_group(child.id());
+    forget_process_group(child.id());
+    let _ = child.kill();
+    let _ = child.wait();
+}
+
 pub struct ProductionClock;
 
 impl Clock for ProductionClock {
@@ -303,6 +525,13 @@ impl FileSystem for ProductionFileSystem {
 struct ProcessState {
     next_handle: u64,
     children: HashMap<u64, std::process::Child>,
+    deadlines: HashMap<u64, ProcessDeadline>,
+}
+
+struct ProcessDeadline {
+    program: String,
+    timeout: Duration,
+    at: Instant,
 }
 
 pub struct ProductionProcessRunner {
@@ -325,8 +554,30 @@ impl Default for ProductionProcessRunner {
 
 impl ProcessRunner for ProductionProcessRunner {
     fn spawn(&self, request: ProcessRequest) -> Result<u64, GardenerError> {
+        if worker_abort_requested() {
+            append_run_log(
+                "warn",
+                "process.spawn.aborted",
+                json!({ "program": request.program, "reason": LEASE_LOST_MESSAGE }),
+            );
+            return Err(GardenerError::Process(LEASE_LOST_MESSAGE.to_string()));
+        }
+        if worker_interrupt_requested() {
+            append_run_log(
+                "warn",
+                "process.spawn.interrupted",
+                json!({ "program": request.program, "reason": INTERRUPT_MESSAGE }),
+            );
+            return Err(GardenerError::Process(INTERRUPT_MESSAGE.to_string()));
+        }
         let mut cmd = std::process::Command::new(&request.program);
         cmd.args(&request.args);
+        #[cfg(unix)]
+        {
+            // Own process group, so an interrupt can kill the whole tree.
+            use std::os::unix::process::CommandExt;
+            cmd.process_group(0);
+        }
         if let Some(cwd) = &request.cwd {
             cmd.current_dir(cwd);
         }
@@ -337,10 +588,24 @@ impl ProcessRunner for ProductionProcessRunner {
         let child = cmd
             .spawn()
             .map_err(|e| GardenerError::Process(e.to_string()))?;
+        live_process_groups()
+            .lock()
+            .unwrap_or_else(std::sync::PoisonError::into_inner)
+            .push(child.id());
         let mut state = self.state.lock().expect("process lock poisoned");
         let handle = state.next_handle;
         state.next_handle += 1;
         state.children.insert(handle, child);
+        if let Some(timeout) = request.timeout {
+            state.deadlines.insert(
+                handle,
+                ProcessDeadline {
+                    program: request.program.clone(),
+                    timeout,
+                    at: Instant::now() + timeout,
+                },
+            );
+        }
         append_run_log(
             "info",
             "process.spawn",
@@ -348,7 +613,8 @@ impl ProcessRunner for ProductionProcessRunner {
                 "handle": handle,
                 "program": request.program,
                 "args": request.args,
-                "cwd": request.cwd.map(|p| p.display().to_string())
+                "cwd": request.cwd.map(|p| p.display().to_string()),
+                "timeout_secs": request.timeout.map(|timeout| timeout.as_secs())
             }),
         );
         Ok(handle)
@@ -364,9 +630,9 @@ impl ProcessRunner for ProductionProcessRunner {
         on_stdout_line: &mut dyn FnMut(&str),
         on_stderr_line: &mut dyn FnMut(&str),
     ) -> Result<ProcessOutput, GardenerError> {
-        let child = {
+        let (child, deadline) = {
             let mut state = self.state.lock().expect("process lock poisoned");
-            state.children.remove(&handle)
+            (state.children.remove(&handle), state.deadlines.remove(&handle))
         };
         let mut child =
             child.ok_or_else(|| GardenerError::Process(format!("unknown handle {handle}")))?;
@@ -437,20 +703,46 @@ impl ProcessRunner for ProductionProcessRunner {
         let mut exit_code: Option<i32> = None;
 
         loop {
-            if INTERRUPT_REQUESTED.swap(false, Ordering::SeqCst) {
-                let _ = child.kill();
-                let _ = child.wait();
+            if INTERRUPT_REQUESTED.swap(false, Ordering::SeqCst) || worker_interrupt_requested() {
+                kill_process_tree(&mut child);
                 append_run_log(
                     "warn",
                     "process.interrupt",
                     json!({
                         "handle": handle,
-                        "reason": "user interrupt requested (q/Ctrl-C)"
+                        "reason": INTERRUPT_MESSAGE
                     }),
                 );
-                return Err(GardenerError::Process(
-                    "user interrupt requested (q/Ctrl-C)".to_string(),
-                ));
+                return Err(GardenerError::Process(INTERRUPT_MESSAGE.to_string()));
+            }
+            if worker_abort_requested() {
+                kill_process_tree(&mut child);
+                append_run_log(
+                    "warn",
+                    "process.aborted",
+                    json!({
+                        "handle": handle,
+                        "reason": LEASE_LOST_MESSAGE
+                    }),
+                );
+                return Err(GardenerError::Process(LEASE_LOST_MESSAGE.to_string()));
+            }
+            if let Some(deadline) = deadline.as_ref().filter(|d| Instant::now() >= d.at) {
+                kill_process_tree(&mut child);
+                append_run_log(
+                    "warn",
+                    "process.timed_out",
+                    json!({
+                        "handle": handle,
+                        "program": deadline.program,
+                        "timeout_secs": deadline.timeout.as_secs()
+                    }),
+                );
+                return Err(GardenerError::Timeout(format!(
+                    "{} did not finish within {}s",
+                    deadline.program,
+                    deadline.timeout.as_secs()
+                )));
             }
 
             match child.try_wait() {
@@ -526,6 +818,7 @@ impl ProcessRunner for ProductionProcessRunner {
         }
 
         let waited_exit_code = child.wait().ok().map(|status| status.code().unwrap_or(-1));
+        forget_process_group(child.id());
         let _ = out_thread.join();
         let _ = err_thread.join();
 
@@ -557,10 +850,13 @@ impl ProcessRunner for ProductionProcessRunner {
     fn kill(&self, handle: u64) -> Result<(), GardenerError> {
         let mut child = {
             let mut state = self.state.lock().expect("process lock poisoned");
+            state.deadlines.remove(&handle);
             state.children.remove(&handle)
         }
         .ok_or_else(|| GardenerError::Process(format!("unknown handle {handle}")))?;
 
+        signal_process_group(child.id());
+        forget_process_group(child.id());
         child
             .kill()
             .map_err(|e| GardenerError::Process(e.to_string()))?;
@@ -691,20 +987,7 @@ impl Terminal for ProductionTerminal {
             return Ok(None);
         }
         match crossterm::event::read().map_err(|e| GardenerError::Io(e.to_string()))? {
-            crossterm::event::Event::Key(key) => match key.code {
-                crossterm::event::KeyCode::Char(c) => {
-                    if key
-                        .modifiers
-                        .contains(crossterm::event::KeyModifiers::CONTROL)
-                        && is_copy_shortcut_key(c)
-                    {
-                        Ok(Some(INTERRUPT_SENTINEL_KEY))
-                    } else {
-                        Ok(Some(c))
-                    }
-                }
-                _ => Ok(None),
-            },
+            crossterm::event::Event::Key(key) => Ok(key_event_char(key)),
             crossterm::event::Event::Resize(_, _) => Ok(Some(RESIZE_SENTINEL_KEY)),
             _ => Ok(None),
         }
@@ -1040,16 +1323,33 @@ fn spawn_clipboard_command(
 
 #[derive(Default, Clone)]
 pub struct FakeProcessRunner {
-    responses: Arc<Mutex<Vec<Result<ProcessOutput, GardenerError>>>>,
+    responses: Arc<Mutex<Vec<FakeResponse>>>,
     spawned: Arc<Mutex<Vec<ProcessRequest>>>,
     waits: Arc<Mutex<Vec<u64>>>,
     kills: Arc<Mutex<Vec<u64>>>,
     next_handle: Arc<Mutex<u64>>,
 }
 
+enum FakeResponse {
+    Output(Result<ProcessOutput, GardenerError>),
+    Hang,
+}
+
 impl FakeProcessRunner {
     pub fn push_response(&self, output: Result<ProcessOutput, GardenerError>) {
-        self.responses.lock().expect("responses lock").push(output);
+        self.responses
+            .lock()
+            .expect("responses lock")
+            .push(FakeResponse::Output(output));
+    }
+
+    /// Queues a process that never exits: its wait times out, and the handle is
+    /// killed, when the request set a `timeout`, and errors otherwise.
+    pub fn push_hang(&self) {
+        self.responses
+            .lock()
+            .unwrap_or_else(std::sync::PoisonError::into_inner)
+            .push(FakeResponse::Hang);
     }
 
     pub fn spawned(&self) -> Vec<ProcessRequest> {
@@ -1082,7 +1382,34 @@ impl ProcessRunner for FakeProcessRunner {
                 "no fake response queued".to_string(),
             ));
         }
-        responses.remove(0)
+        match responses.remove(0) {
+            FakeResponse::Output(output) => output,
+            FakeResponse::Hang => {
+                let request = usize::try_from(handle).ok().and_then(|idx| {
+                    self.spawned
+                        .lock()
+                        .unwrap_or_else(std::sync::PoisonError::into_inner)
+                        .get(idx)
+                        .cloned()
+                });
+                match request.as_ref().and_then(|request| request.timeout) {
+                    Some(timeout) => {
+                        self.kills
+                            .lock()
+                            .unwrap_or_else(std::sync::PoisonError::into_inner)
+                            .push(handle);
+                        Err(GardenerError::Timeout(format!(
+                            "{} did not finish within {}s",
+                            request.map(|request| request.program).unwrap_or_default(),
+                            timeout.as_secs()
+                        )))
+                    }
+                    None => Err(GardenerError::Process(format!(
+                        "fake process {handle} hangs and has no timeout"
+                    ))),
+                }
+            }
+        }
     }
 
     fn kill(&self, handle: u64) -> Result<(), GardenerError> {
@@ -1096,12 +1423,12 @@ mod tests {
     use super::{
         append_and_flush_lines, flush_trailing_line, Clock, FakeClock, FileSystem, FakeFileSystem,
         FakeProcessRunner, FakeTerminal, is_copy_shortcut_key, ProcessOutput, ProcessRequest,
-        ProcessRunner,
+        ProcessRunner, ProductionProcessRunner,
         Terminal,
     };
     use crate::errors::GardenerError;
     use std::path::PathBuf;
-    use std::time::{Duration, SystemTime};
+    use std::time::{Duration, Instant, SystemTime};
 
     #[test]
     fn copy_shortcut_key_matches_c_without_case_sensitive() {
@@ -1191,6 +1518,7 @@ mod tests {
                 program: "echo".to_string(),
                 args: vec!["x".to_string()],
                 cwd: None,
+                timeout: None,
             })
             .expect("spawn");
         assert_eq!(handle, 0);
@@ -1201,4 +1529,45 @@ mod tests {
         assert_eq!(runner.waits(), vec![0]);
         assert_eq!(runner.kills(), vec![42]);
     }
+
+    #[test]
+    fn fake_process_runner_times_out_hung_processes_only_under_a_deadline() {
+        let runner = FakeProcessRunner::default();
+        runner.push_hang();
+        runner.push_hang();
+        let request = |timeout| ProcessRequest {
+            program: "codex".to_string(),
+            args: Vec::new(),
+            cwd: None,
+            timeout,
+        };
+
+        let err = runner
+            .run(request(Some(Duration::from_secs(5))))
+            .expect_err("hung process times out");
+        assert!(matches!(err, GardenerError::Timeout(_)), "{err}");
+        assert_eq!(err.to_string(), "process timed out: codex did not finish within 5s");
+        assert_eq!(runner.kills(), vec![0]);
+
+        let err = runner.run(request(None)).expect_err("no deadline to enforce");
+        assert!(matches!(err, GardenerError::Process(_)), "{err}");
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn production_process_runner_kills_the_process_group_at_the_deadline() {
+        let runner = ProductionProcessRunner::new();
+        let started = Instant::now();
+        let err = runner
+            .run(ProcessRequest {
+                program: "sh".to_string(),
+                // The child sleep shares the group and must die with its parent.
+                args: vec!["-c".to_string(), "sleep 30 & sleep 30".to_string()],
+                cwd: None,
+                timeout: Some(Duration::from_millis(200)),
+            })
+            .expect_err("sleep outlives its deadline");
+        assert!(matches!(err, GardenerError::Timeout(_)), "{err}");
+        assert!(started.elapsed() < Duration::from_secs(10));
+    }
 }
@@ -7,6 +7,7 @@ use crate::runtime::ProcessRunner;
 use crate::types::{AgentKind, RuntimeScope};
 use serde::{Deserialize, Serialize};
 use serde_json::json;
+use std::collections::HashSet;
 use std::path::PathBuf;
 
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
@@ -18,6 +19,9 @@ pub struct SeedTask {
     pub domain: String,
     #[serde(default = "seed_priority_default")]
     pub priority: String,
+    /// Titles of other tasks in the same payload that must merge first.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub blocked_by: Vec<String>,
 }
 
 fn seed_domain_default() -> String {
@@ -99,6 +103,7 @@ pub fn run_legacy_seed_runner_v1_with_events(
         output_file: Some(output_file.clone()),
         permissive_mode: true,
         max_turns: Some(12),
+        timeout: None,
     };
 
     append_run_log(
@@ -156,6 +161,47 @@ pub fn run_legacy_seed_runner_v1_with_events(
     Ok(payload.tasks)
 }
 
+/// Orders seeded tasks so every task comes after the prerequisites it names in
+/// `blocked_by`. Tasks caught in a cycle keep their original relative order and
+/// are appended last; callers drop edges that still point forward.
+pub fn order_seed_tasks_by_dependencies(tasks: Vec<SeedTask>) -> Vec<SeedTask> {
+    let known = tasks
+        .iter()
+        .map(|task| seed_title_key(&task.title))
+        .collect::<HashSet<_>>();
+    let mut placed = HashSet::new();
+    let mut ordered = Vec::with_capacity(tasks.len());
+    let mut pending = tasks;
+    loop {
+        let (ready, waiting): (Vec<_>, Vec<_>) = pending.into_iter().partition(|task| {
+            task.blocked_by.iter().all(|title| {
+                let key = seed_title_key(title);
+                !known.contains(&key) || placed.contains(&key)
+            })
+        });
+        if ready.is_empty() {
+            if !waiting.is_empty() {
+                append_run_log(
+                    "warn",
+                    "seed_runner.dependency_cycle",
+                    json!({
+                        "titles": waiting.iter().map(|task| task.title.clone()).collect::<Vec<_>>(),
+                    }),
+                );
+            }
+            ordered.extend(waiting);
+            return ordered;
+        }
+        placed.extend(ready.iter().map(|task| seed_title_key(&task.title)));
+        ordered.extend(ready);
+        pending = waiting;
+    }
+}
+
+pub fn seed_title_key(title: &str) -> String {
+    title.trim().to_lowercase()
+}
+
 fn parse_seed_payload(value: serde_json::Value) -> Result<SeedPayload, serde_json::Error> {
     if let Ok(payload) = serde_json::from_value::<SeedPayload>(value.clone()) {
         return Ok(payload);
@@ -219,7 +265,8 @@ fn seed_output_schema() -> String {
               "details": { "type": "string", "minLength": 5 },
               "rationale": { "type": "string", "minLength": 10 },
               "domain": { "type": "string", "minLength": 1 },
-              "priority": { "type": "string", "enum": ["P0", "P1", "P2"] }
+              "priority": { "type": "string", "enum": ["P0", "P1", "P2"] },
+              "blocked_by": { "type": "array", "items": { "type": "string" } }
             }
           }
         }
@@ -233,7 +280,7 @@ fn seed_output_schema() -> String {
 
 #[cfg(test)]
 mod tests {
-    use super::run_legacy_seed_runner_v1;
+    use super::{order_seed_tasks_by_dependencies, run_legacy_seed_runner_v1, SeedTask};
     use crate::runtime::{FakeProcessRunner, ProcessOutput};
     use crate::types::{AgentKind, RuntimeScope};
     use tempfile::tempdir;
@@ -265,4 +312,31 @@ mod tests {
         assert_eq!(tasks[0].domain, "backlog");
         assert_eq!(tasks[0].priority, "P1");
     }
+
+    #[test]
+    fn dependency_order_places_prerequisites_first_and_tolerates_cycles() {
+        let seed = |title: &str, blocked_by: &[&str]| SeedTask {
+            title: title.to_string(),
+            details: "d".to_string(),
+            rationale: "r".to_string(),
+            domain: "backlog".to_string(),
+            priority: "P1".to_string(),
+            blocked_by: blocked_by.iter().map(|t| t.to_string()).collect(),
+        };
+        let ordered = order_seed_tasks_by_dependencies(vec![
+            seed("Wire API", &["Add schema"]),
+            seed("Add schema", &[]),
+            seed("Docs", &["wire api ", "Unknown task"]),
+            seed("Loop A", &["Loop B"]),
+            seed("Loop B", &["Loop A"]),
+        ]);
+        let titles = ordered.iter().map(|t| t.title.as_str()).collect::<Vec<_>>();
+        assert_eq!(titles, vec!["Add schema", "Wire API", "Docs", "Loop A", "Loop B"]);
+
+        let parsed: SeedTask = serde_json::from_str(
+            r#"{"title":"t","details":"d","rationale":"r","blocked_by":["Add schema"]}"#,
+        )
+        .expect("seed task");
+        assert_eq!(parsed.blocked_by, vec!["Add schema".to_string()]);
+    }
 }
@@ -108,7 +108,8 @@ pub fn build_seed_prompt_v2(context: &SeedPromptContext) -> String {
     out.push_str("- details: 1-3 sentence implementation scope and expected outcome\n");
     out.push_str("- rationale: 1-2 sentence why this task improves readiness/quality now\n");
     out.push_str("- domain: one of triage, backlog, seeding, worker-pool, agent-adapters, tui, quality-grades, startup, git-integration, prompts, learning, infrastructure\n");
-    out.push_str("- priority: P0|P1|P2\n\n");
+    out.push_str("- priority: P0|P1|P2\n");
+    out.push_str("- blocked_by (optional): titles of other tasks in this payload that must merge first\n\n");
 
     out.push_str("Example (format only; do not copy text)\n");
     out.push_str(
@@ -1,19 +1,24 @@
 use crate::backlog_store::{BacklogStore, NewTask};
 use crate::config::AppConfig;
 use crate::errors::GardenerError;
+use crate::gh::GhClient;
+use crate::issue_import::import_labeled_issues;
 use crate::logging::append_run_log;
 use crate::pr_audit::reconcile_open_prs;
 use crate::priority::Priority;
 use crate::protocol::{AgentEvent, AgentEventKind};
 use crate::quality_grades::render_quality_grade_document;
+use crate::repo_identity::resolve_repo_identity;
 use crate::repo_intelligence::read_profile;
 use crate::runtime::{ProcessRequest, ProductionRuntime};
+use crate::seed_runner::{order_seed_tasks_by_dependencies, seed_title_key};
 use crate::seeding::seed_backlog_if_needed_with_events;
 use crate::task_identity::TaskKind;
 use crate::triage::profile_path;
 use crate::types::RuntimeScope;
 use crate::worktree_audit::reconcile_worktrees;
 use serde_json::json;
+use std::collections::HashMap;
 use std::env;
 use std::path::{Path, PathBuf};
 use std::sync::mpsc;
@@ -31,6 +36,7 @@ pub struct StartupSummary {
     pub pr_collisions_found: usize,
     pub pr_collisions_fixed: usize,
     pub seeded_tasks_upserted: usize,
+    pub issues_imported: usize,
 }
 
 pub fn backlog_db_path(cfg: &crate::config::AppConfig, scope: &RuntimeScope) -> PathBuf {
@@ -65,15 +71,9 @@ pub fn refresh_quality_report(
 ) -> Result<(PathBuf, bool), GardenerError> {
     let profile_loc = profile_path(scope, cfg);
     let profile = read_profile(runtime.file_system.as_ref(), &profile_loc)?;
-    let quality_path = if PathBuf::from(&cfg.quality_report.path).is_absolute() {
-        PathBuf::from(&cfg.quality_report.path)
-    } else {
-        scope.working_dir.join(&cfg.quality_report.path)
-    };
+    let (quality_path, stale) = quality_report_staleness(runtime, cfg, scope)?;
     let stamp_path = quality_stamp_path(&quality_path);
-    let should_regen = force
-        || !runtime.file_system.exists(&quality_path)
-        || report_stamp_is_stale(runtime, cfg, &stamp_path, scope)?;
+    let should_regen = force || stale;
 
     append_run_log(
         "debug",
@@ -125,6 +125,106 @@ pub fn refresh_quality_report(
     Ok((quality_path, should_regen))
 }
 
+/// Where the quality report lives and whether startup would regenerate it.
+fn quality_report_staleness(
+    runtime: &ProductionRuntime,
+    cfg: &AppConfig,
+    scope: &RuntimeScope,
+) -> Result<(PathBuf, bool), GardenerError> {
+    let quality_path = if PathBuf::from(&cfg.quality_report.path).is_absolute() {
+        PathBuf::from(&cfg.quality_report.path)
+    } else {
+        scope.working_dir.join(&cfg.quality_report.path)
+    };
+    let stale = !runtime.file_system.exists(&quality_path)
+        || report_stamp_is_stale(runtime, cfg, &quality_stamp_path(&quality_path), scope)?;
+    append_run_log(
+        "debug",
+        "startup.quality_report.staleness",
+        json!({ "quality_path": quality_path.display().to_string(), "stale": stale }),
+    );
+    Ok((quality_path, stale))
+}
+
+/// Describes what `run_startup_audits` would do, one line per audit, without
+/// doing any of it: nothing is backed up, written, imported, seeded or run, and
+/// neither `gh` nor an agent is called. Like the real audits it inherits the
+/// profile's validation command into `cfg`.
+pub fn preview_startup_audits(
+    runtime: &ProductionRuntime,
+    cfg: &mut AppConfig,
+    scope: &RuntimeScope,
+    store: Option<&BacklogStore>,
+) -> Result<Vec<String>, GardenerError> {
+    let profile_loc = profile_path(scope, cfg);
+    append_run_log(
+        "info",
+        "startup.audits.preview",
+        json!({ "profile_loc": profile_loc.display().to_string() }),
+    );
+    if !runtime.file_system.exists(&profile_loc) {
+        return Ok(vec![format!(
+            "profile: missing at {}; the run would stop here until `--triage-only` completes setup",
+            profile_loc.display()
+        )]);
+    }
+    let profile = read_profile(runtime.file_system.as_ref(), &profile_loc)?;
+    let mut lines = vec![format!("profile: {}", profile_loc.display())];
+    if cfg
+        .startup
+        .validation_command
+        .as_ref()
+        .is_none_or(|command| command.trim().is_empty())
+        && !profile.user_validated.validation_command.trim().is_empty()
+    {
+        cfg.startup.validation_command = Some(profile.user_validated.validation_command.clone());
+        lines.push(format!(
+            "validation: inherited `{}` from the profile",
+            profile.user_validated.validation_command
+        ));
+    }
+    let db_path = backlog_db_path(cfg, scope);
+    if db_path.exists() {
+        lines.push(format!("backup: would copy {}", db_path.display()));
+    }
+    let (quality_path, stale) = quality_report_staleness(runtime, cfg, scope)?;
+    lines.push(if stale {
+        format!("quality report: would regenerate {}", quality_path.display())
+    } else {
+        format!("quality report: {} is fresh", quality_path.display())
+    });
+    lines.push("reconcile: would audit stale worktrees and open PRs".to_string());
+    if cfg.github_issues.enabled {
+        lines.push(format!(
+            "issues: would import GitHub issues labelled `{}`",
+            cfg.github_issues.label
+        ));
+    }
+    if cfg.startup.validate_on_boot {
+        let command = cfg
+            .startup
+            .validation_command
+            .clone()
+            .unwrap_or_else(|| cfg.validation.command.clone());
+        lines.push(format!(
+            "validation: would run `{command}` and queue a P0 recovery task if it fails"
+        ));
+    }
+    let active = match store {
+        Some(store) => store.count_active_tasks()?,
+        None => 0,
+    };
+    lines.push(if should_seed_backlog(true, cfg.execution.test_mode, active) {
+        format!(
+            "seeding: backlog is empty; would seed it with {:?} ({})",
+            cfg.seeding.backend, cfg.seeding.model
+        )
+    } else {
+        format!("seeding: skipped, {active} active task(s)")
+    });
+    Ok(lines)
+}
+
 pub fn run_startup_audits(
     runtime: &ProductionRuntime,
     cfg: &mut AppConfig,
@@ -180,9 +280,13 @@ where
     }
 
     // Open the store at most once, only when needed.
-    let needs_store = cfg.startup.validate_on_boot || (run_seeding && !cfg.execution.test_mode);
+    let import_issues = cfg.github_issues.enabled && !cfg.execution.test_mode;
+    let needs_store = cfg.startup.validate_on_boot
+        || (run_seeding && !cfg.execution.test_mode)
+        || import_issues;
     let store = if needs_store {
-        Some(BacklogStore::open(&db_path)?)
+        let identity = resolve_repo_identity(scope, runtime.process_runner.as_ref());
+        Some(BacklogStore::open_for_repo(&db_path, &identity)?)
     } else {
         None
     };
@@ -235,6 +339,33 @@ where
         }),
     );
 
+    let mut issues_imported = 0usize;
+    if import_issues {
+        // Safety: store is Some because import_issues implies needs_store.
+        let store = store.as_ref().ok_or_else(|| {
+            GardenerError::Database("store not initialized".to_string())
+        })?;
+        progress(&format!(
+            "Importing GitHub issues labelled {}",
+            cfg.github_issues.label
+        ))?;
+        let gh = GhClient::new(runtime.process_runner.as_ref(), &scope.working_dir);
+        match import_labeled_issues(store, &gh, &cfg.github_issues) {
+            Ok(summary) => issues_imported = summary.imported,
+            Err(err) => {
+                // The backlog is still usable without the issue sync; report and go on.
+                append_run_log(
+                    "warn",
+                    "startup.issue_import.failed",
+                    json!({ "label": cfg.github_issues.label, "error": err.to_string() }),
+                );
+                runtime
+                    .terminal
+                    .write_line(&format!("WARN GitHub issue import failed: {err}"))?;
+            }
+        }
+    }
+
     if cfg.startup.validate_on_boot {
         let command = cfg
             .startup
@@ -246,18 +377,13 @@ where
             "startup.validation.running",
             json!({ "command": command }),
         );
-        let out = runtime.process_runner.run(ProcessRequest {
-            program: "sh".to_string(),
-            args: vec!["-lc".to_string(), command.clone()],
-            cwd: Some(scope.working_dir.clone()),
-        })?;
-        if out.exit_code != 0 {
-        append_run_log(
-            "warn",
-            "startup.validation.failed",
+        if let Some(failure) = boot_validation_failure(runtime, cfg, scope, &command)? {
+            append_run_log(
+                "warn",
+                "startup.validation.failed",
                 json!({
                     "command": command,
-                    "exit_code": out.exit_code,
+                    "failure": failure,
                 }),
             );
             runtime
@@ -270,7 +396,7 @@ where
             store.upsert_task(NewTask {
                 kind: TaskKind::Maintenance,
                 title: "Recovery: startup validation failed".to_string(),
-                details: format!("Validation command exited with code {}", out.exit_code),
+                details: failure,
                 scope_key: "startup".to_string(),
                 rationale:
                     "Startup validation failed and requires manual follow-up before workers can run safely."
@@ -279,6 +405,7 @@ where
                 source: "validate_on_boot".to_string(),
                 related_pr: None,
                 related_branch: None,
+                blocked_by: Vec::new(),
             })?;
         } else {
             append_run_log(
@@ -401,12 +528,25 @@ where
                 "Persisting {} seeded task(s) to backlog store",
                 seeded.len()
             ))?;
-            for task in seeded {
+            let mut seeded_ids: HashMap<String, String> = HashMap::new();
+            for task in order_seed_tasks_by_dependencies(seeded) {
                 let scope_key = if task.domain.trim().is_empty() {
                     profile.agent_readiness.primary_gap.clone()
                 } else {
                     task.domain
                 };
+                let mut blocked_by = Vec::new();
+                for title in &task.blocked_by {
+                    match seeded_ids.get(&seed_title_key(title)) {
+                        Some(task_id) => blocked_by.push(task_id.clone()),
+                        None => append_run_log(
+                            "warn",
+                            "startup.seeding.dependency_dropped",
+                            json!({ "title": task.title, "blocked_by": title }),
+                        ),
+                    }
+                }
+                let title_key = seed_title_key(&task.title);
                 let row = store.upsert_task(NewTask {
                     kind: TaskKind::QualityGap,
                     title: task.title,
@@ -417,7 +557,9 @@ where
                     source: format!("seed_runner_v2_gen_{seed_generation}"),
                     related_pr: None,
                     related_branch: None,
+                    blocked_by,
                 })?;
+                seeded_ids.insert(title_key, row.task_id.clone());
                 if !row.task_id.is_empty() {
                     seeded_tasks_upserted = seeded_tasks_upserted.saturating_add(1);
                 }
@@ -497,6 +639,7 @@ where
             "pr_collisions_found": prs.collisions_found,
             "pr_collisions_fixed": prs.collisions_fixed,
             "seeded_tasks_upserted": seeded_tasks_upserted,
+            "issues_imported": issues_imported,
         }),
     );
 
@@ -520,6 +663,7 @@ where
         pr_collisions_found: prs.collisions_found,
         pr_collisions_fixed: prs.collisions_fixed,
         seeded_tasks_upserted,
+        issues_imported,
     })
 }
 
@@ -557,6 +701,36 @@ pub fn backup_db_if_exists(path: &Path) -> Result<Option<PathBuf>, GardenerError
     Ok(Some(bak_path))
 }
 
+/// Runs the boot validation under `[validation] timeout_seconds` and describes
+/// why it failed, if it did. A hung command fails the check like a failing one.
+fn boot_validation_failure(
+    runtime: &ProductionRuntime,
+    cfg: &AppConfig,
+    scope: &RuntimeScope,
+    command: &str,
+) -> Result<Option<String>, GardenerError> {
+    let ran = runtime.process_runner.run(ProcessRequest {
+        program: "sh".to_string(),
+        args: vec!["-lc".to_string(), command.to_string()],
+        cwd: Some(scope.working_dir.clone()),
+        timeout: Some(Duration::from_secs(cfg.validation.timeout_seconds)),
+    });
+    let failure = match ran {
+        Ok(out) if out.exit_code == 0 => None,
+        Ok(out) => Some(format!("Validation command exited with code {}", out.exit_code)),
+        Err(GardenerError::Timeout(reason)) => {
+            Some(format!("Validation command timed out: {reason}"))
+        }
+        Err(err) => return Err(err),
+    };
+    append_run_log(
+        "debug",
+        "startup.validation.finished",
+        json!({ "command": command, "failure": failure }),
+    );
+    Ok(failure)
+}
+
 fn should_seed_backlog(run_seeding: bool, test_mode: bool, existing_backlog_count: usize) -> bool {
     run_seeding && !test_mode && existing_backlog_count == 0
 }
@@ -827,6 +1001,7 @@ fn fallback_seed_tasks(
                 source: source.to_string(),
                 related_pr: None,
                 related_branch: None,
+                blocked_by: Vec::new(),
             }
         })
         .collect();
@@ -862,6 +1037,7 @@ fn fallback_seed_tasks(
                     source: source.to_string(),
                     related_pr: None,
                     related_branch: None,
+                    blocked_by: Vec::new(),
                 }
             })
             .collect();
@@ -943,7 +1119,8 @@ fn report_stamp_is_stale(
 #[cfg(test)]
 mod tests {
     use super::{
-        backlog_db_path, backup_db_if_exists, extract_command_preview, extract_event_label,
+        backlog_db_path, backup_db_if_exists, boot_validation_failure, extract_command_preview,
+        extract_event_label,
         extract_message_preview, fallback_seed_tasks, parse_seed_priority, quality_stamp_path,
         report_stamp_is_stale, seed_generation, should_seed_backlog,
         summarize_seed_agent_event,
@@ -1090,6 +1267,7 @@ mod tests {
             source: "seed_runner_v2_gen_2".into(),
             related_pr: None,
             related_branch: None,
+            blocked_by: Vec::new(),
         });
         let _ = store.upsert_task(NewTask {
             kind: TaskKind::QualityGap,
@@ -1101,6 +1279,7 @@ mod tests {
             source: "seed_runner_v2_gen_7".into(),
             related_pr: None,
             related_branch: None,
+            blocked_by: Vec::new(),
         });
         assert_eq!(seed_generation(&store).expect("generation"), 8);
     }
@@ -1284,4 +1463,38 @@ mod tests {
         };
         assert!(report_stamp_is_stale(&runtime, &cfg, &stamp, &scope).expect("mismatch head"));
     }
+
+    #[test]
+    fn boot_validation_runs_under_the_validation_timeout() {
+        let mut cfg = AppConfig::default();
+        cfg.validation.timeout_seconds = 7;
+        let scope = RuntimeScope {
+            process_cwd: "/repo".into(),
+            repo_root: Some("/repo".into()),
+            working_dir: "/repo".into(),
+        };
+        let process = Arc::new(FakeProcessRunner::default());
+        process.push_hang();
+        process.push_response(Ok(ProcessOutput {
+            exit_code: 0,
+            stdout: String::new(),
+            stderr: String::new(),
+        }));
+        let runtime = ProductionRuntime {
+            clock: Arc::new(FakeClock::default()),
+            file_system: Arc::new(FakeFileSystem::default()),
+            process_runner: process.clone(),
+            terminal: Arc::new(FakeTerminal::default()),
+        };
+
+        let failure = boot_validation_failure(&runtime, &cfg, &scope, "npm test")
+            .expect("a hung validation is a failed check")
+            .expect("failure");
+        assert!(failure.contains("timed out"), "{failure}");
+        assert_eq!(process.spawned()[0].timeout, Some(Duration::from_secs(7)));
+        assert_eq!(
+            boot_validation_failure(&runtime, &cfg, &scope, "npm test").expect("passed"),
+            None
+        );
+    }
 }
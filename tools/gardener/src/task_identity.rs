@@ -14,6 +14,16 @@ pub enum TaskKind {
 }
 
 impl TaskKind {
+    pub const ALL: [TaskKind; 7] = [
+        Self::QualityGap,
+        Self::MergeConflict,
+        Self::PrCollision,
+        Self::Feature,
+        Self::Bugfix,
+        Self::Maintenance,
+        Self::Infra,
+    ];
+
     pub fn as_str(self) -> &'static str {
         match self {
             Self::QualityGap => "quality_gap",
@@ -0,0 +1,117 @@
+use std::collections::BTreeSet;
+
+/// Title similarity at or above which two tasks in the same scope are treated as
+/// the same work.
+pub const NEAR_DUPLICATE_THRESHOLD: f64 = 0.75;
+
+/// When both tasks carry details, they must share at least this fraction of
+/// their detail tokens; reworded titles over unrelated details stay distinct.
+pub const DETAILS_SIMILARITY_FLOOR: f64 = 0.2;
+
+const STOPWORDS: &[&str] = &[
+    "a", "an", "and", "as", "at", "by", "for", "from", "in", "into", "of", "on", "or", "the",
+    "to", "with",
+];
+
+/// Scores how alike two tasks' wording is. Returns the title similarity when the
+/// pair counts as a near-duplicate, `None` otherwise.
+pub fn near_duplicate_score(
+    title: &str,
+    details: &str,
+    other_title: &str,
+    other_details: &str,
+) -> Option<f64> {
+    let score = title_similarity(title, other_title);
+    if score < NEAR_DUPLICATE_THRESHOLD {
+        return None;
+    }
+    let (details, other_details) = (tokens(details), tokens(other_details));
+    if !details.is_empty()
+        && !other_details.is_empty()
+        && jaccard(&details, &other_details) < DETAILS_SIMILARITY_FLOOR
+    {
+        return None;
+    }
+    Some(score)
+}
+
+/// Average of the token-set and character-trigram similarity of two titles, so
+/// both reordered words and small spelling changes score high.
+pub fn title_similarity(a: &str, b: &str) -> f64 {
+    let (a_tokens, b_tokens) = (tokens(a), tokens(b));
+    let token_score = jaccard(&a_tokens, &b_tokens);
+    let trigram_score = jaccard(&trigrams(&a_tokens), &trigrams(&b_tokens));
+    (token_score + trigram_score) / 2.0
+}
+
+/// Lowercased words with stopwords dropped and a plural `s` trimmed, so
+/// `backlog_store` and `tests for backlog store` share `backlog`, `store`, `test`.
+pub fn tokens(text: &str) -> BTreeSet<String> {
+    text.split(|ch: char| !ch.is_alphanumeric())
+        .filter(|word| !word.is_empty())
+        .map(str::to_lowercase)
+        .filter(|word| !STOPWORDS.contains(&word.as_str()))
+        .map(|word| {
+            if word.len() > 3 && word.ends_with('s') && !word.ends_with("ss") {
+                word[..word.len() - 1].to_string()
+            } else {
+                word
+            }
+        })
+        .collect()
+}
+
+fn trigrams(tokens: &BTreeSet<String>) -> BTreeSet<String> {
+    let joined = tokens.iter().cloned().collect::<Vec<_>>().join(" ");
+    let chars = format!(" {joined} ").chars().collect::<Vec<_>>();
+    chars
+        .windows(3)
+        .map(|window| window.iter().collect::<String>())
+        .collect()
+}
+
+fn jaccard(a: &BTreeSet<String>, b: &BTreeSet<String>) -> f64 {
+    let union = a.union(b).count();
+    if union == 0 {
+        return 0.0;
+    }
+    a.intersection(b).count() as f64 / union as f64
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{near_duplicate_score, title_similarity, tokens};
+
+    #[test]
+    fn tokens_ignore_case_punctuation_stopwords_and_plurals() {
+        assert_eq!(
+            tokens("Add unit tests to backlog_store"),
+            ["add", "backlog", "store", "test", "unit"]
+                .into_iter()
+                .map(str::to_string)
+                .collect()
+        );
+        assert!(tokens("process").contains("process"));
+    }
+
+    #[test]
+    fn reworded_titles_match_and_distinct_work_does_not() {
+        assert!(near_duplicate_score(
+            "Add tests for backlog store",
+            "",
+            "Add unit tests to backlog_store",
+            "",
+        )
+        .is_some());
+        assert!(near_duplicate_score("task 1", "", "task 2", "").is_none());
+        assert!(near_duplicate_score("fresh gap", "", "fresh gap two", "").is_none());
+        assert!(near_duplicate_score(
+            "Add tests for backlog store",
+            "cover claim ordering",
+            "Add unit tests to backlog_store",
+            "rewrite the tui renderer",
+        )
+        .is_none());
+        assert!((title_similarity("Same title", "same  TITLE") - 1.0).abs() < f64::EPSILON);
+    }
+}
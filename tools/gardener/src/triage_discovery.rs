@@ -127,6 +127,7 @@ pub fn run_discovery(
         program: program.clone(),
         args,
         cwd: Some(scope.working_dir.clone()),
+        timeout: None,
     })?;
 
     if output.exit_code != 0 {
@@ -54,12 +54,37 @@ pub struct QueueStats {
     pub p0: usize,
     pub p1: usize,
     pub p2: usize,
+    /// Ready tasks waiting because an active task holds their scope.
+    pub held_by_scope: usize,
+    /// Set while the pool drains or interrupts its workers on shutdown.
+    pub pool_status: Option<String>,
+    /// Token and cost totals of the run, once an agent turn has finished.
+    pub usage: Option<String>,
 }
 
 #[derive(Debug, Clone, PartialEq, Eq, Default)]
 pub struct BacklogView {
     pub in_progress: Vec<String>,
     pub queued: Vec<String>,
+    /// Set while the backlog pane shows full-text search results.
+    pub search: Option<BacklogSearch>,
+}
+
+/// The `/` search box: `editing` while the operator is still typing.
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct BacklogSearch {
+    pub query: String,
+    pub editing: bool,
+}
+
+impl BacklogSearch {
+    fn title(&self) -> String {
+        if self.editing {
+            format!("SEARCH /{}_", self.query)
+        } else {
+            format!("BACKLOG MATCHING \"{}\" (esc clears)", self.query)
+        }
+    }
 }
 
 const STARTUP_SPINNER_FRAMES: [&str; 6] = ["⠋", "⠙", "⠸", "⠴", "⠦", "⠇"];
@@ -163,7 +188,7 @@ impl WorkerState {
         match state {
             "reviewing" => Self::Reviewing,
             "complete" => Self::Complete,
-            "failed" | "unresolved" => Self::Failed,
+            "failed" | "unresolved" | "exhausted" => Self::Failed,
             "idle" => Self::Idle,
             _ => Self::Doing,
         }
@@ -828,7 +853,7 @@ fn draw_dashboard_frame(
         ])
         .split(chunks[1]);
 
-    let summary = Paragraph::new(Line::from(vec![
+    let mut summary_spans = vec![
         Span::styled(
             "GARDENER ",
             Style::default()
@@ -866,8 +891,29 @@ fn draw_dashboard_frame(
                 .add_modifier(Modifier::BOLD),
         ),
         Span::raw(format!(" {}", stats.p2)),
-    ]))
-    .block(
+    ];
+    if stats.held_by_scope > 0 {
+        summary_spans.push(Span::styled(
+            "   held by scope ",
+            Style::default().fg(Color::Rgb(170, 178, 210)),
+        ));
+        summary_spans.push(Span::raw(stats.held_by_scope.to_string()));
+    }
+    if let Some(usage) = &stats.usage {
+        summary_spans.push(Span::styled(
+            format!("   {usage}"),
+            Style::default().fg(Color::Rgb(170, 178, 210)),
+        ));
+    }
+    if let Some(status) = &stats.pool_status {
+        summary_spans.push(Span::styled(
+            format!("   {status}"),
+            Style::default()
+                .fg(Color::Yellow)
+                .add_modifier(Modifier::BOLD),
+        ));
+    }
+    let summary = Paragraph::new(Line::from(summary_spans)).block(
         Block::default()
             .borders(Borders::BOTTOM)
             .border_style(Style::default().fg(Color::Rgb(82, 88, 126))),
@@ -1113,12 +1159,20 @@ fn draw_dashboard_frame(
         list_items.push(ListItem::new(format!("... and {hidden} more")));
     }
     if list_items.is_empty() {
-        list_items.push(ListItem::new("No backlog items"));
+        list_items.push(ListItem::new(if backlog.search.is_some() {
+            "No matching tasks"
+        } else {
+            "No backlog items"
+        }));
     }
 
+    let backlog_title = backlog
+        .search
+        .as_ref()
+        .map_or_else(|| "BACKLOG (PRIORITY ORDER)".to_string(), BacklogSearch::title);
     frame.render_widget(
         Paragraph::new(Line::from(vec![Span::styled(
-            "BACKLOG (PRIORITY ORDER)",
+            backlog_title,
             Style::default()
                 .fg(Color::Rgb(245, 196, 95))
                 .add_modifier(Modifier::BOLD),
@@ -1705,7 +1759,8 @@ fn command_stream_window(stream: &str, width: usize, offset: usize) -> String {
 fn normalize_worker_state(state: &str) -> &str {
     match state {
         "init" | "boot" | "backlog_sync" | "working" | "seeding" => "understand",
-        "doing" | "gitting" | "reviewing" | "merging" | "complete" | "failed" | "unresolved" | "idle" => state,
+        "doing" | "gitting" | "reviewing" | "merging" | "complete" | "failed" | "unresolved"
+        | "exhausted" | "idle" => state,
         _ => "unknown",
     }
 }
@@ -1996,7 +2051,7 @@ mod tests {
     use super::{
         format_breadcrumb, format_state_label, render_dashboard, render_dashboard_at_tick,
         render_triage, reset_workers_scroll, scroll_workers_down, scroll_workers_up, AppState,
-        StageState, BacklogView, QueueStats, StartupHeadlineView, WorkerCard, WorkerMetrics, WorkerRow,
+        BacklogSearch, StageState, BacklogView, QueueStats, StartupHeadlineView, WorkerCard, WorkerMetrics, WorkerRow,
         WorkerState,
     };
 
@@ -2027,10 +2082,14 @@ mod tests {
                 p0: 1,
                 p1: 0,
                 p2: 0,
+                held_by_scope: 0,
+                pool_status: None,
+                usage: None,
             },
             &BacklogView {
                 in_progress: vec!["P1 abc123 fix queue".to_string()],
                 queued: vec!["P2 def456 tune logs".to_string()],
+                search: None,
             },
             80,
             40,
@@ -2061,6 +2120,9 @@ mod tests {
                 p0: 1,
                 p1: 2,
                 p2: 2,
+                held_by_scope: 0,
+                pool_status: None,
+                usage: None,
             },
             &BacklogView {
                 in_progress: vec![
@@ -2069,6 +2131,7 @@ mod tests {
                     "INP P2 charlie task".to_string(),
                 ],
                 queued: vec![],
+                search: None,
             },
             80,
             40,
@@ -2088,6 +2151,43 @@ mod tests {
         );
     }
 
+    #[test]
+    fn backlog_pane_title_shows_search_box_and_applied_query() {
+        let stats = QueueStats {
+            ready: 1,
+            active: 0,
+            failed: 0,
+            unresolved: 0,
+            p0: 0,
+            p1: 1,
+            p2: 0,
+            held_by_scope: 0,
+            pool_status: None,
+            usage: None,
+        };
+        let mut backlog = BacklogView {
+            in_progress: Vec::new(),
+            queued: Vec::new(),
+            search: Some(BacklogSearch {
+                query: "flak".to_string(),
+                editing: true,
+            }),
+        };
+        let frame = render_dashboard(&[worker(10, false)], &stats, &backlog, 100, 30);
+        assert!(frame.contains("SEARCH /flak_"));
+        assert!(frame.contains("No matching tasks"));
+
+        backlog.search = Some(BacklogSearch {
+            query: "flaky".to_string(),
+            editing: false,
+        });
+        backlog.queued = vec!["Q P1 abc123 Fix flaky test".to_string()];
+        let frame = render_dashboard(&[worker(10, false)], &stats, &backlog, 100, 30);
+        assert!(frame.contains("BACKLOG MATCHING \"flaky\""));
+        assert!(frame.contains("Fix flaky test"));
+        assert!(!frame.contains("BACKLOG (PRIORITY ORDER)"));
+    }
+
     #[test]
     fn work_now_card_freezes_spinner_after_startup() {
         let active_frame = render_dashboard_at_tick(
@@ -2100,6 +2200,9 @@ mod tests {
                 p0: 0,
                 p1: 1,
                 p2: 0,
+                held_by_scope: 0,
+                pool_status: None,
+                usage: None,
             },
             &BacklogView::default(),
             90,
@@ -2117,6 +2220,9 @@ mod tests {
                 p0: 0,
                 p1: 1,
                 p2: 0,
+                held_by_scope: 0,
+                pool_status: None,
+                usage: None,
             },
             &BacklogView::default(),
             90,
@@ -2143,6 +2249,9 @@ mod tests {
                 p0: 1,
                 p1: 0,
                 p2: 0,
+                held_by_scope: 0,
+                pool_status: None,
+                usage: None,
             },
             &BacklogView::default(),
             80,
@@ -2189,6 +2298,9 @@ mod tests {
                 p0: 0,
                 p1: 2,
                 p2: 0,
+                held_by_scope: 0,
+                pool_status: None,
+                usage: None,
             },
             &BacklogView::default(),
             120,
@@ -2276,6 +2388,9 @@ mod tests {
                 p0: 0,
                 p1: 2,
                 p2: 0,
+                held_by_scope: 0,
+                pool_status: None,
+                usage: None,
             },
             &BacklogView::default(),
             120,
@@ -2370,6 +2485,9 @@ mod tests {
             p0: 0,
             p1: workers.len(),
             p2: 0,
+            held_by_scope: 0,
+            pool_status: None,
+            usage: None,
         };
         let backlog = BacklogView::default();
 
@@ -0,0 +1,199 @@
+use crate::config::{AppConfig, BudgetConfig};
+use crate::logging::append_run_log;
+use crate::protocol::TokenUsage;
+use crate::types::{AgentKind, WorkerState};
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+use std::sync::{Mutex, PoisonError};
+
+/// Token and cost totals over a span of agent turns: one turn, one task or the
+/// whole run.
+#[derive(Debug, Clone, Copy, PartialEq, Default, Serialize, Deserialize)]
+pub struct UsageTotals {
+    pub turns: u64,
+    pub input_tokens: u64,
+    pub cached_input_tokens: u64,
+    pub output_tokens: u64,
+    pub cost_usd: f64,
+}
+
+impl UsageTotals {
+    pub fn total_tokens(&self) -> u64 {
+        self.input_tokens.saturating_add(self.output_tokens)
+    }
+
+    pub fn add_turn(&mut self, usage: &TokenUsage, cost_usd: f64) {
+        self.add(&UsageTotals {
+            turns: 1,
+            input_tokens: usage.input_tokens,
+            cached_input_tokens: usage.cached_input_tokens,
+            output_tokens: usage.output_tokens,
+            cost_usd,
+        });
+    }
+
+    pub fn add(&mut self, other: &UsageTotals) {
+        self.turns = self.turns.saturating_add(other.turns);
+        self.input_tokens = self.input_tokens.saturating_add(other.input_tokens);
+        self.cached_input_tokens = self
+            .cached_input_tokens
+            .saturating_add(other.cached_input_tokens);
+        self.output_tokens = self.output_tokens.saturating_add(other.output_tokens);
+        self.cost_usd += other.cost_usd;
+    }
+
+    /// Short form for the dashboard, e.g. `tokens 12.3k  $0.42`.
+    pub fn summary(&self) -> String {
+        format!(
+            "tokens {}  ${:.2}",
+            compact_count(self.total_tokens()),
+            self.cost_usd
+        )
+    }
+}
+
+fn compact_count(count: u64) -> String {
+    match count {
+        0..=999 => count.to_string(),
+        1_000..=999_999 => format!("{:.1}k", count as f64 / 1_000.0),
+        _ => format!("{:.1}M", count as f64 / 1_000_000.0),
+    }
+}
+
+/// Cost of one turn: from `[pricing]` when the model has a price there,
+/// otherwise whatever the backend reported, otherwise nothing.
+pub fn turn_cost_usd(cfg: &AppConfig, model: &str, usage: &TokenUsage) -> f64 {
+    let Some(price) = cfg.pricing.get(model) else {
+        return usage.reported_cost_usd.unwrap_or(0.0);
+    };
+    let cached = usage.cached_input_tokens.min(usage.input_tokens);
+    let uncached = usage.input_tokens - cached;
+    let cached_rate = price.cached_input_per_mtok.unwrap_or(price.input_per_mtok);
+    (uncached as f64 * price.input_per_mtok
+        + cached as f64 * cached_rate
+        + usage.output_tokens as f64 * price.output_per_mtok)
+        / 1_000_000.0
+}
+
+static RUN_USAGE: Mutex<UsageTotals> = Mutex::new(UsageTotals {
+    turns: 0,
+    input_tokens: 0,
+    cached_input_tokens: 0,
+    output_tokens: 0,
+    cost_usd: 0.0,
+});
+
+/// Adds a finished turn to the run totals and returns what it cost.
+pub fn record_turn_usage(
+    cfg: &AppConfig,
+    worker_id: &str,
+    state: WorkerState,
+    backend: AgentKind,
+    model: &str,
+    usage: &TokenUsage,
+) -> f64 {
+    let cost_usd = turn_cost_usd(cfg, model, usage);
+    let run = {
+        let mut run = RUN_USAGE.lock().unwrap_or_else(PoisonError::into_inner);
+        run.add_turn(usage, cost_usd);
+        *run
+    };
+    append_run_log(
+        "info",
+        "agent.turn.usage",
+        json!({
+            "worker_id": worker_id,
+            "state": state.as_str(),
+            "backend": backend.as_str(),
+            "model": model,
+            "input_tokens": usage.input_tokens,
+            "cached_input_tokens": usage.cached_input_tokens,
+            "output_tokens": usage.output_tokens,
+            "cost_usd": cost_usd,
+            "run_tokens": run.total_tokens(),
+            "run_cost_usd": run.cost_usd,
+        }),
+    );
+    cost_usd
+}
+
+/// Totals over every agent turn this process has run since the last reset.
+pub fn run_usage() -> UsageTotals {
+    *RUN_USAGE.lock().unwrap_or_else(PoisonError::into_inner)
+}
+
+pub fn reset_run_usage() {
+    *RUN_USAGE.lock().unwrap_or_else(PoisonError::into_inner) = UsageTotals::default();
+}
+
+/// Why the run has to stop claiming, once `totals` reach a budget.
+pub fn budget_exhausted(budget: &BudgetConfig, totals: &UsageTotals) -> Option<String> {
+    if let Some(max) = budget.max_tokens.filter(|max| totals.total_tokens() >= *max) {
+        return Some(format!(
+            "token budget reached ({} of {max})",
+            totals.total_tokens()
+        ));
+    }
+    budget
+        .max_cost_usd
+        .filter(|max| totals.cost_usd >= *max)
+        .map(|max| format!("cost budget reached (${:.2} of ${max:.2})", totals.cost_usd))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{budget_exhausted, turn_cost_usd, UsageTotals};
+    use crate::config::{AppConfig, BudgetConfig, ModelPrice};
+    use crate::protocol::TokenUsage;
+
+    fn usage(input: u64, cached: u64, output: u64, reported: Option<f64>) -> TokenUsage {
+        TokenUsage {
+            input_tokens: input,
+            cached_input_tokens: cached,
+            output_tokens: output,
+            reported_cost_usd: reported,
+        }
+    }
+
+    #[test]
+    fn priced_models_use_the_table_and_others_the_reported_cost() {
+        let mut cfg = AppConfig::default();
+        cfg.pricing.insert(
+            "gpt-5-codex".to_string(),
+            ModelPrice {
+                input_per_mtok: 1.25,
+                cached_input_per_mtok: Some(0.125),
+                output_per_mtok: 10.0,
+            },
+        );
+        let cost = turn_cost_usd(&cfg, "gpt-5-codex", &usage(1_000_000, 800_000, 100_000, None));
+        assert!((cost - (0.25 + 0.1 + 1.0)).abs() < 1e-9);
+        let reported = turn_cost_usd(&cfg, "claude-sonnet", &usage(10, 0, 10, Some(0.5)));
+        assert!((reported - 0.5).abs() < 1e-9);
+        assert_eq!(turn_cost_usd(&cfg, "unpriced", &usage(10, 0, 10, None)), 0.0);
+    }
+
+    #[test]
+    fn totals_add_up_and_trip_the_budget() {
+        let mut totals = UsageTotals::default();
+        totals.add_turn(&usage(1_500, 0, 500, None), 0.75);
+        totals.add_turn(&usage(10_000, 0, 0, None), 0.5);
+        assert_eq!(totals.turns, 2);
+        assert_eq!(totals.total_tokens(), 12_000);
+        assert_eq!(totals.summary(), "tokens 12.0k  $1.25");
+
+        let mut budget = BudgetConfig::default();
+        assert_eq!(budget_exhausted(&budget, &totals), None);
+        budget.max_tokens = Some(20_000);
+        budget.max_cost_usd = Some(1.0);
+        assert_eq!(
+            budget_exhausted(&budget, &totals).as_deref(),
+            Some("cost budget reached ($1.25 of $1.00)")
+        );
+        budget.max_tokens = Some(12_000);
+        assert_eq!(
+            budget_exhausted(&budget, &totals).as_deref(),
+            Some("token budget reached (12000 of 12000)")
+        );
+    }
+}
@@ -0,0 +1,478 @@
+use crate::backlog_store::{BacklogStore, TaskFilter};
+use crate::config::AppConfig;
+use crate::errors::GardenerError;
+use crate::logging::{append_run_log, structured_fallback_line};
+use crate::runtime::{
+    shutdown_requests, DrainEnabled, ProductionRuntime, Terminal, INTERRUPT_SENTINEL_KEY,
+};
+use crate::startup::run_startup_audits;
+use crate::types::RuntimeScope;
+use crate::worker_pool::{run_worker_pool, PoolRun};
+use serde::Serialize;
+use serde_json::json;
+use std::path::{Path, PathBuf};
+use std::sync::mpsc::{self, RecvTimeoutError};
+use std::time::{Duration, Instant};
+
+const IDLE_KEY_POLL: Duration = Duration::from_millis(100);
+
+/// What `--watch` is doing, rewritten on every change and on a heartbeat so a
+/// supervisor can tell an idle or busy daemon from a stuck or dead one.
+#[derive(Debug, Clone, Serialize, PartialEq, Eq)]
+pub struct WatchHealth {
+    pub pid: u32,
+    pub state: WatchState,
+    pub started_at: i64,
+    pub updated_at: i64,
+    pub cycles: u64,
+    pub tasks_claimed: usize,
+    pub tasks_completed: usize,
+    pub consecutive_failures: u32,
+    pub last_error: Option<String>,
+    pub last_audit_at: Option<i64>,
+    pub next_poll_at: Option<i64>,
+}
+
+#[derive(Debug, Clone, Copy, Serialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum WatchState {
+    Auditing,
+    Working,
+    Idle,
+    Stopped,
+}
+
+/// Keeps the worker pool running until the operator quits or `target` tasks
+/// have completed. Between pool runs it backs off while the backlog is empty
+/// and re-runs the startup audits every `watch.audit_interval_seconds`. Failing
+/// tasks are retried under the retry policy, and a failed pool run or audit is
+/// recorded in the health file and retried after the backoff.
+pub fn run_watch(
+    runtime: &ProductionRuntime,
+    scope: &RuntimeScope,
+    cfg: &AppConfig,
+    store: &BacklogStore,
+    terminal: &dyn Terminal,
+    target: Option<usize>,
+    claim_filter: Option<&TaskFilter>,
+) -> Result<WatchHealth, GardenerError> {
+    let mut startup_audits =
+        |audit_cfg: &mut AppConfig| run_startup_audits(runtime, audit_cfg, scope, true).map(|_| ());
+    // Test mode never runs the audits, as at startup.
+    let audits: Option<&mut StartupAudits<'_>> = if cfg.execution.test_mode {
+        None
+    } else {
+        Some(&mut startup_audits)
+    };
+    watch_loop(runtime, scope, cfg, store, terminal, target, claim_filter, audits)
+}
+
+/// Re-runs the startup audits on the config copy it is given.
+type StartupAudits<'a> = dyn FnMut(&mut AppConfig) -> Result<(), GardenerError> + 'a;
+
+#[allow(clippy::too_many_arguments)]
+fn watch_loop(
+    runtime: &ProductionRuntime,
+    scope: &RuntimeScope,
+    cfg: &AppConfig,
+    store: &BacklogStore,
+    terminal: &dyn Terminal,
+    target: Option<usize>,
+    claim_filter: Option<&TaskFilter>,
+    mut audits: Option<&mut StartupAudits<'_>>,
+) -> Result<WatchHealth, GardenerError> {
+    let health_path = watch_health_path(cfg, scope);
+    let initial_backoff = Duration::from_secs(cfg.watch.idle_backoff_seconds);
+    let max_backoff = Duration::from_secs(cfg.watch.max_idle_backoff_seconds);
+    let audit_interval = Duration::from_secs(cfg.watch.audit_interval_seconds);
+    let heartbeat = Duration::from_secs(cfg.watch.health_interval_seconds);
+    append_run_log(
+        "info",
+        "watch.started",
+        json!({
+            "target": target,
+            "health_path": health_path.display().to_string(),
+            "idle_backoff_seconds": cfg.watch.idle_backoff_seconds,
+            "audit_interval_seconds": cfg.watch.audit_interval_seconds,
+        }),
+    );
+    let started_at = now_unix_millis();
+    let mut health = WatchHealth {
+        pid: std::process::id(),
+        state: WatchState::Working,
+        started_at,
+        updated_at: started_at,
+        cycles: 0,
+        tasks_claimed: 0,
+        tasks_completed: 0,
+        consecutive_failures: 0,
+        last_error: None,
+        last_audit_at: None,
+        next_poll_at: None,
+    };
+    let mut backoff = initial_backoff;
+    // A signal between pool runs stops the daemon instead of killing it, so
+    // the health file still records the stop.
+    let _drain_enabled = DrainEnabled::acquire();
+    // Startup already ran the audits once before the first cycle.
+    let mut next_audit = Instant::now() + audit_interval;
+
+    loop {
+        if Instant::now() >= next_audit {
+            if let Some(audits) = audits.as_mut() {
+                set_state(runtime, &health_path, &mut health, WatchState::Auditing, None);
+                // Audits may adjust their own copy of the config; the pool keeps
+                // running with the one it started with.
+                let mut audit_cfg = cfg.clone();
+                let audited = with_heartbeat(runtime, &health_path, &health, heartbeat, || {
+                    audits(&mut audit_cfg)
+                });
+                match audited {
+                    Ok(()) => health.last_audit_at = Some(now_unix_millis()),
+                    Err(err) => record_failure(&mut health, "watch.audit.failed", &err),
+                }
+            }
+            next_audit = Instant::now() + audit_interval;
+        }
+        let remaining = target.map(|target| target.saturating_sub(health.tasks_completed));
+        if remaining == Some(0) {
+            break;
+        }
+
+        health.cycles += 1;
+        set_state(runtime, &health_path, &mut health, WatchState::Working, None);
+        let run = PoolRun {
+            target: remaining.unwrap_or(usize::MAX),
+            claim_filter,
+            keep_going: true,
+            claim_until: Some(next_audit),
+            skip_completion_screen: true,
+        };
+        let pool_run = with_heartbeat(runtime, &health_path, &health, heartbeat, || {
+            run_worker_pool(runtime, scope, cfg, store, terminal, &run)
+        });
+        match pool_run {
+            Ok(report) => {
+                health.tasks_claimed += report.claimed;
+                health.tasks_completed += report.completed;
+                health.consecutive_failures = 0;
+                if report.quit_requested || report.budget_exhausted {
+                    break;
+                }
+                if report.claimed > 0 {
+                    backoff = initial_backoff;
+                    continue;
+                }
+            }
+            Err(err) => record_failure(&mut health, "watch.cycle.failed", &err),
+        }
+
+        let next_poll_at = now_unix_millis().saturating_add(backoff.as_millis() as i64);
+        set_state(
+            runtime,
+            &health_path,
+            &mut health,
+            WatchState::Idle,
+            Some(next_poll_at),
+        );
+        if !terminal.stdin_is_tty() {
+            terminal.write_line(&structured_fallback_line(
+                "watch",
+                "idle",
+                &format!("next poll in {}s", backoff.as_secs()),
+            ))?;
+        }
+        if wait_for_next_poll(terminal, backoff)? {
+            break;
+        }
+        backoff = backoff.saturating_mul(2).min(max_backoff);
+    }
+
+    set_state(runtime, &health_path, &mut health, WatchState::Stopped, None);
+    append_run_log(
+        "info",
+        "watch.stopped",
+        json!({
+            "cycles": health.cycles,
+            "tasks_claimed": health.tasks_claimed,
+            "tasks_completed": health.tasks_completed,
+        }),
+    );
+    Ok(health)
+}
+
+pub fn watch_health_path(cfg: &AppConfig, scope: &RuntimeScope) -> PathBuf {
+    let path = PathBuf::from(&cfg.watch.health_path);
+    if path.is_absolute() {
+        path
+    } else {
+        scope.working_dir.join(path)
+    }
+}
+
+fn record_failure(health: &mut WatchHealth, event: &str, err: &GardenerError) {
+    health.consecutive_failures += 1;
+    health.last_error = Some(err.to_string());
+    append_run_log(
+        "error",
+        event,
+        json!({
+            "error": err.to_string(),
+            "consecutive_failures": health.consecutive_failures,
+        }),
+    );
+}
+
+/// Updates the health file.
+fn set_state(
+    runtime: &ProductionRuntime,
+    path: &Path,
+    health: &mut WatchHealth,
+    state: WatchState,
+    next_poll_at: Option<i64>,
+) {
+    health.state = state;
+    health.next_poll_at = next_poll_at;
+    health.updated_at = now_unix_millis();
+    write_health(runtime, path, health);
+}
+
+/// Runs `work` while a heartbeat thread rewrites the health file every
+/// `interval`, so `updated_at` keeps moving through a long audit or pool run.
+fn with_heartbeat<T>(
+    runtime: &ProductionRuntime,
+    path: &Path,
+    health: &WatchHealth,
+    interval: Duration,
+    work: impl FnOnce() -> T,
+) -> T {
+    let (stop, stopped) = mpsc::channel::<()>();
+    std::thread::scope(|threads| {
+        threads.spawn(move || {
+            let mut beat = health.clone();
+            while let Err(RecvTimeoutError::Timeout) = stopped.recv_timeout(interval) {
+                beat.updated_at = now_unix_millis();
+                append_run_log(
+                    "debug",
+                    "watch.health.heartbeat",
+                    json!({ "state": beat.state, "updated_at": beat.updated_at }),
+                );
+                write_health(runtime, path, &beat);
+            }
+        });
+        let result = work();
+        drop(stop);
+        result
+    })
+}
+
+/// Writes the health file. A write failure is logged rather than returned; the
+/// daemon keeps working without it.
+fn write_health(runtime: &ProductionRuntime, path: &Path, health: &WatchHealth) {
+    let written = serde_json::to_string_pretty(health)
+        .map_err(|err| GardenerError::Io(err.to_string()))
+        .and_then(|contents| {
+            if let Some(parent) = path.parent() {
+                runtime.file_system.create_dir_all(parent)?;
+            }
+            runtime.file_system.write_string(path, &contents)
+        });
+    if let Err(err) = written {
+        append_run_log(
+            "warn",
+            "watch.health.write_failed",
+            json!({ "path": path.display().to_string(), "error": err.to_string() }),
+        );
+    }
+}
+
+/// Sleeps out the idle backoff. Returns `true` when the operator pressed `q` or
+/// Ctrl+C, or the process got SIGINT/SIGTERM, while waiting.
+fn wait_for_next_poll(terminal: &dyn Terminal, backoff: Duration) -> Result<bool, GardenerError> {
+    let deadline = Instant::now() + backoff;
+    while Instant::now() < deadline {
+        if shutdown_requests() > 0 {
+            append_run_log("info", "watch.quit", json!({ "while": "idle", "signal": true }));
+            return Ok(true);
+        }
+        if let Some(INTERRUPT_SENTINEL_KEY | 'q') = terminal.poll_key(0)? {
+            append_run_log("info", "watch.quit", json!({ "while": "idle" }));
+            return Ok(true);
+        }
+        std::thread::sleep(IDLE_KEY_POLL.min(deadline.saturating_duration_since(Instant::now())));
+    }
+    Ok(false)
+}
+
+fn now_unix_millis() -> i64 {
+    std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .map(|d| d.as_millis() as i64)
+        .unwrap_or(0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{run_watch, watch_health_path, watch_loop, WatchState};
+    use crate::backlog_store::{BacklogStore, NewTask, TaskStatus};
+    use crate::config::AppConfig;
+    use crate::priority::Priority;
+    use crate::runtime::{
+        FakeClock, FakeProcessRunner, FakeTerminal, ProductionFileSystem, ProductionRuntime,
+    };
+    use crate::task_identity::TaskKind;
+    use crate::types::RuntimeScope;
+    use std::sync::Arc;
+    use tempfile::TempDir;
+
+    fn task(title: &str) -> NewTask {
+        NewTask {
+            kind: TaskKind::Maintenance,
+            title: title.to_string(),
+            details: "details".to_string(),
+            scope_key: "scope".to_string(),
+            rationale: "seeded for the watch loop".to_string(),
+            priority: Priority::P1,
+            source: "test".to_string(),
+            related_pr: None,
+            related_branch: None,
+            blocked_by: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn watch_backs_off_while_idle_and_picks_up_work_added_later() {
+        let dir = TempDir::new().expect("tempdir");
+        let scope = RuntimeScope {
+            process_cwd: dir.path().to_path_buf(),
+            repo_root: Some(dir.path().to_path_buf()),
+            working_dir: dir.path().to_path_buf(),
+        };
+        let store = BacklogStore::open(dir.path().join(".cache/gardener/backlog.sqlite"))
+            .expect("open store");
+        store.upsert_task(task("present at start")).expect("seed");
+
+        let mut cfg = AppConfig::default();
+        cfg.execution.test_mode = true;
+        cfg.orchestrator.parallelism = 1;
+        cfg.watch.idle_backoff_seconds = 1;
+        cfg.quality_report.path = dir
+            .path()
+            .join(".gardener/quality.md")
+            .display()
+            .to_string();
+        let terminal = FakeTerminal::new(false);
+        let runtime = ProductionRuntime {
+            clock: Arc::new(FakeClock::default()),
+            file_system: Arc::new(ProductionFileSystem),
+            process_runner: Arc::new(FakeProcessRunner::default()),
+            terminal: Arc::new(terminal.clone()),
+        };
+
+        let health = std::thread::scope(|threads| {
+            threads.spawn(|| {
+                // Arrives while the daemon is backing off on an empty backlog.
+                std::thread::sleep(std::time::Duration::from_millis(300));
+                store.upsert_task(task("added while idle")).expect("late task");
+            });
+            run_watch(&runtime, &scope, &cfg, &store, &terminal, Some(2), None)
+        })
+        .expect("watch");
+
+        assert_eq!(health.tasks_completed, 2);
+        assert_eq!(health.state, WatchState::Stopped);
+        assert!(health.cycles >= 2, "{health:?}");
+        assert!(store
+            .list_tasks()
+            .expect("list")
+            .iter()
+            .all(|task| task.status == TaskStatus::Complete));
+        assert!(terminal
+            .written_lines()
+            .iter()
+            .any(|line| line.contains("next poll in 1s")));
+
+        let written: serde_json::Value = serde_json::from_str(
+            &std::fs::read_to_string(watch_health_path(&cfg, &scope)).expect("health file"),
+        )
+        .expect("health json");
+        assert_eq!(written["state"], "stopped");
+        assert_eq!(written["tasks_completed"], 2);
+        assert_eq!(written["pid"], std::process::id());
+    }
+
+    #[test]
+    fn watch_reruns_audits_on_their_interval_and_keeps_the_health_file_fresh() {
+        let dir = TempDir::new().expect("tempdir");
+        let scope = RuntimeScope {
+            process_cwd: dir.path().to_path_buf(),
+            repo_root: Some(dir.path().to_path_buf()),
+            working_dir: dir.path().to_path_buf(),
+        };
+        let store = BacklogStore::open(dir.path().join(".cache/gardener/backlog.sqlite"))
+            .expect("open store");
+        store.upsert_task(task("present at start")).expect("seed");
+
+        let mut cfg = AppConfig::default();
+        cfg.execution.test_mode = true;
+        cfg.orchestrator.parallelism = 1;
+        cfg.watch.idle_backoff_seconds = 1;
+        cfg.watch.audit_interval_seconds = 1;
+        cfg.watch.health_interval_seconds = 1;
+        cfg.quality_report.path = dir
+            .path()
+            .join(".gardener/quality.md")
+            .display()
+            .to_string();
+        let terminal = FakeTerminal::new(false);
+        let runtime = ProductionRuntime {
+            clock: Arc::new(FakeClock::default()),
+            file_system: Arc::new(ProductionFileSystem),
+            process_runner: Arc::new(FakeProcessRunner::default()),
+            terminal: Arc::new(terminal.clone()),
+        };
+        let health_path = watch_health_path(&cfg, &scope);
+        let read_health = || -> serde_json::Value {
+            serde_json::from_str(&std::fs::read_to_string(&health_path).expect("health file"))
+                .expect("health json")
+        };
+
+        // Each audit outlasts the heartbeat, so the file moves while it runs.
+        let mut audits = Vec::new();
+        let mut audit = |_: &mut AppConfig| {
+            let before = read_health();
+            std::thread::sleep(std::time::Duration::from_millis(2_200));
+            audits.push((before, read_health()));
+            Ok(())
+        };
+        let health = std::thread::scope(|threads| {
+            threads.spawn(|| {
+                std::thread::sleep(std::time::Duration::from_millis(300));
+                store.upsert_task(task("added while idle")).expect("late task");
+            });
+            watch_loop(
+                &runtime,
+                &scope,
+                &cfg,
+                &store,
+                &terminal,
+                Some(2),
+                None,
+                Some(&mut audit),
+            )
+        })
+        .expect("watch");
+
+        assert_eq!(health.tasks_completed, 2);
+        assert!(health.last_audit_at.is_some(), "{health:?}");
+        assert!(!audits.is_empty(), "the audit interval passed during the run");
+        let (before, during) = &audits[0];
+        assert_eq!(before["state"], "auditing");
+        assert_eq!(during["state"], "auditing");
+        assert!(
+            during["updated_at"].as_i64() > before["updated_at"].as_i64(),
+            "{before} -> {during}"
+        );
+        assert_eq!(read_health()["state"], "stopped");
+    }
+}
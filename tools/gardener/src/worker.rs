@@ -1,4 +1,5 @@
 use crate::agent::factory::AdapterFactory;
+use crate::agent::limits::acquire_agent_slot;
 use crate::config::{effective_agent_for_state, effective_model_for_state, AppConfig};
 use crate::errors::GardenerError;
 use crate::fsm::{
@@ -9,7 +10,9 @@ use crate::gh::{generate_pr_title_body, GhClient};
 use crate::git::GitClient;
 use crate::learning_loop::LearningLoop;
 use crate::logging::append_run_log;
+use crate::merge_queue::merge_queue;
 use crate::output_envelope::{parse_typed_payload, END_MARKER, START_MARKER};
+use crate::priority::Priority;
 use crate::prompt_context::PromptContextItem;
 use crate::prompt_knowledge::to_prompt_lines;
 use crate::prompt_registry::PromptRegistry;
@@ -17,22 +20,27 @@ use crate::prompts::render_state_prompt;
 use crate::protocol::AgentTerminal;
 use crate::replay::recorder::{emit_record, get_recording_worker_id, next_seq, timestamp_ns};
 use crate::replay::recording::{AgentTurnRecord, RecordEntry};
-use crate::runtime::ProcessRunner;
-use crate::types::{RuntimeScope, WorkerState};
+use crate::runtime::{worker_drain_requested, worker_stop_requested, ProcessRunner};
+use crate::types::{AgentKind, RuntimeScope, WorkerState};
+use crate::usage::{record_turn_usage, UsageTotals};
+use crate::worker_checkpoint::{
+    checkpoint_path, clear_checkpoint, load_checkpoint, save_checkpoint, WorkerCheckpoint,
+};
 use crate::worker_identity::WorkerIdentity;
 use crate::worktree::WorktreeClient;
 use serde::Serialize;
 use serde_json::json;
 use std::env;
 use std::path::{Path, PathBuf};
-use std::sync::{Mutex, OnceLock};
 use std::time::{Duration, SystemTime, UNIX_EPOCH};
 
-#[derive(Debug, Clone, PartialEq, Eq)]
+#[derive(Debug, Clone, PartialEq)]
 pub struct WorkerLogEvent {
     pub state: WorkerState,
     pub prompt_version: String,
     pub context_manifest_hash: String,
+    /// Tokens and cost of the agent turn this event records.
+    pub usage: UsageTotals,
 }
 
 #[derive(Debug, Clone, PartialEq, Eq)]
@@ -45,16 +53,33 @@ pub struct TeardownReport {
     pub main_updated: bool,
 }
 
-#[derive(Debug, Clone, PartialEq, Eq)]
+#[derive(Debug, Clone, PartialEq)]
 pub struct WorkerRunSummary {
     pub worker_id: String,
     pub session_id: String,
     pub final_state: WorkerState,
     pub logs: Vec<WorkerLogEvent>,
+    /// PR opened for the task, once the run got that far.
+    pub pr_number: Option<u64>,
+    pub branch: Option<String>,
+    /// Merge commit on main, when the PR was merged.
+    pub merge_sha: Option<String>,
+    pub review_loops: u32,
     pub teardown: Option<TeardownReport>,
     pub failure_reason: Option<String>,
 }
 
+impl WorkerRunSummary {
+    /// Tokens and cost of every agent turn the task ran.
+    pub fn usage(&self) -> UsageTotals {
+        let mut totals = UsageTotals::default();
+        for event in &self.logs {
+            totals.add(&event.usage);
+        }
+        totals
+    }
+}
+
 #[derive(Debug, Clone, PartialEq, Eq, Serialize)]
 struct ReviewArtifact {
     task_id: String,
@@ -64,12 +89,8 @@ struct ReviewArtifact {
     recorded_at_unix_ms: i64,
 }
 
-static MERGE_PHASE_LOCK: OnceLock<Mutex<()>> = OnceLock::new();
-
-fn merge_phase_lock() -> &'static Mutex<()> {
-    MERGE_PHASE_LOCK.get_or_init(|| Mutex::new(()))
-}
-
+/// Failed merges a PR may have, across all its merge queue turns, before the
+/// task fails.
 const MAX_MERGE_REMEDIATION: u32 = 3;
 const MERGEABILITY_POLL_MAX: u32 = 12;
 const MERGEABILITY_POLL_INTERVAL: Duration = Duration::from_secs(5);
@@ -89,6 +110,8 @@ fn extract_failure_reason(payload: &serde_json::Value) -> Option<String> {
     Some(raw.to_string())
 }
 
+/// `priority` only orders the task's PR in the merge queue.
+#[allow(clippy::too_many_arguments)]
 pub fn execute_task(
     cfg: &AppConfig,
     process_runner: &dyn ProcessRunner,
@@ -96,6 +119,7 @@ pub fn execute_task(
     worker_id: &str,
     task_id: &str,
     task_summary: &str,
+    priority: Priority,
     attempt_count: i64,
 ) -> Result<WorkerRunSummary, GardenerError> {
     append_run_log(
@@ -118,10 +142,12 @@ pub fn execute_task(
         worker_id,
         task_id,
         task_summary,
+        priority,
         attempt_count,
     )
 }
 
+#[allow(clippy::too_many_arguments)]
 fn execute_task_live(
     cfg: &AppConfig,
     process_runner: &dyn ProcessRunner,
@@ -129,6 +155,7 @@ fn execute_task_live(
     worker_id: &str,
     task_id: &str,
     task_summary: &str,
+    priority: Priority,
     attempt_count: i64,
 ) -> Result<WorkerRunSummary, GardenerError> {
     append_run_log(
@@ -142,13 +169,44 @@ fn execute_task_live(
     );
     let registry = PromptRegistry::v1().with_retry_rebase(attempt_count);
     let identity = WorkerIdentity::new(worker_id);
-    let mut fsm = FsmSnapshot::default();
     let learning_loop = LearningLoop::default();
     let mut logs = Vec::new();
     let factory = AdapterFactory::with_defaults();
     let repo_root = scope.repo_root.as_ref().unwrap_or(&scope.working_dir);
-    let worktree_path = worktree_path_for(repo_root, worker_id, task_id);
-    let branch = worktree_branch_for(worker_id, task_id);
+    let checkpoint_path = checkpoint_path(scope, &worktree_slug_for_task(task_id));
+    // A previous run that got past Understand left its state, branch and PR
+    // behind; carry on from there rather than starting over.
+    let mut checkpoint = match load_checkpoint(&checkpoint_path, task_id, worker_id) {
+        Some(checkpoint) => {
+            append_run_log(
+                "info",
+                "worker.task.resumed",
+                json!({
+                    "worker_id": worker_id,
+                    "task_id": task_id,
+                    "state": checkpoint.state.as_str(),
+                    "pr_number": checkpoint.pr_number,
+                    "branch": checkpoint.branch
+                }),
+            );
+            checkpoint
+        }
+        None => WorkerCheckpoint {
+            task_id: task_id.to_string(),
+            state: WorkerState::Understand,
+            category: None,
+            doing_turns: 0,
+            review_loops: 0,
+            pr_number: None,
+            branch: worktree_branch_for(worker_id, task_id),
+            worktree_path: worktree_path_for(repo_root, worker_id, task_id),
+            plan: None,
+            updated_at_unix_ms: 0,
+        },
+    };
+    let mut fsm = checkpoint.fsm();
+    let worktree_path = checkpoint.worktree_path.clone();
+    let branch = checkpoint.branch.clone();
     let worktree_client = WorktreeClient::new(process_runner, repo_root);
     worktree_client.create_or_resume(&worktree_path, &branch)?;
 
@@ -165,53 +223,64 @@ fn execute_task_live(
         );
     }
 
-    let understand_result = run_agent_turn(TurnContext {
-        cfg,
-        process_runner,
-        scope,
-        worktree_path: &worktree_path,
-        factory: &factory,
-        registry: &registry,
-        learning_loop: &learning_loop,
-        identity: &identity,
-        state: WorkerState::Understand,
-        task_summary,
-        attempt_count,
-    })?;
-    logs.push(understand_result.log_event);
-    if understand_result.terminal == AgentTerminal::Failure {
-        let failure_reason = extract_failure_reason(&understand_result.payload);
+    if fsm.state == WorkerState::Understand {
+        let understand_result = run_agent_turn(TurnContext {
+            cfg,
+            process_runner,
+            scope,
+            worktree_path: &worktree_path,
+            factory: &factory,
+            registry: &registry,
+            learning_loop: &learning_loop,
+            identity: &identity,
+            state: WorkerState::Understand,
+            task_summary,
+            attempt_count,
+        })?;
+        logs.push(understand_result.log_event);
+        if understand_result.terminal == AgentTerminal::Failure {
+            let failure_reason = extract_failure_reason(&understand_result.payload);
+            append_run_log(
+                "error",
+                "worker.task.terminal_failure",
+                json!({
+                    "worker_id": identity.worker_id,
+                    "state": "understand"
+                }),
+            );
+            return Ok(WorkerRunSummary {
+                worker_id: identity.worker_id,
+                session_id: identity.session.session_id,
+                final_state: WorkerState::Failed,
+                logs,
+                pr_number: None,
+                branch: Some(branch),
+                merge_sha: None,
+                review_loops: fsm.review_loops,
+                teardown: None,
+                failure_reason,
+            });
+        }
+        let understand =
+            parse_understand_output(&understand_result.payload, worker_id, task_summary);
         append_run_log(
-            "error",
-            "worker.task.terminal_failure",
+            "debug",
+            "worker.task.classified",
             json!({
-                "worker_id": identity.worker_id,
-                "state": "understand"
+                "worker_id": worker_id,
+                "task_id": task_id,
+                "task_type": format!("{:?}", understand.task_type),
+                "reasoning": understand.reasoning,
+                "worktree_path": worktree_path.display().to_string(),
+                "branch": branch
             }),
         );
-        return Ok(WorkerRunSummary {
-            worker_id: identity.worker_id,
-            session_id: identity.session.session_id,
-            final_state: WorkerState::Failed,
-            logs,
-            teardown: None,
-            failure_reason,
-        });
+        fsm.apply_understand(&understand)?;
+        save_checkpoint(&checkpoint_path, &mut checkpoint, &fsm, worker_id)?;
+        if worker_drain_requested() {
+            return Ok(drained_summary(identity, task_id, &fsm, logs, None, Some(branch)));
+        }
     }
-    let understand = parse_understand_output(&understand_result.payload, worker_id, task_summary);
-    append_run_log(
-        "debug",
-        "worker.task.classified",
-        json!({
-            "worker_id": worker_id,
-            "task_id": task_id,
-            "task_type": format!("{:?}", understand.task_type),
-            "reasoning": understand.reasoning,
-            "worktree_path": worktree_path.display().to_string(),
-            "branch": branch
-        }),
-    );
-    fsm.apply_understand(&understand)?;
 
     if fsm.state == WorkerState::Planning {
         let planning_result = run_agent_turn(TurnContext {
@@ -243,222 +312,301 @@ fn execute_task_live(
                 session_id: identity.session.session_id,
                 final_state: WorkerState::Failed,
                 logs,
+                pr_number: None,
+                branch: Some(branch),
+                merge_sha: None,
+                review_loops: fsm.review_loops,
                 teardown: None,
                 failure_reason,
             });
         }
+        checkpoint.plan = Some(planning_result.payload);
         fsm.transition(WorkerState::Doing)?;
+        save_checkpoint(&checkpoint_path, &mut checkpoint, &fsm, worker_id)?;
+        if worker_drain_requested() {
+            return Ok(drained_summary(identity, task_id, &fsm, logs, None, Some(branch)));
+        }
     }
 
-    let doing_result = run_agent_turn(TurnContext {
-        cfg,
-        process_runner,
-        scope,
-        worktree_path: &worktree_path,
-        factory: &factory,
-        registry: &registry,
-        learning_loop: &learning_loop,
-        identity: &identity,
-        state: WorkerState::Doing,
-        task_summary,
-        attempt_count,
-    })?;
-    logs.push(doing_result.log_event);
-    if doing_result.terminal == AgentTerminal::Failure {
-        let failure_reason = extract_failure_reason(&doing_result.payload);
-        append_run_log(
-            "error",
-            "worker.task.terminal_failure",
-            json!({
-                "worker_id": identity.worker_id,
-                "state": "doing"
-            }),
-        );
-        return Ok(WorkerRunSummary {
-            worker_id: identity.worker_id,
-            session_id: identity.session.session_id,
-            final_state: WorkerState::Failed,
-            logs,
-            teardown: None,
-            failure_reason,
-        });
+    if fsm.state == WorkerState::Doing {
+        let doing_result = run_agent_turn(TurnContext {
+            cfg,
+            process_runner,
+            scope,
+            worktree_path: &worktree_path,
+            factory: &factory,
+            registry: &registry,
+            learning_loop: &learning_loop,
+            identity: &identity,
+            state: WorkerState::Doing,
+            task_summary,
+            attempt_count,
+        })?;
+        logs.push(doing_result.log_event);
+        if doing_result.terminal == AgentTerminal::Failure {
+            let failure_reason = extract_failure_reason(&doing_result.payload);
+            append_run_log(
+                "error",
+                "worker.task.terminal_failure",
+                json!({
+                    "worker_id": identity.worker_id,
+                    "state": "doing"
+                }),
+            );
+            return Ok(WorkerRunSummary {
+                worker_id: identity.worker_id,
+                session_id: identity.session.session_id,
+                final_state: WorkerState::Failed,
+                logs,
+                pr_number: checkpoint.pr_number,
+                branch: Some(branch),
+                merge_sha: None,
+                review_loops: fsm.review_loops,
+                teardown: None,
+                failure_reason,
+            });
+        }
+        fsm.on_doing_turn_completed()?;
+        if fsm.state == WorkerState::Parked {
+            append_run_log(
+                "info",
+                "worker.task.parked",
+                json!({
+                    "worker_id": identity.worker_id,
+                    "task_id": task_id
+                }),
+            );
+            return Ok(WorkerRunSummary {
+                worker_id: identity.worker_id,
+                session_id: identity.session.session_id,
+                final_state: WorkerState::Parked,
+                logs,
+                pr_number: checkpoint.pr_number,
+                branch: Some(branch),
+                merge_sha: None,
+                review_loops: fsm.review_loops,
+                teardown: None,
+                failure_reason: None,
+            });
+        }
+        if worker_drain_requested() {
+            return Ok(drained_summary(
+                identity,
+                task_id,
+                &fsm,
+                logs,
+                checkpoint.pr_number,
+                Some(branch),
+            ));
+        }
+
+        // --- Deterministic Commit ---
+        // Agent wrote code — we commit deterministically.
+        GitClient::new(process_runner, &worktree_path).commit_all("feat: implement task changes")?;
+        fsm.transition(WorkerState::Gitting)?;
+        save_checkpoint(&checkpoint_path, &mut checkpoint, &fsm, worker_id)?;
     }
-    fsm.on_doing_turn_completed()?;
-    if fsm.state == WorkerState::Parked {
+
+    // --- Deterministic Gitting ---
+    let git = GitClient::new(process_runner, &worktree_path);
+    let gh = GhClient::new(process_runner, &worktree_path);
+    if fsm.state == WorkerState::Gitting {
         append_run_log(
             "info",
-            "worker.task.parked",
+            "worker.gitting.deterministic.started",
             json!({
                 "worker_id": identity.worker_id,
-                "task_id": task_id
+                "task_id": task_id,
+                "branch": branch
             }),
         );
-        return Ok(WorkerRunSummary {
-            worker_id: identity.worker_id,
-            session_id: identity.session.session_id,
-            final_state: WorkerState::Parked,
-            logs,
-            teardown: None,
-            failure_reason: None,
-        });
-    }
 
-    // --- Deterministic Commit ---
-    // Agent wrote code — we commit deterministically.
-    let git = GitClient::new(process_runner, &worktree_path);
-    git.commit_all("feat: implement task changes")?;
+        git.push_with_rebase_recovery(&branch)?;
 
-    // --- Deterministic Gitting ---
-    fsm.transition(WorkerState::Gitting)?;
-    append_run_log(
-        "info",
-        "worker.gitting.deterministic.started",
-        json!({
-            "worker_id": identity.worker_id,
-            "task_id": task_id,
-            "branch": branch
-        }),
-    );
-
-    git.push_with_rebase_recovery(&branch)?;
-
-    let gh = GhClient::new(process_runner, &worktree_path);
-    let (title, body) = generate_pr_title_body(process_runner, &worktree_path, task_summary)?;
-    let (number, _url) = gh.create_pr(&title, &body)?;
-    let pr_number = number;
-    append_run_log(
-        "info",
-        "worker.gitting.deterministic.pr_created",
-        json!({
-            "worker_id": identity.worker_id,
-            "pr_number": number,
-            "branch": branch
-        }),
-    );
+        // The PR outlives review loops and restarts; open it once per task.
+        if let Some(number) = checkpoint.pr_number {
+            append_run_log(
+                "info",
+                "worker.gitting.deterministic.pr_reused",
+                json!({
+                    "worker_id": identity.worker_id,
+                    "pr_number": number,
+                    "branch": branch
+                }),
+            );
+        } else {
+            let (title, body) =
+                generate_pr_title_body(process_runner, &worktree_path, task_summary)?;
+            let (number, _url) = gh.create_pr(&title, &body)?;
+            checkpoint.pr_number = Some(number);
+            save_checkpoint(&checkpoint_path, &mut checkpoint, &fsm, worker_id)?;
+            append_run_log(
+                "info",
+                "worker.gitting.deterministic.pr_created",
+                json!({
+                    "worker_id": identity.worker_id,
+                    "pr_number": number,
+                    "branch": branch
+                }),
+            );
+        }
+        if worker_drain_requested() {
+            return Ok(drained_summary(
+                identity,
+                task_id,
+                &fsm,
+                logs,
+                checkpoint.pr_number,
+                Some(branch),
+            ));
+        }
+        fsm.transition(WorkerState::Reviewing)?;
+        save_checkpoint(&checkpoint_path, &mut checkpoint, &fsm, worker_id)?;
+    }
+    let Some(pr_number) = checkpoint.pr_number else {
+        return Err(GardenerError::Process(format!(
+            "task {task_id} reached {} without a PR",
+            fsm.state.as_str()
+        )));
+    };
 
     // --- Reviewing ---
-    fsm.transition(WorkerState::Reviewing)?;
-    let reviewing_result = run_agent_turn(TurnContext {
-        cfg,
-        process_runner,
-        scope,
-        worktree_path: &worktree_path,
-        factory: &factory,
-        registry: &registry,
-        learning_loop: &learning_loop,
-        identity: &identity,
-        state: WorkerState::Reviewing,
-        task_summary,
-        attempt_count,
-    })?;
-    logs.push(reviewing_result.log_event);
-    if reviewing_result.terminal == AgentTerminal::Failure {
-        let failure_reason = extract_failure_reason(&reviewing_result.payload);
-        append_run_log(
-            "error",
-            "worker.task.terminal_failure",
-            json!({
-                "worker_id": identity.worker_id,
-                "state": "reviewing"
-            }),
-        );
-        return Ok(WorkerRunSummary {
-            worker_id: identity.worker_id,
-            session_id: identity.session.session_id,
-            final_state: WorkerState::Failed,
-            logs,
-            teardown: None,
-            failure_reason,
-        });
-    }
+    if fsm.state == WorkerState::Reviewing {
+        let reviewing_result = run_agent_turn(TurnContext {
+            cfg,
+            process_runner,
+            scope,
+            worktree_path: &worktree_path,
+            factory: &factory,
+            registry: &registry,
+            learning_loop: &learning_loop,
+            identity: &identity,
+            state: WorkerState::Reviewing,
+            task_summary,
+            attempt_count,
+        })?;
+        logs.push(reviewing_result.log_event);
+        if reviewing_result.terminal == AgentTerminal::Failure {
+            let failure_reason = extract_failure_reason(&reviewing_result.payload);
+            append_run_log(
+                "error",
+                "worker.task.terminal_failure",
+                json!({
+                    "worker_id": identity.worker_id,
+                    "state": "reviewing"
+                }),
+            );
+            return Ok(WorkerRunSummary {
+                worker_id: identity.worker_id,
+                session_id: identity.session.session_id,
+                final_state: WorkerState::Failed,
+                logs,
+                pr_number: Some(pr_number),
+                branch: Some(branch),
+                merge_sha: None,
+                review_loops: fsm.review_loops,
+                teardown: None,
+                failure_reason,
+            });
+        }
 
-    let reviewing_output = parse_reviewing_output(&reviewing_result.payload);
-    log_and_persist_review_output(scope, task_id, &identity.worker_id, &reviewing_output);
-    if reviewing_output.verdict == ReviewVerdict::NeedsChanges {
-        append_run_log(
-            "info",
-            "worker.review.needs_changes",
-            json!({
-                "worker_id": identity.worker_id,
-                "task_id": task_id,
-                "review_loops": fsm.review_loops,
-                "max_review_loops": MAX_REVIEW_LOOPS,
-                "suggestions_count": reviewing_output.suggestions.len(),
-                "suggestions": reviewing_output.suggestions
-            }),
-        );
-        if fsm.review_loops >= MAX_REVIEW_LOOPS {
+        let reviewing_output = parse_reviewing_output(&reviewing_result.payload);
+        log_and_persist_review_output(scope, task_id, &identity.worker_id, &reviewing_output);
+        if reviewing_output.verdict == ReviewVerdict::NeedsChanges {
             append_run_log(
-                "warn",
-                "worker.review.loop_cap_reached",
+                "info",
+                "worker.review.needs_changes",
+                json!({
+                    "worker_id": identity.worker_id,
+                    "task_id": task_id,
+                    "review_loops": fsm.review_loops,
+                    "max_review_loops": MAX_REVIEW_LOOPS,
+                    "suggestions_count": reviewing_output.suggestions.len(),
+                    "suggestions": reviewing_output.suggestions
+                }),
+            );
+            if fsm.review_loops >= MAX_REVIEW_LOOPS {
+                append_run_log(
+                    "warn",
+                    "worker.review.loop_cap_reached",
+                    json!({
+                        "worker_id": identity.worker_id,
+                        "task_id": task_id,
+                        "review_loops": fsm.review_loops
+                    }),
+                );
+                fsm.on_review_loop_back()?;
+                return Ok(WorkerRunSummary {
+                    worker_id: identity.worker_id,
+                    session_id: identity.session.session_id,
+                    final_state: fsm.state,
+                    logs,
+                    pr_number: Some(pr_number),
+                    branch: Some(branch),
+                    merge_sha: None,
+                    review_loops: fsm.review_loops,
+                    teardown: None,
+                    failure_reason: None,
+                });
+            }
+            fsm.on_review_loop_back()?;
+            fsm.transition(WorkerState::Doing)?;
+        } else {
+            append_run_log(
+                "info",
+                "worker.review.approved",
                 json!({
                     "worker_id": identity.worker_id,
                     "task_id": task_id,
-                    "review_loops": fsm.review_loops
+                    "review_loops": fsm.review_loops,
+                    "suggestions_count": reviewing_output.suggestions.len(),
+                    "suggestions": reviewing_output.suggestions
                 }),
             );
-            fsm.on_review_loop_back()?;
-            return Ok(WorkerRunSummary {
-                worker_id: identity.worker_id,
-                session_id: identity.session.session_id,
-                final_state: fsm.state,
-                logs,
-                teardown: None,
-                failure_reason: None,
-            });
+            fsm.transition(WorkerState::Merging)?;
         }
-        fsm.on_review_loop_back()?;
-        fsm.transition(WorkerState::Doing)?;
-    } else {
-        append_run_log(
-            "info",
-            "worker.review.approved",
-            json!({
-                "worker_id": identity.worker_id,
-                "task_id": task_id,
-                "review_loops": fsm.review_loops,
-                "suggestions_count": reviewing_output.suggestions.len(),
-                "suggestions": reviewing_output.suggestions
-            }),
-        );
-        fsm.transition(WorkerState::Merging)?;
+        save_checkpoint(&checkpoint_path, &mut checkpoint, &fsm, worker_id)?;
+    }
+    if worker_drain_requested() {
+        return Ok(drained_summary(
+            identity,
+            task_id,
+            &fsm,
+            logs,
+            Some(pr_number),
+            Some(branch),
+        ));
     }
 
     // --- Deterministic Merging ---
-    append_run_log(
-        "info",
-        "worker.merging.lock.waiting",
-        json!({
-            "worker_id": identity.worker_id,
-            "task_id": task_id,
-            "branch": branch
-        }),
-    );
-    let _merge_guard = merge_phase_lock()
-        .lock()
-        .map_err(|_| GardenerError::Process("worker merging lock poisoned".to_string()))?;
-    append_run_log(
-        "info",
-        "worker.merging.lock.acquired",
-        json!({
-            "worker_id": identity.worker_id,
-            "task_id": task_id,
-            "branch": branch
-        }),
-    );
-
+    // Each PR takes its turn in the merge queue, rebased onto the main the
+    // previous turn (or the previous member of its batch) left behind, so it
+    // is checked against what it lands on.
     let pr = pr_number;
-    let mut merge_output = MergingOutput {
-        merged: false,
-        merge_sha: None,
+    let changed_files = if cfg.merge_queue.batch_size > 1 {
+        git.changed_files("main").unwrap_or_default()
+    } else {
+        Vec::new()
     };
-
-    for attempt in 0..MAX_MERGE_REMEDIATION {
-        // Wait for GitHub to compute mergeability
-        let _ = gh.poll_mergeability(pr, MERGEABILITY_POLL_MAX, MERGEABILITY_POLL_INTERVAL)?;
-
-        match gh.merge_pr(pr) {
+    let ticket = merge_queue().enqueue(&identity.worker_id, task_id, pr, priority, changed_files);
+    let merge_output;
+
+    // Held through post-merge validation, so the next PR rebases onto a main
+    // that has been checked.
+    let merge_turn = loop {
+        let turn = ticket.wait_turn(&cfg.merge_queue, &worker_stop_requested);
+        let merged = git
+            .rebase_onto_main("main")
+            .and_then(|()| git.force_push_with_lease(&branch))
+            .and_then(|()| {
+                // Wait for GitHub to compute mergeability of the rebased head
+                gh.poll_mergeability(pr, MERGEABILITY_POLL_MAX, MERGEABILITY_POLL_INTERVAL)?;
+                gh.merge_pr(pr)
+            });
+        let merge_err = match merged {
             Ok(()) => {
+                // Merged PRs are never resumed; a later attempt starts fresh.
+                clear_checkpoint(&checkpoint_path, task_id, worker_id);
                 let view = gh.view_pr(pr)?;
                 let sha = view
                     .merge_commit
@@ -468,94 +616,113 @@ fn execute_task_live(
                     merged: true,
                     merge_sha: Some(sha),
                 };
+                turn.mark_landed();
                 append_run_log(
                     "info",
                     "worker.merging.deterministic.succeeded",
                     json!({
                         "worker_id": identity.worker_id,
-                        "pr_number": pr,
-                        "attempt": attempt + 1
+                        "pr_number": pr
                     }),
                 );
-                break;
+                break turn;
             }
-            Err(merge_err) => {
-                if attempt + 1 >= MAX_MERGE_REMEDIATION {
-                    append_run_log(
-                        "error",
-                        "worker.merging.deterministic.exhausted",
-                        json!({
-                            "worker_id": identity.worker_id,
-                            "pr_number": pr,
-                            "attempts": MAX_MERGE_REMEDIATION,
-                            "error": merge_err.to_string()
-                        }),
-                    );
-                    return Ok(WorkerRunSummary {
-                        worker_id: identity.worker_id,
-                        session_id: identity.session.session_id,
-                        final_state: WorkerState::Failed,
-                        logs,
-                        teardown: None,
-                        failure_reason: Some(format!(
-                            "merge failed after {} remediation attempts: {}",
-                            MAX_MERGE_REMEDIATION, merge_err
-                        )),
-                    });
-                }
-
-                let status = gh.check_mergeability(pr)?;
-                append_run_log(
-                    "warn",
-                    "worker.merging.deterministic.remediation",
-                    json!({
-                        "worker_id": identity.worker_id,
-                        "pr_number": pr,
-                        "attempt": attempt + 1,
-                        "mergeable": format!("{:?}", status.mergeable),
-                        "merge_state_status": format!("{:?}", status.merge_state_status),
-                        "error": merge_err.to_string()
-                    }),
-                );
+            Err(merge_err) => merge_err,
+        };
+        let failed_attempts = ticket.record_failed_attempt();
+        if failed_attempts >= MAX_MERGE_REMEDIATION {
+            append_run_log(
+                "error",
+                "worker.merging.deterministic.exhausted",
+                json!({
+                    "worker_id": identity.worker_id,
+                    "pr_number": pr,
+                    "attempts": failed_attempts,
+                    "error": merge_err.to_string()
+                }),
+            );
+            return Ok(WorkerRunSummary {
+                worker_id: identity.worker_id,
+                session_id: identity.session.session_id,
+                final_state: WorkerState::Failed,
+                logs,
+                pr_number: Some(pr),
+                branch: Some(branch),
+                merge_sha: None,
+                review_loops: fsm.review_loops,
+                teardown: None,
+                failure_reason: Some(format!(
+                    "merge failed after {} remediation attempts: {}",
+                    MAX_MERGE_REMEDIATION, merge_err
+                )),
+            });
+        }
 
-                // Agent remediation turn — agent fixes code
-                let remediation_result = run_agent_turn(TurnContext {
-                    cfg,
-                    process_runner,
-                    scope,
-                    worktree_path: &worktree_path,
-                    factory: &factory,
-                    registry: &registry,
-                    learning_loop: &learning_loop,
-                    identity: &identity,
-                    state: WorkerState::Merging,
-                    task_summary,
-                    attempt_count,
-                })?;
-                logs.push(remediation_result.log_event);
-                if remediation_result.terminal == AgentTerminal::Failure {
-                    let failure_reason = extract_failure_reason(&remediation_result.payload);
-                    return Ok(WorkerRunSummary {
-                        worker_id: identity.worker_id,
-                        session_id: identity.session.session_id,
-                        final_state: WorkerState::Failed,
-                        logs,
-                        teardown: None,
-                        failure_reason,
-                    });
-                }
-
-                // We commit + push for the agent
-                git.commit_all("fix: merge remediation")?;
-                git.push_with_rebase_recovery(&branch)?;
-            }
+        let status = gh.check_mergeability(pr)?;
+        append_run_log(
+            "warn",
+            "worker.merging.deterministic.remediation",
+            json!({
+                "worker_id": identity.worker_id,
+                "pr_number": pr,
+                "attempt": failed_attempts,
+                "mergeable": format!("{:?}", status.mergeable),
+                "merge_state_status": format!("{:?}", status.merge_state_status),
+                "error": merge_err.to_string()
+            }),
+        );
+        // The fix can take a while; let the PRs behind this one merge meanwhile.
+        turn.yield_for_remediation();
+
+        // Agent remediation turn — agent fixes code
+        let remediation_result = run_agent_turn(TurnContext {
+            cfg,
+            process_runner,
+            scope,
+            worktree_path: &worktree_path,
+            factory: &factory,
+            registry: &registry,
+            learning_loop: &learning_loop,
+            identity: &identity,
+            state: WorkerState::Merging,
+            task_summary,
+            attempt_count,
+        })?;
+        logs.push(remediation_result.log_event);
+        if remediation_result.terminal == AgentTerminal::Failure {
+            let failure_reason = extract_failure_reason(&remediation_result.payload);
+            return Ok(WorkerRunSummary {
+                worker_id: identity.worker_id,
+                session_id: identity.session.session_id,
+                final_state: WorkerState::Failed,
+                logs,
+                pr_number: Some(pr),
+                branch: Some(branch),
+                merge_sha: None,
+                review_loops: fsm.review_loops,
+                teardown: None,
+                failure_reason,
+            });
         }
-    }
+
+        // We commit + push for the agent
+        git.commit_all("fix: merge remediation")?;
+        git.push_with_rebase_recovery(&branch)?;
+        if cfg.merge_queue.batch_size > 1 {
+            ticket.update_files(git.changed_files("main").unwrap_or_default());
+        }
+    };
 
     // --- Post-merge validation ---
+    // Batch members share the main checkout, so they pull and validate it one
+    // at a time.
+    let _main_checkout = merge_turn.lock_main_checkout();
     let repo_root_git = GitClient::new(process_runner, &scope.working_dir);
     repo_root_git.pull_main().ok(); // best-effort sync
-    if let Err(err) = repo_root_git.run_validation_command(&cfg.validation.command) {
+    if let Err(err) = repo_root_git.run_validation_command(
+        &cfg.validation.command,
+        Some(Duration::from_secs(cfg.validation.timeout_seconds)),
+    ) {
         append_run_log(
             "error",
             "worker.merging.post_validation_failed",
@@ -570,6 +737,10 @@ fn execute_task_live(
             session_id: identity.session.session_id,
             final_state: WorkerState::Failed,
             logs,
+            pr_number: Some(pr),
+            branch: Some(branch),
+            merge_sha: merge_output.merge_sha,
+            review_loops: fsm.review_loops,
             teardown: None,
             failure_reason: Some(format!("post-merge validation failed: {err}")),
         });
@@ -597,10 +768,11 @@ fn execute_task_live(
     );
     append_run_log(
         "info",
-        "worker.merging.lock.releasing",
+        "worker.merging.turn.releasing",
         json!({
             "worker_id": identity.worker_id,
-            "task_id": task_id
+            "task_id": task_id,
+            "pr_number": pr
         }),
     );
 
@@ -609,15 +781,346 @@ fn execute_task_live(
         session_id: identity.session.session_id,
         final_state: WorkerState::Complete,
         logs,
+        pr_number: Some(pr),
+        branch: Some(branch),
+        merge_sha: merge_output.merge_sha,
+        review_loops: fsm.review_loops,
         teardown: Some(teardown),
         failure_reason: None,
     })
 }
 
+/// Ends a run at a state boundary because the pool is draining. The summary
+/// keeps the state reached so far, and the pool releases the task for a later
+/// run instead of failing it.
+fn drained_summary(
+    identity: WorkerIdentity,
+    task_id: &str,
+    fsm: &FsmSnapshot,
+    logs: Vec<WorkerLogEvent>,
+    pr_number: Option<u64>,
+    branch: Option<String>,
+) -> WorkerRunSummary {
+    append_run_log(
+        "info",
+        "worker.task.drained",
+        json!({
+            "worker_id": identity.worker_id,
+            "task_id": task_id,
+            "state": fsm.state.as_str(),
+        }),
+    );
+    WorkerRunSummary {
+        worker_id: identity.worker_id,
+        session_id: identity.session.session_id,
+        final_state: fsm.state,
+        logs,
+        pr_number,
+        branch,
+        merge_sha: None,
+        review_loops: fsm.review_loops,
+        teardown: None,
+        failure_reason: None,
+    }
+}
+
+/// What the live worker would do for a task, worked out without running an
+/// agent, git or gh: every agent turn with its rendered prompt, and every
+/// command of the deterministic steps, in the order `execute_task_live` runs
+/// them. A PR number the run has yet to learn shows as `<pr>`, and the PR title
+/// and body shown are the task summary the branch's commit subjects refine.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TaskPlan {
+    pub task_id: String,
+    pub branch: String,
+    pub worktree_path: PathBuf,
+    /// State a checkpoint from an earlier run would resume the task at.
+    pub resume_from: Option<WorkerState>,
+    pub pr_number: Option<u64>,
+    pub steps: Vec<PlannedStep>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum PlannedStep {
+    AgentTurn {
+        state: WorkerState,
+        backend: Option<AgentKind>,
+        model: String,
+        prompt_version: String,
+        prompt: String,
+        /// Set when the turn only happens on some runs.
+        only_if: Option<&'static str>,
+    },
+    Command {
+        state: WorkerState,
+        cwd: PathBuf,
+        argv: Vec<String>,
+        only_if: Option<&'static str>,
+    },
+}
+
+pub fn plan_task_live(
+    cfg: &AppConfig,
+    scope: &RuntimeScope,
+    worker_id: &str,
+    task_id: &str,
+    task_summary: &str,
+    attempt_count: i64,
+) -> Result<TaskPlan, GardenerError> {
+    append_run_log(
+        "debug",
+        "worker.task.plan.started",
+        json!({
+            "worker_id": worker_id,
+            "task_id": task_id,
+            "attempt_count": attempt_count
+        }),
+    );
+    let registry = PromptRegistry::v1().with_retry_rebase(attempt_count);
+    let learning_loop = LearningLoop::default();
+    let repo_root = scope.repo_root.as_ref().unwrap_or(&scope.working_dir);
+    let checkpoint = load_checkpoint(
+        &checkpoint_path(scope, &worktree_slug_for_task(task_id)),
+        task_id,
+        worker_id,
+    );
+    let resume_from = checkpoint.as_ref().map(|checkpoint| checkpoint.state);
+    let pr_number = checkpoint.as_ref().and_then(|checkpoint| checkpoint.pr_number);
+    let branch = checkpoint.as_ref().map_or_else(
+        || worktree_branch_for(worker_id, task_id),
+        |checkpoint| checkpoint.branch.clone(),
+    );
+    let worktree_path = checkpoint.as_ref().map_or_else(
+        || worktree_path_for(repo_root, worker_id, task_id),
+        |checkpoint| checkpoint.worktree_path.clone(),
+    );
+    let pr = pr_number.map_or_else(|| "<pr>".to_string(), |number| number.to_string());
+    let pending = |state: WorkerState| {
+        resume_from.is_none_or(|resumed| live_state_rank(state) >= live_state_rank(resumed))
+    };
+    let turn = |state: WorkerState, only_if: Option<&'static str>| {
+        let prepared = prepare_prompt(
+            cfg,
+            &registry,
+            &learning_loop,
+            state,
+            worker_id,
+            task_summary,
+            attempt_count,
+        )?;
+        Ok::<_, GardenerError>(PlannedStep::AgentTurn {
+            state,
+            backend: effective_agent_for_state(cfg, state),
+            model: effective_model_for_state(cfg, state),
+            prompt_version: prepared.prompt_version,
+            prompt: prepared.rendered,
+            only_if,
+        })
+    };
+    let command = |state: WorkerState, cwd: &Path, argv: &[&str], only_if: Option<&'static str>| {
+        PlannedStep::Command {
+            state,
+            cwd: cwd.to_path_buf(),
+            argv: argv.iter().map(|arg| (*arg).to_string()).collect(),
+            only_if,
+        }
+    };
+    let worktree = worktree_path.display().to_string();
+
+    let mut steps = vec![
+        command(
+            WorkerState::Understand,
+            repo_root,
+            &["git", "worktree", "list", "--porcelain"],
+            None,
+        ),
+        command(
+            WorkerState::Understand,
+            repo_root,
+            &["git", "worktree", "add", &worktree, "-b", &branch],
+            Some("the worktree does not exist yet"),
+        ),
+    ];
+    for state in [
+        WorkerState::Understand,
+        WorkerState::Planning,
+        WorkerState::Doing,
+    ] {
+        if pending(state) {
+            steps.push(turn(state, None)?);
+        }
+    }
+    if pending(WorkerState::Doing) {
+        steps.push(command(
+            WorkerState::Doing,
+            &worktree_path,
+            &["git", "status", "--porcelain"],
+            None,
+        ));
+        steps.push(command(
+            WorkerState::Doing,
+            &worktree_path,
+            &["git", "add", "-A"],
+            Some("the worktree has changes"),
+        ));
+        steps.push(command(
+            WorkerState::Doing,
+            &worktree_path,
+            &["git", "commit", "-m", "feat: implement task changes"],
+            Some("the worktree has changes"),
+        ));
+    }
+    if pending(WorkerState::Gitting) {
+        steps.push(command(
+            WorkerState::Gitting,
+            &worktree_path,
+            &["git", "push", "origin", &branch],
+            None,
+        ));
+        steps.push(command(
+            WorkerState::Gitting,
+            &worktree_path,
+            &["git", "pull", "--rebase", "origin", &branch],
+            Some("the push is rejected; the push is then retried"),
+        ));
+        if pr_number.is_none() {
+            steps.push(command(
+                WorkerState::Gitting,
+                &worktree_path,
+                &["git", "log", "main..HEAD", "--format=%s"],
+                None,
+            ));
+            steps.push(command(
+                WorkerState::Gitting,
+                &worktree_path,
+                &[
+                    "gh",
+                    "pr",
+                    "create",
+                    "--title",
+                    task_summary,
+                    "--body",
+                    task_summary,
+                ],
+                None,
+            ));
+        }
+    }
+    if pending(WorkerState::Reviewing) {
+        steps.push(turn(WorkerState::Reviewing, None)?);
+    }
+    if cfg.merge_queue.batch_size > 1 {
+        steps.push(command(
+            WorkerState::Merging,
+            &worktree_path,
+            &["git", "diff", "--name-only", "origin/main...HEAD"],
+            None,
+        ));
+    }
+    for argv in [
+        &["git", "fetch", "origin", "main"][..],
+        &["git", "rebase", "origin/main"],
+        &["git", "push", "--force-with-lease", "origin", &branch],
+    ] {
+        steps.push(command(
+            WorkerState::Merging,
+            &worktree_path,
+            argv,
+            Some("the PR's turn in the merge queue comes"),
+        ));
+    }
+    steps.push(command(
+        WorkerState::Merging,
+        &worktree_path,
+        &["gh", "pr", "view", &pr, "--json", "mergeable,mergeStateStatus"],
+        Some("polled until GitHub knows the mergeability"),
+    ));
+    steps.push(command(
+        WorkerState::Merging,
+        &worktree_path,
+        &["gh", "pr", "merge", &pr, "--squash"],
+        None,
+    ));
+    steps.push(command(
+        WorkerState::Merging,
+        &worktree_path,
+        &["gh", "pr", "merge", &pr, "--merge"],
+        Some("the squash merge is refused"),
+    ));
+    steps.push(turn(
+        WorkerState::Merging,
+        Some(
+            "the rebase or merge fails; followed by a commit and push, then the PR \
+             waits for another turn",
+        ),
+    )?);
+    steps.push(command(
+        WorkerState::Merging,
+        &worktree_path,
+        &["gh", "pr", "view", &pr, "--json", "mergedAt,mergeCommit,headRefName,state"],
+        None,
+    ));
+    for argv in [
+        ["git", "fetch", "origin", "main"],
+        ["git", "merge", "--ff-only", "origin/main"],
+    ] {
+        steps.push(command(WorkerState::Merging, &scope.working_dir, &argv, None));
+    }
+    steps.push(command(
+        WorkerState::Merging,
+        &scope.working_dir,
+        &["sh", "-lc", &cfg.validation.command],
+        None,
+    ));
+    steps.push(command(
+        WorkerState::Complete,
+        repo_root,
+        &["git", "worktree", "remove", "--force", &worktree],
+        None,
+    ));
+    for argv in [
+        ["git", "fetch", "origin", "main"],
+        ["git", "merge", "--ff-only", "origin/main"],
+    ] {
+        steps.push(command(WorkerState::Complete, &scope.working_dir, &argv, None));
+    }
+    append_run_log(
+        "debug",
+        "worker.task.plan.finished",
+        json!({
+            "worker_id": worker_id,
+            "task_id": task_id,
+            "resume_from": resume_from.map(|state| state.as_str()),
+            "steps": steps.len()
+        }),
+    );
+    Ok(TaskPlan {
+        task_id: task_id.to_string(),
+        branch,
+        worktree_path,
+        resume_from,
+        pr_number,
+        steps,
+    })
+}
+
+/// Position of a state along the live worker's happy path.
+fn live_state_rank(state: WorkerState) -> u8 {
+    match state {
+        WorkerState::Seeding | WorkerState::Understand => 0,
+        WorkerState::Planning => 1,
+        WorkerState::Doing => 2,
+        WorkerState::Gitting => 3,
+        WorkerState::Reviewing => 4,
+        WorkerState::Merging => 5,
+        WorkerState::Complete | WorkerState::Failed | WorkerState::Parked => 6,
+    }
+}
+
 fn execute_task_simulated(
     cfg: &AppConfig,
     worker_id: &str,
-    _task_id: &str,
+    task_id: &str,
     task_summary: &str,
 ) -> Result<WorkerRunSummary, GardenerError> {
     append_run_log(
@@ -676,10 +1179,17 @@ fn execute_task_simulated(
             session_id: identity.session.session_id,
             final_state: WorkerState::Parked,
             logs,
+            pr_number: None,
+            branch: None,
+            merge_sha: None,
+            review_loops: fsm.review_loops,
             teardown: None,
             failure_reason: None,
         });
     }
+    if worker_drain_requested() {
+        return Ok(drained_summary(identity, task_id, &fsm, logs, None, None));
+    }
 
     // Deterministic gitting (simulated)
     fsm.transition(WorkerState::Gitting)?;
@@ -703,6 +1213,10 @@ fn execute_task_simulated(
                 session_id: identity.session.session_id,
                 final_state: fsm.state,
                 logs,
+                pr_number: None,
+                branch: None,
+                merge_sha: None,
+                review_loops: fsm.review_loops,
                 teardown: None,
                 failure_reason: None,
             });
@@ -746,6 +1260,10 @@ fn execute_task_simulated(
         session_id: identity.session.session_id,
         final_state: WorkerState::Complete,
         logs,
+        pr_number: None,
+        branch: None,
+        merge_sha: merge_output.merge_sha,
+        review_loops: fsm.review_loops,
         teardown: Some(teardown),
         failure_reason: None,
     })
@@ -763,6 +1281,7 @@ impl PreparedPrompt {
             state,
             prompt_version: self.prompt_version.clone(),
             context_manifest_hash: self.context_manifest_hash.clone(),
+            usage: UsageTotals::default(),
         }
     }
 }
@@ -826,6 +1345,7 @@ fn run_agent_turn(context: TurnContext<'_>) -> Result<TurnResult, GardenerError>
         std::fs::create_dir_all(parent).map_err(|e| GardenerError::Io(e.to_string()))?;
     }
     let estimated_prompt_tokens = prepared.rendered.split_whitespace().count();
+    let slot = acquire_agent_slot(&cfg.agent, backend, &model, &identity.worker_id, state);
     append_run_log(
         "info",
         "agent.turn.started",
@@ -847,7 +1367,7 @@ fn run_agent_turn(context: TurnContext<'_>) -> Result<TurnResult, GardenerError>
             worker_id: identity.worker_id.clone(),
             session_id: identity.session.session_id.clone(),
             sandbox_id: identity.session.sandbox_id.clone(),
-            model,
+            model: model.clone(),
             cwd: worktree_path.to_path_buf(),
             prompt_version: prepared.prompt_version.clone(),
             context_manifest_hash: prepared.context_manifest_hash.clone(),
@@ -855,10 +1375,20 @@ fn run_agent_turn(context: TurnContext<'_>) -> Result<TurnResult, GardenerError>
             output_file: Some(output_file),
             permissive_mode: cfg.execution.permissions_mode == "permissive_v1",
             max_turns,
+            timeout: Some(timeout_for_state(cfg, state)),
         },
         &prepared.rendered,
         None,
     )?;
+    drop(slot);
+    let cost_usd = record_turn_usage(
+        cfg,
+        &identity.worker_id,
+        state,
+        backend,
+        &model,
+        &step.usage,
+    );
     append_run_log(
         if step.terminal == AgentTerminal::Success {
             "info"
@@ -874,7 +1404,9 @@ fn run_agent_turn(context: TurnContext<'_>) -> Result<TurnResult, GardenerError>
                 AgentTerminal::Success => "success",
                 AgentTerminal::Failure => "failure"
             },
-            "diagnostic_count": step.diagnostics.len()
+            "diagnostic_count": step.diagnostics.len(),
+            "total_tokens": step.usage.total_tokens(),
+            "cost_usd": cost_usd
         }),
     );
     emit_record(RecordEntry::AgentTurn(AgentTurnRecord {
@@ -888,11 +1420,14 @@ fn run_agent_turn(context: TurnContext<'_>) -> Result<TurnResult, GardenerError>
         },
         payload: step.payload.clone(),
         diagnostic_count: step.diagnostics.len(),
+        usage: step.usage,
     }));
+    let mut log_event = prepared.log_event(state);
+    log_event.usage.add_turn(&step.usage, cost_usd);
     Ok(TurnResult {
         terminal: step.terminal,
         payload: step.payload,
-        log_event: prepared.log_event(state),
+        log_event,
     })
 }
 
@@ -1263,6 +1798,22 @@ fn max_turns_for_state(cfg: &AppConfig, state: WorkerState) -> u32 {
     }
 }
 
+fn timeout_for_state(cfg: &AppConfig, state: WorkerState) -> Duration {
+    let timeouts = &cfg.prompts.turn_timeout_seconds;
+    Duration::from_secs(match state {
+        WorkerState::Understand => timeouts.understand,
+        WorkerState::Planning => timeouts.planning,
+        WorkerState::Doing => timeouts.doing,
+        WorkerState::Gitting => timeouts.gitting,
+        WorkerState::Reviewing => timeouts.reviewing,
+        WorkerState::Merging => timeouts.merging,
+        WorkerState::Seeding
+        | WorkerState::Complete
+        | WorkerState::Failed
+        | WorkerState::Parked => timeouts.doing,
+    })
+}
+
 fn classify_task(task_summary: &str) -> crate::fsm::TaskCategory {
     let lower = task_summary.to_ascii_lowercase();
     if lower.contains("bug") || lower.contains("fix") {
@@ -1292,10 +1843,18 @@ mod tests {
         worktree_branch_for, worktree_path_for, worktree_slug_for_task, worktree_slug_suffix,
         WORKTREE_TASK_SLUG_PREFIX_CHARS,
     };
+    use super::WorkerRunSummary;
+    use crate::backlog_store::{BacklogStore, BacklogTask, NewTask};
     use crate::config::AppConfig;
-    use crate::runtime::FakeProcessRunner;
+    use crate::errors::GardenerError;
+    use crate::priority::Priority;
+    use crate::runtime::{set_worker_shutdown, FakeProcessRunner, PoolShutdown, ProcessOutput};
+    use crate::task_identity::TaskKind;
     use crate::types::{RuntimeScope, WorkerState};
+    use crate::worker_checkpoint::{checkpoint_path, load_checkpoint, WorkerCheckpoint};
     use std::path::PathBuf;
+    use std::sync::Arc;
+    use tempfile::TempDir;
 
     #[test]
     fn worker_executes_fsm_and_teardown_protocol() {
@@ -1314,6 +1873,7 @@ mod tests {
             "worker-1",
             "task-1",
             "feature: add prompt packet",
+            Priority::P1,
             1,
         )
         .expect("ok");
@@ -1336,6 +1896,206 @@ mod tests {
         assert!(teardown.state_cleared);
     }
 
+    #[test]
+    fn draining_worker_stops_after_its_current_state_without_merging() {
+        let mut cfg = AppConfig::default();
+        cfg.execution.test_mode = true;
+        let runner = FakeProcessRunner::default();
+        let scope = RuntimeScope {
+            process_cwd: PathBuf::from("/repo"),
+            repo_root: Some(PathBuf::from("/repo")),
+            working_dir: PathBuf::from("/repo"),
+        };
+        let shutdown = Arc::new(PoolShutdown::default());
+        shutdown.drain();
+        set_worker_shutdown(Some(shutdown));
+        let summary = execute_task(
+            &cfg,
+            &runner,
+            &scope,
+            "worker-1",
+            "task-1",
+            "feature: add prompt packet",
+            Priority::P1,
+            1,
+        );
+        set_worker_shutdown(None);
+        let summary = summary.expect("ok");
+
+        assert_eq!(summary.final_state, WorkerState::Doing);
+        assert!(summary.merge_sha.is_none());
+        assert!(summary.teardown.is_none());
+    }
+
+    fn output(exit_code: i32, stdout: &str) -> Result<ProcessOutput, GardenerError> {
+        Ok(ProcessOutput {
+            exit_code,
+            stdout: stdout.to_string(),
+            stderr: String::new(),
+        })
+    }
+
+    fn turn(result: &str) -> Result<ProcessOutput, GardenerError> {
+        output(0, &format!("{{\"type\":\"turn.completed\",\"result\":{result}}}\n"))
+    }
+
+    /// Prompt intents of the agent turns a run started, in order.
+    fn agent_turns(runner: &FakeProcessRunner) -> Vec<String> {
+        runner
+            .spawned()
+            .into_iter()
+            .filter(|request| request.program == "codex")
+            .filter_map(|request| request.args.last().cloned())
+            .map(|prompt| prompt.lines().next().unwrap_or_default().to_string())
+            .collect()
+    }
+
+    fn live_task(store: &BacklogStore) -> BacklogTask {
+        store
+            .upsert_task(NewTask {
+                kind: TaskKind::Feature,
+                title: "feature: add prompt packet".to_string(),
+                details: "details".to_string(),
+                rationale: String::new(),
+                scope_key: "worker".to_string(),
+                priority: Priority::P1,
+                source: "test".to_string(),
+                related_pr: None,
+                related_branch: None,
+                blocked_by: Vec::new(),
+            })
+            .expect("upsert")
+    }
+
+    fn run_live(
+        runner: &FakeProcessRunner,
+        scope: &RuntimeScope,
+        worker_id: &str,
+        task: &BacklogTask,
+    ) -> Result<WorkerRunSummary, GardenerError> {
+        execute_task(
+            &AppConfig::default(),
+            runner,
+            scope,
+            worker_id,
+            &task.task_id,
+            &task.title,
+            task.priority,
+            task.attempt_count,
+        )
+    }
+
+    #[test]
+    fn killed_worker_resumes_from_its_checkpoint_on_the_next_claim() {
+        let dir = TempDir::new().expect("tempdir");
+        let scope = RuntimeScope {
+            process_cwd: dir.path().to_path_buf(),
+            repo_root: Some(dir.path().to_path_buf()),
+            working_dir: dir.path().to_path_buf(),
+        };
+        let store = BacklogStore::open(dir.path().join("backlog.sqlite")).expect("open store");
+        live_task(&store);
+        let claimed = store.claim_next("worker-1", 60).expect("claim").expect("task");
+        let checkpoint_path = checkpoint_path(&scope, &worktree_slug_for_task(&claimed.task_id));
+
+        // Understand and Planning finish, then the agent dies during Doing.
+        let first = FakeProcessRunner::default();
+        first.push_response(output(0, ""));
+        first.push_response(output(0, ""));
+        first.push_response(output(1, ""));
+        first.push_response(turn(r#"{"task_type":"feature","reasoning":"new behavior"}"#));
+        first.push_response(turn(r#"{"steps":["write the test"]}"#));
+        first.push_response(Err(GardenerError::Process("killed by signal 9".to_string())));
+        run_live(&first, &scope, "worker-1", &claimed).expect_err("killed mid-state");
+        assert_eq!(agent_turns(&first).len(), 3);
+        let checkpoint =
+            load_checkpoint(&checkpoint_path, &claimed.task_id, "worker-1").expect("checkpoint");
+        assert_eq!(checkpoint.state, WorkerState::Doing);
+        assert_eq!(checkpoint.plan, Some(serde_json::json!({ "steps": ["write the test"] })));
+
+        // The lease runs out and another worker claims the task.
+        assert_eq!(store.recover_stale_leases(i64::MAX).expect("recover"), 1);
+        let reclaimed = store.claim_next("worker-2", 60).expect("claim").expect("task");
+        assert_eq!(reclaimed.task_id, claimed.task_id);
+        let second = FakeProcessRunner::default();
+        second.push_response(output(
+            0,
+            &format!(
+                "worktree {}\nbranch refs/heads/{}\n",
+                checkpoint.worktree_path.display(),
+                checkpoint.branch
+            ),
+        ));
+        second.push_response(output(1, ""));
+        second.push_response(Err(GardenerError::Process("killed again".to_string())));
+        run_live(&second, &scope, "worker-2", &reclaimed).expect_err("killed again");
+
+        // It picks up at Doing, in the first run's worktree and branch.
+        let turns = agent_turns(&second);
+        assert_eq!(turns.len(), 1);
+        assert!(turns[0].contains("implement changes and verify behavior"), "{turns:?}");
+        assert!(!second
+            .spawned()
+            .iter()
+            .any(|request| request.args.starts_with(&["worktree".to_string(), "add".to_string()])));
+        let resumed =
+            load_checkpoint(&checkpoint_path, &claimed.task_id, "worker-2").expect("checkpoint");
+        assert_eq!(resumed.branch, checkpoint.branch);
+        assert_eq!(resumed.worktree_path, checkpoint.worktree_path);
+    }
+
+    #[test]
+    fn stale_or_corrupt_checkpoints_are_ignored_and_replaced() {
+        let dir = TempDir::new().expect("tempdir");
+        let scope = RuntimeScope {
+            process_cwd: dir.path().to_path_buf(),
+            repo_root: Some(dir.path().to_path_buf()),
+            working_dir: dir.path().to_path_buf(),
+        };
+        let store = BacklogStore::open(dir.path().join("backlog.sqlite")).expect("open store");
+        let task = live_task(&store);
+        let checkpoint_path = checkpoint_path(&scope, &worktree_slug_for_task(&task.task_id));
+        std::fs::create_dir_all(checkpoint_path.parent().expect("parent")).expect("mkdir");
+        let stale = WorkerCheckpoint {
+            task_id: "another-task".to_string(),
+            state: WorkerState::Reviewing,
+            category: None,
+            doing_turns: 1,
+            review_loops: 0,
+            pr_number: Some(99),
+            branch: "gardener/worker-9-another-task".to_string(),
+            worktree_path: dir.path().join("another-task"),
+            plan: None,
+            updated_at_unix_ms: 0,
+        };
+
+        for leftover in [
+            "{\"task_id\": \"trunc".to_string(),
+            serde_json::to_string(&stale).expect("encode"),
+        ] {
+            std::fs::write(&checkpoint_path, leftover).expect("write checkpoint");
+            let runner = FakeProcessRunner::default();
+            runner.push_response(output(0, ""));
+            runner.push_response(output(0, ""));
+            runner.push_response(output(1, ""));
+            runner.push_response(turn(r#"{"task_type":"feature","reasoning":"new behavior"}"#));
+            runner.push_response(Err(GardenerError::Process("killed by signal 9".to_string())));
+            run_live(&runner, &scope, "worker-1", &task).expect_err("killed in planning");
+
+            // A fresh start: Understand first, on this worker's own branch.
+            let turns = agent_turns(&runner);
+            assert_eq!(turns.len(), 2);
+            assert!(turns[0].starts_with("Intent: categorize"), "{turns:?}");
+            assert!(runner.spawned()[1]
+                .args
+                .contains(&worktree_branch_for("worker-1", &task.task_id)));
+            let checkpoint =
+                load_checkpoint(&checkpoint_path, &task.task_id, "worker-1").expect("replaced");
+            assert_eq!(checkpoint.state, WorkerState::Planning);
+            assert_eq!(checkpoint.pr_number, None);
+        }
+    }
+
     #[test]
     fn classify_build_and_implement_as_feature_for_planning() {
         assert_eq!(
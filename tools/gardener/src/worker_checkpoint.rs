@@ -0,0 +1,219 @@
+use crate::errors::GardenerError;
+use crate::fsm::{FsmSnapshot, TaskCategory};
+use crate::logging::append_run_log;
+use crate::types::{RuntimeScope, WorkerState};
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+use std::path::{Path, PathBuf};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+/// The last state boundary a live worker reached on a task. Rewritten on every
+/// FSM transition so the next claim resumes there, on the same branch and PR,
+/// after a crash, a drain or a failed attempt.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct WorkerCheckpoint {
+    pub task_id: String,
+    pub state: WorkerState,
+    pub category: Option<TaskCategory>,
+    pub doing_turns: u32,
+    pub review_loops: u32,
+    pub pr_number: Option<u64>,
+    pub branch: String,
+    pub worktree_path: PathBuf,
+    /// Payload of the planning turn, kept so a resumed run does not plan again.
+    pub plan: Option<serde_json::Value>,
+    pub updated_at_unix_ms: i64,
+}
+
+impl WorkerCheckpoint {
+    pub fn fsm(&self) -> FsmSnapshot {
+        FsmSnapshot {
+            state: self.state,
+            category: self.category,
+            doing_turns: self.doing_turns,
+            review_loops: self.review_loops,
+            failure_reason: None,
+        }
+    }
+
+    fn record(&mut self, fsm: &FsmSnapshot) {
+        self.state = fsm.state;
+        self.category = fsm.category;
+        self.doing_turns = fsm.doing_turns;
+        self.review_loops = fsm.review_loops;
+        self.updated_at_unix_ms = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|d| d.as_millis() as i64)
+            .unwrap_or(0);
+    }
+}
+
+pub fn checkpoint_path(scope: &RuntimeScope, task_slug: &str) -> PathBuf {
+    scope
+        .working_dir
+        .join(".cache/gardener/checkpoints")
+        .join(format!("{task_slug}.json"))
+}
+
+/// Reads the checkpoint a previous run left for `task_id`. Terminal states,
+/// another task's file and unreadable files all mean "start from Understand".
+pub fn load_checkpoint(path: &Path, task_id: &str, worker_id: &str) -> Option<WorkerCheckpoint> {
+    let raw = std::fs::read_to_string(path).ok()?;
+    let checkpoint = match serde_json::from_str::<WorkerCheckpoint>(&raw) {
+        Ok(checkpoint) => checkpoint,
+        Err(err) => {
+            append_run_log(
+                "warn",
+                "worker.checkpoint.unreadable",
+                json!({
+                    "worker_id": worker_id,
+                    "task_id": task_id,
+                    "path": path.display().to_string(),
+                    "error": err.to_string(),
+                }),
+            );
+            return None;
+        }
+    };
+    let resumable = checkpoint.task_id == task_id
+        && !matches!(
+            checkpoint.state,
+            WorkerState::Understand
+                | WorkerState::Complete
+                | WorkerState::Failed
+                | WorkerState::Parked
+                | WorkerState::Seeding
+        );
+    append_run_log(
+        "info",
+        "worker.checkpoint.loaded",
+        json!({
+            "worker_id": worker_id,
+            "task_id": task_id,
+            "state": checkpoint.state.as_str(),
+            "pr_number": checkpoint.pr_number,
+            "resumable": resumable,
+        }),
+    );
+    resumable.then_some(checkpoint)
+}
+
+/// Records `fsm` into the checkpoint and writes it through a temp file and a
+/// rename, so a crash mid-write leaves the previous checkpoint in place rather
+/// than a truncated one.
+pub fn save_checkpoint(
+    path: &Path,
+    checkpoint: &mut WorkerCheckpoint,
+    fsm: &FsmSnapshot,
+    worker_id: &str,
+) -> Result<(), GardenerError> {
+    checkpoint.record(fsm);
+    append_run_log(
+        "debug",
+        "worker.checkpoint.saved",
+        json!({
+            "worker_id": worker_id,
+            "task_id": checkpoint.task_id,
+            "state": checkpoint.state.as_str(),
+            "pr_number": checkpoint.pr_number,
+        }),
+    );
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent).map_err(|e| GardenerError::Io(e.to_string()))?;
+    }
+    let payload = serde_json::to_string_pretty(checkpoint)
+        .map_err(|e| GardenerError::Io(e.to_string()))?;
+    let tmp = path.with_extension("json.tmp");
+    std::fs::write(&tmp, payload).map_err(|e| GardenerError::Io(e.to_string()))?;
+    std::fs::rename(&tmp, path).map_err(|e| GardenerError::Io(e.to_string()))
+}
+
+pub fn clear_checkpoint(path: &Path, task_id: &str, worker_id: &str) {
+    match std::fs::remove_file(path) {
+        Ok(()) => append_run_log(
+            "debug",
+            "worker.checkpoint.cleared",
+            json!({ "worker_id": worker_id, "task_id": task_id }),
+        ),
+        Err(err) if err.kind() == std::io::ErrorKind::NotFound => {}
+        Err(err) => append_run_log(
+            "warn",
+            "worker.checkpoint.clear_failed",
+            json!({
+                "worker_id": worker_id,
+                "task_id": task_id,
+                "path": path.display().to_string(),
+                "error": err.to_string(),
+            }),
+        ),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        checkpoint_path, clear_checkpoint, load_checkpoint, save_checkpoint, WorkerCheckpoint,
+    };
+    use crate::fsm::{FsmSnapshot, TaskCategory};
+    use crate::types::{RuntimeScope, WorkerState};
+    use std::path::PathBuf;
+    use tempfile::TempDir;
+
+    fn checkpoint() -> WorkerCheckpoint {
+        WorkerCheckpoint {
+            task_id: "task-1".to_string(),
+            state: WorkerState::Understand,
+            category: None,
+            doing_turns: 0,
+            review_loops: 0,
+            pr_number: Some(42),
+            branch: "gardener/worker-1-task-1".to_string(),
+            worktree_path: PathBuf::from("/tmp/gardener-worktrees/worker-1-task-1"),
+            plan: Some(serde_json::json!({ "steps": ["write the test"] })),
+            updated_at_unix_ms: 0,
+        }
+    }
+
+    fn fsm(state: WorkerState) -> FsmSnapshot {
+        FsmSnapshot {
+            state,
+            category: Some(TaskCategory::Feature),
+            doing_turns: 2,
+            review_loops: 1,
+            failure_reason: None,
+        }
+    }
+
+    #[test]
+    fn checkpoint_round_trips_and_resumes_only_mid_flight_states_of_the_same_task() {
+        let dir = TempDir::new().expect("tempdir");
+        let scope = RuntimeScope {
+            process_cwd: dir.path().to_path_buf(),
+            repo_root: Some(dir.path().to_path_buf()),
+            working_dir: dir.path().to_path_buf(),
+        };
+        let path = checkpoint_path(&scope, "task-1-0123456789abcdef");
+        assert!(load_checkpoint(&path, "task-1", "worker-1").is_none());
+
+        let mut saved = checkpoint();
+        save_checkpoint(&path, &mut saved, &fsm(WorkerState::Reviewing), "worker-1")
+            .expect("save");
+        let loaded = load_checkpoint(&path, "task-1", "worker-1").expect("resumable");
+        assert_eq!(loaded, saved);
+        assert_eq!(loaded.fsm(), fsm(WorkerState::Reviewing));
+        assert_eq!(loaded.pr_number, Some(42));
+        assert!(loaded.plan.is_some());
+        assert!(load_checkpoint(&path, "task-2", "worker-1").is_none());
+
+        save_checkpoint(&path, &mut saved, &fsm(WorkerState::Complete), "worker-1")
+            .expect("save");
+        assert!(load_checkpoint(&path, "task-1", "worker-1").is_none());
+
+        std::fs::write(&path, "{ not json").expect("corrupt");
+        assert!(load_checkpoint(&path, "task-1", "worker-1").is_none());
+
+        clear_checkpoint(&path, "task-1", "worker-1");
+        assert!(!path.exists());
+        clear_checkpoint(&path, "task-1", "worker-1");
+    }
+}
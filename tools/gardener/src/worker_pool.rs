@@ -1,35 +1,48 @@
-use crate::backlog_store::BacklogStore;
+use crate::backlog_store::{
+    BacklogStore, ClaimOptions, NewTaskOutcome, TaskFilter, TaskStatus, TransitionContext,
+};
 use crate::config::AppConfig;
 use crate::errors::GardenerError;
+use crate::gh::GhClient;
 use crate::hotkeys::{
     action_for_key_with_mode, operator_hotkeys_enabled, HotkeyAction as AppHotkeyAction,
 };
+use crate::issue_import::resolve_issue_for_merged_task;
 use crate::logging::{
     append_run_log, current_log_line_count, recent_worker_log_lines, recent_worker_state_events,
     recent_worker_tool_commands, structured_fallback_line,
 };
+use crate::merge_queue::{merge_queue, QueueSlot};
 use crate::priority::Priority;
 use crate::replay::recorder::{emit_record, next_seq, set_recording_worker_id, timestamp_ns};
 use crate::replay::recording::{BacklogMutationRecord, RecordEntry};
 use crate::runtime::Terminal;
 use crate::runtime::{
-    clear_interrupt, request_interrupt, ProductionRuntime, INTERRUPT_SENTINEL_KEY,
+    clear_interrupt, set_text_input_active, set_worker_abort_flag,
+    set_worker_shutdown, shutdown_requests, DrainEnabled, PoolShutdown, ProcessRunner,
+    ProductionRuntime, BACKSPACE_KEY, INTERRUPT_MESSAGE, KEY_LISTENER_ACTIVE, ENTER_KEY, ESCAPE_KEY, INTERRUPT_SENTINEL_KEY,
 };
 use crate::startup::refresh_quality_report;
 use crate::task_identity::TaskKind;
 use crate::tui::{
-    reset_workers_scroll, scroll_workers_down, scroll_workers_up, BacklogView, QueueStats,
-    WorkerRow,
+    reset_workers_scroll, scroll_workers_down, scroll_workers_up, BacklogSearch, BacklogView,
+    QueueStats, WorkerRow,
 };
 use crate::types::RuntimeScope;
+use crate::usage::{budget_exhausted, run_usage};
 use crate::worker::execute_task;
 use serde_json::json;
-use std::sync::mpsc;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{mpsc, Arc, Mutex, MutexGuard, PoisonError};
 use std::time::{Duration, Instant};
 
 const WORKER_POOL_ID: &str = "worker_pool";
 const WORKER_COMMAND_HISTORY_LIMIT: usize = 32;
 const COPY_SHORTCUT_KEY: char = 'c';
+const HEARTBEAT_POLL: Duration = Duration::from_millis(25);
+/// How long an idle slot waits before asking the backlog again after a claim
+/// came back empty.
+const CLAIM_RETRY_INTERVAL: Duration = Duration::from_secs(1);
 
 type WorkerResultMessage = (
     usize,
@@ -37,6 +50,63 @@ type WorkerResultMessage = (
     Result<crate::worker::WorkerRunSummary, GardenerError>,
 );
 
+/// Lease held by one in-flight worker, renewed by the heartbeat thread until the
+/// worker finishes or ownership is lost.
+#[derive(Clone)]
+struct LeaseHeartbeat {
+    idx: usize,
+    worker_id: String,
+    task_id: String,
+    finished: Arc<AtomicBool>,
+    lost: Arc<AtomicBool>,
+}
+
+impl LeaseHeartbeat {
+    fn active(&self) -> bool {
+        !self.finished.load(Ordering::SeqCst) && !self.lost.load(Ordering::SeqCst)
+    }
+}
+
+/// What the pool knew about a task when it handed it to a worker, kept for the
+/// outcome row written once the worker finishes.
+struct ClaimedRun {
+    title: String,
+    attempt: i64,
+    started_at: i64,
+}
+
+/// Marks a lease finished when the worker thread exits, including by panic, so
+/// the heartbeat thread never outlives its workers.
+struct FinishedOnDrop(Arc<AtomicBool>);
+
+impl Drop for FinishedOnDrop {
+    fn drop(&mut self) {
+        self.0.store(true, Ordering::SeqCst);
+    }
+}
+
+/// One claimed task handed to a worker slot. Dropping `finished` once the task
+/// returns tells the heartbeat thread to stop renewing its lease.
+struct WorkerAssignment {
+    task_id: String,
+    task_summary: String,
+    priority: Priority,
+    attempt_count: i64,
+    finished: FinishedOnDrop,
+    lost: Arc<AtomicBool>,
+    shutdown: Arc<PoolShutdown>,
+}
+
+/// Hands `q` back to the quit shortcut when the pool exits with the search box
+/// still open.
+struct TextInputOnDrop;
+
+impl Drop for TextInputOnDrop {
+    fn drop(&mut self) {
+        set_text_input_active(false);
+    }
+}
+
 struct HotkeyState<'a> {
     runtime: &'a ProductionRuntime,
     scope: &'a RuntimeScope,
@@ -46,6 +116,33 @@ struct HotkeyState<'a> {
     operator_hotkeys: bool,
     terminal: &'a dyn Terminal,
     report_visible: &'a mut bool,
+    backlog_search: &'a mut Option<BacklogSearch>,
+    pool_status: Option<&'a str>,
+}
+
+/// How one pool run ends and how it treats a worker that errors.
+#[derive(Debug, Clone, Default)]
+pub struct PoolRun<'a> {
+    pub target: usize,
+    /// Restricts claims to matching tasks, such as a single task id.
+    pub claim_filter: Option<&'a TaskFilter>,
+    /// Record a worker error as a failed attempt and keep dispatching, instead
+    /// of stopping the pool on the error screen.
+    pub keep_going: bool,
+    /// Stop claiming once this passes; tasks already running still finish.
+    pub claim_until: Option<Instant>,
+    /// Return as soon as the pool stops instead of drawing the completion
+    /// screen and waiting for a key.
+    pub skip_completion_screen: bool,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct PoolRunReport {
+    pub claimed: usize,
+    pub completed: usize,
+    pub quit_requested: bool,
+    /// The pool drained because the run reached `[budget]`.
+    pub budget_exhausted: bool,
 }
 
 pub fn run_worker_pool_fsm(
@@ -55,8 +152,31 @@ pub fn run_worker_pool_fsm(
     store: &BacklogStore,
     terminal: &dyn Terminal,
     target: usize,
-    task_override: Option<&str>,
+    claim_filter: Option<&TaskFilter>,
 ) -> Result<usize, GardenerError> {
+    let run = PoolRun {
+        target,
+        claim_filter,
+        ..PoolRun::default()
+    };
+    run_worker_pool(runtime, scope, cfg, store, terminal, &run).map(|report| report.completed)
+}
+
+pub fn run_worker_pool(
+    runtime: &ProductionRuntime,
+    scope: &RuntimeScope,
+    cfg: &AppConfig,
+    store: &BacklogStore,
+    terminal: &dyn Terminal,
+    run: &PoolRun<'_>,
+) -> Result<PoolRunReport, GardenerError> {
+    let PoolRun {
+        target,
+        claim_filter,
+        keep_going,
+        claim_until,
+        skip_completion_screen,
+    } = *run;
     clear_interrupt();
     reset_workers_scroll();
     append_run_log(
@@ -66,13 +186,20 @@ pub fn run_worker_pool_fsm(
             "worker_id": WORKER_POOL_ID,
             "target": target,
             "configured_parallelism": cfg.orchestrator.parallelism,
-            "task_override": task_override
+            "claim_filter": claim_filter.map(|filter| format!("{filter:?}")),
+            "keep_going": keep_going
         }),
     );
     let operator_hotkeys = operator_hotkeys_enabled();
     let mut report_visible = false;
+    let mut backlog_search: Option<BacklogSearch> = None;
+    let _text_input = TextInputOnDrop;
     let hb = cfg.scheduler.heartbeat_interval_seconds;
     let lt = cfg.scheduler.lease_timeout_seconds;
+    let claim_options = ClaimOptions {
+        filter: claim_filter.cloned().unwrap_or_default(),
+        ..ClaimOptions::from(&cfg.scheduler)
+    };
     let configured_parallelism = cfg.orchestrator.parallelism.max(1) as usize;
     let parallelism = configured_parallelism.min(target.max(1));
     let mut workers = (0..parallelism)
@@ -98,330 +225,636 @@ pub fn run_worker_pool_fsm(
     let mut last_activity_pulse = vec![Instant::now(); workers.len()];
     let command_poll_chunk = 32;
     let mut completed = 0usize;
+    let mut claimed = 0usize;
     refresh_worker_heartbeats(&mut workers, &last_activity_pulse);
-    render(terminal, &workers, &dashboard_snapshot(store)?, hb, lt)?;
-
-    while completed < target {
-        if handle_hotkeys(&mut HotkeyState {
-            runtime,
-            scope,
-            cfg,
-            store,
-            workers: &mut workers,
-            operator_hotkeys,
-            terminal,
-            report_visible: &mut report_visible,
-        })? {
-            return Ok(completed);
-        }
-        if report_visible {
-            continue;
-        }
-        let mut claimed = Vec::new();
-        let mut claimed_any = false;
-        let available_slots = parallelism.min(target.saturating_sub(completed));
-        for idx in 0..available_slots {
-            let worker_id = workers[idx].worker_id.clone();
-            let claimed_task =
-                store.claim_next(&worker_id, cfg.scheduler.lease_timeout_seconds as i64)?;
-            let Some(task) = claimed_task else {
-                workers[idx].state = "idle".to_string();
-                workers[idx].task_title = "idle".to_string();
-                workers[idx].lease_held = false;
-                if workers[idx].tool_line != "waiting for claim" {
-                    append_worker_command(&mut workers[idx], "waiting for claim");
-                }
-                workers[idx].tool_line = "waiting for claim".to_string();
-                continue;
-            };
-            claimed_any = true;
-            append_run_log(
-                "info",
-                "worker.task.claimed",
-                json!({
-                    "worker_id": worker_id,
-                    "task_id": task.task_id,
-                    "title": task.title
-                }),
-            );
-            emit_record(RecordEntry::BacklogMutation(BacklogMutationRecord {
-                seq: next_seq(),
-                timestamp_ns: timestamp_ns(),
-                worker_id: worker_id.clone(),
-                operation: "claim_next".to_string(),
-                task_id: task.task_id.clone(),
-                result_ok: true,
-            }));
-            let _ = store.mark_in_progress(&task.task_id, &worker_id)?;
-            emit_record(RecordEntry::BacklogMutation(BacklogMutationRecord {
-                seq: next_seq(),
-                timestamp_ns: timestamp_ns(),
-                worker_id: worker_id.clone(),
-                operation: "mark_in_progress".to_string(),
-                task_id: task.task_id.clone(),
-                result_ok: true,
-            }));
-
-            workers[idx].state = "doing".to_string();
-            workers[idx].task_title = task.title.clone();
-            workers[idx].tool_line = "claimed".to_string();
-            workers[idx].breadcrumb = "claim>doing".to_string();
-            workers[idx].lease_held = true;
-            append_worker_command(&mut workers[idx], "claimed");
-            refresh_worker_heartbeats(&mut workers, &last_activity_pulse);
-            render(terminal, &workers, &dashboard_snapshot(store)?, hb, lt)?;
-            claimed.push((idx, task));
-        }
-
-        if !claimed_any {
-            break;
+    render(
+        terminal,
+        &workers,
+        &dashboard_snapshot(store, &claim_options, backlog_search.as_ref(), None)?,
+        hb,
+        lt,
+    )?;
+
+    let (result_tx, result_rx): (
+        mpsc::Sender<WorkerResultMessage>,
+        mpsc::Receiver<WorkerResultMessage>,
+    ) = mpsc::channel();
+    let leases = Mutex::new(Vec::<LeaseHeartbeat>::new());
+    let pool_finished = Arc::new(AtomicBool::new(false));
+    // What each slot is running; `None` while the slot waits for a claim.
+    let mut slots = (0..parallelism).map(|_| None).collect::<Vec<Option<ClaimedRun>>>();
+    let mut shutdown_error: Option<(String, String, String)> = None;
+    let mut quit_requested = false;
+    let mut last_claim_attempt: Option<Instant> = None;
+    let mut last_dashboard_refresh = Instant::now();
+    let shutdown = Arc::new(PoolShutdown::default());
+    let _drain_enabled = DrainEnabled::acquire();
+    // Quit keys read straight from the terminal; the live key listener counts
+    // its own as shutdown requests instead.
+    let mut quit_presses = shutdown_requests();
+    let mut pool_status: Option<String> = None;
+    let mut budget_reason: Option<String> = None;
+
+    std::thread::scope(|scope_guard| -> Result<(), GardenerError> {
+        // Stops the heartbeat thread however this closure exits, so the scope
+        // can join it.
+        let _heartbeat_stop = FinishedOnDrop(Arc::clone(&pool_finished));
+        let mut assignments = Vec::with_capacity(parallelism);
+        for (idx, worker) in workers.iter().enumerate() {
+            let (assignment_tx, assignment_rx) = mpsc::channel();
+            assignments.push(assignment_tx);
+            let results = result_tx.clone();
+            let worker_id = worker.worker_id.clone();
+            let process_runner = runtime.process_runner.as_ref();
+            scope_guard.spawn(move || {
+                run_worker_slot(
+                    idx,
+                    &worker_id,
+                    cfg,
+                    process_runner,
+                    scope,
+                    assignment_rx,
+                    results,
+                )
+            });
         }
-
-        let mut active = claimed.len();
-        let mut shutdown_error: Option<(String, String, String)> = None;
-        let mut quit_requested = false;
-        let (tx, rx): (
-            mpsc::Sender<WorkerResultMessage>,
-            mpsc::Receiver<WorkerResultMessage>,
-        ) = mpsc::channel();
-        let runtime_scope = scope.clone();
-        let mut last_dashboard_refresh = Instant::now();
-
-        std::thread::scope(|scope_guard| -> Result<(), GardenerError> {
-            for (idx, task) in claimed {
-                let tx = tx.clone();
-                let worker_id = workers[idx].worker_id.clone();
-                let task_id = task.task_id.clone();
-                let task_summary = task_override.unwrap_or(task.title.as_str()).to_string();
-                let attempt_count = task.attempt_count;
-                let cfg = cfg.clone();
-                let process_runner = runtime.process_runner.clone();
-                let worker_scope = runtime_scope.clone();
-                scope_guard.spawn(move || {
-                    set_recording_worker_id(&worker_id);
-                    let result = execute_task(
-                        &cfg,
-                        process_runner.as_ref(),
-                        &worker_scope,
-                        &worker_id,
-                        &task_id,
-                        &task_summary,
-                        attempt_count,
+        drop(result_tx);
+        let leases = &leases;
+        let pool_finished = pool_finished.as_ref();
+        scope_guard.spawn(move || run_lease_heartbeat(store, leases, cfg, pool_finished));
+
+        loop {
+            if handle_hotkeys(&mut HotkeyState {
+                runtime,
+                scope,
+                cfg,
+                store,
+                workers: &mut workers,
+                operator_hotkeys,
+                terminal,
+                report_visible: &mut report_visible,
+                backlog_search: &mut backlog_search,
+                pool_status: pool_status.as_deref(),
+            })? && !KEY_LISTENER_ACTIVE.load(Ordering::SeqCst)
+            {
+                quit_presses += 1;
+            }
+            let requests = quit_presses.max(shutdown_requests());
+            if requests > 0 && !shutdown.is_draining() {
+                shutdown.drain();
+                quit_requested = true;
+                begin_drain(&mut workers, &slots);
+            }
+            if !shutdown.is_draining() {
+                if let Some(reason) = budget_exhausted(&cfg.budget, &run_usage()) {
+                    append_run_log(
+                        "warn",
+                        "worker_pool.budget.exhausted",
+                        json!({
+                            "worker_id": WORKER_POOL_ID,
+                            "reason": reason,
+                            "in_flight": slots.iter().filter(|slot| slot.is_some()).count(),
+                        }),
                     );
-                    let _ = tx.send((idx, task_id, result));
-                });
+                    shutdown.drain();
+                    begin_drain(&mut workers, &slots);
+                    budget_reason = Some(reason);
+                }
             }
-            drop(tx);
-
-            while active > 0 {
-                if handle_hotkeys(&mut HotkeyState {
-                    runtime,
-                    scope: &runtime_scope,
-                    cfg,
-                    store,
-                    workers: &mut workers,
-                    operator_hotkeys,
+            // After a budget drain the first quit already interrupts.
+            let interrupt_after = if budget_reason.is_some() { 0 } else { 1 };
+            if requests > interrupt_after && !shutdown.is_interrupting() {
+                shutdown.interrupt();
+                append_run_log(
+                    "warn",
+                    "worker_pool.drain.interrupted",
+                    json!({
+                        "worker_id": WORKER_POOL_ID,
+                        "in_flight": slots.iter().filter(|slot| slot.is_some()).count(),
+                    }),
+                );
+            }
+            let status = drain_status(&shutdown, &slots, budget_reason.as_deref());
+            if status != pool_status {
+                pool_status = status;
+                render(
                     terminal,
-                    report_visible: &mut report_visible,
-                })? {
-                    request_interrupt();
-                    quit_requested = true;
+                    &workers,
+                    &dashboard_snapshot(
+                        store,
+                        &claim_options,
+                        backlog_search.as_ref(),
+                        pool_status.as_deref(),
+                    )?,
+                    hb,
+                    lt,
+                )?;
+            }
+            let claim_window_closed = claim_until.is_some_and(|until| Instant::now() >= until);
+            let stopping = quit_requested
+                || budget_reason.is_some()
+                || shutdown_error.is_some()
+                || claim_window_closed;
+            let claim_due = last_claim_attempt
+                .is_none_or(|attempted| attempted.elapsed() >= CLAIM_RETRY_INTERVAL);
+            let mut backlog_drained = false;
+            if !stopping && !report_visible && claim_due {
+                last_claim_attempt = Some(Instant::now());
+                for idx in 0..parallelism {
+                    let in_flight = slots.iter().filter(|slot| slot.is_some()).count();
+                    if slots[idx].is_some() || completed.saturating_add(in_flight) >= target {
+                        continue;
+                    }
+                    let worker_id = workers[idx].worker_id.clone();
+                    let claimed_task = store.claim_next_with(
+                        &worker_id,
+                        cfg.scheduler.lease_timeout_seconds as i64,
+                        &claim_options,
+                    )?;
+                    let Some(task) = claimed_task else {
+                        backlog_drained = true;
+                        for (worker, slot) in workers.iter_mut().zip(&slots) {
+                            if slot.is_some() {
+                                continue;
+                            }
+                            worker.state = "idle".to_string();
+                            worker.task_title = "idle".to_string();
+                            worker.lease_held = false;
+                            if worker.tool_line != "waiting for claim" {
+                                append_worker_command(worker, "waiting for claim");
+                            }
+                            worker.tool_line = "waiting for claim".to_string();
+                        }
+                        break;
+                    };
+                    claimed += 1;
+                    append_run_log(
+                        "info",
+                        "worker.task.claimed",
+                        json!({
+                            "worker_id": worker_id,
+                            "task_id": task.task_id,
+                            "title": task.title
+                        }),
+                    );
+                    emit_record(RecordEntry::BacklogMutation(BacklogMutationRecord {
+                        seq: next_seq(),
+                        timestamp_ns: timestamp_ns(),
+                        worker_id: worker_id.clone(),
+                        operation: "claim_next".to_string(),
+                        task_id: task.task_id.clone(),
+                        result_ok: true,
+                    }));
+                    let _ = store.mark_in_progress(&task.task_id, &worker_id)?;
+                    emit_record(RecordEntry::BacklogMutation(BacklogMutationRecord {
+                        seq: next_seq(),
+                        timestamp_ns: timestamp_ns(),
+                        worker_id: worker_id.clone(),
+                        operation: "mark_in_progress".to_string(),
+                        task_id: task.task_id.clone(),
+                        result_ok: true,
+                    }));
+
+                    workers[idx].state = "doing".to_string();
+                    workers[idx].task_title = task.title.clone();
+                    workers[idx].tool_line = "claimed".to_string();
+                    workers[idx].breadcrumb = "claim>doing".to_string();
+                    workers[idx].lease_held = true;
+                    append_worker_command(&mut workers[idx], "claimed");
+                    last_activity_pulse[idx] = Instant::now();
+
+                    let lease = LeaseHeartbeat {
+                        idx,
+                        worker_id: worker_id.clone(),
+                        task_id: task.task_id.clone(),
+                        finished: Arc::new(AtomicBool::new(false)),
+                        lost: Arc::new(AtomicBool::new(false)),
+                    };
+                    let assignment = WorkerAssignment {
+                        task_id: task.task_id.clone(),
+                        task_summary: task.title.clone(),
+                        priority: task.priority,
+                        attempt_count: task.attempt_count,
+                        finished: FinishedOnDrop(Arc::clone(&lease.finished)),
+                        lost: Arc::clone(&lease.lost),
+                        shutdown: Arc::clone(&shutdown),
+                    };
+                    lock_leases(leases).push(lease);
+                    slots[idx] = Some(ClaimedRun {
+                        title: task.title.clone(),
+                        attempt: task.attempt_count,
+                        started_at: now_unix_millis(),
+                    });
+                    if assignments[idx].send(assignment).is_err() {
+                        return Err(GardenerError::Process(format!(
+                            "worker {worker_id} stopped before it could take task {}",
+                            task.task_id
+                        )));
+                    }
+                    refresh_worker_heartbeats(&mut workers, &last_activity_pulse);
+                    render(
+                        terminal,
+                        &workers,
+                        &dashboard_snapshot(
+                            store,
+                            &claim_options,
+                            backlog_search.as_ref(),
+                            pool_status.as_deref(),
+                        )?,
+                        hb,
+                        lt,
+                    )?;
                 }
+            }
 
-                match rx.recv_timeout(Duration::from_millis(25)) {
-                    Ok((idx, task_id, turn_result)) => {
-                        active = active.saturating_sub(1);
-                        let worker_id = workers[idx].worker_id.clone();
-                        if shutdown_error.is_none() {
-                            let summary = match turn_result {
-                                Ok(summary) => summary,
-                                Err(GardenerError::Process(message))
-                                    if message.contains("user interrupt requested") =>
-                                {
-                                    quit_requested = true;
-                                    continue;
-                                }
-                                Err(err) => {
-                                    let msg = err.to_string();
-                                    append_run_log(
-                                        "error",
-                                        "worker.task.process_error",
-                                        json!({
-                                            "worker_id": worker_id,
-                                            "task_id": task_id,
-                                            "error": msg
-                                        }),
-                                    );
-                                    shutdown_error = Some((worker_id, task_id, msg));
-                                    request_interrupt();
-                                    continue;
-                                }
-                            };
-                            for event in summary.logs {
-                                workers[idx].state = event.state.as_str().to_string();
-                                let prompt = format!("prompt {}", event.prompt_version);
-                                workers[idx].tool_line = prompt.clone();
-                                append_worker_command(&mut workers[idx], &prompt);
-                                workers[idx].breadcrumb = format!("state>{}", event.state.as_str());
-                                let now = Instant::now();
-                                last_activity_pulse[idx] = now;
-                                workers[idx].last_heartbeat_secs = 0;
-                                workers[idx].session_age_secs = 0;
-                                append_run_log(
-                                    "debug",
-                                    "worker.turn.state",
-                                    json!({
-                                        "worker_id": worker_id,
-                                        "state": event.state.as_str(),
-                                        "prompt_version": event.prompt_version,
-                                        "context_manifest_hash": event.context_manifest_hash
-                                    }),
-                                );
-                                refresh_worker_heartbeats(&mut workers, &last_activity_pulse);
-                                render(terminal, &workers, &dashboard_snapshot(store)?, hb, lt)?;
-                            }
+            let in_flight = slots.iter().filter(|slot| slot.is_some()).count();
+            if in_flight == 0 && (stopping || backlog_drained || completed >= target) {
+                break;
+            }
 
-                            if summary.final_state == crate::types::WorkerState::Complete {
-                                let _ = store.mark_complete(&task_id, &worker_id)?;
-                                emit_record(RecordEntry::BacklogMutation(BacklogMutationRecord {
-                                    seq: next_seq(),
-                                    timestamp_ns: timestamp_ns(),
-                                    worker_id: worker_id.clone(),
-                                    operation: "mark_complete".to_string(),
-                                    task_id: task_id.clone(),
-                                    result_ok: true,
-                                }));
-                                completed = completed.saturating_add(1);
-                                workers[idx].state = "complete".to_string();
-                                let completed_message = format!("completed {}", task_id);
-                                workers[idx].tool_line = completed_message.clone();
-                                append_worker_command(&mut workers[idx], &completed_message);
-                                workers[idx].breadcrumb = "complete".to_string();
-                                workers[idx].lease_held = false;
+            match result_rx.recv_timeout(Duration::from_millis(25)) {
+                Ok((idx, task_id, turn_result)) => {
+                    // The slot is free again; claim for it on the next pass.
+                    let claimed_run = slots[idx].take();
+                    last_claim_attempt = None;
+                    let worker_id = workers[idx].worker_id.clone();
+                    let lease_lost = {
+                        let mut leases = lock_leases(leases);
+                        let lost = leases
+                            .iter()
+                            .any(|lease| lease.idx == idx && lease.lost.load(Ordering::SeqCst));
+                        leases.retain(|lease| lease.idx != idx);
+                        lost
+                    };
+                    if lease_lost {
+                        // Another owner holds the task now; it must not be
+                        // completed, released or marked unresolved from here.
+                        append_run_log(
+                            "error",
+                            "worker.task.lease_lost",
+                            json!({
+                                "worker_id": worker_id,
+                                "task_id": task_id,
+                                "result_ok": turn_result.is_ok(),
+                            }),
+                        );
+                        let lost_message = format!("lease lost {}", task_id);
+                        workers[idx].state = "lease_lost".to_string();
+                        workers[idx].tool_line = lost_message.clone();
+                        append_worker_command(&mut workers[idx], &lost_message);
+                        workers[idx].breadcrumb = "lease_lost".to_string();
+                        workers[idx].lease_held = false;
+                    } else if is_interrupted(&turn_result, &shutdown) {
+                        // A second quit killed the agent mid-state; hand the task
+                        // back so the next run starts it again.
+                        let note = format!("interrupted at state {}", workers[idx].state);
+                        let _ = store.release_lease_with(
+                            &task_id,
+                            &worker_id,
+                            TransitionContext {
+                                reason: Some(note.clone()),
+                                ..TransitionContext::default()
+                            },
+                        )?;
+                        emit_record(RecordEntry::BacklogMutation(BacklogMutationRecord {
+                            seq: next_seq(),
+                            timestamp_ns: timestamp_ns(),
+                            worker_id: worker_id.clone(),
+                            operation: "release_lease".to_string(),
+                            task_id: task_id.clone(),
+                            result_ok: true,
+                        }));
+                        record_task_outcome(
+                            store,
+                            &worker_id,
+                            &task_id,
+                            claimed_run.as_ref(),
+                            TaskRunResult::Error(&note),
+                        )?;
+                        append_run_log(
+                            "warn",
+                            "worker.task.interrupted",
+                            json!({
+                                "worker_id": worker_id,
+                                "task_id": task_id,
+                                "note": note,
+                            }),
+                        );
+                        workers[idx].state = "interrupted".to_string();
+                        workers[idx].tool_line = note.clone();
+                        append_worker_command(&mut workers[idx], &note);
+                        workers[idx].breadcrumb = "interrupted".to_string();
+                        workers[idx].lease_held = false;
+                    } else if shutdown_error.is_none() {
+                        let summary = match turn_result {
+                            Ok(summary) => summary,
+                            // A timeout is the process hanging, not the task being
+                            // wrong: release it for a retry instead of stopping.
+                            Err(err)
+                                if keep_going || matches!(err, GardenerError::Timeout(_)) =>
+                            {
+                                let msg = err.to_string();
                                 append_run_log(
-                                    "info",
-                                    "worker.task.completed",
+                                    "error",
+                                    "worker.task.process_error",
                                     json!({
                                         "worker_id": worker_id,
                                         "task_id": task_id,
-                                        "completed": completed
+                                        "error": msg,
+                                        "keep_going": keep_going,
+                                        "timed_out": matches!(err, GardenerError::Timeout(_))
                                     }),
                                 );
-                            } else {
-                                workers[idx].state = "failed".to_string();
-                                let failed_message =
-                                    if let Some(reason) = summary.failure_reason.clone() {
-                                        if reason.is_empty() {
-                                            format!("failed {}", task_id)
-                                        } else {
-                                            let truncated =
-                                                reason.chars().take(150).collect::<String>();
-                                            if reason.chars().count() > 150 {
-                                                format!("failed: {}…", truncated)
-                                            } else {
-                                                format!("failed: {}", reason)
-                                            }
-                                        }
-                                    } else {
-                                        format!("failed {}", task_id)
-                                    };
-                                workers[idx].tool_line = failed_message.clone();
-                                append_worker_command(&mut workers[idx], &failed_message);
-                                workers[idx].breadcrumb = "failed".to_string();
+                                record_task_outcome(
+                                    store,
+                                    &worker_id,
+                                    &task_id,
+                                    claimed_run.as_ref(),
+                                    TaskRunResult::Error(&msg),
+                                )?;
+                                let attempt = record_failed_attempt(
+                                    store,
+                                    cfg,
+                                    &worker_id,
+                                    &task_id,
+                                    Some(msg.clone()),
+                                )?;
+                                let (state, outcome_message) = failed_attempt_status(
+                                    &task_id,
+                                    attempt,
+                                    &format!("failed {}", task_id),
+                                );
+                                workers[idx].state = state.to_string();
+                                workers[idx].tool_line = outcome_message.clone();
+                                workers[idx].breadcrumb = state.to_string();
                                 workers[idx].lease_held = false;
+                                append_worker_command(&mut workers[idx], &outcome_message);
+                                continue;
+                            }
+                            Err(err) => {
+                                let msg = err.to_string();
                                 append_run_log(
                                     "error",
-                                    "worker.task.failed",
+                                    "worker.task.process_error",
                                     json!({
                                         "worker_id": worker_id,
                                         "task_id": task_id,
-                                        "final_state": summary.final_state.as_str()
+                                        "error": msg
                                     }),
                                 );
-                                if summary.final_state == crate::types::WorkerState::Failed {
-                                    let unresolved = store.mark_unresolved(&task_id, &worker_id)?;
-                                    append_run_log(
-                                        "warn",
-                                        "worker.task.unresolved",
-                                        json!({
-                                            "worker_id": worker_id,
-                                            "task_id": task_id,
-                                            "marked_unresolved": unresolved,
-                                            "failure_reason": summary.failure_reason,
-                                        }),
-                                    );
-                                    let unresolved_message = if unresolved {
-                                        format!("unresolved {}", task_id)
+                                record_task_outcome(
+                                    store,
+                                    &worker_id,
+                                    &task_id,
+                                    claimed_run.as_ref(),
+                                    TaskRunResult::Error(&msg),
+                                )?;
+                                shutdown_error = Some((worker_id, task_id, msg));
+                                shutdown.interrupt();
+                                continue;
+                            }
+                        };
+                        record_task_outcome(
+                            store,
+                            &worker_id,
+                            &task_id,
+                            claimed_run.as_ref(),
+                            TaskRunResult::Finished(&summary),
+                        )?;
+                        for event in &summary.logs {
+                            workers[idx].state = event.state.as_str().to_string();
+                            let prompt = format!("prompt {}", event.prompt_version);
+                            workers[idx].tool_line = prompt.clone();
+                            append_worker_command(&mut workers[idx], &prompt);
+                            workers[idx].breadcrumb = format!("state>{}", event.state.as_str());
+                            let now = Instant::now();
+                            last_activity_pulse[idx] = now;
+                            workers[idx].last_heartbeat_secs = 0;
+                            workers[idx].session_age_secs = 0;
+                            append_run_log(
+                                "debug",
+                                "worker.turn.state",
+                                json!({
+                                    "worker_id": worker_id,
+                                    "state": event.state.as_str(),
+                                    "prompt_version": event.prompt_version,
+                                    "context_manifest_hash": event.context_manifest_hash
+                                }),
+                            );
+                            refresh_worker_heartbeats(&mut workers, &last_activity_pulse);
+                            render(
+                                terminal,
+                                &workers,
+                                &dashboard_snapshot(
+                                    store,
+                                    &claim_options,
+                                    backlog_search.as_ref(),
+                                    pool_status.as_deref(),
+                                )?,
+                                hb,
+                                lt,
+                            )?;
+                        }
+
+                        if summary.final_state == crate::types::WorkerState::Complete {
+                            let _ = store.mark_complete(&task_id, &worker_id)?;
+                            emit_record(RecordEntry::BacklogMutation(BacklogMutationRecord {
+                                seq: next_seq(),
+                                timestamp_ns: timestamp_ns(),
+                                worker_id: worker_id.clone(),
+                                operation: "mark_complete".to_string(),
+                                task_id: task_id.clone(),
+                                result_ok: true,
+                            }));
+                            completed = completed.saturating_add(1);
+                            workers[idx].state = "complete".to_string();
+                            let completed_message = format!("completed {}", task_id);
+                            workers[idx].tool_line = completed_message.clone();
+                            append_worker_command(&mut workers[idx], &completed_message);
+                            workers[idx].breadcrumb = "complete".to_string();
+                            workers[idx].lease_held = false;
+                            append_run_log(
+                                "info",
+                                "worker.task.completed",
+                                json!({
+                                    "worker_id": worker_id,
+                                    "task_id": task_id,
+                                    "completed": completed
+                                }),
+                            );
+                            resolve_linked_issue(
+                                runtime,
+                                scope,
+                                cfg,
+                                store,
+                                &worker_id,
+                                &task_id,
+                                &summary,
+                            );
+                        } else {
+                            workers[idx].state = "failed".to_string();
+                            let failed_message =
+                                if let Some(reason) = summary.failure_reason.clone() {
+                                    if reason.is_empty() {
+                                        format!("failed {}", task_id)
                                     } else {
-                                        failed_message.clone()
-                                    };
-                                    workers[idx].state = "unresolved".to_string();
-                                    workers[idx].tool_line = unresolved_message.clone();
-                                    workers[idx].breadcrumb = "unresolved".to_string();
-                                    append_worker_command(&mut workers[idx], &unresolved_message);
+                                        let truncated =
+                                            reason.chars().take(150).collect::<String>();
+                                        if reason.chars().count() > 150 {
+                                            format!("failed: {}…", truncated)
+                                        } else {
+                                            format!("failed: {}", reason)
+                                        }
+                                    }
                                 } else {
-                                    let _ = store.release_lease(&task_id, &worker_id)?;
-                                    emit_record(RecordEntry::BacklogMutation(
-                                        BacklogMutationRecord {
-                                            seq: next_seq(),
-                                            timestamp_ns: timestamp_ns(),
-                                            worker_id: worker_id.clone(),
-                                            operation: "release_lease".to_string(),
-                                            task_id: task_id.clone(),
-                                            result_ok: true,
-                                        },
-                                    ));
-                                }
+                                    format!("failed {}", task_id)
+                                };
+                            workers[idx].tool_line = failed_message.clone();
+                            append_worker_command(&mut workers[idx], &failed_message);
+                            workers[idx].breadcrumb = "failed".to_string();
+                            workers[idx].lease_held = false;
+                            append_run_log(
+                                "error",
+                                "worker.task.failed",
+                                json!({
+                                    "worker_id": worker_id,
+                                    "task_id": task_id,
+                                    "final_state": summary.final_state.as_str()
+                                }),
+                            );
+                            if summary.final_state == crate::types::WorkerState::Failed {
+                                let attempt = record_failed_attempt(
+                                    store,
+                                    cfg,
+                                    &worker_id,
+                                    &task_id,
+                                    summary.failure_reason.clone(),
+                                )?;
+                                let (state, outcome_message) =
+                                    failed_attempt_status(&task_id, attempt, &failed_message);
+                                workers[idx].state = state.to_string();
+                                workers[idx].tool_line = outcome_message.clone();
+                                workers[idx].breadcrumb = state.to_string();
+                                append_worker_command(&mut workers[idx], &outcome_message);
+                            } else {
+                                let _ = store.release_lease_with(
+                                    &task_id,
+                                    &worker_id,
+                                    TransitionContext {
+                                        reason: Some(if shutdown.is_draining() {
+                                            format!(
+                                                "drained after state {}",
+                                                summary.final_state.as_str()
+                                            )
+                                        } else {
+                                            format!(
+                                                "worker stopped in state {}",
+                                                summary.final_state.as_str()
+                                            )
+                                        }),
+                                        failure_reason: summary.failure_reason.clone(),
+                                        ..TransitionContext::default()
+                                    },
+                                )?;
+                                emit_record(RecordEntry::BacklogMutation(
+                                    BacklogMutationRecord {
+                                        seq: next_seq(),
+                                        timestamp_ns: timestamp_ns(),
+                                        worker_id: worker_id.clone(),
+                                        operation: "release_lease".to_string(),
+                                        task_id: task_id.clone(),
+                                        result_ok: true,
+                                    },
+                                ));
                             }
-                        } else {
-                            request_interrupt();
-                        }
-                        refresh_worker_heartbeats(&mut workers, &last_activity_pulse);
-                        render(terminal, &workers, &dashboard_snapshot(store)?, hb, lt)?;
-                    }
-                    Err(std::sync::mpsc::RecvTimeoutError::Timeout) => {
-                        let updated_commands = append_worker_tool_commands(
-                            &mut workers,
-                            &mut last_worker_command_line,
-                            command_poll_chunk,
-                        );
-                        let updated_states = append_worker_state_events(
-                            &mut workers,
-                            &mut last_worker_state_line,
-                            command_poll_chunk,
-                        );
-                        if updated_commands
-                            || updated_states
-                            || last_dashboard_refresh.elapsed() >= Duration::from_secs(1)
-                        {
-                            refresh_worker_heartbeats(&mut workers, &last_activity_pulse);
-                            render(terminal, &workers, &dashboard_snapshot(store)?, hb, lt)?;
-                            last_dashboard_refresh = Instant::now();
                         }
+                    } else {
+                        shutdown.interrupt();
                     }
-                    Err(std::sync::mpsc::RecvTimeoutError::Disconnected) => {
-                        active = 0;
+                    refresh_worker_heartbeats(&mut workers, &last_activity_pulse);
+                    render(
+                        terminal,
+                        &workers,
+                        &dashboard_snapshot(
+                            store,
+                            &claim_options,
+                            backlog_search.as_ref(),
+                            pool_status.as_deref(),
+                        )?,
+                        hb,
+                        lt,
+                    )?;
+                }
+                Err(mpsc::RecvTimeoutError::Timeout) => {
+                    let updated_commands = append_worker_tool_commands(
+                        &mut workers,
+                        &mut last_worker_command_line,
+                        command_poll_chunk,
+                    );
+                    let updated_states = append_worker_state_events(
+                        &mut workers,
+                        &mut last_worker_state_line,
+                        command_poll_chunk,
+                    );
+                    let updated_queue = apply_merge_queue_slots(
+                        &mut workers,
+                        &merge_queue().slots(cfg.merge_queue.order),
+                    );
+                    if updated_commands
+                        || updated_states
+                        || updated_queue
+                        || last_dashboard_refresh.elapsed() >= Duration::from_secs(1)
+                    {
+                        refresh_worker_heartbeats(&mut workers, &last_activity_pulse);
+                        render(
+                            terminal,
+                            &workers,
+                            &dashboard_snapshot(
+                                store,
+                                &claim_options,
+                                backlog_search.as_ref(),
+                                pool_status.as_deref(),
+                            )?,
+                            hb,
+                            lt,
+                        )?;
+                        last_dashboard_refresh = Instant::now();
                     }
                 }
+                Err(mpsc::RecvTimeoutError::Disconnected) => {
+                    // Every worker thread is gone; nothing in flight can report back.
+                    append_run_log(
+                        "error",
+                        "worker_pool.workers_disconnected",
+                        json!({ "worker_id": WORKER_POOL_ID, "in_flight": in_flight }),
+                    );
+                    break;
+                }
             }
-            Ok(())
-        })?;
-
-        if let Some((worker_id, task_id, reason)) = shutdown_error {
-            let shutdown_message = worker_failure_prompt(&worker_id, &task_id, &reason);
-            if terminal.stdin_is_tty() {
-                terminal.draw_shutdown_screen("Error", &shutdown_message)?;
-            } else {
-                terminal.write_line(&format!(
-                    "Error: worker {worker_id} task {task_id}: {reason}"
-                ))?;
-            }
-            wait_for_quit(terminal, Some(&shutdown_message))?;
-            return Ok(completed);
         }
-        if quit_requested {
-            return Ok(completed);
+        Ok(())
+    })?;
+
+    let report = PoolRunReport {
+        claimed,
+        completed,
+        quit_requested,
+        budget_exhausted: budget_reason.is_some(),
+    };
+    if let Some((worker_id, task_id, reason)) = shutdown_error {
+        let shutdown_message = worker_failure_prompt(&worker_id, &task_id, &reason);
+        if terminal.stdin_is_tty() {
+            terminal.draw_shutdown_screen("Error", &shutdown_message)?;
+        } else {
+            terminal.write_line(&format!(
+                "Error: worker {worker_id} task {task_id}: {reason}"
+            ))?;
         }
+        wait_for_quit(terminal, Some(&shutdown_message))?;
+        return Ok(report);
+    }
+    if quit_requested {
+        return Ok(report);
     }
     append_run_log(
         "info",
@@ -432,12 +865,22 @@ pub fn run_worker_pool_fsm(
             "target": target
         }),
     );
-    let shutdown_title = if completed >= target {
+    if skip_completion_screen {
+        return Ok(report);
+    }
+    let shutdown_title = if budget_reason.is_some() {
+        "Budget Reached".to_string()
+    } else if completed >= target {
         "All Tasks Complete".to_string()
     } else {
         "No More Work".to_string()
     };
-    let shutdown_message = if completed >= target {
+    let shutdown_message = if let Some(reason) = &budget_reason {
+        format!(
+            "Stopped claiming: {reason}. Completed {completed} task(s); {}.",
+            run_usage().summary()
+        )
+    } else if completed >= target {
         format!("Completed {completed} of {target} task(s).")
     } else if completed == 0 {
         "No tasks were available in the backlog.".to_string()
@@ -450,7 +893,65 @@ pub fn run_worker_pool_fsm(
         terminal.write_line(&format!("{shutdown_title}: {shutdown_message}"))?;
     }
     wait_for_quit(terminal, None)?;
-    Ok(completed)
+    Ok(report)
+}
+
+/// Stops claiming and tells every busy worker to wrap up after its current
+/// state; idle rows show that nothing more will be picked up.
+fn begin_drain(workers: &mut [WorkerRow], slots: &[Option<ClaimedRun>]) {
+    let in_flight = slots.iter().filter(|slot| slot.is_some()).count();
+    append_run_log(
+        "warn",
+        "worker_pool.drain.started",
+        json!({
+            "worker_id": WORKER_POOL_ID,
+            "in_flight": in_flight,
+        }),
+    );
+    for (worker, slot) in workers.iter_mut().zip(slots) {
+        let line = if slot.is_some() {
+            format!("draining: finishing {}", worker.state)
+        } else {
+            worker.state = "drained".to_string();
+            worker.breadcrumb = "drained".to_string();
+            "drained".to_string()
+        };
+        append_worker_command(worker, &line);
+        worker.tool_line = line;
+    }
+}
+
+fn drain_status(
+    shutdown: &PoolShutdown,
+    slots: &[Option<ClaimedRun>],
+    budget_reason: Option<&str>,
+) -> Option<String> {
+    let in_flight = slots.iter().filter(|slot| slot.is_some()).count();
+    if shutdown.is_interrupting() {
+        Some(format!("stopping: interrupting {in_flight} task(s)"))
+    } else if let Some(reason) = budget_reason {
+        Some(format!(
+            "{reason}: finishing {in_flight} task(s), q to stop now"
+        ))
+    } else if shutdown.is_draining() {
+        Some(format!(
+            "draining: finishing {in_flight} task(s), q again to stop now"
+        ))
+    } else {
+        None
+    }
+}
+
+/// Whether a worker result comes from the second quit killing its process
+/// tree rather than from the task itself.
+fn is_interrupted(
+    result: &Result<crate::worker::WorkerRunSummary, GardenerError>,
+    shutdown: &PoolShutdown,
+) -> bool {
+    match result {
+        Ok(_) => false,
+        Err(err) => shutdown.is_interrupting() || err.to_string().contains(INTERRUPT_MESSAGE),
+    }
 }
 
 fn wait_for_quit(terminal: &dyn Terminal, copy_target: Option<&str>) -> Result<(), GardenerError> {
@@ -538,6 +1039,7 @@ fn handle_hotkeys(state: &mut HotkeyState<'_>) -> Result<bool, GardenerError> {
     let operator_hotkeys = state.operator_hotkeys;
     let terminal = state.terminal;
     let report_visible = &mut *state.report_visible;
+    let backlog_search = &mut *state.backlog_search;
 
     if !terminal.stdin_is_tty() {
         return Ok(false);
@@ -547,6 +1049,21 @@ fn handle_hotkeys(state: &mut HotkeyState<'_>) -> Result<bool, GardenerError> {
         if key == '\0' {
             redraw_dashboard = true;
         }
+        if key != INTERRUPT_SENTINEL_KEY
+            && backlog_search.as_ref().is_some_and(|search| search.editing)
+        {
+            redraw_dashboard |= edit_backlog_search(backlog_search, key);
+        } else if key == ESCAPE_KEY && backlog_search.is_some() {
+            *backlog_search = None;
+            append_run_log(
+                "info",
+                "hotkey.search.cleared",
+                json!({
+                    "worker_id": WORKER_POOL_ID
+                }),
+            );
+            redraw_dashboard = true;
+        }
         if key == INTERRUPT_SENTINEL_KEY {
             append_run_log(
                 "warn",
@@ -555,10 +1072,11 @@ fn handle_hotkeys(state: &mut HotkeyState<'_>) -> Result<bool, GardenerError> {
                     "worker_id": WORKER_POOL_ID
                 }),
             );
-            request_interrupt();
             return Ok(true);
         }
-        match hotkey_action(key, operator_hotkeys) {
+        let typed_into_search = backlog_search.as_ref().is_some_and(|search| search.editing)
+            || key == ESCAPE_KEY;
+        match hotkey_action(key, operator_hotkeys).filter(|_| !typed_into_search) {
             Some(AppHotkeyAction::Quit) => {
                 append_run_log(
                     "warn",
@@ -567,7 +1085,6 @@ fn handle_hotkeys(state: &mut HotkeyState<'_>) -> Result<bool, GardenerError> {
                         "worker_id": WORKER_POOL_ID
                     }),
                 );
-                request_interrupt();
                 return Ok(true);
             }
             Some(AppHotkeyAction::ScrollDown) => {
@@ -622,6 +1139,7 @@ fn handle_hotkeys(state: &mut HotkeyState<'_>) -> Result<bool, GardenerError> {
                     source: "tui_hotkey".to_string(),
                     related_pr: None,
                     related_branch: None,
+                    blocked_by: Vec::new(),
                 })?;
                 terminal.write_line(&format!(
                     "park/escalate requested: created P0 escalation task {}",
@@ -647,7 +1165,23 @@ fn handle_hotkeys(state: &mut HotkeyState<'_>) -> Result<bool, GardenerError> {
                 *report_visible = false;
                 redraw_dashboard = true;
             }
-            None => {}
+            Some(AppHotkeyAction::Search) if !*report_visible => {
+                let query = backlog_search.take().map(|search| search.query);
+                *backlog_search = Some(BacklogSearch {
+                    query: query.unwrap_or_default(),
+                    editing: true,
+                });
+                set_text_input_active(true);
+                append_run_log(
+                    "info",
+                    "hotkey.search.opened",
+                    json!({
+                        "worker_id": WORKER_POOL_ID
+                    }),
+                );
+                redraw_dashboard = true;
+            }
+            Some(AppHotkeyAction::Search) | None => {}
         }
     }
     if *report_visible {
@@ -659,7 +1193,12 @@ fn handle_hotkeys(state: &mut HotkeyState<'_>) -> Result<bool, GardenerError> {
         };
         terminal.draw_report(&report_path.display().to_string(), &report)?;
     } else if redraw_dashboard {
-        let snapshot = dashboard_snapshot(store)?;
+        let snapshot = dashboard_snapshot(
+            store,
+            &ClaimOptions::from(&cfg.scheduler),
+            backlog_search.as_ref(),
+            state.pool_status,
+        )?;
         render(
             terminal,
             workers,
@@ -671,6 +1210,359 @@ fn handle_hotkeys(state: &mut HotkeyState<'_>) -> Result<bool, GardenerError> {
     Ok(false)
 }
 
+/// Applies one key typed into the open `/` search box. Enter keeps the results
+/// on screen, Esc closes the box; an empty query closes it either way.
+/// Returns whether the dashboard needs a redraw.
+fn edit_backlog_search(backlog_search: &mut Option<BacklogSearch>, key: char) -> bool {
+    let Some(search) = backlog_search.as_mut() else {
+        return false;
+    };
+    match key {
+        ENTER_KEY | ESCAPE_KEY => {
+            search.editing = false;
+            if key == ESCAPE_KEY || search.query.trim().is_empty() {
+                *backlog_search = None;
+            }
+            set_text_input_active(false);
+            append_run_log(
+                "info",
+                "hotkey.search.closed",
+                json!({
+                    "worker_id": WORKER_POOL_ID,
+                    "applied": backlog_search.is_some()
+                }),
+            );
+        }
+        BACKSPACE_KEY => {
+            search.query.pop();
+        }
+        key if !key.is_control() => search.query.push(key),
+        _ => return false,
+    }
+    true
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum FailedAttempt {
+    Retry { delay_secs: u64 },
+    Exhausted { attempts: i64 },
+    /// The worker no longer held the lease, so the row was left alone.
+    NotOwned,
+}
+
+/// Applies the retry policy to a failed attempt: the task is either requeued
+/// behind an exponential backoff or, once the kind's attempt limit is reached,
+/// moved to the terminal `exhausted` status. Either way the failure reason is
+/// kept on the row.
+/// Worker state and command line shown for a failed attempt; `not_owned` is
+/// shown when the task was released elsewhere first.
+fn failed_attempt_status(
+    task_id: &str,
+    attempt: FailedAttempt,
+    not_owned: &str,
+) -> (&'static str, String) {
+    match attempt {
+        FailedAttempt::Retry { delay_secs } => {
+            ("failed", format!("retry {} in {}s", task_id, delay_secs))
+        }
+        FailedAttempt::Exhausted { attempts } => (
+            "exhausted",
+            format!("exhausted {} after {} attempt(s)", task_id, attempts),
+        ),
+        FailedAttempt::NotOwned => ("failed", not_owned.to_string()),
+    }
+}
+
+fn record_failed_attempt(
+    store: &BacklogStore,
+    cfg: &AppConfig,
+    worker_id: &str,
+    task_id: &str,
+    failure_reason: Option<String>,
+) -> Result<FailedAttempt, GardenerError> {
+    let Some(task) = store.get_task(task_id)? else {
+        return Ok(FailedAttempt::NotOwned);
+    };
+    let max_attempts = cfg.retry.max_attempts_for(task.kind);
+    let context = TransitionContext {
+        failure_reason: failure_reason.clone(),
+        ..TransitionContext::default()
+    };
+    let outcome = if task.attempt_count >= i64::from(max_attempts) {
+        if store.mark_exhausted(task_id, worker_id, context)? {
+            FailedAttempt::Exhausted {
+                attempts: task.attempt_count,
+            }
+        } else {
+            FailedAttempt::NotOwned
+        }
+    } else {
+        let delay_secs = cfg.retry.backoff_seconds(task.attempt_count);
+        let not_before =
+            now_unix_millis().saturating_add((delay_secs as i64).saturating_mul(1000));
+        if store.schedule_retry(task_id, worker_id, not_before, context)? {
+            FailedAttempt::Retry { delay_secs }
+        } else {
+            FailedAttempt::NotOwned
+        }
+    };
+    append_run_log(
+        "warn",
+        "worker.task.retry_policy_applied",
+        json!({
+            "worker_id": worker_id,
+            "task_id": task_id,
+            "attempt_count": task.attempt_count,
+            "max_attempts": max_attempts,
+            "outcome": format!("{outcome:?}"),
+            "failure_reason": failure_reason,
+        }),
+    );
+    Ok(outcome)
+}
+
+/// Runs the tasks handed to one worker slot, one after another, for the life of
+/// the pool. The slot stops once the pool drops its assignment sender.
+fn run_worker_slot(
+    idx: usize,
+    worker_id: &str,
+    cfg: &AppConfig,
+    process_runner: &dyn ProcessRunner,
+    scope: &RuntimeScope,
+    assignments: mpsc::Receiver<WorkerAssignment>,
+    results: mpsc::Sender<WorkerResultMessage>,
+) {
+    set_recording_worker_id(worker_id);
+    append_run_log(
+        "debug",
+        "worker.slot.started",
+        json!({ "worker_id": worker_id, "slot": idx }),
+    );
+    let mut tasks_run = 0usize;
+    for assignment in assignments {
+        let WorkerAssignment {
+            task_id,
+            task_summary,
+            priority,
+            attempt_count,
+            finished,
+            lost,
+            shutdown,
+        } = assignment;
+        set_worker_abort_flag(Some(lost));
+        set_worker_shutdown(Some(shutdown));
+        // A panicking task must not take the slot, and every later task it
+        // would have run, down with it.
+        let result = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| {
+            execute_task(
+                cfg,
+                process_runner,
+                scope,
+                worker_id,
+                &task_id,
+                &task_summary,
+                priority,
+                attempt_count,
+            )
+        }))
+        .unwrap_or_else(|_| {
+            Err(GardenerError::Process(format!(
+                "worker {worker_id} panicked while running task {task_id}"
+            )))
+        });
+        set_worker_abort_flag(None);
+        set_worker_shutdown(None);
+        drop(finished);
+        tasks_run += 1;
+        if results.send((idx, task_id, result)).is_err() {
+            break;
+        }
+    }
+    append_run_log(
+        "debug",
+        "worker.slot.stopped",
+        json!({ "worker_id": worker_id, "slot": idx, "tasks_run": tasks_run }),
+    );
+}
+
+fn lock_leases(leases: &Mutex<Vec<LeaseHeartbeat>>) -> MutexGuard<'_, Vec<LeaseHeartbeat>> {
+    leases.lock().unwrap_or_else(PoisonError::into_inner)
+}
+
+/// Renews every in-flight lease on the configured heartbeat interval until the
+/// pool finishes. A rejected renewal means the task was recovered or released
+/// elsewhere, so the owning worker is aborted rather than left running
+/// alongside the new owner.
+fn run_lease_heartbeat(
+    store: &BacklogStore,
+    leases: &Mutex<Vec<LeaseHeartbeat>>,
+    cfg: &AppConfig,
+    pool_finished: &AtomicBool,
+) {
+    let interval = Duration::from_secs(cfg.scheduler.heartbeat_interval_seconds.max(1));
+    let lease_millis = (cfg.scheduler.lease_timeout_seconds as i64).saturating_mul(1000);
+    append_run_log(
+        "debug",
+        "worker.lease.heartbeat_started",
+        json!({
+            "worker_id": WORKER_POOL_ID,
+            "interval_secs": interval.as_secs(),
+        }),
+    );
+    let mut last_renewal = Instant::now();
+    while !pool_finished.load(Ordering::SeqCst) {
+        std::thread::sleep(HEARTBEAT_POLL);
+        if last_renewal.elapsed() < interval {
+            continue;
+        }
+        last_renewal = Instant::now();
+        let lease_expires_at = now_unix_millis().saturating_add(lease_millis);
+        // Renew from a copy so claims and completions never wait on the store.
+        let active = lock_leases(leases)
+            .iter()
+            .filter(|lease| lease.active())
+            .cloned()
+            .collect::<Vec<_>>();
+        for lease in &active {
+            match store.renew_lease(&lease.task_id, &lease.worker_id, lease_expires_at) {
+                Ok(true) => {}
+                // The worker may have finished and released the task between the
+                // activity check and the renewal.
+                Ok(false) if lease.finished.load(Ordering::SeqCst) => {}
+                Ok(false) => {
+                    lease.lost.store(true, Ordering::SeqCst);
+                    append_run_log(
+                        "error",
+                        "worker.lease.lost",
+                        json!({
+                            "worker_id": lease.worker_id,
+                            "task_id": lease.task_id,
+                        }),
+                    );
+                }
+                Err(err) => append_run_log(
+                    "warn",
+                    "worker.lease.renewal_error",
+                    json!({
+                        "worker_id": lease.worker_id,
+                        "task_id": lease.task_id,
+                        "error": err.to_string(),
+                    }),
+                ),
+            }
+        }
+    }
+}
+
+/// Closes or comments on the GitHub issue a completed task came from. A failure
+/// here is logged and does not stop the pool; the issue stays unresolved.
+fn resolve_linked_issue(
+    runtime: &ProductionRuntime,
+    scope: &RuntimeScope,
+    cfg: &AppConfig,
+    store: &BacklogStore,
+    worker_id: &str,
+    task_id: &str,
+    summary: &crate::worker::WorkerRunSummary,
+) {
+    let gh = GhClient::new(runtime.process_runner.as_ref(), &scope.working_dir);
+    let result = resolve_issue_for_merged_task(
+        store,
+        &gh,
+        &cfg.github_issues,
+        task_id,
+        summary.pr_number,
+        summary.merge_sha.as_deref(),
+    );
+    match result {
+        Ok(Some(issue_number)) => append_run_log(
+            "info",
+            "worker.task.issue_resolved",
+            json!({
+                "worker_id": worker_id,
+                "task_id": task_id,
+                "issue_number": issue_number,
+            }),
+        ),
+        Ok(None) => {}
+        Err(err) => append_run_log(
+            "warn",
+            "worker.task.issue_resolve_failed",
+            json!({
+                "worker_id": worker_id,
+                "task_id": task_id,
+                "error": err.to_string(),
+            }),
+        ),
+    }
+}
+
+/// How a worker run ended, as far as its outcome row is concerned.
+enum TaskRunResult<'a> {
+    Finished(&'a crate::worker::WorkerRunSummary),
+    Error(&'a str),
+}
+
+fn record_task_outcome(
+    store: &BacklogStore,
+    worker_id: &str,
+    task_id: &str,
+    claimed_run: Option<&ClaimedRun>,
+    result: TaskRunResult<'_>,
+) -> Result<(), GardenerError> {
+    let finished_at = now_unix_millis();
+    let (title, attempt, started_at) = claimed_run.map_or_else(
+        || (String::new(), 0, finished_at),
+        |run| (run.title.clone(), run.attempt, run.started_at),
+    );
+    let usage = match &result {
+        TaskRunResult::Finished(summary) => summary.usage(),
+        TaskRunResult::Error(_) => crate::usage::UsageTotals::default(),
+    };
+    let mut outcome = NewTaskOutcome {
+        task_id: task_id.to_string(),
+        title,
+        worker_id: worker_id.to_string(),
+        attempt,
+        final_state: "error".to_string(),
+        pr_number: None,
+        branch: None,
+        merge_sha: None,
+        review_loops: 0,
+        failure_reason: None,
+        started_at,
+        finished_at,
+    };
+    match result {
+        TaskRunResult::Finished(summary) => {
+            outcome.final_state = summary.final_state.as_str().to_string();
+            outcome.pr_number = summary.pr_number.map(|pr| pr as i64);
+            outcome.branch = summary.branch.clone();
+            outcome.merge_sha = summary.merge_sha.clone();
+            outcome.review_loops = i64::from(summary.review_loops);
+            outcome.failure_reason = summary.failure_reason.clone();
+        }
+        TaskRunResult::Error(message) => outcome.failure_reason = Some(message.to_string()),
+    }
+    append_run_log(
+        "info",
+        "worker.task.outcome",
+        json!({
+            "worker_id": worker_id,
+            "task_id": task_id,
+            "final_state": outcome.final_state,
+            "pr_number": outcome.pr_number,
+            "merge_sha": outcome.merge_sha,
+            "duration_ms": outcome.finished_at - outcome.started_at,
+            "agent_turns": usage.turns,
+            "total_tokens": usage.total_tokens(),
+            "cost_usd": usage.cost_usd,
+        }),
+    );
+    store.record_outcome(outcome)?;
+    Ok(())
+}
+
 fn now_unix_millis() -> i64 {
     std::time::SystemTime::now()
         .duration_since(std::time::UNIX_EPOCH)
@@ -743,17 +1635,24 @@ fn append_worker_state_events(
 ) -> bool {
     let events = recent_worker_state_events(*last_worker_state_line, max_events);
     let mut updated = false;
-    for (line, worker_id, state) in events {
+    for (line, worker_id, state, queued_backend) in events {
         for worker in workers.iter_mut() {
             if worker.worker_id != worker_id {
                 continue;
             }
+            let tool_line = match &queued_backend {
+                Some(backend) => format!("queued for {backend}"),
+                None => format!("running {state}"),
+            };
             if worker.state != state {
                 worker.state = state.clone();
                 worker.breadcrumb = format!("state>{state}");
-                worker.tool_line = format!("running {state}");
+                worker.tool_line = tool_line;
                 append_worker_command(worker, &format!("state {state}"));
                 updated = true;
+            } else if worker.tool_line != tool_line {
+                worker.tool_line = tool_line;
+                updated = true;
             }
             break;
         }
@@ -762,6 +1661,23 @@ fn append_worker_state_events(
     updated
 }
 
+/// Shows each worker with a PR in the merge queue where it stands there, in
+/// place of the state line.
+fn apply_merge_queue_slots(workers: &mut [WorkerRow], slots: &[(String, QueueSlot)]) -> bool {
+    let mut updated = false;
+    for (worker_id, slot) in slots {
+        let label = slot.label();
+        if let Some(worker) = workers
+            .iter_mut()
+            .find(|worker| &worker.worker_id == worker_id && worker.tool_line != label)
+        {
+            worker.tool_line = label;
+            updated = true;
+        }
+    }
+    updated
+}
+
 fn is_copy_shortcut_key(key: char) -> bool {
     key.eq_ignore_ascii_case(&COPY_SHORTCUT_KEY)
 }
@@ -776,7 +1692,12 @@ struct DashboardSnapshot {
     backlog: BacklogView,
 }
 
-fn dashboard_snapshot(store: &BacklogStore) -> Result<DashboardSnapshot, GardenerError> {
+fn dashboard_snapshot(
+    store: &BacklogStore,
+    claim_options: &ClaimOptions,
+    backlog_search: Option<&BacklogSearch>,
+    pool_status: Option<&str>,
+) -> Result<DashboardSnapshot, GardenerError> {
     append_run_log(
         "debug",
         "worker_pool.dashboard_snapshot.started",
@@ -785,6 +1706,7 @@ fn dashboard_snapshot(store: &BacklogStore) -> Result<DashboardSnapshot, Gardene
         }),
     );
     let tasks = store.list_tasks()?;
+    let usage = run_usage();
     let mut stats = QueueStats {
         ready: 0,
         active: 0,
@@ -793,14 +1715,47 @@ fn dashboard_snapshot(store: &BacklogStore) -> Result<DashboardSnapshot, Gardene
         p0: 0,
         p1: 0,
         p2: 0,
+        held_by_scope: claim_options.held_back(&tasks),
+        pool_status: pool_status.map(str::to_string),
+        usage: (usage.turns > 0).then(|| usage.summary()),
+    };
+    // With a search query the pane lists matching open tasks instead of the
+    // whole queue; the summary counts always cover the full backlog.
+    let matches = match backlog_search.filter(|search| !search.query.trim().is_empty()) {
+        Some(search) => Some(store.search(
+            &search.query,
+            &TaskFilter {
+                statuses: vec![TaskStatus::Ready, TaskStatus::Leased, TaskStatus::InProgress],
+                ..TaskFilter::default()
+            },
+        )?),
+        None => None,
     };
-    let mut backlog = BacklogView::default();
-    for task in tasks {
+    let mut backlog = BacklogView {
+        search: backlog_search.cloned(),
+        ..BacklogView::default()
+    };
+    for task in &tasks {
         match task.status {
             crate::backlog_store::TaskStatus::Ready => stats.ready += 1,
+            crate::backlog_store::TaskStatus::Leased
+            | crate::backlog_store::TaskStatus::InProgress => stats.active += 1,
+            crate::backlog_store::TaskStatus::Failed
+            | crate::backlog_store::TaskStatus::Exhausted => stats.failed += 1,
+            crate::backlog_store::TaskStatus::Unresolved => stats.unresolved += 1,
+            crate::backlog_store::TaskStatus::Complete
+            | crate::backlog_store::TaskStatus::Cancelled => {}
+        }
+        match task.priority {
+            crate::priority::Priority::P0 => stats.p0 += 1,
+            crate::priority::Priority::P1 => stats.p1 += 1,
+            crate::priority::Priority::P2 => stats.p2 += 1,
+        }
+    }
+    for task in matches.as_ref().unwrap_or(&tasks) {
+        match task.status {
             crate::backlog_store::TaskStatus::Leased
             | crate::backlog_store::TaskStatus::InProgress => {
-                stats.active += 1;
                 backlog.in_progress.push(format!(
                     "INP {} {} {}",
                     task.priority.as_str(),
@@ -808,22 +1763,15 @@ fn dashboard_snapshot(store: &BacklogStore) -> Result<DashboardSnapshot, Gardene
                     task.title
                 ));
             }
-            crate::backlog_store::TaskStatus::Failed => stats.failed += 1,
-            crate::backlog_store::TaskStatus::Unresolved => stats.unresolved += 1,
-            crate::backlog_store::TaskStatus::Complete => {}
-        }
-        if matches!(task.status, crate::backlog_store::TaskStatus::Ready) {
-            backlog.queued.push(format!(
-                "Q {} {} {}",
-                task.priority.as_str(),
-                short_task_id(&task.task_id),
-                task.title
-            ));
-        }
-        match task.priority {
-            crate::priority::Priority::P0 => stats.p0 += 1,
-            crate::priority::Priority::P1 => stats.p1 += 1,
-            crate::priority::Priority::P2 => stats.p2 += 1,
+            crate::backlog_store::TaskStatus::Ready => {
+                backlog.queued.push(format!(
+                    "Q {} {} {}",
+                    task.priority.as_str(),
+                    short_task_id(&task.task_id),
+                    task.title
+                ));
+            }
+            _ => {}
         }
     }
     Ok(DashboardSnapshot { stats, backlog })
@@ -898,18 +1846,30 @@ fn quality_report_path(cfg: &AppConfig, scope: &RuntimeScope) -> std::path::Path
 
 #[cfg(test)]
 mod tests {
-    use super::{hotkey_action, run_worker_pool_fsm, wait_for_quit, INTERRUPT_SENTINEL_KEY};
-    use crate::backlog_store::{BacklogStore, NewTask};
+    use super::{
+        apply_merge_queue_slots, dashboard_snapshot, drain_status, edit_backlog_search, hotkey_action, is_interrupted,
+        record_failed_attempt, run_lease_heartbeat, run_worker_pool, run_worker_pool_fsm,
+        wait_for_quit, ClaimedRun, FailedAttempt, LeaseHeartbeat, PoolRun, BACKSPACE_KEY,
+        ENTER_KEY, ESCAPE_KEY, INTERRUPT_SENTINEL_KEY,
+    };
+    use crate::backlog_store::{BacklogStore, ClaimOptions, NewTask, TaskStatus};
     use crate::config::AppConfig;
+    use crate::errors::GardenerError;
     use crate::hotkeys::{action_for_key, HotkeyAction, DASHBOARD_BINDINGS, REPORT_BINDINGS};
+    use crate::merge_queue::QueueSlot;
     use crate::priority::Priority;
     use crate::runtime::{
-        FakeClock, FakeProcessRunner, FakeTerminal, ProductionFileSystem, ProductionRuntime,
+        FakeClock, FakeProcessRunner, FakeTerminal, PoolShutdown, ProductionFileSystem,
+        ProductionRuntime, INTERRUPT_MESSAGE,
     };
+    use crate::protocol::TokenUsage;
     use crate::task_identity::TaskKind;
-    use crate::types::RuntimeScope;
+    use crate::tui::{render_dashboard, BacklogSearch, WorkerRow};
+    use crate::types::{AgentKind, RuntimeScope, WorkerState};
+    use crate::usage::record_turn_usage;
     use std::path::PathBuf;
-    use std::sync::Arc;
+    use std::sync::atomic::{AtomicBool, Ordering};
+    use std::sync::{Arc, Mutex};
     use tempfile::TempDir;
 
     fn seed_task(store: &BacklogStore, title: &str) {
@@ -924,6 +1884,7 @@ mod tests {
                 source: "test".to_string(),
                 related_pr: None,
                 related_branch: None,
+                blocked_by: Vec::new(),
             })
             .expect("seed task");
     }
@@ -963,6 +1924,7 @@ mod tests {
             Some(HotkeyAction::RegenerateReport)
         ); // hotkey:g
         assert_eq!(hotkey_action('b', false), Some(HotkeyAction::Back)); // hotkey:b
+        assert_eq!(hotkey_action('/', false), Some(HotkeyAction::Search)); // hotkey:/
         assert_eq!(hotkey_action('r', false), None);
         assert_eq!(hotkey_action('l', false), None);
         assert_eq!(hotkey_action('p', false), None);
@@ -1035,6 +1997,46 @@ mod tests {
         assert!(!terminal.report_draws().is_empty());
     }
 
+    #[test]
+    fn backlog_search_box_edits_query_and_filters_the_pane() {
+        let dir = TempDir::new().expect("tempdir");
+        let store = BacklogStore::open(dir.path().join("backlog.sqlite")).expect("open store");
+        seed_task(&store, "Fix flaky queue test");
+        seed_task(&store, "Document config loading");
+
+        let mut search = Some(BacklogSearch {
+            query: String::new(),
+            editing: true,
+        });
+        for key in ['q', 'x', BACKSPACE_KEY, BACKSPACE_KEY] {
+            assert!(edit_backlog_search(&mut search, key));
+        }
+        for key in "flaky".chars() {
+            edit_backlog_search(&mut search, key);
+        }
+        assert!(edit_backlog_search(&mut search, ENTER_KEY));
+        assert_eq!(
+            search,
+            Some(BacklogSearch {
+                query: "flaky".to_string(),
+                editing: false,
+            })
+        );
+
+        let snapshot =
+            dashboard_snapshot(&store, &ClaimOptions::default(), search.as_ref(), None).expect("snap");
+        assert_eq!(snapshot.stats.ready, 2);
+        assert_eq!(snapshot.backlog.queued.len(), 1);
+        assert!(snapshot.backlog.queued[0].ends_with("Fix flaky queue test"));
+        assert_eq!(snapshot.backlog.search, search);
+
+        search.as_mut().expect("search").editing = true;
+        assert!(edit_backlog_search(&mut search, ESCAPE_KEY));
+        assert_eq!(search, None);
+        let snapshot = dashboard_snapshot(&store, &ClaimOptions::default(), None, None).expect("snap");
+        assert_eq!(snapshot.backlog.queued.len(), 2);
+    }
+
     #[test]
     fn run_worker_pool_fsm_handles_v_and_b_with_report_draws() {
         let dir = TempDir::new().expect("tempdir");
@@ -1156,6 +2158,82 @@ mod tests {
 
         let _ = run_worker_pool_fsm(&runtime, &scope, &cfg, &store, &terminal, 1, None)
             .expect("run fsm");
+        // Quitting drains: nothing new is claimed and no lease is left behind.
+        assert!(store
+            .list_tasks()
+            .expect("list")
+            .iter()
+            .all(|task| task.status == TaskStatus::Ready && task.lease_owner.is_none()));
+    }
+
+    #[test]
+    fn second_quit_turns_the_drain_into_an_interrupt() {
+        let shutdown = PoolShutdown::default();
+        let slots = vec![
+            Some(ClaimedRun {
+                title: "busy".to_string(),
+                attempt: 1,
+                started_at: 0,
+            }),
+            None,
+        ];
+        assert_eq!(drain_status(&shutdown, &slots, None), None);
+        let killed = Err(GardenerError::Process(INTERRUPT_MESSAGE.to_string()));
+        assert!(is_interrupted(&killed, &shutdown));
+        assert!(!is_interrupted(
+            &Err(GardenerError::Process("exit 1".to_string())),
+            &shutdown
+        ));
+
+        shutdown.drain();
+        assert_eq!(
+            drain_status(&shutdown, &slots, None).as_deref(),
+            Some("draining: finishing 1 task(s), q again to stop now")
+        );
+        assert!(!is_interrupted(
+            &Err(GardenerError::Process("exit 1".to_string())),
+            &shutdown
+        ));
+
+        shutdown.interrupt();
+        assert_eq!(
+            drain_status(&shutdown, &slots, None).as_deref(),
+            Some("stopping: interrupting 1 task(s)")
+        );
+        // Once interrupting, any failure is the kill, not the task's fault.
+        assert!(is_interrupted(
+            &Err(GardenerError::Process("exit 137".to_string())),
+            &shutdown
+        ));
+    }
+
+    #[test]
+    fn drain_banner_renders_in_the_dashboard_summary() {
+        let dir = TempDir::new().expect("tempdir");
+        let store =
+            BacklogStore::open(dir.path().join("backlog.sqlite")).expect("open store");
+        seed_task(&store, "banner task");
+        let shutdown = PoolShutdown::default();
+        let slots = vec![Some(ClaimedRun {
+            title: "busy".to_string(),
+            attempt: 1,
+            started_at: 0,
+        })];
+        let frame = |shutdown: &PoolShutdown| {
+            let status = drain_status(shutdown, &slots, None);
+            let snapshot =
+                dashboard_snapshot(&store, &ClaimOptions::default(), None, status.as_deref())
+                    .expect("snap");
+            render_dashboard(&[], &snapshot.stats, &snapshot.backlog, 160, 40)
+        };
+
+        assert!(!frame(&shutdown).contains("draining:"));
+        shutdown.drain();
+        assert!(frame(&shutdown).contains("draining: finishing 1 task(s), q again to stop now"));
+        shutdown.interrupt();
+        let stopping = frame(&shutdown);
+        assert!(stopping.contains("stopping: interrupting 1 task(s)"));
+        assert!(!stopping.contains("draining:"));
     }
 
     #[test]
@@ -1289,4 +2367,251 @@ mod tests {
         assert!(writes.iter().any(|line| line.contains("worker-1")));
         assert!(!writes.iter().any(|line| line.contains("worker-2")));
     }
+
+    #[test]
+    fn slots_claim_the_next_task_as_soon_as_they_free_up_and_stop_at_target() {
+        let dir = TempDir::new().expect("tempdir");
+        let scope = test_scope(&dir);
+        let store = BacklogStore::open(dir.path().join(".cache/gardener/backlog.sqlite"))
+            .expect("open store");
+        for title in ["first", "second", "third", "fourth", "fifth"] {
+            seed_task(&store, title);
+        }
+
+        let mut cfg = AppConfig::default();
+        cfg.execution.test_mode = true;
+        cfg.orchestrator.parallelism = 2;
+        cfg.quality_report.path = dir
+            .path()
+            .join(".gardener/quality.md")
+            .display()
+            .to_string();
+        let terminal = FakeTerminal::new(false);
+        let runtime = ProductionRuntime {
+            clock: Arc::new(FakeClock::default()),
+            file_system: Arc::new(ProductionFileSystem),
+            process_runner: Arc::new(FakeProcessRunner::default()),
+            terminal: Arc::new(terminal.clone()),
+        };
+        let completed =
+            run_worker_pool_fsm(&runtime, &scope, &cfg, &store, &terminal, 4, None)
+                .expect("run fsm");
+        assert_eq!(completed, 4);
+
+        let tasks = store.list_tasks().expect("list");
+        let done = tasks
+            .iter()
+            .filter(|task| task.status == TaskStatus::Complete)
+            .count();
+        assert_eq!(done, 4);
+        assert_eq!(
+            tasks
+                .iter()
+                .filter(|task| task.status == TaskStatus::Ready)
+                .count(),
+            1,
+            "no slot claims past the target"
+        );
+        let outcomes = tasks
+            .iter()
+            .flat_map(|task| store.outcomes_for_task(&task.task_id).expect("outcomes"))
+            .collect::<Vec<_>>();
+        assert_eq!(outcomes.len(), 4);
+        // Four tasks ran on the two long-lived slots; no extra workers were spawned.
+        assert!(outcomes
+            .iter()
+            .all(|outcome| ["worker-1", "worker-2"].contains(&outcome.worker_id.as_str())));
+    }
+
+    #[test]
+    fn spent_budget_stops_claims_and_drains_the_pool() {
+        let dir = TempDir::new().expect("tempdir");
+        let scope = test_scope(&dir);
+        let store = BacklogStore::open(dir.path().join(".cache/gardener/backlog.sqlite"))
+            .expect("open store");
+        seed_task(&store, "over budget");
+
+        let mut cfg = AppConfig::default();
+        cfg.execution.test_mode = true;
+        cfg.budget.max_tokens = Some(1);
+        record_turn_usage(
+            &cfg,
+            "worker-1",
+            WorkerState::Doing,
+            AgentKind::Codex,
+            "gpt-5-codex",
+            &TokenUsage {
+                input_tokens: 10,
+                output_tokens: 5,
+                ..TokenUsage::default()
+            },
+        );
+        let terminal = FakeTerminal::new(false);
+        let runtime = ProductionRuntime {
+            clock: Arc::new(FakeClock::default()),
+            file_system: Arc::new(ProductionFileSystem),
+            process_runner: Arc::new(FakeProcessRunner::default()),
+            terminal: Arc::new(terminal.clone()),
+        };
+        let run = PoolRun {
+            target: 1,
+            skip_completion_screen: true,
+            ..PoolRun::default()
+        };
+        let report = run_worker_pool(&runtime, &scope, &cfg, &store, &terminal, &run)
+            .expect("run pool");
+        assert!(report.budget_exhausted);
+        assert_eq!(report.claimed, 0);
+        assert!(store
+            .list_tasks()
+            .expect("list")
+            .iter()
+            .all(|task| task.status == TaskStatus::Ready));
+        assert!(terminal
+            .written_lines()
+            .iter()
+            .any(|line| line.contains("drained")));
+    }
+
+    #[test]
+    fn finished_runs_record_an_outcome_for_the_task() {
+        let dir = TempDir::new().expect("tempdir");
+        let scope = test_scope(&dir);
+        let store = BacklogStore::open(dir.path().join(".cache/gardener/backlog.sqlite"))
+            .expect("open store");
+        seed_task(&store, "outcome task");
+        let task_id = store.list_tasks().expect("list")[0].task_id.clone();
+
+        let mut cfg = AppConfig::default();
+        cfg.execution.test_mode = true;
+        cfg.quality_report.path = dir
+            .path()
+            .join(".gardener/quality.md")
+            .display()
+            .to_string();
+        let terminal = FakeTerminal::new(false);
+        let runtime = ProductionRuntime {
+            clock: Arc::new(FakeClock::default()),
+            file_system: Arc::new(ProductionFileSystem),
+            process_runner: Arc::new(FakeProcessRunner::default()),
+            terminal: Arc::new(terminal.clone()),
+        };
+        run_worker_pool_fsm(&runtime, &scope, &cfg, &store, &terminal, 1, None).expect("run fsm");
+
+        let outcomes = store.outcomes_for_task(&task_id).expect("outcomes");
+        assert_eq!(outcomes.len(), 1);
+        assert_eq!(outcomes[0].final_state, "complete");
+        assert_eq!(outcomes[0].title, "outcome task");
+        assert_eq!(outcomes[0].worker_id, "worker-1");
+        assert!(outcomes[0].merge_sha.is_some());
+        assert!(outcomes[0].finished_at >= outcomes[0].started_at);
+    }
+
+    #[test]
+    fn lease_heartbeat_renews_owned_leases_and_flags_lost_ones() {
+        let dir = TempDir::new().expect("tempdir");
+        let store = BacklogStore::open(dir.path().join("backlog.sqlite")).expect("open store");
+        seed_task(&store, "kept");
+        seed_task(&store, "stolen");
+        let kept = store.claim_next("worker-1", 1).expect("claim").expect("task");
+        let stolen = store.claim_next("worker-2", 1).expect("claim").expect("task");
+        assert!(store
+            .release_lease(&stolen.task_id, "worker-2")
+            .expect("release elsewhere"));
+
+        let mut cfg = AppConfig::default();
+        cfg.scheduler.heartbeat_interval_seconds = 1;
+        cfg.scheduler.lease_timeout_seconds = 600;
+        let leases = [(0, &kept), (1, &stolen)]
+            .into_iter()
+            .map(|(idx, task)| LeaseHeartbeat {
+                idx,
+                worker_id: task.lease_owner.clone().expect("owner"),
+                task_id: task.task_id.clone(),
+                finished: Arc::new(AtomicBool::new(false)),
+                lost: Arc::new(AtomicBool::new(false)),
+            })
+            .collect::<Vec<_>>();
+        let registry = Mutex::new(leases.clone());
+        let pool_finished = AtomicBool::new(false);
+
+        std::thread::scope(|scope| {
+            scope.spawn(|| run_lease_heartbeat(&store, &registry, &cfg, &pool_finished));
+            while !leases[1].lost.load(Ordering::SeqCst) {
+                std::thread::sleep(std::time::Duration::from_millis(10));
+            }
+            leases[0].finished.store(true, Ordering::SeqCst);
+            pool_finished.store(true, Ordering::SeqCst);
+        });
+
+        assert!(!leases[0].lost.load(Ordering::SeqCst));
+        let renewed = store.get_task(&kept.task_id).expect("fetch").expect("task");
+        assert!(renewed.lease_expires_at > kept.lease_expires_at);
+    }
+
+    #[test]
+    fn failed_attempts_back_off_until_the_kind_limit_is_reached() {
+        let dir = TempDir::new().expect("tempdir");
+        let store = BacklogStore::open(dir.path().join("backlog.sqlite")).expect("open store");
+        seed_task(&store, "keeps failing");
+        let mut cfg = AppConfig::default();
+        cfg.retry.backoff_base_seconds = 0;
+        cfg.retry
+            .max_attempts_by_kind
+            .insert("maintenance".to_string(), 2);
+
+        let first = store.claim_next("worker-1", 60).expect("claim").expect("task");
+        let outcome = record_failed_attempt(
+            &store,
+            &cfg,
+            "worker-1",
+            &first.task_id,
+            Some("lint failed".to_string()),
+        )
+        .expect("first failure");
+        assert_eq!(outcome, FailedAttempt::Retry { delay_secs: 0 });
+
+        let second = store.claim_next("worker-1", 60).expect("claim").expect("task");
+        assert_eq!(second.task_id, first.task_id);
+        let outcome = record_failed_attempt(&store, &cfg, "worker-1", &second.task_id, None)
+            .expect("second failure");
+        assert_eq!(outcome, FailedAttempt::Exhausted { attempts: 2 });
+
+        let row = store.get_task(&first.task_id).expect("get").expect("row");
+        assert_eq!(row.status, TaskStatus::Exhausted);
+        assert_eq!(row.failure_reason.as_deref(), Some("lint failed"));
+        assert_eq!(
+            record_failed_attempt(&store, &cfg, "worker-1", &first.task_id, None)
+                .expect("not owned"),
+            FailedAttempt::NotOwned
+        );
+    }
+
+    #[test]
+    fn merge_queue_slots_show_on_queued_workers_only() {
+        let row = |worker_id: &str| WorkerRow {
+            worker_id: worker_id.to_string(),
+            state: "merging".to_string(),
+            task_title: "task".to_string(),
+            tool_line: "running merging".to_string(),
+            breadcrumb: "state>merging".to_string(),
+            last_heartbeat_secs: 0,
+            session_age_secs: 0,
+            lease_held: true,
+            session_missing: false,
+            command_details: Vec::new(),
+        };
+        let mut workers = vec![row("worker-1"), row("worker-2"), row("worker-3")];
+        let slots = vec![
+            ("worker-2".to_string(), QueueSlot::Merging { pr_number: 12 }),
+            ("worker-3".to_string(), QueueSlot::Waiting { position: 1, of: 1 }),
+        ];
+        assert!(apply_merge_queue_slots(&mut workers, &slots));
+        let lines = workers
+            .iter()
+            .map(|worker| worker.tool_line.as_str())
+            .collect::<Vec<_>>();
+        assert_eq!(lines, ["running merging", "merging PR #12", "merge queue 1 of 1"]);
+        assert!(!apply_merge_queue_slots(&mut workers, &slots));
+    }
 }
@@ -34,6 +34,7 @@ impl<'a> WorktreeClient<'a> {
                 "--porcelain".to_string(),
             ],
             cwd: Some(self.cwd.clone()),
+            timeout: None,
         })?;
         if out.exit_code != 0 {
             append_run_log(
@@ -104,6 +105,7 @@ impl<'a> WorktreeClient<'a> {
                     path.display().to_string(),
                 ],
                 cwd: Some(self.cwd.clone()),
+                timeout: None,
             })?;
             if remove.exit_code != 0 {
                 append_run_log(
@@ -192,6 +194,7 @@ impl<'a> WorktreeClient<'a> {
                 branch.to_string(),
             ],
             cwd: Some(self.cwd.clone()),
+            timeout: None,
         })?;
         if out.exit_code != 0 {
             let branch_exists = self.branch_exists(branch)?;
@@ -220,6 +223,7 @@ impl<'a> WorktreeClient<'a> {
                         branch.to_string(),
                     ],
                     cwd: Some(self.cwd.clone()),
+                    timeout: None,
                 })?;
                 if attach.exit_code == 0 {
                     append_run_log(
@@ -340,6 +344,7 @@ impl<'a> WorktreeClient<'a> {
                 "hooks".to_string(),
             ],
             cwd: Some(self.cwd.clone()),
+            timeout: None,
         })?;
         if out.exit_code != 0 {
             return Err(GardenerError::Process(format!(
@@ -401,6 +406,7 @@ impl<'a> WorktreeClient<'a> {
                 reference.to_string(),
             ],
             cwd: Some(self.cwd.clone()),
+            timeout: None,
         })?;
         Ok(check.exit_code == 0)
     }
@@ -423,6 +429,7 @@ impl<'a> WorktreeClient<'a> {
                 path.display().to_string(),
             ],
             cwd: Some(self.cwd.clone()),
+            timeout: None,
         })?;
         if out.exit_code != 0 {
             append_run_log(
@@ -462,6 +469,7 @@ impl<'a> WorktreeClient<'a> {
             program: "git".to_string(),
             args: vec!["worktree".to_string(), "prune".to_string()],
             cwd: Some(self.cwd.clone()),
+            timeout: None,
         })?;
         if out.exit_code != 0 {
             append_run_log(
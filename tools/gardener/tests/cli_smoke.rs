@@ -58,3 +58,37 @@ fn invalid_config_path_exits_nonzero() {
         .arg(fixture("configs/missing.toml"));
     cmd.assert().failure();
 }
+
+#[test]
+fn backlog_subcommands_add_and_list_through_the_store() {
+    let temp = tempfile::tempdir().expect("tempdir");
+    let db = temp.path().join("backlog.sqlite");
+    let logs = temp.path().join("otel-logs.jsonl");
+    let backlog = |args: &[&str]| {
+        let mut cmd = cargo_bin_cmd!("gardener");
+        cmd.arg("--config")
+            .arg(fixture("configs/phase09-cutover.toml"))
+            .arg("--working-dir")
+            .arg(temp.path())
+            .arg("backlog")
+            .args(args)
+            .env("GARDENER_DB_PATH", &db)
+            .env("GARDENER_LOG_PATH", &logs);
+        let out = cmd.assert().success();
+        String::from_utf8(out.get_output().stdout.clone()).expect("utf8")
+    };
+
+    let added = backlog(&[
+        "add",
+        "--title",
+        "Smoke task",
+        "--details",
+        "Added from the CLI",
+        "--priority",
+        "P0",
+    ]);
+    assert!(added.contains("upserted"), "{added}");
+
+    let listed = backlog(&["list", "--priority", "P0", "--format", "json"]);
+    assert!(listed.contains("\"title\":\"Smoke task\""), "{listed}");
+}
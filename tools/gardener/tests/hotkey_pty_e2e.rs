@@ -1,6 +1,7 @@
 use expectrl::{Eof, Expect};
 use gardener::backlog_store::{BacklogStore, NewTask, TaskStatus};
 use gardener::priority::Priority;
+use gardener::repo_identity::RepoIdentity;
 use gardener::task_identity::TaskKind;
 use std::process::Command;
 use std::time::Duration;
@@ -18,10 +19,18 @@ fn upsert_task(store: &BacklogStore, title: &str) {
             source: "pty-test".to_string(),
             related_pr: None,
             related_branch: None,
+            blocked_by: Vec::new(),
         })
         .expect("upsert task");
 }
 
+/// Opens the backlog in the namespace the binary will use. The binary runs with
+/// the tempdir as its cwd and working dir, which is no git checkout with an
+/// `origin` remote, so its repo identity is the tempdir with no remote.
+fn open_repo_store(dir: &std::path::Path, db_path: &std::path::Path) -> BacklogStore {
+    BacklogStore::open_for_repo(db_path, &RepoIdentity::new(dir, None)).expect("open store")
+}
+
 fn write_exec(path: &std::path::Path, contents: &str) {
     use std::os::unix::fs::PermissionsExt;
     std::fs::write(path, contents).expect("write script");
@@ -84,13 +93,14 @@ stale_if_head_commit_differs = true
     .expect("write config");
 
     let db_path = dir.path().join(".cache/gardener/backlog.sqlite");
-    let store = BacklogStore::open(&db_path).expect("open store");
+    let store = open_repo_store(dir.path(), &db_path);
     for idx in 0..500 {
         upsert_task(&store, &format!("PTY task {idx}"));
     }
 
     let mut cmd = Command::new(&bin);
-    cmd.arg("--config")
+    cmd.current_dir(dir.path())
+        .arg("--config")
         .arg(&config_path)
         .arg("--working-dir")
         .arg(dir.path())
@@ -231,11 +241,12 @@ max_turns = 12
     .expect("write config");
 
     let db_path = dir.path().join(".cache/gardener/backlog.sqlite");
-    let store = BacklogStore::open(&db_path).expect("open store");
+    let store = open_repo_store(dir.path(), &db_path);
     upsert_task(&store, "long running task");
 
     let mut cmd = Command::new(&bin);
-    cmd.arg("--config")
+    cmd.current_dir(dir.path())
+        .arg("--config")
         .arg(&config_path)
         .arg("--working-dir")
         .arg(dir.path())
@@ -255,9 +266,79 @@ max_turns = 12
     (dir, store, cmd)
 }
 
+/// Event types from the run log, in the order they were written.
+fn run_log_events(dir: &std::path::Path) -> Vec<String> {
+    std::fs::read_to_string(dir.join("otel-logs.jsonl"))
+        .expect("read run log")
+        .lines()
+        .filter_map(|line| serde_json::from_str::<serde_json::Value>(line).ok())
+        .filter_map(|line| line["event_type"].as_str().map(str::to_string))
+        .collect()
+}
+
+/// Blocks until a worker holds a task, so a quit lands while it is in flight.
+fn wait_for_claim(store: &BacklogStore) {
+    let deadline = std::time::Instant::now() + Duration::from_secs(15);
+    while std::time::Instant::now() < deadline {
+        let tasks = store.list_tasks().expect("list tasks");
+        if tasks
+            .iter()
+            .any(|task| matches!(task.status, TaskStatus::Leased | TaskStatus::InProgress))
+        {
+            return;
+        }
+        std::thread::sleep(Duration::from_millis(50));
+    }
+    panic!("no task was claimed within 15s");
+}
+
+/// The first quit drained the pool: nothing was claimed after the drain began
+/// and no task is left leased or in progress.
+fn assert_drained(dir: &std::path::Path, store: &BacklogStore) {
+    let events = run_log_events(dir);
+    let drain_started = events
+        .iter()
+        .position(|event| event == "worker_pool.drain.started")
+        .expect("first quit starts a drain");
+    assert!(
+        !events[drain_started..]
+            .iter()
+            .any(|event| event == "worker.task.claimed"),
+        "nothing is claimed once the drain has started"
+    );
+    let tasks = store.list_tasks().expect("list tasks");
+    assert!(
+        tasks.iter().all(|task| task.lease_owner.is_none()
+            && !matches!(task.status, TaskStatus::Leased | TaskStatus::InProgress)),
+        "every lease is released on exit: {tasks:?}"
+    );
+}
+
+/// The second quit killed the in-flight turn and handed its task back.
+fn assert_interrupted(dir: &std::path::Path, store: &BacklogStore) {
+    assert_drained(dir, store);
+    assert!(
+        run_log_events(dir)
+            .iter()
+            .any(|event| event == "worker_pool.drain.interrupted"),
+        "second quit interrupts the drain"
+    );
+    let tasks = store.list_tasks().expect("list tasks");
+    let task = tasks
+        .iter()
+        .find(|task| task.title == "long running task")
+        .expect("seeded task");
+    assert_eq!(task.status, TaskStatus::Ready);
+    let note = task.status_note.as_deref().unwrap_or_default();
+    assert!(
+        note.starts_with("interrupted at state "),
+        "interrupted task carries the state it stopped in: {note:?}"
+    );
+}
+
 #[test]
 fn pty_e2e_hotkeys_v_g_b_q_drive_screen_transitions() {
-    let (report_path, _dir, store, cmd) = setup_pty_fixture();
+    let (report_path, dir, store, cmd) = setup_pty_fixture();
     let mut session = expectrl::Session::spawn(cmd).expect("spawn pty");
     session.set_expect_timeout(Some(Duration::from_secs(30)));
 
@@ -266,6 +347,9 @@ fn pty_e2e_hotkeys_v_g_b_q_drive_screen_transitions() {
     std::thread::sleep(Duration::from_millis(300));
     session.send("b").expect("send b");
     session.send("q").expect("send q");
+    std::thread::sleep(Duration::from_millis(300));
+    // The first q only drains; the second stops whatever is still running.
+    let _ = session.send("q");
     session.expect(Eof).expect("session exited");
 
     let report = std::fs::read_to_string(&report_path).expect("read report");
@@ -283,14 +367,18 @@ fn pty_e2e_hotkeys_v_g_b_q_drive_screen_transitions() {
         remaining > 0,
         "expected quit hotkey to stop run before finishing all seeded tasks"
     );
+    assert_drained(dir.path(), &store);
 }
 
 #[test]
 fn pty_e2e_ctrl_c_quits() {
-    let (_report_path, _dir, store, cmd) = setup_pty_fixture();
+    let (_report_path, dir, store, cmd) = setup_pty_fixture();
     let mut session = expectrl::Session::spawn(cmd).expect("spawn pty");
     session.set_expect_timeout(Some(Duration::from_secs(30)));
+    wait_for_claim(&store);
     session.send("\u{3}").expect("send ctrl-c");
+    std::thread::sleep(Duration::from_millis(300));
+    let _ = session.send("\u{3}");
     session.expect(Eof).expect("session exited");
     let tasks = store.list_tasks().expect("list tasks");
     let remaining = tasks
@@ -301,47 +389,33 @@ fn pty_e2e_ctrl_c_quits() {
         remaining > 0,
         "expected ctrl-c to stop run before finishing all seeded tasks"
     );
+    assert_drained(dir.path(), &store);
 }
 
 #[test]
 fn pty_e2e_q_interrupts_live_blocking_turn() {
-    let (_dir, store, cmd) = setup_live_interrupt_fixture();
+    let (dir, store, cmd) = setup_live_interrupt_fixture();
     let mut session = expectrl::Session::spawn(cmd).expect("spawn pty");
     session.set_expect_timeout(Some(Duration::from_secs(30)));
-    std::thread::sleep(Duration::from_millis(350));
-    for _ in 0..5 {
-        session.send("q").expect("send q");
-        std::thread::sleep(Duration::from_millis(150));
-    }
+    wait_for_claim(&store);
+    session.send("q").expect("send q");
+    std::thread::sleep(Duration::from_millis(300));
+    session.send("q").expect("send second q");
     session.expect(Eof).expect("session exited");
 
-    let tasks = store.list_tasks().expect("list tasks");
-    let remaining = tasks
-        .iter()
-        .filter(|task| task.status != TaskStatus::Complete)
-        .count();
-    assert!(
-        remaining > 0,
-        "expected q to interrupt before all tasks complete"
-    );
+    assert_interrupted(dir.path(), &store);
 }
 
 #[test]
 fn pty_e2e_ctrl_c_interrupts_live_blocking_turn() {
-    let (_dir, store, cmd) = setup_live_interrupt_fixture();
+    let (dir, store, cmd) = setup_live_interrupt_fixture();
     let mut session = expectrl::Session::spawn(cmd).expect("spawn pty");
     session.set_expect_timeout(Some(Duration::from_secs(8)));
-    std::thread::sleep(Duration::from_millis(350));
+    wait_for_claim(&store);
     session.send("\u{3}").expect("send ctrl-c");
+    std::thread::sleep(Duration::from_millis(300));
+    session.send("\u{3}").expect("send second ctrl-c");
     session.expect(Eof).expect("session exited");
 
-    let tasks = store.list_tasks().expect("list tasks");
-    let remaining = tasks
-        .iter()
-        .filter(|task| task.status != TaskStatus::Complete)
-        .count();
-    assert!(
-        remaining > 0,
-        "expected ctrl-c to interrupt before all tasks complete"
-    );
+    assert_interrupted(dir.path(), &store);
 }
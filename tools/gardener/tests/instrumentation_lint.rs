@@ -23,6 +23,7 @@ const EXCLUDED_FILES: &[&str] = &[
     "replay/replayer.rs",
     "runtime/mod.rs",
     "task_identity.rs",
+    "task_similarity.rs",
     "tui.rs",
     "types.rs",
     "worker_identity.rs",
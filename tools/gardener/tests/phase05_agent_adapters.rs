@@ -30,6 +30,7 @@ fn claude_context() -> AdapterContext {
         output_file: None,
         permissive_mode: true,
         max_turns: Some(4),
+        timeout: None,
     }
 }
 
@@ -46,6 +47,7 @@ fn codex_context() -> AdapterContext {
         output_file: Some(PathBuf::from("/repo/out.json")),
         permissive_mode: true,
         max_turns: None,
+        timeout: None,
     }
 }
 
@@ -25,6 +25,9 @@ fn zero_stats() -> QueueStats {
         p0: 0,
         p1: 0,
         p2: 0,
+        held_by_scope: 0,
+        pool_status: None,
+        usage: None,
     }
 }
 
@@ -61,6 +64,9 @@ fn dashboard_header_shows_queue_stats() {
         p0: 1,
         p1: 1,
         p2: 0,
+        held_by_scope: 0,
+        pool_status: None,
+        usage: None,
     };
     let frame = render_dashboard(&workers, &stats, &empty_backlog(), 120, 30);
     assert!(frame.contains("GARDENER"), "frame should contain GARDENER header");
@@ -99,6 +105,9 @@ fn dashboard_keeps_three_workers_visible_in_short_viewports_without_backlog() {
         p0: 0,
         p1: 3,
         p2: 0,
+        held_by_scope: 0,
+        pool_status: None,
+        usage: None,
     }, &BacklogView::default(), 80, 19);
     assert!(frame.contains("Lawn Mower"), "first worker card should be visible");
     assert!(frame.contains("Leaf Blower"), "second worker card should be visible");
@@ -115,6 +124,7 @@ fn dashboard_keeps_three_workers_visible_with_backlog() {
     let backlog = BacklogView {
         in_progress: vec!["INP 5d8c91a fix lint errors".to_string()],
         queued: vec!["Q 2f4b1e4 update docs".to_string()],
+        search: None,
     };
     let frame = render_dashboard(&workers, &QueueStats {
         ready: 2,
@@ -124,6 +134,9 @@ fn dashboard_keeps_three_workers_visible_with_backlog() {
         p0: 0,
         p1: 3,
         p2: 0,
+        held_by_scope: 0,
+        pool_status: None,
+        usage: None,
     }, &backlog, 80, 24);
     assert!(frame.contains("Lawn Mower"), "first worker card should be visible");
     assert!(frame.contains("Leaf Blower"), "second worker card should be visible");
@@ -162,6 +175,7 @@ fn dashboard_backlog_priority_badges() {
     let backlog = BacklogView {
         in_progress: vec!["P0 abc123 Critical task".to_string()],
         queued: vec!["P1 def456 Normal task".to_string()],
+        search: None,
     };
     let frame = render_dashboard(
         &[make_worker("w-01", "doing", "task")],
@@ -173,6 +187,9 @@ fn dashboard_backlog_priority_badges() {
             p0: 1,
             p1: 1,
             p2: 0,
+            held_by_scope: 0,
+            pool_status: None,
+            usage: None,
         },
         &backlog,
         120,
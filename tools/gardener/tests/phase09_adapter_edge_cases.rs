@@ -21,6 +21,7 @@ fn claude_context() -> AdapterContext {
         output_file: None,
         permissive_mode: true,
         max_turns: Some(4),
+        timeout: None,
     }
 }
 
@@ -37,6 +38,7 @@ fn codex_context() -> AdapterContext {
         output_file: Some("/repo/out.json".into()),
         permissive_mode: true,
         max_turns: None,
+        timeout: None,
     }
 }
 
@@ -26,6 +26,9 @@ fn zero_stats() -> QueueStats {
         p0: 0,
         p1: 0,
         p2: 0,
+        held_by_scope: 0,
+        pool_status: None,
+        usage: None,
     }
 }
 
@@ -1,7 +1,7 @@
 use assert_cmd::cargo::cargo_bin_cmd;
 use gardener::config::{
     effective_agent_for_state, effective_model_for_state, load_config, resolve_scope,
-    resolve_validation_command, AppConfig, CliOverrides, StateConfig,
+    resolve_validation_command, AppConfig, CliOverrides, ScopeExclusivity, StateConfig,
 };
 use gardener::errors::GardenerError;
 use gardener::output_envelope::{parse_last_envelope, END_MARKER, START_MARKER};
@@ -10,6 +10,7 @@ use gardener::runtime::{
     ProcessRequest, ProcessRunner, ProductionClock, ProductionFileSystem, ProductionProcessRunner,
     ProductionRuntime, Terminal,
 };
+use gardener::task_identity::TaskKind;
 use gardener::triage_agent_detection::{is_non_interactive, EnvMap};
 use gardener::types::{AgentKind, NonInteractiveReason, WorkerState};
 use std::path::{Path, PathBuf};
@@ -105,7 +106,16 @@ fn cli_help_contract() {
         "--working-dir",
         "--parallelism",
         "--task",
+        "--task-id",
+        "--priority",
+        "--kind",
+        "--scope",
+        "--source",
         "--quit-after",
+        "--max-cost",
+        "--max-tokens",
+        "--watch",
+        "--dry-run",
         "--prune-only",
         "--backlog-only",
         "--quality-grades-only",
@@ -387,6 +397,22 @@ fn config_exhaustive_overrides_and_validation_paths() {
 [scheduler]
 lease_timeout_seconds = 111
 heartbeat_interval_seconds = 22
+scope_separator = "/"
+
+[scheduler.scope_exclusivity]
+feature = "prefix"
+merge_conflict = "exact"
+
+[scheduler.priority_aging]
+p1_to_p0_seconds = 0
+
+[retry]
+max_attempts = 4
+backoff_base_seconds = 30
+backoff_max_seconds = 100
+
+[retry.max_attempts_by_kind]
+merge_conflict = 1
 
 [prompts.turn_budget]
 understand = 10
@@ -424,6 +450,26 @@ test_mode = true
     let (cfg, scope) = load_config(&overrides, Path::new("/cwd"), &fs, &process_runner).expect("test fixture should not fail");
     assert_eq!(cfg.scheduler.lease_timeout_seconds, 111);
     assert_eq!(cfg.scheduler.heartbeat_interval_seconds, 22);
+    assert_eq!(cfg.scheduler.scope_separator, "/");
+    assert_eq!(cfg.scheduler.priority_aging.p2_to_p1_seconds, 86_400);
+    assert_eq!(cfg.scheduler.priority_aging.p1_to_p0_seconds, 0);
+    assert_eq!(
+        cfg.scheduler.scope_exclusivity_for(TaskKind::Feature),
+        ScopeExclusivity::Prefix
+    );
+    assert_eq!(
+        cfg.scheduler.scope_exclusivity_for(TaskKind::MergeConflict),
+        ScopeExclusivity::Exact
+    );
+    assert_eq!(
+        cfg.scheduler.scope_exclusivity_for(TaskKind::Bugfix),
+        ScopeExclusivity::Off
+    );
+    assert_eq!(cfg.retry.max_attempts_for(TaskKind::Feature), 4);
+    assert_eq!(cfg.retry.max_attempts_for(TaskKind::MergeConflict), 1);
+    assert_eq!(cfg.retry.backoff_seconds(1), 30);
+    assert_eq!(cfg.retry.backoff_seconds(2), 60);
+    assert_eq!(cfg.retry.backoff_seconds(3), 100);
     assert_eq!(cfg.prompts.turn_budget.understand, 10);
     assert_eq!(cfg.prompts.turn_budget.planning, 20);
     assert_eq!(cfg.prompts.turn_budget.doing, 30);
@@ -507,6 +553,70 @@ test_mode = true
         matches!(err, GardenerError::InvalidConfig(message) if message.contains("states.doing.model"))
     );
 
+    for (path, contents, expected) in [
+        ("/bad5.toml", "[retry]\nmax_attempts = 0\n", "retry.max_attempts"),
+        (
+            "/bad6.toml",
+            "[retry.max_attempts_by_kind]\nchores = 2\n",
+            "retry.max_attempts_by_kind.chores",
+        ),
+        (
+            "/bad7.toml",
+            "[retry]\nbackoff_base_seconds = 10\nbackoff_max_seconds = 5\n",
+            "retry.backoff_base_seconds",
+        ),
+        (
+            "/bad8.toml",
+            "[scheduler.scope_exclusivity]\nchores = \"exact\"\n",
+            "scheduler.scope_exclusivity.chores",
+        ),
+        (
+            "/bad9.toml",
+            "[scheduler]\nscope_separator = \"\"\n",
+            "scheduler.scope_separator",
+        ),
+        (
+            "/bad10.toml",
+            "[watch]\nidle_backoff_seconds = 600\n",
+            "watch.idle_backoff_seconds",
+        ),
+        (
+            "/bad11.toml",
+            "[prompts.turn_timeout_seconds]\ndoing = 0\n",
+            "prompts.turn_timeout_seconds",
+        ),
+        (
+            "/bad12.toml",
+            "[agent.concurrency]\ncodex = 0\n",
+            "agent.concurrency.codex",
+        ),
+        (
+            "/bad13.toml",
+            "[budget]\nmax_cost_usd = 0.0\n",
+            "budget.max_cost_usd",
+        ),
+        (
+            "/bad14.toml",
+            "[merge_queue]\nbatch_size = 0\n",
+            "merge_queue.batch_size",
+        ),
+    ] {
+        let err = load_config(
+            &CliOverrides {
+                config_path: Some(PathBuf::from(path)),
+                ..CliOverrides::default()
+            },
+            Path::new("/cwd"),
+            &FakeFileSystem::with_file(path, contents),
+            &FakeProcessRunner::default(),
+        )
+        .expect_err("invalid scheduler or retry config");
+        assert!(
+            matches!(&err, GardenerError::InvalidConfig(message) if message.contains(expected)),
+            "{err}"
+        );
+    }
+
     let mut cfg2 = AppConfig::default();
     cfg2.agent.default = Some(AgentKind::Claude);
     cfg2.states.insert(
@@ -780,6 +890,7 @@ fn runtime_fake_contracts() {
             program: "git".to_string(),
             args: vec![],
             cwd: None,
+            timeout: None,
         })
         .expect("test fixture should not fail");
     assert_eq!(out.exit_code, 0);
@@ -820,6 +931,7 @@ fn runtime_extra_branch_coverage() {
             program: "x".to_string(),
             args: vec![],
             cwd: None,
+            timeout: None,
         })
         .expect("test fixture should not fail");
     let _ = runner.wait(h).expect("test fixture should not fail");
@@ -855,6 +967,7 @@ fn runtime_production_contracts() {
             program: "sh".to_string(),
             args: vec!["-c".to_string(), "printf ok".to_string()],
             cwd: None,
+            timeout: None,
         })
         .expect("test fixture should not fail");
     assert_eq!(out.stdout, "ok");
@@ -864,6 +977,7 @@ fn runtime_production_contracts() {
             program: "sh".to_string(),
             args: vec!["-c".to_string(), "sleep 1".to_string()],
             cwd: None,
+            timeout: None,
         })
         .expect("test fixture should not fail");
     runner.kill(handle).expect("test fixture should not fail");
@@ -12,7 +12,8 @@ use gardener::replay::replayer::{
 };
 use gardener::agent::codex::CodexAdapter;
 use gardener::agent::{AdapterContext, AgentAdapter};
-use gardener::protocol::AgentTerminal;
+use gardener::protocol::{AgentTerminal, TokenUsage};
+use gardener::usage::UsageTotals;
 use gardener::runtime::{FakeProcessRunner, ProcessOutput, ProcessRequest};
 use gardener::types::AgentKind;
 use std::sync::Arc;
@@ -38,6 +39,11 @@ fn fake_task_record(task_id: &str) -> BacklogTaskRecord {
         related_branch: None,
         attempt_count: 1,
         created_at: 0,
+        blocked_by: Vec::new(),
+        blocked_reason: None,
+        status_note: None,
+        not_before: None,
+        failure_reason: None,
     }
 }
 
@@ -83,6 +89,7 @@ fn write_minimal_recording(
     entries.push(RecordEntry::SessionEnd(SessionEndRecord {
         completed_tasks: 1,
         total_duration_ns: 0,
+        usage: UsageTotals::default(),
     }));
 
     use std::io::Write;
@@ -132,6 +139,7 @@ fn replay_process_runner_returns_recorded_responses() {
             program: "echo".to_string(),
             args: vec!["hello".to_string()],
             cwd: None,
+            timeout: None,
         })
         .expect("test");
     let out = runner.wait(handle).expect("test");
@@ -144,6 +152,7 @@ fn replay_process_runner_returns_recorded_responses() {
             program: "echo".to_string(),
             args: vec!["bye".to_string()],
             cwd: None,
+            timeout: None,
         })
         .expect("test");
     assert!(runner.wait(handle2).is_err(), "expected empty queue error");
@@ -160,6 +169,12 @@ fn replay_agent_adapter_returns_recorded_step_results() {
         terminal: "success".to_string(),
         payload: serde_json::json!({"task_type": "task", "reasoning": "test"}),
         diagnostic_count: 0,
+        usage: TokenUsage {
+            input_tokens: 1200,
+            cached_input_tokens: 200,
+            output_tokens: 80,
+            reported_cost_usd: None,
+        },
     };
     write_minimal_recording(tmp.path(), "worker-1", "task-1", vec![], vec![turn]);
 
@@ -184,6 +199,7 @@ fn replay_agent_adapter_returns_recorded_step_results() {
                 output_file: None,
                 permissive_mode: false,
                 max_turns: None,
+                timeout: None,
             },
             "prompt",
             None,
@@ -192,6 +208,7 @@ fn replay_agent_adapter_returns_recorded_step_results() {
 
     assert_eq!(step.terminal, AgentTerminal::Success);
     assert_eq!(step.payload["task_type"], "task");
+    assert_eq!(step.usage.total_tokens(), 1280);
 
     // Second call should fail (queue empty)
     assert!(
@@ -207,6 +224,7 @@ fn replay_agent_adapter_returns_recorded_step_results() {
             output_file: None,
             permissive_mode: false,
             max_turns: None,
+            timeout: None,
         }, "prompt", None).is_err()
     );
 }
@@ -270,6 +288,7 @@ fn recording_round_trip_process_calls() {
             program: "cmd1".to_string(),
             args: vec![],
             cwd: None,
+            timeout: None,
         })
         .expect("test");
     let h2 = recording_runner
@@ -277,6 +296,7 @@ fn recording_round_trip_process_calls() {
             program: "cmd2".to_string(),
             args: vec!["--flag".to_string()],
             cwd: None,
+            timeout: None,
         })
         .expect("test");
     let out1 = recording_runner.wait(h1).expect("test");
@@ -306,6 +326,7 @@ fn recording_round_trip_process_calls() {
             program: "cmd1".to_string(),
             args: vec![],
             cwd: None,
+            timeout: None,
         })
         .expect("test");
     let rh2 = replayer
@@ -313,6 +334,7 @@ fn recording_round_trip_process_calls() {
             program: "cmd2".to_string(),
             args: vec!["--flag".to_string()],
             cwd: None,
+            timeout: None,
         })
         .expect("test");
     let rout1 = replayer.wait(rh1).expect("test");
@@ -359,6 +381,7 @@ fn replay_catches_request_mismatch() {
             program: "cmd-different".to_string(),
             args: vec![],
             cwd: None,
+            timeout: None,
         })
         .expect("test");
     let _ = runner.wait(handle).expect("test");
@@ -389,6 +412,7 @@ fn session_recording_worker_ids() {
             terminal: "success".to_string(),
             payload: serde_json::json!({}),
             diagnostic_count: 0,
+            usage: TokenUsage::default(),
         }),
         RecordEntry::AgentTurn(AgentTurnRecord {
             seq: 2,
@@ -398,6 +422,7 @@ fn session_recording_worker_ids() {
             terminal: "failure".to_string(),
             payload: serde_json::json!({}),
             diagnostic_count: 1,
+            usage: TokenUsage::default(),
         }),
         RecordEntry::AgentTurn(AgentTurnRecord {
             seq: 3,
@@ -407,6 +432,7 @@ fn session_recording_worker_ids() {
             terminal: "success".to_string(),
             payload: serde_json::json!({}),
             diagnostic_count: 0,
+            usage: TokenUsage::default(),
         }),
     ];
     let mut f = std::fs::File::create(tmp.path()).expect("test");
@@ -456,6 +482,7 @@ fn recording_overhead_acceptable() {
                 program: "noop".to_string(),
                 args: vec![],
                 cwd: None,
+                timeout: None,
             })
             .expect("test");
         let _ = recording_runner.wait(h).expect("test");
@@ -500,6 +527,7 @@ fn session_replay_reproduces_missing_terminal_event_bug() {
         output_file: Some(std::path::PathBuf::from("/tmp/codex-last-message.json")),
         permissive_mode: false,
         max_turns: None,
+        timeout: None,
     };
 
     let step = CodexAdapter
@@ -27,6 +27,9 @@ fn fake_terminal_captures_tui_frames_and_interactions() {
             p0: 1,
             p1: 1,
             p2: 0,
+            held_by_scope: 0,
+            pool_status: None,
+            usage: None,
         },
         &BacklogView {
             in_progress: vec!["P1 abc123 implement worker loop".to_string()],
@@ -34,6 +37,7 @@ fn fake_terminal_captures_tui_frames_and_interactions() {
                 "P0 deadbe unblock ci".to_string(),
                 "P2 cafe00 cleanup docs".to_string(),
             ],
+            search: None,
         },
         80,
         18,